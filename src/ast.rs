@@ -1,7 +1,33 @@
 use crate::defs::blocks::ScopedBlocks;
+use crate::defs::errors::MascalWarning;
+use crate::defs::span::Span;
+use crate::defs::symbol_table::SymbolTable;
+use std::rc::Rc;
 
 #[derive(Debug, Clone)]
 pub struct AbstractSyntaxTree {
     pub blocks: Vec<ScopedBlocks>,
     pub program_index: usize,
+    pub symbol_table: SymbolTable,
+    pub warnings: Vec<MascalWarning>,
+    /* The imported files, a span pointing into the file numbered n is found at n - 1 */
+    pub imported_files: Vec<SourceFile>,
+}
+
+/*
+ An imported file as it was read, kept around so whatever goes wrong inside of it later on can be
+ shown against its own text rather than the entry file's. Inputs the REPL kept around from before
+ are in here as well, those weren't imported from anywhere
+*/
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub path: String,
+    pub text: Rc<str>,
+    pub imported_at: Option<Span>,
+}
+
+pub fn imported_file(imported_files: &[SourceFile], span: Span) -> Option<&SourceFile> {
+    span.file
+        .checked_sub(1)
+        .and_then(|index| imported_files.get(index))
 }
@@ -0,0 +1,25 @@
+pub mod compiler;
+pub mod instructions;
+pub mod vm;
+
+use crate::bytecode::instructions::CompiledFunction;
+use crate::defs::blocks::ScopedBlocks;
+use crate::defs::builtins::builtin_functions::BuiltinFunction;
+use rustc_hash::FxHashMap;
+
+/*
+ The whole program lowered into bytecode, calls refer to functions and builtins by their index
+ in here so nothing has to be looked up by name while running
+*/
+pub struct BytecodeProgram {
+    pub program: CompiledFunction,
+    pub functions: Vec<CompiledFunction>,
+    pub builtins: Vec<BuiltinFunction>,
+    /* Function values only know the name of the function they stand for */
+    pub function_indices: FxHashMap<String, usize>,
+    /*
+     Lambdas and the builtins taking functions call through the tree-walker, which looks up the
+     functions of the program in their blocks
+    */
+    pub function_blocks: Vec<ScopedBlocks>,
+}
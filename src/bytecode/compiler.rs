@@ -0,0 +1,1259 @@
+use crate::ast::AbstractSyntaxTree;
+use crate::bytecode::BytecodeProgram;
+use crate::bytecode::instructions::{CompiledFunction, Instruction};
+use crate::defs::blocks::{ExecutionBlock, MascalParameter, ScopedBlocks};
+use crate::defs::builtins::builtin_functions::{BUILT_IN_FUNCTION_TABLE, BuiltinFunction};
+use crate::defs::declerations::MascalVariableInitialDeclaration;
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
+use crate::defs::literal::MascalLiteral;
+use crate::defs::operators::MascalBinaryOperators;
+use crate::defs::span::Span;
+use crate::defs::statements::{MascalCasePattern, MascalStatement, MascalStatementKind};
+use crate::defs::symbol_table::SymbolTable;
+use crate::defs::types::{MascalType, MascalUnprocessedType, to_processed_type};
+use crate::runtime::execute_function_expression::builtin_key;
+use crate::runtime::execute_statement::coerce_for_bound;
+use crate::runtime::values::{MascalFunction, MascalValue};
+use rustc_hash::FxHashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+struct FunctionSignature {
+    index: usize,
+    mutable_parameters: Vec<bool>,
+}
+
+/* Whatever is shared between every function being compiled */
+struct ProgramContext<'a> {
+    symbol_table: &'a SymbolTable,
+    signatures: FxHashMap<String, FunctionSignature>,
+    native_functions: &'a FxHashMap<String, BuiltinFunction>,
+    builtins: Vec<BuiltinFunction>,
+    builtin_indices: FxHashMap<String, usize>,
+}
+
+struct SlotInfo {
+    index: usize,
+    is_constant: bool,
+    atomic_variable_type: Arc<MascalType>,
+}
+
+#[derive(Default)]
+struct LoopLabels {
+    label: Option<String>,
+    break_jumps: Vec<usize>,
+    continue_jumps: Vec<usize>,
+}
+
+struct FunctionCompiler<'a, 'b> {
+    context: &'b mut ProgramContext<'a>,
+    function: CompiledFunction,
+    slots: FxHashMap<Rc<str>, SlotInfo>,
+    loops: Vec<LoopLabels>,
+    is_function: bool,
+}
+
+fn unsupported(what: &str, span: Span) -> MascalError {
+    MascalError {
+        error_type: MascalErrorType::UndefinedOperation,
+        span: Some(span),
+        source: format!("{} is not supported by the bytecode backend", what),
+    }
+}
+
+/*
+ Lowers a whole program into bytecode. Anything the VM can't run (yet) makes the compilation fail
+ with an UndefinedOperation error, which lets the interpreter fall back to the tree-walker
+*/
+pub fn compile_program(
+    abstract_syntax_tree: &AbstractSyntaxTree,
+    native_functions: &FxHashMap<String, BuiltinFunction>,
+) -> Result<BytecodeProgram, MascalError> {
+    let mut signatures: FxHashMap<String, FunctionSignature> = FxHashMap::default();
+    let function_blocks = abstract_syntax_tree
+        .blocks
+        .iter()
+        .filter(|block| matches!(block, ScopedBlocks::Function { .. }));
+    for (index, block) in function_blocks.enumerate() {
+        if let ScopedBlocks::Function {
+            name, parameters, ..
+        } = block
+        {
+            signatures.entry(name.clone()).or_insert(FunctionSignature {
+                index,
+                mutable_parameters: parameters
+                    .iter()
+                    .map(|parameter| parameter.is_mutable)
+                    .collect(),
+            });
+        }
+    }
+    let mut context: ProgramContext = ProgramContext {
+        symbol_table: &abstract_syntax_tree.symbol_table,
+        signatures,
+        native_functions,
+        builtins: Vec::new(),
+        builtin_indices: FxHashMap::default(),
+    };
+
+    let mut functions: Vec<CompiledFunction> = Vec::new();
+    let mut program: Option<CompiledFunction> = None;
+    for block in abstract_syntax_tree.blocks.iter() {
+        match block {
+            ScopedBlocks::Program(execution_block) => {
+                program = Some(FunctionCompiler::compile(
+                    &mut context,
+                    Rc::from("DEFINE_PROGRAM"),
+                    execution_block,
+                    &[],
+                    None,
+                    false,
+                )?);
+            }
+
+            ScopedBlocks::Function {
+                name,
+                parameters,
+                return_type,
+                execution_block,
+                span,
+            } => {
+                let return_type: Option<MascalType> = match return_type {
+                    Some(return_type) => Some(
+                        context
+                            .resolve_type(return_type.clone())
+                            .map_err(|err| err.located_at(*span))?,
+                    ),
+                    None => None,
+                };
+                functions.push(FunctionCompiler::compile(
+                    &mut context,
+                    Rc::from(name.as_str()),
+                    execution_block,
+                    parameters,
+                    return_type,
+                    true,
+                )?);
+            }
+
+            /* Both live in the symbol table already, the VM looks them up in there */
+            ScopedBlocks::Record { .. } | ScopedBlocks::Enum { .. } => {}
+        }
+    }
+
+    Ok(BytecodeProgram {
+        program: program.unwrap(),
+        functions,
+        builtins: context.builtins,
+        function_indices: context
+            .signatures
+            .into_iter()
+            .map(|(name, signature)| (name, signature.index))
+            .collect(),
+        function_blocks: abstract_syntax_tree
+            .blocks
+            .iter()
+            .filter(|block| !matches!(block, ScopedBlocks::Program(..)))
+            .cloned()
+            .collect(),
+    })
+}
+
+impl ProgramContext<'_> {
+    /* Named types are read as records at first, the symbol table knows which ones are enums */
+    fn resolve_type(&self, unprocessed: MascalUnprocessedType) -> Result<MascalType, MascalError> {
+        Ok(self
+            .symbol_table
+            .resolve_type(to_processed_type(unprocessed)?))
+    }
+}
+
+impl<'a, 'b> FunctionCompiler<'a, 'b> {
+    fn compile(
+        context: &'b mut ProgramContext<'a>,
+        name: Rc<str>,
+        execution_block: &ExecutionBlock,
+        parameters: &[MascalParameter],
+        return_type: Option<MascalType>,
+        is_function: bool,
+    ) -> Result<CompiledFunction, MascalError> {
+        let variables = &execution_block.variables;
+        let mut typed_declarations: Vec<(&[MascalVariableInitialDeclaration], MascalType)> = vec![
+            (&variables.integers, MascalType::Integer),
+            (&variables.floats, MascalType::Float),
+            (&variables.strings, MascalType::String),
+            (&variables.booleans, MascalType::Boolean),
+            (&variables.dynamics, MascalType::Dynamic),
+            (&variables.types, MascalType::Type),
+        ];
+        for custom_type in variables.custom_types.iter() {
+            let variable_type: MascalType = context
+                .resolve_type(custom_type.variable_type.clone())
+                .map_err(|err| err.located_at(custom_type.span))?;
+            typed_declarations.push((&custom_type.variables, variable_type));
+        }
+        let mut slots: FxHashMap<Rc<str>, SlotInfo> = FxHashMap::default();
+        let mut slot_names: Vec<Rc<str>> = Vec::new();
+        for (declarations, atomic_variable_type) in typed_declarations {
+            let atomic_variable_type: Arc<MascalType> = Arc::new(atomic_variable_type);
+            for declaration in declarations.iter() {
+                if slots.contains_key(&declaration.name) {
+                    continue;
+                }
+                slots.insert(
+                    declaration.name.clone(),
+                    SlotInfo {
+                        index: slot_names.len(),
+                        is_constant: declaration.is_constant,
+                        atomic_variable_type: atomic_variable_type.clone(),
+                    },
+                );
+                slot_names.push(declaration.name.clone());
+            }
+        }
+        let mut parameter_slots: Vec<usize> = Vec::with_capacity(parameters.len());
+        for parameter in parameters {
+            match slots.get(&parameter.name) {
+                Some(slot) => parameter_slots.push(slot.index),
+                None => return Err(unsupported("An undeclared parameter", parameter.span)),
+            }
+        }
+
+        let mut compiler: FunctionCompiler = FunctionCompiler {
+            context,
+            function: CompiledFunction {
+                name,
+                code: Vec::new(),
+                spans: Vec::new(),
+                constants: Vec::new(),
+                variables: variables.clone(),
+                slot_names,
+                parameter_slots,
+                mutable_parameters: parameters
+                    .iter()
+                    .map(|parameter| parameter.is_mutable)
+                    .collect(),
+                register_count: 0,
+                return_type,
+            },
+            slots,
+            loops: Vec::new(),
+            is_function,
+        };
+        compiler.compile_statements(&execution_block.body)?;
+        compiler.emit(Instruction::ReturnNothing, Span::default());
+        Ok(compiler.function)
+    }
+
+    fn emit(&mut self, instruction: Instruction, span: Span) -> usize {
+        self.function.code.push(instruction);
+        self.function.spans.push(span);
+        self.function.code.len() - 1
+    }
+
+    fn emit_constant(&mut self, value: MascalValue, span: Span) {
+        self.function.constants.push(value);
+        let index: usize = self.function.constants.len() - 1;
+        self.emit(Instruction::Constant(index), span);
+    }
+
+    fn emit_fail(&mut self, error_type: MascalErrorType, message: String, span: Span) {
+        self.emit(
+            Instruction::Fail {
+                error_type,
+                message: Rc::from(message),
+            },
+            span,
+        );
+    }
+
+    /* Errors found while compiling are only raised if the VM actually gets to that point */
+    fn emit_error(&mut self, error: MascalError, span: Span) {
+        let span: Span = error.span.unwrap_or(span);
+        self.emit_fail(error.error_type, error.source, span);
+    }
+
+    fn new_register(&mut self) -> usize {
+        self.function.register_count += 1;
+        self.function.register_count - 1
+    }
+
+    fn patch_jump(&mut self, at: usize, target: usize) {
+        match &mut self.function.code[at] {
+            Instruction::Jump(destination) | Instruction::JumpIfFalse(destination) => {
+                *destination = target
+            }
+            Instruction::ForCondition { exit, .. }
+            | Instruction::ForEachNext { exit, .. }
+            | Instruction::ShortCircuit { exit, .. } => *exit = target,
+            Instruction::MatchValue { matched, .. }
+            | Instruction::MatchRange { matched, .. }
+            | Instruction::MatchType { matched, .. } => *matched = target,
+            _ => unreachable!(),
+        }
+    }
+
+    fn compile_statements(&mut self, statements: &[MascalStatement]) -> Result<(), MascalError> {
+        for statement in statements {
+            self.compile_statement(statement)?;
+        }
+        Ok(())
+    }
+
+    fn compile_loop_body(
+        &mut self,
+        statements: &[MascalStatement],
+        label: &Option<String>,
+    ) -> Result<LoopLabels, MascalError> {
+        self.loops.push(LoopLabels {
+            label: label.clone(),
+            ..LoopLabels::default()
+        });
+        self.compile_statements(statements)?;
+        Ok(self.loops.pop().unwrap())
+    }
+
+    fn compile_statement(&mut self, statement: &MascalStatement) -> Result<(), MascalError> {
+        let span: Span = statement.span;
+        match &statement.kind {
+            MascalStatementKind::ConditionalStatement(branches) => {
+                let mut end_jumps: Vec<usize> = Vec::new();
+                for branch in branches {
+                    let Some(condition) = &branch.condition else {
+                        self.compile_statements(&branch.statements)?;
+                        break;
+                    };
+                    self.compile_expression(condition)?;
+                    let skip_jump: usize = self.emit(Instruction::JumpIfFalse(0), condition.span);
+                    self.compile_statements(&branch.statements)?;
+                    end_jumps.push(self.emit(Instruction::Jump(0), span));
+                    let next_branch: usize = self.function.code.len();
+                    self.patch_jump(skip_jump, next_branch);
+                }
+                let end: usize = self.function.code.len();
+                for jump in end_jumps {
+                    self.patch_jump(jump, end);
+                }
+            }
+
+            MascalStatementKind::While { branch, label } => {
+                let condition: &MascalExpression = branch.condition.as_ref().unwrap();
+                let start: usize = self.function.code.len();
+                self.compile_expression(condition)?;
+                let exit_jump: usize = self.emit(Instruction::JumpIfFalse(0), condition.span);
+                let labels: LoopLabels = self.compile_loop_body(&branch.statements, label)?;
+                self.emit(Instruction::Jump(start), span);
+                let end: usize = self.function.code.len();
+                self.patch_jump(exit_jump, end);
+                for jump in labels.break_jumps {
+                    self.patch_jump(jump, end);
+                }
+                for jump in labels.continue_jumps {
+                    self.patch_jump(jump, start);
+                }
+            }
+
+            MascalStatementKind::Repeat {
+                statements,
+                condition,
+                is_until,
+                label,
+            } => {
+                let start: usize = self.function.code.len();
+                let labels: LoopLabels = self.compile_loop_body(statements, label)?;
+                let condition_start: usize = self.function.code.len();
+                self.compile_expression(condition)?;
+                if *is_until {
+                    self.emit(Instruction::JumpIfFalse(start), condition.span);
+                } else {
+                    let exit_jump: usize = self.emit(Instruction::JumpIfFalse(0), condition.span);
+                    self.emit(Instruction::Jump(start), span);
+                    let end: usize = self.function.code.len();
+                    self.patch_jump(exit_jump, end);
+                }
+                let end: usize = self.function.code.len();
+                for jump in labels.break_jumps {
+                    self.patch_jump(jump, end);
+                }
+                for jump in labels.continue_jumps {
+                    self.patch_jump(jump, condition_start);
+                }
+            }
+
+            MascalStatementKind::For {
+                variable,
+                from,
+                to,
+                step,
+                statements,
+                label,
+            } => {
+                let Some((slot, atomic_variable_type)) = self
+                    .slots
+                    .get(variable.as_str())
+                    .map(|slot| (slot.index, slot.atomic_variable_type.clone()))
+                else {
+                    self.emit_fail(
+                        MascalErrorType::RuntimeError,
+                        format!("Variable {:?} not found", variable),
+                        span,
+                    );
+                    return Ok(());
+                };
+                let counter: usize = self.new_register();
+                let limit: usize = self.new_register();
+                let step_register: usize = self.new_register();
+                for (bound, register) in [(from, counter), (to, limit), (step, step_register)] {
+                    let bound: MascalExpression =
+                        coerce_for_bound(bound.clone(), &atomic_variable_type);
+                    self.compile_expression(&bound)?;
+                    self.emit(Instruction::CheckForBound(slot), bound.span);
+                    if register == step_register {
+                        self.emit(Instruction::CheckForStep, bound.span);
+                    }
+                    self.emit(Instruction::StoreRegister(register), bound.span);
+                }
+                let start: usize = self.emit(
+                    Instruction::ForCondition {
+                        counter,
+                        limit,
+                        step: step_register,
+                        exit: 0,
+                    },
+                    span,
+                );
+                self.emit(Instruction::StoreLoopVariable { slot, counter }, span);
+                let labels: LoopLabels = self.compile_loop_body(statements, label)?;
+                let step_label: usize = self.emit(
+                    Instruction::ForStep {
+                        counter,
+                        step: step_register,
+                    },
+                    span,
+                );
+                self.emit(Instruction::Jump(start), span);
+                let end: usize = self.function.code.len();
+                self.patch_jump(start, end);
+                for jump in labels.break_jumps {
+                    self.patch_jump(jump, end);
+                }
+                for jump in labels.continue_jumps {
+                    self.patch_jump(jump, step_label);
+                }
+            }
+
+            MascalStatementKind::ExpressionStatement(expression) => {
+                self.compile_expression(expression)?;
+                self.emit(Instruction::Pop, span);
+            }
+
+            MascalStatementKind::Declaration { variable, value } => {
+                self.compile_declaration(variable, value, span)?;
+            }
+
+            MascalStatementKind::Throw {
+                error_type,
+                message,
+            } => {
+                self.compile_expression(message)?;
+                self.emit(
+                    Instruction::Throw {
+                        error_type: error_type.clone(),
+                        message_span: message.span,
+                    },
+                    span,
+                );
+            }
+
+            MascalStatementKind::Break(label) | MascalStatementKind::Continue(label) => {
+                let is_break: bool = matches!(statement.kind, MascalStatementKind::Break(..));
+                /* The semantic pass already made sure a named loop is around */
+                let Some(target) = self
+                    .loops
+                    .iter()
+                    .rposition(|labels| label.is_none() || labels.label.as_ref() == label.as_ref())
+                else {
+                    self.emit_fail(
+                        MascalErrorType::ContextError,
+                        String::from(if is_break {
+                            "Break statement is not allowed outside of a loop statement"
+                        } else {
+                            "Continue statement is not allowed outside of a loop statement"
+                        }),
+                        span,
+                    );
+                    return Ok(());
+                };
+                let jump: usize = self.emit(Instruction::Jump(0), span);
+                let labels: &mut LoopLabels = &mut self.loops[target];
+                if is_break {
+                    labels.break_jumps.push(jump);
+                } else {
+                    labels.continue_jumps.push(jump);
+                }
+            }
+
+            MascalStatementKind::Try { .. } => {
+                return Err(unsupported("A try statement", span));
+            }
+
+            MascalStatementKind::ForEach {
+                variable,
+                index_variable,
+                iterable,
+                statements,
+                label,
+            } => {
+                let items: usize = self.new_register();
+                let counter: usize = self.new_register();
+                self.compile_expression(iterable)?;
+                self.emit(Instruction::ForEachStart { items, counter }, iterable.span);
+                let start: usize = self.emit(
+                    Instruction::ForEachNext {
+                        items,
+                        counter,
+                        exit: 0,
+                    },
+                    span,
+                );
+                /* Whatever can't be assigned to only fails once there is an element to assign */
+                if let Some(slot) = self.assignable_slot(variable, span) {
+                    self.emit(Instruction::Store(slot), span);
+                }
+                if let Some(index_variable) = index_variable
+                    && let Some(slot) = self.assignable_slot(index_variable, span)
+                {
+                    self.emit(Instruction::LoadRegister(counter), span);
+                    self.emit(Instruction::Store(slot), span);
+                }
+                let labels: LoopLabels = self.compile_loop_body(statements, label)?;
+                let step_label: usize = self.emit(Instruction::ForEachStep(counter), span);
+                self.emit(Instruction::Jump(start), span);
+                let end: usize = self.function.code.len();
+                self.patch_jump(start, end);
+                for jump in labels.break_jumps {
+                    self.patch_jump(jump, end);
+                }
+                for jump in labels.continue_jumps {
+                    self.patch_jump(jump, step_label);
+                }
+            }
+
+            /*
+             The patterns are tried in order and jump into the body of their case on a match, so
+             the ones after the first match never get evaluated
+            */
+            MascalStatementKind::Match {
+                subject,
+                cases,
+                default,
+            } => {
+                let subject_register: usize = self.new_register();
+                self.compile_expression(subject)?;
+                self.emit(Instruction::StoreRegister(subject_register), subject.span);
+                let mut case_jumps: Vec<Vec<usize>> = Vec::with_capacity(cases.len());
+                for case in cases {
+                    let mut jumps: Vec<usize> = Vec::with_capacity(case.patterns.len());
+                    for pattern in case.patterns.iter() {
+                        let (instruction, pattern_span): (Instruction, Span) = match pattern {
+                            MascalCasePattern::Value(value) => {
+                                self.compile_expression(value)?;
+                                (
+                                    Instruction::MatchValue {
+                                        subject: subject_register,
+                                        matched: 0,
+                                    },
+                                    span,
+                                )
+                            }
+                            MascalCasePattern::Range { from, to } => {
+                                self.compile_expression(from)?;
+                                self.compile_expression(to)?;
+                                (
+                                    Instruction::MatchRange {
+                                        subject: subject_register,
+                                        matched: 0,
+                                    },
+                                    span,
+                                )
+                            }
+                            MascalCasePattern::Type(pattern_type) => {
+                                self.compile_expression(pattern_type)?;
+                                (
+                                    Instruction::MatchType {
+                                        subject: subject_register,
+                                        matched: 0,
+                                    },
+                                    pattern_type.span,
+                                )
+                            }
+                        };
+                        jumps.push(self.emit(instruction, pattern_span));
+                    }
+                    case_jumps.push(jumps);
+                }
+                let mut end_jumps: Vec<usize> = Vec::with_capacity(cases.len() + 1);
+                if let Some(default) = default {
+                    self.compile_statements(default)?;
+                }
+                end_jumps.push(self.emit(Instruction::Jump(0), span));
+                for (case, jumps) in cases.iter().zip(case_jumps) {
+                    let body: usize = self.function.code.len();
+                    for jump in jumps {
+                        self.patch_jump(jump, body);
+                    }
+                    self.compile_statements(&case.statements)?;
+                    end_jumps.push(self.emit(Instruction::Jump(0), span));
+                }
+                let end: usize = self.function.code.len();
+                for jump in end_jumps {
+                    self.patch_jump(jump, end);
+                }
+            }
+
+            MascalStatementKind::Return(value) => {
+                if !self.is_function {
+                    self.emit_fail(
+                        MascalErrorType::ContextError,
+                        String::from("Return statement is not allowed outside of a function"),
+                        span,
+                    );
+                    return Ok(());
+                }
+                match value {
+                    Some(expression) => {
+                        self.compile_expression(expression)?;
+                        self.emit(Instruction::Return, span);
+                    }
+                    None => {
+                        self.emit(Instruction::ReturnNothing, span);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /* The slot a plain assignment goes into, anything it can't go into fails once reached */
+    fn assignable_slot(&mut self, varname: &str, span: Span) -> Option<usize> {
+        let Some((slot, is_constant)) = self
+            .slots
+            .get(varname)
+            .map(|slot| (slot.index, slot.is_constant))
+        else {
+            self.emit_fail(
+                MascalErrorType::RuntimeError,
+                format!(
+                    "Expected a variable name, however got an unknown one called {:?}",
+                    varname
+                ),
+                span,
+            );
+            return None;
+        };
+        if is_constant {
+            self.emit_fail(
+                MascalErrorType::RuntimeError,
+                format!(
+                    "Cannot assign a new value to the constant variable called {:?}",
+                    varname
+                ),
+                span,
+            );
+            return None;
+        }
+        Some(slot)
+    }
+
+    fn compile_declaration(
+        &mut self,
+        variable: &MascalExpression,
+        value: &MascalExpression,
+        span: Span,
+    ) -> Result<(), MascalError> {
+        match &variable.kind {
+            MascalExpressionKind::Symbolic(varname) => {
+                /* Assigning to the name of the running function returns from it */
+                if self.is_function
+                    && *self.function.name == **varname
+                    && !self.slots.contains_key(varname.as_str())
+                {
+                    self.compile_expression(value)?;
+                    self.emit(Instruction::Return, span);
+                    return Ok(());
+                }
+                let Some(slot) = self.assignable_slot(varname, variable.span) else {
+                    return Ok(());
+                };
+                self.compile_expression(value)?;
+                self.emit(Instruction::Store(slot), span);
+            }
+
+            MascalExpressionKind::Indexing { .. } => {
+                let mut dynamics: Vec<bool> = Vec::new();
+                let mut base: &MascalExpression = variable;
+                while let MascalExpressionKind::Indexing {
+                    array,
+                    index,
+                    is_dynamic,
+                } = &base.kind
+                {
+                    self.compile_expression(index)?;
+                    dynamics.push(*is_dynamic);
+                    base = array;
+                }
+                dynamics.reverse();
+                let varname: &String = match &base.kind {
+                    MascalExpressionKind::Symbolic(varname) => varname,
+                    other => {
+                        self.emit_fail(
+                            MascalErrorType::RuntimeError,
+                            format!("Left hand-side must be a variable, but got {:?}", other),
+                            base.span,
+                        );
+                        return Ok(());
+                    }
+                };
+                let Some((slot, is_constant)) = self
+                    .slots
+                    .get(varname.as_str())
+                    .map(|slot| (slot.index, slot.is_constant))
+                else {
+                    self.emit_fail(
+                        MascalErrorType::RuntimeError,
+                        format!(
+                            "Expected a defined variable but got an unknown called {}",
+                            varname
+                        ),
+                        base.span,
+                    );
+                    return Ok(());
+                };
+                if is_constant {
+                    self.emit_fail(
+                        MascalErrorType::RuntimeError,
+                        format!("Cannot assign an array to a constant variable {}", varname),
+                        base.span,
+                    );
+                    return Ok(());
+                }
+                self.compile_expression(value)?;
+                self.emit(
+                    Instruction::StoreIndexed {
+                        slot,
+                        dynamics: Rc::from(dynamics),
+                    },
+                    variable.span,
+                );
+            }
+
+            _ => return Err(unsupported("This kind of assignment", variable.span)),
+        }
+        Ok(())
+    }
+
+    fn compile_expression(&mut self, expression: &MascalExpression) -> Result<(), MascalError> {
+        let span: Span = expression.span;
+        match &expression.kind {
+            MascalExpressionKind::Literal(literal) => {
+                let value: MascalValue = match literal {
+                    MascalLiteral::String(s) => MascalValue::String(Arc::from(s.as_str())),
+                    MascalLiteral::Integer(i) => MascalValue::Integer(i.clone()),
+                    MascalLiteral::Float(f) => MascalValue::Float(*f),
+                    MascalLiteral::Null => MascalValue::Null,
+                    MascalLiteral::Boolean(b) => MascalValue::Boolean(*b),
+                };
+                self.emit_constant(value, span);
+            }
+
+            MascalExpressionKind::FieldAccess { .. } => {
+                return Err(unsupported("Accessing a record's field", span));
+            }
+
+            MascalExpressionKind::Map(..) => {
+                return Err(unsupported("A map literal", span));
+            }
+
+            MascalExpressionKind::Lambda { parameters, body } => {
+                self.emit(
+                    Instruction::MakeLambda {
+                        parameters: parameters.clone(),
+                        body: body.clone(),
+                    },
+                    span,
+                );
+            }
+
+            MascalExpressionKind::Symbolic(name) => {
+                match self.slots.get(name.as_str()).map(|slot| slot.index) {
+                    Some(slot) => {
+                        self.emit(Instruction::Load(slot), span);
+                    }
+                    None if self.context.symbol_table.is_record(name) => {
+                        self.emit_constant(
+                            MascalValue::Type(MascalType::Record(Arc::from(name.as_str()))),
+                            span,
+                        );
+                    }
+                    None if let Some(enum_type) = self.context.symbol_table.enum_type(name) => {
+                        self.emit_constant(MascalValue::Type(enum_type), span);
+                    }
+                    None if self.is_callable_name(name) => {
+                        self.emit_constant(
+                            MascalValue::Function(MascalFunction::Named(Arc::from(name.as_str()))),
+                            span,
+                        );
+                    }
+                    None => self.emit_fail(
+                        MascalErrorType::RuntimeError,
+                        format!("Unknown expression {:?} found", name),
+                        span,
+                    ),
+                }
+            }
+
+            MascalExpressionKind::Indexing {
+                array,
+                index,
+                is_dynamic,
+            } => {
+                /* Indexing a type only turns it into an array type, the index is never looked at */
+                if let MascalExpressionKind::Type(unprocessed_type) = &array.kind {
+                    match self.context.resolve_type(*unprocessed_type.clone()) {
+                        Ok(array_type) => self.emit_constant(
+                            MascalValue::Type(if *is_dynamic {
+                                MascalType::DynamicArray(Box::new(array_type))
+                            } else {
+                                MascalType::StaticArray(Box::new(array_type))
+                            }),
+                            span,
+                        ),
+                        Err(err) => self.emit_error(err, array.span),
+                    }
+                    return Ok(());
+                }
+                self.compile_expression(array)?;
+                self.compile_expression(index)?;
+                self.emit(
+                    Instruction::Index {
+                        is_dynamic: *is_dynamic,
+                    },
+                    span,
+                );
+            }
+
+            MascalExpressionKind::DynamicArray(elements)
+            | MascalExpressionKind::StaticArray(elements) => {
+                for element in elements.iter() {
+                    self.compile_expression(element)?;
+                }
+                self.emit(
+                    Instruction::MakeArray {
+                        length: elements.len(),
+                        is_dynamic: matches!(
+                            expression.kind,
+                            MascalExpressionKind::DynamicArray(_)
+                        ),
+                    },
+                    span,
+                );
+            }
+
+            MascalExpressionKind::Type(unprocessed_type) => {
+                match self.context.resolve_type(*unprocessed_type.clone()) {
+                    Ok(processed_type) => {
+                        self.emit_constant(MascalValue::Type(processed_type), span)
+                    }
+                    Err(err) => self.emit_error(err, span),
+                }
+            }
+
+            MascalExpressionKind::Unary { operator, value } => {
+                self.compile_expression(value)?;
+                self.emit(Instruction::Unary(operator.clone()), span);
+            }
+
+            MascalExpressionKind::Binary {
+                left,
+                operator,
+                right,
+            } => {
+                self.compile_expression(left)?;
+                let short_circuit: Option<usize> = if matches!(
+                    operator,
+                    MascalBinaryOperators::And | MascalBinaryOperators::Or
+                ) {
+                    Some(self.emit(
+                        Instruction::ShortCircuit {
+                            operator: operator.clone(),
+                            exit: 0,
+                        },
+                        span,
+                    ))
+                } else {
+                    None
+                };
+                self.compile_expression(right)?;
+                self.emit(Instruction::Binary(operator.clone()), span);
+                if let Some(short_circuit) = short_circuit {
+                    let end: usize = self.function.code.len();
+                    self.patch_jump(short_circuit, end);
+                }
+            }
+
+            MascalExpressionKind::Call {
+                function,
+                arguments,
+            } => self.compile_call(function, arguments, span)?,
+
+            MascalExpressionKind::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.compile_expression(condition)?;
+                let else_jump: usize = self.emit(Instruction::JumpIfFalse(0), condition.span);
+                self.compile_expression(then_branch)?;
+                let end_jump: usize = self.emit(Instruction::Jump(0), span);
+                let else_start: usize = self.function.code.len();
+                self.patch_jump(else_jump, else_start);
+                self.compile_expression(else_branch)?;
+                let end: usize = self.function.code.len();
+                self.patch_jump(end_jump, end);
+            }
+        }
+        Ok(())
+    }
+
+    fn compile_arguments(&mut self, arguments: &[MascalExpression]) -> Result<(), MascalError> {
+        for argument in arguments {
+            self.compile_expression(argument)?;
+        }
+        Ok(())
+    }
+
+    /* Anything a call by that name could end up at, other than a variable holding a function */
+    fn is_callable_name(&mut self, name: &str) -> bool {
+        self.context.signatures.contains_key(name)
+            || self.resolve_builtin(name).is_some()
+            || self.context.symbol_table.is_record(name)
+            || self.context.symbol_table.is_enum(name)
+    }
+
+    fn resolve_builtin(&mut self, fn_name: &str) -> Option<BuiltinFunction> {
+        let key: String = builtin_key(fn_name)?;
+        BUILT_IN_FUNCTION_TABLE
+            .with(|table| table.get(&key).cloned())
+            .or_else(|| self.context.native_functions.get(&key).cloned())
+    }
+
+    fn compile_call(
+        &mut self,
+        function: &MascalExpression,
+        arguments: &[MascalExpression],
+        span: Span,
+    ) -> Result<(), MascalError> {
+        let fn_name: &String = match &function.kind {
+            MascalExpressionKind::Symbolic(fn_name)
+                if self.slots.contains_key(fn_name.as_str()) =>
+            {
+                /* Should the variable hold no function, the tree-walker goes looking for one by name */
+                if self.is_callable_name(fn_name) {
+                    return Err(unsupported(
+                        "A variable going by the name of a function",
+                        function.span,
+                    ));
+                }
+                self.emit(
+                    Instruction::LoadCallee(self.slots[fn_name.as_str()].index),
+                    span,
+                );
+                self.compile_arguments(arguments)?;
+                self.emit(
+                    Instruction::CallValue {
+                        argument_spans: arguments.iter().map(|argument| argument.span).collect(),
+                    },
+                    span,
+                );
+                return Ok(());
+            }
+            MascalExpressionKind::Symbolic(fn_name) => fn_name,
+
+            MascalExpressionKind::Type(unprocessed_type) => {
+                let Some(argument) = arguments.first() else {
+                    return Err(unsupported("A type cast without a value", span));
+                };
+                self.compile_expression(argument)?;
+                match self.context.resolve_type(*unprocessed_type.clone()) {
+                    Ok(processed_type) => {
+                        self.emit(Instruction::Cast(processed_type), span);
+                    }
+                    Err(err) => self.emit_error(err, span),
+                }
+                return Ok(());
+            }
+
+            _ => {
+                self.compile_expression(function)?;
+                self.emit(
+                    Instruction::ExpectCallable {
+                        argument_count: arguments.len(),
+                        call_span: span,
+                    },
+                    function.span,
+                );
+                self.compile_arguments(arguments)?;
+                self.emit(
+                    Instruction::CallValue {
+                        argument_spans: arguments.iter().map(|argument| argument.span).collect(),
+                    },
+                    span,
+                );
+                return Ok(());
+            }
+        };
+
+        if let Some(builtin_function) = self.resolve_builtin(fn_name) {
+            /* READ and SWAP work on the variables themselves, so they get instructions of their own */
+            match fn_name.to_lowercase().as_str() {
+                "read" => return self.compile_read(arguments, span),
+                "swap" => return self.compile_swap(arguments, span),
+                _ => {}
+            }
+            let BuiltinFunction::ValueBased {
+                fixed_argument_types,
+                ..
+            } = &builtin_function
+            else {
+                return Err(unsupported(&format!("The builtin {:?}", fn_name), span));
+            };
+            if arguments.len() < fixed_argument_types.len() {
+                self.emit_fail(
+                    MascalErrorType::ArgumentError,
+                    format!(
+                        "Expected at least {} argument(s) but got {} argument(s) instead",
+                        fixed_argument_types.len(),
+                        arguments.len()
+                    ),
+                    span,
+                );
+                return Ok(());
+            }
+            let key: String = fn_name.to_lowercase();
+            let builtin: usize = match self.context.builtin_indices.get(&key) {
+                Some(builtin) => *builtin,
+                None => {
+                    self.context.builtins.push(builtin_function);
+                    let builtin: usize = self.context.builtins.len() - 1;
+                    self.context.builtin_indices.insert(key, builtin);
+                    builtin
+                }
+            };
+            self.compile_arguments(arguments)?;
+            self.emit(
+                Instruction::CallBuiltin {
+                    builtin,
+                    argument_spans: arguments.iter().map(|argument| argument.span).collect(),
+                },
+                span,
+            );
+            return Ok(());
+        }
+
+        if self.context.symbol_table.is_record(fn_name) {
+            if !arguments.is_empty() {
+                self.emit_fail(
+                    MascalErrorType::ArgumentError,
+                    format!(
+                        "Expected no arguments when creating the record {:?} but got {} argument(s)",
+                        fn_name,
+                        arguments.len()
+                    ),
+                    span,
+                );
+                return Ok(());
+            }
+            self.emit(
+                Instruction::InstantiateRecord(Arc::from(fn_name.as_str())),
+                span,
+            );
+            return Ok(());
+        }
+        if let Some(enum_type) = self.context.symbol_table.enum_type(fn_name) {
+            let [argument] = arguments else {
+                self.emit_fail(
+                    MascalErrorType::ArgumentError,
+                    String::from("Expected one value but got none or multiple values"),
+                    span,
+                );
+                return Ok(());
+            };
+            self.compile_expression(argument)?;
+            self.emit(Instruction::Cast(enum_type), argument.span);
+            return Ok(());
+        }
+        let Some((index, mutable_parameters)) = self
+            .context
+            .signatures
+            .get(fn_name)
+            .map(|signature| (signature.index, signature.mutable_parameters.clone()))
+        else {
+            self.emit_fail(
+                MascalErrorType::RuntimeError,
+                format!("Unidentified function with the name of {:?}", fn_name),
+                span,
+            );
+            return Ok(());
+        };
+        if mutable_parameters.len() != arguments.len() {
+            self.emit_fail(
+                MascalErrorType::ArgumentError,
+                format!(
+                    "Expected {} argument(s) but got {} argument(s) instead",
+                    mutable_parameters.len(),
+                    arguments.len()
+                ),
+                span,
+            );
+            return Ok(());
+        }
+        /* Mutable parameters take the variable itself, so nothing gets evaluated for them */
+        let mut write_backs: Vec<(usize, usize)> = Vec::new();
+        for (parameter, (argument, is_mutable)) in
+            arguments.iter().zip(mutable_parameters).enumerate()
+        {
+            if !is_mutable {
+                self.compile_expression(argument)?;
+                continue;
+            }
+            let MascalExpressionKind::Symbolic(varname) = &argument.kind else {
+                self.emit_fail(
+                    MascalErrorType::ArgumentError,
+                    String::from(
+                        "Expected a variable name in order to update to, but got something else",
+                    ),
+                    argument.span,
+                );
+                return Ok(());
+            };
+            let Some(slot) = self.slots.get(varname.as_str()).map(|slot| slot.index) else {
+                self.emit_fail(
+                    MascalErrorType::RuntimeError,
+                    format!(
+                        "Expected a variable name, however got an unknown one called {:?}",
+                        varname
+                    ),
+                    argument.span,
+                );
+                return Ok(());
+            };
+            write_backs.push((parameter, slot));
+        }
+        self.emit(
+            Instruction::Call {
+                function: index,
+                argument_count: arguments.len() - write_backs.len(),
+                write_backs: Rc::from(write_backs),
+            },
+            span,
+        );
+        Ok(())
+    }
+
+    /* The slot of a variable handed to READ or SWAP, which have to be given plain variable names */
+    fn variable_argument(&self, argument: &MascalExpression) -> Option<Result<usize, String>> {
+        let MascalExpressionKind::Symbolic(varname) = &argument.kind else {
+            return None;
+        };
+        Some(
+            self.slots
+                .get(varname.as_str())
+                .map(|slot| slot.index)
+                .ok_or_else(|| varname.clone()),
+        )
+    }
+
+    /* Every variable is read in turn, so a failing one still leaves the ones before it read */
+    fn compile_read(
+        &mut self,
+        arguments: &[MascalExpression],
+        span: Span,
+    ) -> Result<(), MascalError> {
+        for argument in arguments {
+            match self.variable_argument(argument) {
+                Some(Ok(slot)) => {
+                    self.emit(Instruction::Read(slot), span);
+                }
+                Some(Err(varname)) => {
+                    self.emit_fail(
+                        MascalErrorType::RuntimeError,
+                        format!("The variable name {:?} does not exist", varname),
+                        span,
+                    );
+                    return Ok(());
+                }
+                None => {
+                    self.emit_fail(
+                        MascalErrorType::RuntimeError,
+                        String::from(
+                            "Expected an identifier for a variable name but found other expression",
+                        ),
+                        argument.span,
+                    );
+                    return Ok(());
+                }
+            }
+        }
+        self.emit_constant(MascalValue::Null, span);
+        Ok(())
+    }
+
+    fn compile_swap(
+        &mut self,
+        arguments: &[MascalExpression],
+        span: Span,
+    ) -> Result<(), MascalError> {
+        if arguments.len() != 2 {
+            self.emit_fail(
+                MascalErrorType::ArgumentError,
+                format!(
+                    "Expected 2 variable names but got {} arguments instead",
+                    arguments.len()
+                ),
+                span,
+            );
+            return Ok(());
+        }
+        let mut slots: Vec<usize> = Vec::with_capacity(2);
+        let mut is_unknown: bool = false;
+        for (argument, position) in arguments.iter().zip(["first", "second"]) {
+            match self.variable_argument(argument) {
+                Some(Ok(slot)) => slots.push(slot),
+                Some(Err(_)) => is_unknown = true,
+                None => {
+                    self.emit_fail(
+                        MascalErrorType::ArgumentError,
+                        format!(
+                            "Expected a variable name as {} argument but got something else instead",
+                            position
+                        ),
+                        argument.span,
+                    );
+                    return Ok(());
+                }
+            }
+        }
+        if is_unknown {
+            self.emit_fail(
+                MascalErrorType::RuntimeError,
+                String::from("Expected variable names but got at least one unknown"),
+                span,
+            );
+            return Ok(());
+        }
+        self.emit(Instruction::Swap(slots[0], slots[1]), span);
+        self.emit_constant(MascalValue::Null, span);
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+use crate::defs::blocks::VariableBlock;
+use crate::defs::errors::MascalErrorType;
+use crate::defs::expressions::MascalExpression;
+use crate::defs::operators::{MascalBinaryOperators, MascalUnaryOperators};
+use crate::defs::span::Span;
+use crate::defs::types::MascalType;
+use crate::runtime::values::MascalValue;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/*
+ Every instruction works on the operand stack of the frame it runs in, variables are addressed by
+ the slot the compiler resolved for them. Loops keep their counter, limit and step in registers
+ which live next to the slots but can't be seen (or messed with) by the script itself
+*/
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    /* Pushes a value out of the constant pool */
+    Constant(usize),
+
+    /* Pushes the value of a variable slot */
+    Load(usize),
+
+    /* Pops a value and assigns it to a variable slot */
+    Store(usize),
+
+    /*
+     Pops the right hand-side followed by one index per layer, then assigns into the element of
+     the array held by the slot. The flags tell whether each layer was indexed dynamically
+    */
+    StoreIndexed {
+        slot: usize,
+        dynamics: Rc<[bool]>,
+    },
+
+    Pop,
+    Unary(MascalUnaryOperators),
+    Binary(MascalBinaryOperators),
+
+    /* Pops the elements (the first one being the deepest) and pushes the array */
+    MakeArray {
+        length: usize,
+        is_dynamic: bool,
+    },
+
+    /* Pops the index followed by the array and pushes the element */
+    Index {
+        is_dynamic: bool,
+    },
+
+    /* Pops a value and pushes it casted to an already known type */
+    Cast(MascalType),
+
+    /*
+     Makes sure the value on top of the stack can be called with the given number of arguments,
+     which means being a function or a type to cast a single value to
+    */
+    ExpectCallable {
+        argument_count: usize,
+        call_span: Span,
+    },
+
+    /*
+     Pushes the function held by a variable that is being called, a variable holding anything else
+     (or nothing at all) means there is no function by that name
+    */
+    LoadCallee(usize),
+
+    /* Pops the arguments followed by the function value and calls it, types cast their argument */
+    CallValue {
+        argument_spans: Rc<[Span]>,
+    },
+
+    /* Creates a lambda that captures the variables as they are right now */
+    MakeLambda {
+        parameters: Box<[Rc<str>]>,
+        body: Box<MascalExpression>,
+    },
+
+    /* Pops the arguments and calls a value based builtin (or a function the host registered) */
+    CallBuiltin {
+        builtin: usize,
+        argument_spans: Rc<[Span]>,
+    },
+
+    /* Pushes a new record with every field at its initial value */
+    InstantiateRecord(Arc<str>),
+
+    /* Reads a line of input into a variable slot, parsed according to the variable's type */
+    Read(usize),
+
+    /* Swaps two variable slots entirely, their types and dimensions go along with the values */
+    Swap(usize, usize),
+
+    /*
+     Pops the arguments and calls a function defined by the script, the variables passed to its
+     mutable parameters get whatever those parameters ended up holding
+    */
+    Call {
+        function: usize,
+        argument_count: usize,
+        write_backs: Rc<[(usize, usize)]>,
+    },
+
+    Jump(usize),
+
+    /* Pops a boolean and jumps if it is false, anything that isn't a boolean is an error */
+    JumpIfFalse(usize),
+
+    /*
+     Jumps past the right side of an AND or OR when the left side on top of the stack already
+     settled it, the left side is then left behind as the result
+    */
+    ShortCircuit {
+        operator: MascalBinaryOperators,
+        exit: usize,
+    },
+
+    /* Pops a value into a register */
+    StoreRegister(usize),
+
+    /* Pushes the value of a register */
+    LoadRegister(usize),
+
+    /* Makes sure the value on top of the stack can be used as a bound for the loop variable */
+    CheckForBound(usize),
+
+    /* Makes sure the step on top of the stack isn't zero, a loop like that would never end */
+    CheckForStep,
+
+    /* Jumps to the exit once the counter went past the limit, which side depends on the step */
+    ForCondition {
+        counter: usize,
+        limit: usize,
+        step: usize,
+        exit: usize,
+    },
+
+    /* Copies the counter into the loop variable */
+    StoreLoopVariable {
+        slot: usize,
+        counter: usize,
+    },
+
+    ForStep {
+        counter: usize,
+        step: usize,
+    },
+
+    /*
+     Pops what a FOR EACH loops over and keeps its elements in a register (the characters for a
+     string), the counter starts at the first one
+    */
+    ForEachStart {
+        items: usize,
+        counter: usize,
+    },
+
+    /* Pushes the element the counter is at, or jumps to the exit once there is none left */
+    ForEachNext {
+        items: usize,
+        counter: usize,
+        exit: usize,
+    },
+
+    ForEachStep(usize),
+
+    /* Pops a value and jumps if it equals the subject of the MATCH held by the register */
+    MatchValue {
+        subject: usize,
+        matched: usize,
+    },
+
+    /*
+     Pops the end followed by the start of a range and jumps if the subject falls in it, a subject
+     that can't be compared with the ends just doesn't
+    */
+    MatchRange {
+        subject: usize,
+        matched: usize,
+    },
+
+    /* Pops a type and jumps if the subject is of it, DYNAMIC lets anything through */
+    MatchType {
+        subject: usize,
+        matched: usize,
+    },
+
+    /* Pops a value and hands it back to the caller */
+    Return,
+
+    /* Leaves the function without a value, be it through a bare RETURN or the end of the body */
+    ReturnNothing,
+
+    /* Pops the message and raises an error of the given type with it */
+    Throw {
+        error_type: MascalErrorType,
+        message_span: Span,
+    },
+
+    /* Raises an error, used for anything that is known to fail once reached */
+    Fail {
+        error_type: MascalErrorType,
+        message: Rc<str>,
+    },
+}
+
+/*
+ A function (or the program block) lowered into bytecode, spans line up with the instructions so
+ errors can still point at the piece of source that caused them
+*/
+#[derive(Debug, Clone)]
+pub struct CompiledFunction {
+    pub name: Rc<str>,
+    pub code: Vec<Instruction>,
+    pub spans: Vec<Span>,
+    pub constants: Vec<MascalValue>,
+    pub variables: VariableBlock,
+    pub slot_names: Vec<Rc<str>>,
+    pub parameter_slots: Vec<usize>,
+    pub mutable_parameters: Vec<bool>,
+    pub register_count: usize,
+    pub return_type: Option<MascalType>,
+}
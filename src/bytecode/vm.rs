@@ -0,0 +1,730 @@
+use crate::bytecode::BytecodeProgram;
+use crate::bytecode::instructions::{CompiledFunction, Instruction};
+use crate::defs::blocks::ExecutionBlock;
+use crate::defs::builtins::builtin_functions::{
+    BuiltinFunction, NativeFunctionTable, is_builtin_function,
+};
+use crate::defs::builtins::utils::read_into_variable;
+use crate::defs::dynamic_int::IntegerNum;
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
+use crate::defs::symbol_table::SymbolTable;
+use crate::defs::types::MascalType;
+use crate::runtime::ExecutionData;
+use crate::runtime::execute_binary_expression::{apply_binary_operator, settles_without_right};
+use crate::runtime::execute_builtin_function::check_builtin_argument;
+use crate::runtime::execute_declaration_statement::conform_assigned_value;
+use crate::runtime::execute_declaration_statement::execute_index_based_decleration::assign_into_array;
+use crate::runtime::execute_function_expression::{
+    builtin_key, call_function_value, check_returned_value,
+};
+use crate::runtime::execute_statement::{check_for_bound, thrown_error, zero_step_error};
+use crate::runtime::execute_typecast::execute_processed_typecast;
+use crate::runtime::execute_unary_expression::apply_unary_operator;
+use crate::runtime::io_streams::SharedIO;
+use crate::runtime::utils::index_array;
+use crate::runtime::values::{MascalFunction, MascalLambda, MascalValue};
+use crate::runtime::variable_table::{
+    VariableData, VariableTable, create_variable_table, instantiate_record,
+};
+use crate::uninit_cell_error;
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+struct Frame {
+    slots: Vec<VariableData>,
+    registers: Vec<MascalValue>,
+    stack: Vec<MascalValue>,
+}
+
+pub struct VirtualMachine<'a> {
+    program: &'a BytecodeProgram,
+    io: SharedIO,
+    native_functions: NativeFunctionTable,
+    symbol_table: Rc<SymbolTable>,
+}
+
+impl<'a> VirtualMachine<'a> {
+    pub fn new(
+        program: &'a BytecodeProgram,
+        io: SharedIO,
+        native_functions: NativeFunctionTable,
+        symbol_table: Rc<SymbolTable>,
+    ) -> Self {
+        VirtualMachine {
+            program,
+            io,
+            native_functions,
+            symbol_table,
+        }
+    }
+
+    /* Runs the program block and hands back every variable of it that holds a value */
+    pub fn run(&self) -> Result<FxHashMap<Rc<str>, MascalValue>, MascalError> {
+        let program: &CompiledFunction = &self.program.program;
+        let mut frame: Frame = self.create_frame(program)?;
+        self.execute(program, &mut frame)?;
+        Ok(program
+            .slot_names
+            .iter()
+            .zip(frame.slots)
+            .filter_map(|(name, data)| {
+                data.value
+                    .map(|value| (name.clone(), value.borrow().clone()))
+            })
+            .collect())
+    }
+
+    /*
+     The declarations are evaluated the same way the tree-walker does it, the resulting table is
+     then taken apart into slots in the order the compiler handed them out
+    */
+    fn create_frame(&self, function: &CompiledFunction) -> Result<Frame, MascalError> {
+        let (variable_table, _) = create_variable_table(
+            ExecutionBlock {
+                variables: function.variables.clone(),
+                body: Box::new([]),
+            },
+            self.io.clone(),
+            self.native_functions.clone(),
+            self.symbol_table.clone(),
+        )?;
+        let mut variable_table = variable_table.borrow_mut();
+        Ok(Frame {
+            slots: function
+                .slot_names
+                .iter()
+                .map(|name| variable_table.remove(name).unwrap())
+                .collect(),
+            registers: vec![MascalValue::Null; function.register_count],
+            stack: Vec::new(),
+        })
+    }
+
+    /* Lambdas and builtins taking functions run on the tree-walker, outside of any scope */
+    fn execution_data(&self) -> ExecutionData<'_> {
+        ExecutionData {
+            variable_table: None,
+            scoped_blocks: &self.program.function_blocks,
+            io: self.io.clone(),
+            native_functions: self.native_functions.clone(),
+            symbol_table: self.symbol_table.clone(),
+        }
+    }
+
+    /*
+     Runs a function of the program with the values for its immutable parameters, the frame is
+     handed back so the caller can pick up whatever its mutable parameters ended up holding
+    */
+    fn call_compiled(
+        &self,
+        callee: &CompiledFunction,
+        arguments: Vec<MascalValue>,
+    ) -> Result<(MascalValue, Frame), MascalError> {
+        let mut callee_frame: Frame = self.create_frame(callee)?;
+        let immutable_slots = callee
+            .parameter_slots
+            .iter()
+            .zip(&callee.mutable_parameters)
+            .filter(|(_, is_mutable)| !**is_mutable)
+            .map(|(slot, _)| *slot);
+        for (slot, argument) in immutable_slots.zip(arguments) {
+            callee_frame.slots[slot].value = Some(Rc::new(RefCell::new(argument)));
+        }
+        let value: Option<MascalValue> = self.execute(callee, &mut callee_frame)?;
+        Ok((
+            check_returned_value(value, &callee.return_type, None)?,
+            callee_frame,
+        ))
+    }
+
+    /*
+     Named functions of the program (which take no variables) get to stay in the VM, anything else
+     a FUNCTION value stands for is left to the tree-walker
+    */
+    fn call_function_value(
+        &self,
+        function: &MascalFunction,
+        arguments: Vec<MascalValue>,
+        span: Span,
+    ) -> Result<MascalValue, MascalError> {
+        if let MascalFunction::Named(fn_name) = function
+            && let Some(callee) = self.compiled_function(fn_name)
+            && !callee.mutable_parameters.contains(&true)
+        {
+            if callee.parameter_slots.len() != arguments.len() {
+                return Err(MascalError {
+                    error_type: MascalErrorType::ArgumentError,
+                    span: None,
+                    source: format!(
+                        "Expected {} argument(s) but got {} argument(s) instead",
+                        callee.parameter_slots.len(),
+                        arguments.len()
+                    ),
+                });
+            }
+            return Ok(self.call_compiled(callee, arguments)?.0);
+        }
+        call_function_value(function, arguments, Some(span), &mut self.execution_data())
+    }
+
+    /* Builtins and functions the host registered win over the functions of the program */
+    fn compiled_function(&self, fn_name: &str) -> Option<&CompiledFunction> {
+        if let Some(lowercased) = builtin_key(fn_name)
+            && (is_builtin_function(&lowercased) || self.native_functions.contains_key(&lowercased))
+        {
+            return None;
+        }
+        let index: usize = *self.program.function_indices.get(fn_name)?;
+        Some(&self.program.functions[index])
+    }
+
+    fn execute(
+        &self,
+        function: &CompiledFunction,
+        frame: &mut Frame,
+    ) -> Result<Option<MascalValue>, MascalError> {
+        let mut pc: usize = 0;
+        loop {
+            let index: usize = pc;
+            pc += 1;
+            match self.execute_instruction(&function.code[index], function, frame, &mut pc) {
+                Ok(None) => {}
+                Ok(Some(value)) => return Ok(value),
+                Err(err) => return Err(err.located_at(function.spans[index])),
+            }
+        }
+    }
+
+    /*
+     Errors raised in here are left unlocated on purpose (unless they carry their own position),
+     the span of the instruction that failed gets filled in on the way out. Hands back something
+     once the function returns, holding whatever value it returned
+    */
+    #[inline(always)]
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction,
+        function: &CompiledFunction,
+        frame: &mut Frame,
+        pc: &mut usize,
+    ) -> Result<Option<Option<MascalValue>>, MascalError> {
+        match instruction {
+            Instruction::Constant(index) => {
+                frame.stack.push(function.constants[*index].clone());
+            }
+
+            Instruction::Load(slot) => match &frame.slots[*slot].value {
+                Some(value) => frame.stack.push(value.borrow().clone()),
+                None => {
+                    return Err(MascalError {
+                        error_type: MascalErrorType::RuntimeError,
+                        span: None,
+                        source: format!(
+                            "Variable {:?} not initialized",
+                            function.slot_names[*slot]
+                        ),
+                    });
+                }
+            },
+
+            Instruction::Store(slot) => {
+                let value: MascalValue = frame.stack.pop().unwrap();
+                let data: &mut VariableData = &mut frame.slots[*slot];
+                value.is_expected_array(
+                    data.array_dimensions.clone(),
+                    data.is_dynamic_array.clone(),
+                )?;
+                let value: MascalValue = conform_assigned_value(
+                    &function.slot_names[*slot],
+                    &data.array_dimensions,
+                    &data.atomic_variable_type,
+                    value,
+                )?;
+                match &data.value {
+                    Some(cell) => *cell.borrow_mut() = value,
+                    None => data.value = Some(Rc::new(RefCell::new(value))),
+                }
+            }
+
+            Instruction::StoreIndexed { slot, dynamics } => {
+                let rhs: MascalValue = frame.stack.pop().unwrap();
+                let mut indices: Vec<MascalValue> =
+                    frame.stack.split_off(frame.stack.len() - dynamics.len());
+                indices.reverse();
+                let varname: String = function.slot_names[*slot].to_string();
+                let data: &VariableData = &frame.slots[*slot];
+                if data.value.is_none() {
+                    return Err(MascalError {
+                        error_type: MascalErrorType::RuntimeError,
+                        span: None,
+                        source: format!(
+                            "Cannot assign an array to a uninitialized variable {}",
+                            varname
+                        ),
+                    });
+                }
+                let layers: Vec<(MascalValue, bool)> =
+                    indices.into_iter().zip(dynamics.iter().copied()).collect();
+                assign_into_array(&varname, data, &layers, rhs)?;
+            }
+
+            Instruction::Pop => {
+                frame.stack.pop();
+            }
+
+            Instruction::Unary(operator) => {
+                let value: MascalValue = frame.stack.pop().unwrap();
+                frame
+                    .stack
+                    .push(apply_unary_operator(operator.clone(), value)?);
+            }
+
+            Instruction::Binary(operator) => {
+                let right: MascalValue = frame.stack.pop().unwrap();
+                let left: MascalValue = frame.stack.pop().unwrap();
+                frame
+                    .stack
+                    .push(apply_binary_operator(operator.clone(), left, right)?);
+            }
+
+            Instruction::MakeArray { length, is_dynamic } => {
+                let elements: Vec<Rc<RefCell<Option<MascalValue>>>> = frame
+                    .stack
+                    .split_off(frame.stack.len() - length)
+                    .into_iter()
+                    .map(|element| Rc::new(RefCell::new(Some(element))))
+                    .collect();
+                frame.stack.push(if *is_dynamic {
+                    MascalValue::DynamicArray(elements)
+                } else {
+                    MascalValue::StaticArray(elements.into_boxed_slice())
+                });
+            }
+
+            Instruction::Index { is_dynamic } => {
+                let index_value: MascalValue = frame.stack.pop().unwrap();
+                let array_value: MascalValue = frame.stack.pop().unwrap();
+                frame.stack.push(index_array(
+                    array_value,
+                    index_value,
+                    *is_dynamic,
+                    function.spans[*pc - 1],
+                )?);
+            }
+
+            Instruction::Cast(mascal_type) => {
+                let value: MascalValue = frame.stack.pop().unwrap();
+                frame
+                    .stack
+                    .push(execute_processed_typecast(mascal_type.clone(), value)?);
+            }
+
+            Instruction::ExpectCallable {
+                argument_count,
+                call_span,
+            } => match frame.stack.last() {
+                Some(MascalValue::Function(_)) => {}
+                Some(MascalValue::Type(_)) if *argument_count == 1 => {}
+                Some(MascalValue::Type(_)) => {
+                    return Err(MascalError {
+                        error_type: MascalErrorType::ArgumentError,
+                        span: Some(*call_span),
+                        source: String::from("Expected one value but got none or multiple values"),
+                    });
+                }
+                _ => {
+                    return Err(MascalError {
+                        error_type: MascalErrorType::TypeError,
+                        span: None,
+                        source: String::from(
+                            "Expected an identifier for the function call but got a expression",
+                        ),
+                    });
+                }
+            },
+
+            Instruction::LoadCallee(slot) => {
+                let value: Option<MascalValue> = frame.slots[*slot]
+                    .value
+                    .as_ref()
+                    .map(|value| value.borrow().clone());
+                let Some(function @ MascalValue::Function(_)) = value else {
+                    return Err(MascalError {
+                        error_type: MascalErrorType::RuntimeError,
+                        span: None,
+                        source: format!(
+                            "Unidentified function with the name of {:?}",
+                            function.slot_names[*slot]
+                        ),
+                    });
+                };
+                frame.stack.push(function);
+            }
+
+            Instruction::CallValue { argument_spans } => {
+                let arguments: Vec<MascalValue> = frame
+                    .stack
+                    .split_off(frame.stack.len() - argument_spans.len());
+                let value: MascalValue = match frame.stack.pop().unwrap() {
+                    MascalValue::Function(callee) => {
+                        self.call_function_value(&callee, arguments, function.spans[*pc - 1])?
+                    }
+                    MascalValue::Type(mascal_type) => {
+                        let argument: MascalValue = arguments.into_iter().next().unwrap();
+                        execute_processed_typecast(mascal_type, argument)
+                            .map_err(|err| err.located_at(argument_spans[0]))?
+                    }
+                    _ => unreachable!(),
+                };
+                frame.stack.push(value);
+            }
+
+            Instruction::MakeLambda { parameters, body } => {
+                /* Lambdas keep the variables as they were, later assignments don't reach them */
+                let captured_variables: VariableTable = function
+                    .slot_names
+                    .iter()
+                    .zip(&frame.slots)
+                    .map(|(name, data)| {
+                        let value: Option<Rc<RefCell<MascalValue>>> = data
+                            .value
+                            .as_ref()
+                            .map(|value| Rc::new(RefCell::new(value.borrow().clone())));
+                        (
+                            name.clone(),
+                            VariableData {
+                                value,
+                                ..data.clone()
+                            },
+                        )
+                    })
+                    .collect();
+                frame
+                    .stack
+                    .push(MascalValue::Function(MascalFunction::Lambda(Rc::new(
+                        MascalLambda {
+                            parameters: parameters.clone(),
+                            body: (**body).clone(),
+                            captured_variables,
+                        },
+                    ))));
+            }
+
+            Instruction::CallBuiltin {
+                builtin,
+                argument_spans,
+            } => {
+                let BuiltinFunction::ValueBased {
+                    fixed_argument_types,
+                    supports_dynamic_arguments,
+                    execution,
+                } = &self.program.builtins[*builtin]
+                else {
+                    unreachable!()
+                };
+                let arguments: Vec<MascalValue> = frame
+                    .stack
+                    .split_off(frame.stack.len() - argument_spans.len());
+                for (index, argument) in arguments.iter().enumerate() {
+                    check_builtin_argument(
+                        fixed_argument_types,
+                        *supports_dynamic_arguments,
+                        index,
+                        arguments.len(),
+                        argument,
+                        Some(argument_spans[index]),
+                    )?;
+                }
+                let value: Option<MascalValue> = execution(arguments, &mut self.execution_data())?;
+                frame.stack.push(value.unwrap_or(MascalValue::Null));
+            }
+
+            Instruction::InstantiateRecord(name) => {
+                frame.stack.push(instantiate_record(
+                    name,
+                    &self.io,
+                    &self.native_functions,
+                    &self.symbol_table,
+                )?);
+            }
+
+            Instruction::Read(slot) => {
+                read_into_variable(
+                    &function.slot_names[*slot],
+                    &mut frame.slots[*slot],
+                    &self.io,
+                )?;
+            }
+
+            Instruction::Swap(first, second) => {
+                frame.slots.swap(*first, *second);
+            }
+
+            Instruction::Call {
+                function: callee_index,
+                argument_count,
+                write_backs,
+            } => {
+                let callee: &CompiledFunction = &self.program.functions[*callee_index];
+                let arguments: Vec<MascalValue> =
+                    frame.stack.split_off(frame.stack.len() - argument_count);
+                let (value, callee_frame) = self.call_compiled(callee, arguments)?;
+                for (parameter, slot) in write_backs.iter() {
+                    frame.slots[*slot].value = callee_frame.slots
+                        [callee.parameter_slots[*parameter]]
+                        .value
+                        .clone();
+                }
+                frame.stack.push(value);
+            }
+
+            Instruction::Jump(target) => {
+                *pc = *target;
+            }
+
+            Instruction::JumpIfFalse(target) => match frame.stack.pop().unwrap() {
+                MascalValue::Boolean(true) => {}
+                MascalValue::Boolean(false) => *pc = *target,
+                value => {
+                    return Err(MascalError {
+                        error_type: MascalErrorType::RuntimeError,
+                        span: None,
+                        source: format!(
+                            "Expected a boolean variable on the condition but got {:?}",
+                            value.as_string()?
+                        ),
+                    });
+                }
+            },
+
+            Instruction::ShortCircuit { operator, exit } => {
+                if settles_without_right(operator, frame.stack.last().unwrap()) {
+                    *pc = *exit;
+                }
+            }
+
+            Instruction::StoreRegister(register) => {
+                frame.registers[*register] = frame.stack.pop().unwrap();
+            }
+
+            Instruction::LoadRegister(register) => {
+                frame.stack.push(frame.registers[*register].clone());
+            }
+
+            Instruction::CheckForBound(slot) => {
+                check_for_bound(
+                    frame.stack.last().unwrap(),
+                    &frame.slots[*slot].atomic_variable_type,
+                    &function.slot_names[*slot],
+                    function.spans[*pc - 1],
+                )?;
+            }
+
+            Instruction::CheckForStep => {
+                let step: &MascalValue = frame.stack.last().unwrap();
+                let is_zero: bool = match step {
+                    MascalValue::Integer(step) => step.to_i128() == 0,
+                    step => step.extract_as_float().unwrap() == 0.0,
+                };
+                if is_zero {
+                    return Err(zero_step_error(function.spans[*pc - 1]));
+                }
+            }
+
+            Instruction::ForCondition {
+                counter,
+                limit,
+                step,
+                exit,
+            } => {
+                let is_within_limit: bool = match (
+                    &frame.registers[*counter],
+                    &frame.registers[*limit],
+                    &frame.registers[*step],
+                ) {
+                    (
+                        MascalValue::Integer(curr),
+                        MascalValue::Integer(limit),
+                        MascalValue::Integer(step),
+                    ) if step.to_i128() < 0 => curr.to_i128() >= limit.to_i128(),
+                    (MascalValue::Integer(curr), MascalValue::Integer(limit), _) => {
+                        curr.to_i128() <= limit.to_i128()
+                    }
+                    (curr, limit, step) if step.extract_as_float().unwrap() < 0.0 => {
+                        curr.extract_as_float().unwrap() >= limit.extract_as_float().unwrap()
+                    }
+                    (curr, limit, _) => {
+                        curr.extract_as_float().unwrap() <= limit.extract_as_float().unwrap()
+                    }
+                };
+                if !is_within_limit {
+                    *pc = *exit;
+                }
+            }
+
+            Instruction::StoreLoopVariable { slot, counter } => {
+                let value: MascalValue = frame.registers[*counter].clone();
+                let data: &mut VariableData = &mut frame.slots[*slot];
+                match &data.value {
+                    Some(cell) => *cell.borrow_mut() = value,
+                    None => data.value = Some(Rc::new(RefCell::new(value))),
+                }
+            }
+
+            Instruction::ForStep { counter, step } => {
+                let next: MascalValue = match (&frame.registers[*counter], &frame.registers[*step])
+                {
+                    (MascalValue::Integer(curr), MascalValue::Integer(step)) => {
+                        MascalValue::Integer(IntegerNum::new(curr.to_i128() + step.to_i128()))
+                    }
+                    (curr, step) => MascalValue::Float(
+                        curr.extract_as_float().unwrap() + step.extract_as_float().unwrap(),
+                    ),
+                };
+                frame.registers[*counter] = next;
+            }
+
+            Instruction::ForEachStart { items, counter } => {
+                let elements: Vec<Rc<RefCell<Option<MascalValue>>>> =
+                    match frame.stack.pop().unwrap() {
+                        MascalValue::StaticArray(cells) => cells.into_vec(),
+                        MascalValue::DynamicArray(cells) => cells,
+                        MascalValue::String(string) => string
+                            .chars()
+                            .map(|char| {
+                                Rc::new(RefCell::new(Some(MascalValue::String(Arc::from(
+                                    char.to_string(),
+                                )))))
+                            })
+                            .collect(),
+                        other => {
+                            return Err(MascalError {
+                                error_type: MascalErrorType::TypeError,
+                                span: None,
+                                source: format!(
+                                    "Expected an array or a string to loop over but got {}",
+                                    other.as_type_string()?
+                                ),
+                            });
+                        }
+                    };
+                frame.registers[*items] = MascalValue::DynamicArray(elements);
+                frame.registers[*counter] = MascalValue::Integer(IntegerNum::new(0));
+            }
+
+            Instruction::ForEachNext {
+                items,
+                counter,
+                exit,
+            } => {
+                let (MascalValue::DynamicArray(elements), MascalValue::Integer(index)) =
+                    (&frame.registers[*items], &frame.registers[*counter])
+                else {
+                    unreachable!()
+                };
+                let Some(element) = elements.get(index.to_i128() as usize) else {
+                    *pc = *exit;
+                    return Ok(None);
+                };
+                let Some(element) = element.borrow().clone() else {
+                    uninit_cell_error!();
+                };
+                frame.stack.push(element);
+            }
+
+            Instruction::ForEachStep(counter) => {
+                let MascalValue::Integer(index) = &frame.registers[*counter] else {
+                    unreachable!()
+                };
+                frame.registers[*counter] =
+                    MascalValue::Integer(IntegerNum::new(index.to_i128() + 1));
+            }
+
+            Instruction::MatchValue { subject, matched } => {
+                let value: MascalValue = frame.stack.pop().unwrap();
+                if matches!(
+                    MascalValue::equals(&frame.registers[*subject], &value)?,
+                    MascalValue::Boolean(true)
+                ) {
+                    *pc = *matched;
+                }
+            }
+
+            Instruction::MatchRange { subject, matched } => {
+                let to: MascalValue = frame.stack.pop().unwrap();
+                let from: MascalValue = frame.stack.pop().unwrap();
+                let subject: &MascalValue = &frame.registers[*subject];
+                if matches!(
+                    (
+                        MascalValue::less_than_or_equal(&from, subject),
+                        MascalValue::less_than_or_equal(subject, &to)
+                    ),
+                    (
+                        Ok(MascalValue::Boolean(true)),
+                        Ok(MascalValue::Boolean(true))
+                    )
+                ) {
+                    *pc = *matched;
+                }
+            }
+
+            Instruction::MatchType { subject, matched } => {
+                let is_matching: bool = match frame.stack.pop().unwrap() {
+                    MascalValue::Type(MascalType::Dynamic) => true,
+                    MascalValue::Type(pattern_type) => frame.registers[*subject]
+                        .as_mascal_type()
+                        .is_ok_and(|subject_type| subject_type == pattern_type),
+                    other => {
+                        return Err(MascalError {
+                            error_type: MascalErrorType::TypeError,
+                            span: None,
+                            source: format!(
+                                "Expected a type for the case to check against but got {}",
+                                other.as_type_string()?
+                            ),
+                        });
+                    }
+                };
+                if is_matching {
+                    *pc = *matched;
+                }
+            }
+
+            Instruction::Return => {
+                return Ok(Some(Some(frame.stack.pop().unwrap())));
+            }
+
+            Instruction::ReturnNothing => {
+                return Ok(Some(None));
+            }
+
+            Instruction::Throw {
+                error_type,
+                message_span,
+            } => {
+                let message: MascalValue = frame.stack.pop().unwrap();
+                return Err(thrown_error(
+                    error_type.clone(),
+                    message,
+                    function.spans[*pc - 1],
+                    *message_span,
+                ));
+            }
+
+            Instruction::Fail {
+                error_type,
+                message,
+            } => {
+                return Err(MascalError {
+                    error_type: error_type.clone(),
+                    span: None,
+                    source: message.to_string(),
+                });
+            }
+        }
+        Ok(None)
+    }
+}
@@ -7,7 +7,9 @@ pub mod errors;
 pub mod expressions;
 pub mod literal;
 pub mod operators;
+pub mod span;
 pub mod statements;
+pub mod symbol_table;
 pub mod token;
 pub mod types;
 
@@ -1,6 +1,7 @@
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
 use crate::defs::literal::MascalLiteral;
 use crate::defs::operators::{MascalBinaryOperators, MascalUnaryOperators};
+use crate::defs::span::Span;
 
 #[derive(Hash, Clone)]
 pub struct BindingPower {
@@ -17,14 +18,14 @@ impl BindingPower {
     }
 }
 
-pub fn get_binding_power(expression: MascalExpression) -> BindingPower {
+pub fn get_binding_power(expression: MascalExpressionKind) -> BindingPower {
     match expression {
-        MascalExpression::Call { .. } => BindingPower::new(100),
-        MascalExpression::Unary { .. } => BindingPower {
+        MascalExpressionKind::Call { .. } => BindingPower::new(100),
+        MascalExpressionKind::Unary { .. } => BindingPower {
             left_binding_power: 0,
             right_binding_power: 90,
         },
-        MascalExpression::Binary { operator, .. } => match operator {
+        MascalExpressionKind::Binary { operator, .. } => match operator {
             MascalBinaryOperators::Exponentiation => BindingPower::new(80),
 
             MascalBinaryOperators::Divide
@@ -45,21 +46,39 @@ pub fn get_binding_power(expression: MascalExpression) -> BindingPower {
 
             _ => BindingPower::new(60),
         },
+        /* The branches take in every operator after them, so nothing binds looser than this */
+        MascalExpressionKind::Conditional { .. } => BindingPower::new(10),
         _ => BindingPower::new(0),
     }
 }
 
+#[inline(always)]
+fn null_placeholder() -> MascalExpression {
+    MascalExpression::new(
+        MascalExpressionKind::Literal(MascalLiteral::Null),
+        Span::default(),
+    )
+}
+
 pub fn get_binding_power_from_bsign(sign: MascalBinaryOperators) -> BindingPower {
-    get_binding_power(MascalExpression::Binary {
-        left: Box::new(MascalExpression::Literal(MascalLiteral::Null)),
+    get_binding_power(MascalExpressionKind::Binary {
+        left: Box::new(null_placeholder()),
         operator: sign,
-        right: Box::new(MascalExpression::Literal(MascalLiteral::Null)),
+        right: Box::new(null_placeholder()),
     })
 }
 
 pub fn get_binding_power_from_psign(sign: MascalUnaryOperators) -> BindingPower {
-    get_binding_power(MascalExpression::Unary {
-        value: Box::new(MascalExpression::Literal(MascalLiteral::Null)),
+    get_binding_power(MascalExpressionKind::Unary {
+        value: Box::new(null_placeholder()),
         operator: sign,
     })
 }
+
+pub fn get_binding_power_of_conditional() -> BindingPower {
+    get_binding_power(MascalExpressionKind::Conditional {
+        condition: Box::new(null_placeholder()),
+        then_branch: Box::new(null_placeholder()),
+        else_branch: Box::new(null_placeholder()),
+    })
+}
@@ -1,4 +1,5 @@
 use crate::defs::declerations::MascalVariableInitialDeclaration;
+use crate::defs::span::Span;
 use crate::defs::statements::MascalStatement;
 use crate::defs::types::MascalUnprocessedType;
 use std::rc::Rc;
@@ -11,6 +12,18 @@ pub struct VariableBlock {
     pub booleans: Box<[MascalVariableInitialDeclaration]>,
     pub dynamics: Box<[MascalVariableInitialDeclaration]>,
     pub types: Box<[MascalVariableInitialDeclaration]>,
+    pub custom_types: Box<[CustomTypeVariableBlock]>,
+}
+
+/*
+ The variables declared under a type that has no keyword block of its own, such as a record's
+ name ``Point {a; b;}`` or a map ``MAP<STRING, INTEGER> {a; b;}``
+*/
+#[derive(Debug, Clone)]
+pub struct CustomTypeVariableBlock {
+    pub variable_type: MascalUnprocessedType,
+    pub variables: Box<[MascalVariableInitialDeclaration]>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -23,6 +36,7 @@ pub struct ExecutionBlock {
 pub struct MascalParameter {
     pub name: Rc<str>,
     pub is_mutable: bool,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -33,6 +47,17 @@ pub enum ScopedBlocks {
         name: String,
         return_type: Option<MascalUnprocessedType>,
         execution_block: ExecutionBlock,
+        span: Span,
+    },
+    Record {
+        name: String,
+        variables: VariableBlock,
+        span: Span,
+    },
+    Enum {
+        name: String,
+        variants: Box<[String]>,
+        span: Span,
     },
 }
 
@@ -44,6 +69,7 @@ impl VariableBlock {
         strings: Vec<MascalVariableInitialDeclaration>,
         dynamics: Vec<MascalVariableInitialDeclaration>,
         types: Vec<MascalVariableInitialDeclaration>,
+        custom_types: Vec<CustomTypeVariableBlock>,
     ) -> Self {
         VariableBlock {
             integers: integers.into_boxed_slice(),
@@ -52,6 +78,7 @@ impl VariableBlock {
             strings: strings.into_boxed_slice(),
             dynamics: dynamics.into_boxed_slice(),
             types: types.into_boxed_slice(),
+            custom_types: custom_types.into_boxed_slice(),
         }
     }
 
@@ -64,6 +91,11 @@ impl VariableBlock {
             .chain(self.booleans.iter())
             .chain(self.dynamics.iter())
             .chain(self.types.iter())
+            .chain(
+                self.custom_types
+                    .iter()
+                    .flat_map(|block| block.variables.iter()),
+            )
             .collect()
     }
 }
@@ -1,2 +1,2 @@
 pub mod builtin_functions;
-mod utils;
+pub(crate) mod utils;
@@ -1,25 +1,30 @@
-use crate::defs::builtins::utils::{flatten_impl, sum_internal};
+use crate::defs::builtins::utils::{
+    ArrayCell, array_cells, call_callback, call_predicate, cell_value, copy_cells, flatten_impl,
+    merge_sort, read_into_variable, rebuild_array, sum_internal,
+};
 use crate::defs::dynamic_int::IntegerNum;
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
-use crate::defs::types::{MascalType, MascalTypeKind};
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
+use crate::defs::types::MascalTypeKind;
 use crate::runtime::ExecutionData;
 use crate::runtime::execute_typecast::execute_processed_typecast;
 use crate::runtime::utils::{get_dimensions, get_sizes};
 use crate::runtime::values::MascalValue;
+use crate::runtime::values::value_map::MapKey;
 use crate::{check_boundaries, join_array_impl, min_max_common_operation, uninit_cell_error};
-use once_cell::sync::Lazy;
 use rand::Rng;
 use rand::seq::SliceRandom;
 use rustc_hash::FxHashMap;
 use std::cell::RefCell;
-use std::io;
-use std::num::{ParseFloatError, ParseIntError};
 use std::rc::Rc;
 use std::sync::Arc;
 
-type BuiltinValueBased =
-    fn(Vec<MascalValue>, &mut ExecutionData) -> Result<Option<MascalValue>, MascalError>;
+/*
+ Value based functions are reference counted closures rather than plain function pointers, that
+ way the host embedding the interpreter can hand over functions that capture their own state
+*/
+pub type BuiltinValueBased =
+    Rc<dyn Fn(Vec<MascalValue>, &mut ExecutionData) -> Result<Option<MascalValue>, MascalError>>;
 type BuiltinExpressionBased = fn(
     Vec<&MascalExpression>,
     &mut ExecutionData,
@@ -41,15 +46,16 @@ pub enum BuiltinFunction {
 }
 
 impl BuiltinFunction {
-    fn new_value_based(
+    pub fn new_value_based(
         supports_dynamic_arguments: bool,
         fixed_argument_types: Vec<Vec<MascalTypeKind>>,
-        execution: BuiltinValueBased,
+        execution: impl Fn(Vec<MascalValue>, &mut ExecutionData) -> Result<Option<MascalValue>, MascalError>
+        + 'static,
     ) -> Self {
         BuiltinFunction::ValueBased {
             supports_dynamic_arguments,
             fixed_argument_types,
-            execution,
+            execution: Rc::new(execution),
         }
     }
 
@@ -77,7 +83,18 @@ macro_rules! define_builtin_function {
     };
 }
 
-pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = Lazy::new(|| {
+pub type NativeFunctionTable = Rc<FxHashMap<String, BuiltinFunction>>;
+
+thread_local! {
+    pub static BUILT_IN_FUNCTION_TABLE: FxHashMap<String, BuiltinFunction> =
+        create_builtin_function_table();
+}
+
+pub fn is_builtin_function(name: &str) -> bool {
+    BUILT_IN_FUNCTION_TABLE.with(|table| table.contains_key(&name.to_lowercase()))
+}
+
+fn create_builtin_function_table() -> FxHashMap<String, BuiltinFunction> {
     let mut map: FxHashMap<String, BuiltinFunction> = FxHashMap::default();
     define_builtin_function!(
         BuiltinFunction::new_value_based,
@@ -85,11 +102,19 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
         map,
         vec![],
         true,
-        |args, _| {
+        |args, exec_data| {
+            let mut text: String = String::new();
             for val in &args[..args.len() - 1] {
-                print!("{} ", val.as_string()?);
+                text.push_str(&format!("{} ", val.as_string()?));
+            }
+            text.push_str(&format!("{}\n", args.last().unwrap().as_string()?));
+            if exec_data.io.borrow_mut().write(&text).is_err() {
+                return Err(MascalError {
+                    error_type: MascalErrorType::RuntimeError,
+                    span: None,
+                    source: String::from("Could not write the output"),
+                });
             }
-            println!("{}", args.last().unwrap().as_string()?);
             Ok(None)
         }
     );
@@ -377,8 +402,7 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
             if (min < 0 || min >= main_str_len) || (max < 0 || max >= main_str_len) || min > max {
                 return Err(MascalError {
                     error_type: MascalErrorType::IndexError,
-                    line: 0,
-                    character: 0,
+                    span: None,
                     source: format!(
                         "Cannot take a substring in the range of from {} to {}",
                         min, max
@@ -590,12 +614,12 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
             match &args[0] {
                 MascalValue::StaticArray(values) => {
                     for (index, value) in values.iter().enumerate() {
-                        if let Some(unwrapped_value) = &*value.borrow() {
-                            if unwrapped_value.is_equal(&args[1]) {
-                                return Ok(Some(MascalValue::Integer(IntegerNum::new(
-                                    index as i128,
-                                ))));
-                            }
+                        if let Some(unwrapped_value) = &*value.borrow()
+                            && unwrapped_value.is_equal(&args[1])
+                        {
+                            return Ok(Some(MascalValue::Integer(IntegerNum::new(
+                                index as i128,
+                            ))));
                         }
                     }
                     Ok(Some(MascalValue::Integer(IntegerNum::I8(-1))))
@@ -603,12 +627,12 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
 
                 MascalValue::DynamicArray(values) => {
                     for (index, value) in values.iter().enumerate() {
-                        if let Some(unwrapped_value) = &*value.borrow() {
-                            if unwrapped_value.is_equal(&args[1]) {
-                                return Ok(Some(MascalValue::Integer(IntegerNum::new(
-                                    index as i128,
-                                ))));
-                            }
+                        if let Some(unwrapped_value) = &*value.borrow()
+                            && unwrapped_value.is_equal(&args[1])
+                        {
+                            return Ok(Some(MascalValue::Integer(IntegerNum::new(
+                                index as i128,
+                            ))));
                         }
                     }
                     Ok(Some(MascalValue::Integer(IntegerNum::I8(-1))))
@@ -646,10 +670,10 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
                 MascalValue::StaticArray(values) => {
                     let mut counter: usize = 0;
                     for value in values.iter() {
-                        if let Some(unwrapped_value) = &*value.borrow() {
-                            if unwrapped_value.is_equal(&args[1]) {
-                                counter += 1;
-                            }
+                        if let Some(unwrapped_value) = &*value.borrow()
+                            && unwrapped_value.is_equal(&args[1])
+                        {
+                            counter += 1;
                         }
                     }
                     Ok(Some(MascalValue::Integer(IntegerNum::new(counter as i128))))
@@ -657,10 +681,10 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
                 MascalValue::DynamicArray(values) => {
                     let mut counter: usize = 0;
                     for value in values.iter() {
-                        if let Some(unwrapped_value) = &*value.borrow() {
-                            if unwrapped_value.is_equal(&args[1]) {
-                                counter += 1;
-                            }
+                        if let Some(unwrapped_value) = &*value.borrow()
+                            && unwrapped_value.is_equal(&args[1])
+                        {
+                            counter += 1;
                         }
                     }
                     Ok(Some(MascalValue::Integer(IntegerNum::new(counter as i128))))
@@ -774,13 +798,15 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
         map,
         vec![vec![
             MascalTypeKind::StaticArray,
-            MascalTypeKind::DynamicArray
+            MascalTypeKind::DynamicArray,
+            MascalTypeKind::Map
         ]],
         false,
         |args, _exec_data| {
             let length: usize = match args.first().unwrap() {
                 MascalValue::StaticArray(v) => v.len(),
                 MascalValue::DynamicArray(v) => v.len(),
+                MascalValue::Map(m) => m.entries.borrow().len(),
                 _ => {
                     unreachable!()
                 }
@@ -824,8 +850,7 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
             if args.len() == 1 {
                 return Err(MascalError {
                     error_type: MascalErrorType::ArgumentError,
-                    line: 0,
-                    character: 0,
+                    span: None,
                     source: String::from("Expected a value (element) to be pushed to the array"),
                 });
             }
@@ -851,8 +876,7 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
             if args.len() == 1 {
                 return Err(MascalError {
                     error_type: MascalErrorType::ArgumentError,
-                    line: 0,
-                    character: 0,
+                    span: None,
                     source: String::from(
                         "Expected an array of elements to be appended to the array",
                     ),
@@ -867,8 +891,7 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
                         _ => {
                             return Err(MascalError {
                                 error_type: MascalErrorType::ArgumentError,
-                                line: 0,
-                                character: 0,
+                                span: None,
                                 source: String::from(
                                     "Expected an array to push but got an atomic type",
                                 ),
@@ -952,6 +975,241 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
         }
     );
 
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "Keys",
+        map,
+        vec![vec![MascalTypeKind::Map]],
+        false,
+        |args, _exec_data| {
+            let MascalValue::Map(m) = &args[0] else {
+                unreachable!()
+            };
+            let keys = m
+                .entries
+                .borrow()
+                .iter()
+                .map(|(key, _)| Rc::new(RefCell::new(Some(key.clone()))))
+                .collect();
+            Ok(Some(MascalValue::DynamicArray(keys)))
+        }
+    );
+
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "Values",
+        map,
+        vec![vec![MascalTypeKind::Map]],
+        false,
+        |args, _exec_data| {
+            let MascalValue::Map(m) = &args[0] else {
+                unreachable!()
+            };
+            let values = m
+                .entries
+                .borrow()
+                .iter()
+                .map(|(_, value)| Rc::new(RefCell::new(Some(value.clone()))))
+                .collect();
+            Ok(Some(MascalValue::DynamicArray(values)))
+        }
+    );
+
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "Has_Key",
+        map,
+        vec![vec![MascalTypeKind::Map], vec![MascalTypeKind::Dynamic]],
+        false,
+        |args, _exec_data| {
+            let MascalValue::Map(m) = &args[0] else {
+                unreachable!()
+            };
+            let key: MapKey = MapKey::new(args[1].clone())?;
+            Ok(Some(MascalValue::Boolean(m.entries.borrow().contains_key(&key))))
+        }
+    );
+
+    /* Removes the entry in place, handing back the value it had (or NULL if there was none) */
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "Remove_Key",
+        map,
+        vec![vec![MascalTypeKind::Map], vec![MascalTypeKind::Dynamic]],
+        false,
+        |args, _exec_data| {
+            let MascalValue::Map(m) = &args[0] else {
+                unreachable!()
+            };
+            let key: MapKey = MapKey::new(args[1].clone())?;
+            let removed: Option<MascalValue> = m.entries.borrow_mut().remove(&key);
+            Ok(Some(removed.unwrap_or(MascalValue::Null)))
+        }
+    );
+
+    /*
+     The higher order ones below call back into the program for every element, they all hand back
+     fresh arrays and leave the one they were given untouched
+    */
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "Map",
+        map,
+        vec![
+            vec![MascalTypeKind::StaticArray, MascalTypeKind::DynamicArray],
+            vec![MascalTypeKind::Function]
+        ],
+        false,
+        |args, exec_data| {
+            let mut mapped: Vec<ArrayCell> = Vec::with_capacity(array_cells(&args[0]).len());
+            for cell in array_cells(&args[0]) {
+                let result: MascalValue =
+                    call_callback(&args[1], vec![cell_value(cell)?], exec_data)?;
+                mapped.push(Rc::new(RefCell::new(Some(result))));
+            }
+            Ok(Some(rebuild_array(&args[0], mapped)))
+        }
+    );
+
+    /* Always a dynamic array, there is no telling how many elements are kept beforehand */
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "Filter",
+        map,
+        vec![
+            vec![MascalTypeKind::StaticArray, MascalTypeKind::DynamicArray],
+            vec![MascalTypeKind::Function]
+        ],
+        false,
+        |args, exec_data| {
+            let mut kept: Vec<ArrayCell> = Vec::new();
+            for cell in array_cells(&args[0]) {
+                let value: MascalValue = cell_value(cell)?;
+                if call_predicate(&args[1], vec![value.clone()], exec_data)? {
+                    kept.push(Rc::new(RefCell::new(Some(value))));
+                }
+            }
+            Ok(Some(MascalValue::DynamicArray(kept)))
+        }
+    );
+
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "Reduce",
+        map,
+        vec![
+            vec![MascalTypeKind::StaticArray, MascalTypeKind::DynamicArray],
+            vec![MascalTypeKind::Function],
+            vec![MascalTypeKind::Dynamic]
+        ],
+        false,
+        |args, exec_data| {
+            let mut accumulator: MascalValue = args[2].clone();
+            for cell in array_cells(&args[0]) {
+                accumulator =
+                    call_callback(&args[1], vec![accumulator, cell_value(cell)?], exec_data)?;
+            }
+            Ok(Some(accumulator))
+        }
+    );
+
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "Sort",
+        map,
+        vec![vec![
+            MascalTypeKind::StaticArray,
+            MascalTypeKind::DynamicArray
+        ]],
+        false,
+        |args, _exec_data| {
+            let sorted: Vec<ArrayCell> = merge_sort(copy_cells(&args[0])?, &mut |left, right| {
+                Ok(matches!(
+                    MascalValue::less_than(left, right)?,
+                    MascalValue::Boolean(true)
+                ))
+            })?;
+            Ok(Some(rebuild_array(&args[0], sorted)))
+        }
+    );
+
+    /* The function is asked whether its first argument belongs before the second one */
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "Sort_By",
+        map,
+        vec![
+            vec![MascalTypeKind::StaticArray, MascalTypeKind::DynamicArray],
+            vec![MascalTypeKind::Function]
+        ],
+        false,
+        |args, exec_data| {
+            let sorted: Vec<ArrayCell> = merge_sort(copy_cells(&args[0])?, &mut |left, right| {
+                call_predicate(&args[1], vec![left.clone(), right.clone()], exec_data)
+            })?;
+            Ok(Some(rebuild_array(&args[0], sorted)))
+        }
+    );
+
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "Any",
+        map,
+        vec![
+            vec![MascalTypeKind::StaticArray, MascalTypeKind::DynamicArray],
+            vec![MascalTypeKind::Function]
+        ],
+        false,
+        |args, exec_data| {
+            for cell in array_cells(&args[0]) {
+                if call_predicate(&args[1], vec![cell_value(cell)?], exec_data)? {
+                    return Ok(Some(MascalValue::Boolean(true)));
+                }
+            }
+            Ok(Some(MascalValue::Boolean(false)))
+        }
+    );
+
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "All",
+        map,
+        vec![
+            vec![MascalTypeKind::StaticArray, MascalTypeKind::DynamicArray],
+            vec![MascalTypeKind::Function]
+        ],
+        false,
+        |args, exec_data| {
+            for cell in array_cells(&args[0]) {
+                if !call_predicate(&args[1], vec![cell_value(cell)?], exec_data)? {
+                    return Ok(Some(MascalValue::Boolean(false)));
+                }
+            }
+            Ok(Some(MascalValue::Boolean(true)))
+        }
+    );
+
+    /* Gives back the first element the function agrees with, or NULL when there is none */
+    define_builtin_function!(
+        BuiltinFunction::new_value_based,
+        "Find",
+        map,
+        vec![
+            vec![MascalTypeKind::StaticArray, MascalTypeKind::DynamicArray],
+            vec![MascalTypeKind::Function]
+        ],
+        false,
+        |args, exec_data| {
+            for cell in array_cells(&args[0]) {
+                let value: MascalValue = cell_value(cell)?;
+                if call_predicate(&args[1], vec![value.clone()], exec_data)? {
+                    return Ok(Some(value));
+                }
+            }
+            Ok(Some(MascalValue::Null))
+        }
+    );
+
     define_builtin_function!(
         BuiltinFunction::new_expresion_based,
         "Swap",
@@ -962,34 +1220,31 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
             if args.len() != 2 {
                 return Err(MascalError {
                     error_type: MascalErrorType::ArgumentError,
-                    line: 0,
-                    character: 0,
+                    span: None,
                     source: format!(
                         "Expected 2 variable names but got {} arguments instead",
                         args.len()
                     ),
                 });
             }
-            let varname1: &String = match args[0] {
-                MascalExpression::Symbolic(s) => s,
+            let varname1: &String = match &args[0].kind {
+                MascalExpressionKind::Symbolic(s) => s,
                 _ => {
                     return Err(MascalError {
                         error_type: MascalErrorType::ArgumentError,
-                        line: 0,
-                        character: 0,
+                        span: Some(args[0].span),
                         source: String::from(
                             "Expected a variable name as first argument but got something else instead",
                         ),
                     });
                 }
             };
-            let varname2: &String = match args[1] {
-                MascalExpression::Symbolic(s) => s,
+            let varname2: &String = match &args[1].kind {
+                MascalExpressionKind::Symbolic(s) => s,
                 _ => {
                     return Err(MascalError {
                         error_type: MascalErrorType::ArgumentError,
-                        line: 0,
-                        character: 0,
+                        span: Some(args[1].span),
                         source: String::from(
                             "Expected a variable name as second argument but got something else instead",
                         ),
@@ -998,6 +1253,10 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
             };
             if let Some(wrapped_vartable) = &exec_data.variable_table {
                 let mut vartable = wrapped_vartable.borrow_mut();
+                /* Swapping a variable with itself changes nothing, asking for it twice would panic */
+                if varname1 == varname2 && vartable.contains_key(varname1.as_str()) {
+                    return Ok(None);
+                }
                 if let [Some(variable_data1), Some(variable_data2)] =
                     vartable.get_disjoint_mut([varname1.as_str(), varname2.as_str()])
                 {
@@ -1006,8 +1265,7 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
                 }
                 return Err(MascalError {
                     error_type: MascalErrorType::RuntimeError,
-                    character: 0,
-                    line: 0,
+                    span: None,
                     source: String::from("Expected variable names but got at least one unknown"),
                 });
             }
@@ -1023,14 +1281,13 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
         true,
         |args, exec_data| {
             for arg in args {
-                let varname: &str = match arg {
-                    MascalExpression::Symbolic(s) => s.as_str(),
+                let varname: &str = match &arg.kind {
+                    MascalExpressionKind::Symbolic(s) => s.as_str(),
 
                     _ => {
                         return Err(MascalError {
                             error_type: MascalErrorType::RuntimeError,
-                            line: 0,
-                            character: 0,
+                            span: Some(arg.span),
                             source: String::from(
                                 "Expected an identifier for a variable name but found other expression",
                             ),
@@ -1040,91 +1297,11 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
                 let extracted_vartable = exec_data.variable_table.as_ref().unwrap();
                 let mut mutable_borrow_vartable = extracted_vartable.borrow_mut();
                 if let Some(unwrapped_vardata) = mutable_borrow_vartable.get_mut(varname) {
-                    if !unwrapped_vardata.array_dimensions.is_empty() {
-                        return Err(MascalError {
-                            error_type: MascalErrorType::RuntimeError,
-                            line: 0,
-                            character: 0,
-                            source: format!(
-                                "The variable called {:?} is an array type which is unsupported",
-                                varname
-                            ),
-                        });
-                    }
-                    let atomic_type: &MascalType = unwrapped_vardata.atomic_variable_type.as_ref();
-                    let mut input: String = String::new();
-                    let result: Result<usize, _> = io::stdin().read_line(&mut input);
-                    let input_str: &str = input.trim();
-                    if result.is_err() {
-                        return Err(MascalError {
-                            error_type: MascalErrorType::InputError,
-                            line: 0,
-                            character: 0,
-                            source: String::from("Could not read user input"),
-                        });
-                    }
-                    let read_value: MascalValue = match atomic_type {
-                        MascalType::Integer => {
-                            let int: Result<i128, ParseIntError> = input_str.parse::<i128>();
-                            if int.is_err() {
-                                return Err(MascalError {
-                                    error_type: MascalErrorType::InputError,
-                                    line: 0,
-                                    character: 0,
-                                    source: String::from(
-                                        "The user input cannot be parsed as an integer",
-                                    ),
-                                });
-                            }
-                            MascalValue::Integer(IntegerNum::new(int.unwrap()))
-                        }
-                        MascalType::Float => {
-                            let int: Result<f64, ParseFloatError> = input_str.parse::<f64>();
-                            if int.is_err() {
-                                return Err(MascalError {
-                                    error_type: MascalErrorType::InputError,
-                                    line: 0,
-                                    character: 0,
-                                    source: String::from(
-                                        "The user input cannot be parsed as a float",
-                                    ),
-                                });
-                            }
-                            MascalValue::Float(int.unwrap())
-                        }
-                        MascalType::Boolean => match input_str {
-                            "true" | "TRUE" => MascalValue::Boolean(true),
-                            "false" | "FALSE" => MascalValue::Boolean(false),
-                            _ => {
-                                return Err(MascalError {
-                                    error_type: MascalErrorType::InputError,
-                                    line: 0,
-                                    character: 0,
-                                    source: String::from(
-                                        "The user input cannot be parsed as a boolean",
-                                    ),
-                                });
-                            }
-                        },
-                        MascalType::String => MascalValue::String(Arc::from(input_str)),
-                        _ => {
-                            return Err(MascalError {
-                                error_type: MascalErrorType::TypeError,
-                                line: 0,
-                                character: 0,
-                                source: String::from(
-                                    "This type of variable is unsupported when reading a user input",
-                                ),
-                            });
-                        }
-                    };
-                    let value_ref = &mut unwrapped_vardata.value;
-                    *value_ref = Some(Rc::new(RefCell::new(read_value)));
+                    read_into_variable(varname, unwrapped_vardata, &exec_data.io)?;
                 } else {
                     return Err(MascalError {
                         error_type: MascalErrorType::RuntimeError,
-                        line: 0,
-                        character: 0,
+                        span: None,
                         source: format!("The variable name {:?} does not exist", varname),
                     });
                 }
@@ -1133,4 +1310,4 @@ pub static BUILT_IN_FUNCTION_TABLE: Lazy<FxHashMap<String, BuiltinFunction>> = L
         }
     );
     map
-});
+}
@@ -1,8 +1,18 @@
+use crate::defs::dynamic_int::IntegerNum;
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::runtime::values::MascalValue;
+use crate::defs::types::MascalType;
+use crate::runtime::ExecutionData;
+use crate::runtime::execute_function_expression::call_function_value;
+use crate::runtime::io_streams::SharedIO;
+use crate::runtime::values::{MascalFunction, MascalValue};
+use crate::runtime::variable_table::VariableData;
 use crate::uninit_cell_error;
 use std::cell::RefCell;
+use std::num::{ParseFloatError, ParseIntError};
 use std::rc::Rc;
+use std::sync::Arc;
+
+pub type ArrayCell = Rc<RefCell<Option<MascalValue>>>;
 
 pub fn flatten_impl(
     target: Rc<RefCell<Option<MascalValue>>>,
@@ -74,8 +84,7 @@ macro_rules! check_boundaries {
         if $val1 > $val2 {
             return Err(MascalError {
                 error_type: MascalErrorType::ValueError,
-                character: 0,
-                line: 0,
+                span: None,
                 source: String::from("Unallowed range between maximum value and minimum value"),
             });
         }
@@ -129,8 +138,7 @@ pub fn sum_internal(
 
         Some(val) => Err(MascalError {
             error_type: MascalErrorType::ArgumentError,
-            line: 0,
-            character: 0,
+            span: None,
             source: format!(
                 "Expected a numeric value (i.e float or integer) but got {:?}",
                 val.as_string()?
@@ -166,3 +174,188 @@ macro_rules! join_array_impl {
         return Ok(Some(MascalValue::String(Arc::from(result))));
     };
 }
+
+pub fn array_cells(array: &MascalValue) -> &[ArrayCell] {
+    match array {
+        MascalValue::StaticArray(cells) => cells,
+        MascalValue::DynamicArray(cells) => cells,
+        _ => unreachable!(),
+    }
+}
+
+pub fn cell_value(cell: &ArrayCell) -> Result<MascalValue, MascalError> {
+    match &*cell.borrow() {
+        Some(value) => Ok(value.clone()),
+        None => {
+            uninit_cell_error!()
+        }
+    }
+}
+
+/* Copies every element into a cell of its own, so the copy can be changed without touching the original */
+pub fn copy_cells(array: &MascalValue) -> Result<Vec<ArrayCell>, MascalError> {
+    array_cells(array)
+        .iter()
+        .map(|cell| Ok(Rc::new(RefCell::new(Some(cell_value(cell)?)))))
+        .collect()
+}
+
+/* Hands the cells back in the same kind of array they came from */
+pub fn rebuild_array(array: &MascalValue, cells: Vec<ArrayCell>) -> MascalValue {
+    match array {
+        MascalValue::StaticArray(_) => MascalValue::StaticArray(cells.into_boxed_slice()),
+        _ => MascalValue::DynamicArray(cells),
+    }
+}
+
+/*
+ Calls the function handed to a builtin, there is no span to give so that whatever goes wrong in
+ there gets pointed at the builtin's call instead
+*/
+pub fn call_callback(
+    function: &MascalValue,
+    arguments: Vec<MascalValue>,
+    exec_data: &mut ExecutionData,
+) -> Result<MascalValue, MascalError> {
+    let MascalValue::Function(function) = function else {
+        unreachable!()
+    };
+    call_function_value(function, arguments, None, exec_data)
+}
+
+pub fn call_predicate(
+    function: &MascalValue,
+    arguments: Vec<MascalValue>,
+    exec_data: &mut ExecutionData,
+) -> Result<bool, MascalError> {
+    match call_callback(function, arguments, exec_data)? {
+        MascalValue::Boolean(result) => Ok(result),
+        other => Err(MascalError {
+            error_type: MascalErrorType::TypeError,
+            span: None,
+            source: format!(
+                "Expected the function {} to give back a BOOLEAN but got {}",
+                describe_function(function),
+                other.as_type_string()?
+            ),
+        }),
+    }
+}
+
+fn describe_function(function: &MascalValue) -> String {
+    match function {
+        MascalValue::Function(MascalFunction::Named(name)) => format!("{:?}", name),
+        _ => String::from("passed in"),
+    }
+}
+
+/*
+ A plain merge sort rather than the standard library's, the ordering comes from the program itself
+ so it can fail halfway through or not even be consistent, neither of which std's sort tolerates
+*/
+pub fn merge_sort(
+    cells: Vec<ArrayCell>,
+    less_than: &mut impl FnMut(&MascalValue, &MascalValue) -> Result<bool, MascalError>,
+) -> Result<Vec<ArrayCell>, MascalError> {
+    if cells.len() <= 1 {
+        return Ok(cells);
+    }
+    let mut left: Vec<ArrayCell> = cells;
+    let right: Vec<ArrayCell> = left.split_off(left.len() / 2);
+    let left: Vec<ArrayCell> = merge_sort(left, less_than)?;
+    let right: Vec<ArrayCell> = merge_sort(right, less_than)?;
+
+    let mut merged: Vec<ArrayCell> = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(left_cell), Some(right_cell)) = (left.peek(), right.peek()) {
+        /* Taking from the left on ties keeps equal elements in the order they were in */
+        if less_than(&cell_value(right_cell)?, &cell_value(left_cell)?)? {
+            merged.push(right.next().unwrap());
+        } else {
+            merged.push(left.next().unwrap());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
+
+/*
+ Reads a line of input into a variable, parsed according to the variable's type. Shared by READ
+ and the bytecode VM, the latter having the variable sitting in a slot rather than a table
+*/
+pub fn read_into_variable(
+    varname: &str,
+    variable_data: &mut VariableData,
+    io: &SharedIO,
+) -> Result<(), MascalError> {
+    if !variable_data.array_dimensions.is_empty() {
+        return Err(MascalError {
+            error_type: MascalErrorType::RuntimeError,
+            span: None,
+            source: format!(
+                "The variable called {:?} is an array type which is unsupported",
+                varname
+            ),
+        });
+    }
+    let atomic_type: &MascalType = variable_data.atomic_variable_type.as_ref();
+    let result: Result<String, std::io::Error> = io.borrow_mut().read_line();
+    if result.is_err() {
+        return Err(MascalError {
+            error_type: MascalErrorType::InputError,
+            span: None,
+            source: String::from("Could not read user input"),
+        });
+    }
+    let input: String = result.unwrap();
+    let input_str: &str = input.trim();
+    let read_value: MascalValue = match atomic_type {
+        MascalType::Integer => {
+            let int: Result<i128, ParseIntError> = input_str.parse::<i128>();
+            if int.is_err() {
+                return Err(MascalError {
+                    error_type: MascalErrorType::InputError,
+                    span: None,
+                    source: String::from("The user input cannot be parsed as an integer"),
+                });
+            }
+            MascalValue::Integer(IntegerNum::new(int.unwrap()))
+        }
+        MascalType::Float => {
+            let int: Result<f64, ParseFloatError> = input_str.parse::<f64>();
+            if int.is_err() {
+                return Err(MascalError {
+                    error_type: MascalErrorType::InputError,
+                    span: None,
+                    source: String::from("The user input cannot be parsed as a float"),
+                });
+            }
+            MascalValue::Float(int.unwrap())
+        }
+        MascalType::Boolean => match input_str {
+            "true" | "TRUE" => MascalValue::Boolean(true),
+            "false" | "FALSE" => MascalValue::Boolean(false),
+            _ => {
+                return Err(MascalError {
+                    error_type: MascalErrorType::InputError,
+                    span: None,
+                    source: String::from("The user input cannot be parsed as a boolean"),
+                });
+            }
+        },
+        MascalType::String => MascalValue::String(Arc::from(input_str)),
+        _ => {
+            return Err(MascalError {
+                error_type: MascalErrorType::TypeError,
+                span: None,
+                source: String::from(
+                    "This type of variable is unsupported when reading a user input",
+                ),
+            });
+        }
+    };
+    variable_data.value = Some(Rc::new(RefCell::new(read_value)));
+    Ok(())
+}
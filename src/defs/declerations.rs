@@ -1,4 +1,5 @@
 use crate::defs::expressions::MascalExpression;
+use crate::defs::span::Span;
 use std::rc::Rc;
 
 #[derive(Debug, Clone)]
@@ -9,4 +10,5 @@ pub struct MascalVariableInitialDeclaration {
     pub dimensions: Box<[MascalExpression]>,
     pub initial_value: Option<MascalExpression>,
     pub is_dynamic_array: Box<[bool]>,
+    pub span: Span,
 }
@@ -24,8 +24,7 @@ where
     }
 
     Err(MascalError {
-        character: 0,
-        line: 0,
+        span: None,
         error_type: MascalErrorType::OverflowError,
         source: String::from("Integer overflow beyond i128 range"),
     })
@@ -88,8 +87,7 @@ impl IntegerNum {
         if other.to_i128() == 0 {
             return Err(MascalError {
                 error_type: MascalErrorType::UndefinedOperation,
-                line: 0,
-                character: 0,
+                span: None,
                 source: String::from("Division by zero"),
             });
         }
@@ -104,8 +102,7 @@ impl IntegerNum {
         }
 
         Err(MascalError {
-            character: 0,
-            line: 0,
+            span: None,
             error_type: MascalErrorType::OverflowError,
             source: String::from("Integer overflow beyond i128 range"),
         })
@@ -114,8 +111,7 @@ impl IntegerNum {
     pub fn modulo(&self, other: IntegerNum) -> Result<IntegerNum, MascalError> {
         if other.to_i128() == 0 {
             return Err(MascalError {
-                character: 0,
-                line: 0,
+                span: None,
                 error_type: MascalErrorType::UndefinedOperation,
                 source: String::from("Modulo by zero"),
             });
@@ -132,8 +128,7 @@ impl IntegerNum {
         let num = self.to_i128();
         if num < 0 {
             return Err(MascalError {
-                character: 0,
-                line: 0,
+                span: None,
                 error_type: MascalErrorType::UndefinedOperation,
                 source: String::from("Cannot get the square root of a negative number"),
             });
@@ -147,8 +142,7 @@ impl IntegerNum {
         if num <= 0 {
             return Err(MascalError {
                 error_type: MascalErrorType::UndefinedOperation,
-                line: 0,
-                character: 0,
+                span: None,
                 source: String::from(
                     "Cannot use the logarithm operation with a negative or zero value",
                 ),
@@ -181,8 +175,7 @@ impl IntegerNum {
         let self_val: i128 = self.to_i128();
         if self_val <= 0 {
             return Err(MascalError {
-                character: 0,
-                line: 0,
+                span: None,
                 error_type: MascalErrorType::UndefinedOperation,
                 source: String::from("Cannot perform exponentation with a negative or zero base"),
             });
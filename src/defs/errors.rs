@@ -1,4 +1,6 @@
+use crate::defs::span::Span;
 use std::fmt;
+use std::rc::Rc;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[repr(u8)]
@@ -15,25 +17,80 @@ pub enum MascalErrorType {
     UndefinedErrorType,
     InputError,
     ContextError,
+
+    /* Declared by the program itself inside an ERRORS block */
+    Custom(Rc<str>),
+}
+
+impl MascalErrorType {
+    /* The builtin error types a script is allowed to name, whether it is to throw or to catch them */
+    pub fn from_usable_name(name: &str) -> Option<MascalErrorType> {
+        match name {
+            "TypeError" => Some(MascalErrorType::TypeError),
+            "RuntimeError" => Some(MascalErrorType::RuntimeError),
+            "OverflowError" => Some(MascalErrorType::OverflowError),
+            "UndefinedOperationError" => Some(MascalErrorType::UndefinedOperation),
+            "IndexError" => Some(MascalErrorType::IndexError),
+            "InputError" => Some(MascalErrorType::InputError),
+            "ArgumentError" => Some(MascalErrorType::ArgumentError),
+            "ValueError" => Some(MascalErrorType::ValueError),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MascalErrorType {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MascalErrorType::Custom(name) => fmt.write_str(name),
+            _ => write!(fmt, "{:?}", self),
+        }
+    }
 }
 
-#[derive(Debug)]
+/*
+ Errors raised by position-agnostic code (value operations, builtins... etc.) have no span of
+ their own, the node that was being evaluated fills it in on the way out
+*/
+#[derive(Debug, Clone)]
 pub struct MascalError {
-    pub character: usize,
-    pub line: usize,
+    pub span: Option<Span>,
     pub error_type: MascalErrorType,
     pub source: String,
 }
 
+/*
+ Something that doesn't stop the program from running but is most likely not what was meant,
+ the semantic pass hands out most of these
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct MascalWarning {
+    pub span: Option<Span>,
+    pub source: String,
+}
+
+impl MascalError {
+    /* Only claims errors that are still missing a position, so the innermost node wins */
+    pub fn located_at(mut self, span: impl Into<Option<Span>>) -> Self {
+        if self.span.is_none() {
+            self.span = span.into();
+        }
+        self
+    }
+}
+
 impl fmt::Display for MascalError {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(span) = self.span else {
+            return write!(fmt, "\x1b[1;31m{}: {}\x1b[0m", self.error_type, self.source);
+        };
         fmt.write_str(
             format!(
-                "\x1b[1;31m{:?}: {}\nAT LINE: {}; STARTING IN CHARACTER POSITION: {}\x1b[0m",
+                "\x1b[1;31m{}: {}\nAT LINE: {}; STARTING IN CHARACTER POSITION: {}\x1b[0m",
                 self.error_type,
                 self.source,
-                self.line + 1,
-                self.character + 1
+                span.line + 1,
+                span.character + 1
             )
             .as_str(),
         )
@@ -1,14 +1,23 @@
 use crate::defs::literal::MascalLiteral;
 use crate::defs::operators::{MascalBinaryOperators, MascalUnaryOperators};
+use crate::defs::span::Span;
 use crate::defs::types::MascalUnprocessedType;
+use std::rc::Rc;
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum MascalExpression {
+pub struct MascalExpression {
+    pub kind: MascalExpressionKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MascalExpressionKind {
     // === Simple Expressions ===
     Literal(MascalLiteral),
     Symbolic(String),
     DynamicArray(Box<[MascalExpression]>),
     StaticArray(Box<[MascalExpression]>),
+    Map(Box<[(MascalExpression, MascalExpression)]>),
     Type(Box<MascalUnprocessedType>),
 
     // === Complex Expressions ===
@@ -33,4 +42,27 @@ pub enum MascalExpression {
         index: Box<MascalExpression>,
         is_dynamic: bool,
     },
+
+    FieldAccess {
+        record: Box<MascalExpression>,
+        field: String,
+    },
+
+    Lambda {
+        parameters: Box<[Rc<str>]>,
+        body: Box<MascalExpression>,
+    },
+
+    /* IF a > b THEN a ELSE b */
+    Conditional {
+        condition: Box<MascalExpression>,
+        then_branch: Box<MascalExpression>,
+        else_branch: Box<MascalExpression>,
+    },
+}
+
+impl MascalExpression {
+    pub fn new(kind: MascalExpressionKind, span: Span) -> Self {
+        MascalExpression { kind, span }
+    }
 }
@@ -1,7 +1,19 @@
-#[derive(Eq, PartialEq, Debug)]
-#[repr(u8)]
+/* A BREAK or CONTINUE naming a loop carries its label, so every loop in between lets it through */
+#[derive(Eq, PartialEq, Debug, Clone)]
 pub enum LoopFlags {
-    Continue,
-    Break,
+    Continue(Option<String>),
+    Break(Option<String>),
     None,
 }
+
+impl LoopFlags {
+    /* Flags without a label are always meant for the innermost loop */
+    pub fn is_meant_for(&self, label: Option<&str>) -> bool {
+        match self {
+            LoopFlags::Continue(target) | LoopFlags::Break(target) => {
+                target.is_none() || target.as_deref() == label
+            }
+            LoopFlags::None => false,
+        }
+    }
+}
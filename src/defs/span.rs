@@ -0,0 +1,29 @@
+use crate::defs::token::Token;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub character: usize,
+    /* Which file of the program the span points into, the entry file is always the first one */
+    pub file: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, character: usize) -> Self {
+        Span {
+            line,
+            character,
+            file: 0,
+        }
+    }
+}
+
+impl From<&Token<'_>> for Span {
+    fn from(token: &Token<'_>) -> Self {
+        Span {
+            line: token.line,
+            character: token.start,
+            file: token.file,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::defs::errors::MascalErrorType;
 use crate::defs::expressions::MascalExpression;
+use crate::defs::span::Span;
 
 #[derive(Debug, Clone)]
 pub struct MascalConditionalBranch {
@@ -7,8 +8,50 @@ pub struct MascalConditionalBranch {
     pub statements: Box<[MascalStatement]>,
 }
 
+/*
+ A single CATCH of a TRY statement, no error type means it catches anything
+*/
 #[derive(Debug, Clone)]
-pub enum MascalStatement {
+pub struct MascalCatchBranch {
+    pub error_type: Option<MascalErrorType>,
+    pub variable: Option<String>,
+    pub statements: Box<[MascalStatement]>,
+    pub span: Span,
+}
+
+/*
+ What a CASE of a MATCH statement compares against, a CASE can list several of them
+*/
+#[derive(Debug, Clone)]
+pub enum MascalCasePattern {
+    /* CASE 1, 2 */
+    Value(MascalExpression),
+
+    /* CASE 3 TO 9, both ends are included */
+    Range {
+        from: MascalExpression,
+        to: MascalExpression,
+    },
+
+    /* CASE TYPE INTEGER */
+    Type(MascalExpression),
+}
+
+#[derive(Debug, Clone)]
+pub struct MascalCaseBranch {
+    pub patterns: Vec<MascalCasePattern>,
+    pub statements: Box<[MascalStatement]>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct MascalStatement {
+    pub kind: MascalStatementKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum MascalStatementKind {
     /*
     IF a = b {
       // ...
@@ -20,8 +63,29 @@ pub enum MascalStatement {
     WHILE a = b {
       // ...
     };
+    outer: WHILE a = b {
+      // ...
+    };
     */
-    While(MascalConditionalBranch),
+    While {
+        branch: MascalConditionalBranch,
+        label: Option<String>,
+    },
+
+    /*
+    REPEAT {
+      // ...
+    } UNTIL a = b;
+    DO {
+      // ...
+    } WHILE a = b;
+    */
+    Repeat {
+        statements: Vec<MascalStatement>,
+        condition: MascalExpression,
+        is_until: bool,
+        label: Option<String>,
+    },
 
     /*
     FOR i FROM a TO b {
@@ -34,6 +98,42 @@ pub enum MascalStatement {
         to: MascalExpression,
         step: MascalExpression,
         statements: Vec<MascalStatement>,
+        label: Option<String>,
+    },
+
+    /*
+    FOR EACH item IN items {
+      // ...
+    };
+    FOR EACH i, item IN items {
+      // ...
+    };
+    */
+    ForEach {
+        variable: String,
+        index_variable: Option<String>,
+        iterable: MascalExpression,
+        statements: Vec<MascalStatement>,
+        label: Option<String>,
+    },
+
+    /*
+    MATCH a {
+      CASE 1, 2 {
+        // ...
+      }
+      CASE 3 TO 9 {
+        // ...
+      }
+      DEFAULT {
+        // ...
+      }
+    }
+    */
+    Match {
+        subject: MascalExpression,
+        cases: Vec<MascalCaseBranch>,
+        default: Option<Box<[MascalStatement]>>,
     },
 
     /*
@@ -50,16 +150,49 @@ pub enum MascalStatement {
     },
 
     /*
-    THROW RuntimeError: "...";
+    THROW RuntimeError: "..." + STRING(a);
      */
     Throw {
         error_type: MascalErrorType,
-        message: String,
+        message: MascalExpression,
     },
 
-    /* BREAK; */
-    Break,
+    /*
+    TRY {
+      // ...
+    } CATCH ValueError AS e {
+      // ...
+    } FINALLY {
+      // ...
+    }
+    */
+    Try {
+        statements: Box<[MascalStatement]>,
+        catches: Vec<MascalCatchBranch>,
+        finally: Option<Box<[MascalStatement]>>,
+    },
+
+    /*
+    BREAK;
+    BREAK outer;
+    */
+    Break(Option<String>),
+
+    /*
+    CONTINUE;
+    CONTINUE outer;
+    */
+    Continue(Option<String>),
+
+    /*
+    RETURN a * 2;
+    RETURN;
+    */
+    Return(Option<MascalExpression>),
+}
 
-    /* CONTINUE */
-    Continue,
+impl MascalStatement {
+    pub fn new(kind: MascalStatementKind, span: Span) -> Self {
+        MascalStatement { kind, span }
+    }
 }
@@ -0,0 +1,119 @@
+use crate::defs::blocks::VariableBlock;
+use crate::defs::span::Span;
+use crate::defs::types::{MascalType, MascalUnprocessedType};
+use rustc_hash::FxHashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionSymbol {
+    pub parameter_count: usize,
+    pub return_type: Option<MascalUnprocessedType>,
+    pub span: Span,
+}
+
+/* The fields of a record, every instance gets them created from scratch */
+#[derive(Debug, Clone)]
+pub struct RecordSymbol {
+    pub fields: VariableBlock,
+    pub span: Span,
+}
+
+/* The variants of an enum, in the order they got declared in (which is also how they compare) */
+#[derive(Debug, Clone)]
+pub struct EnumSymbol {
+    pub variants: Arc<[Arc<str>]>,
+    pub span: Span,
+}
+
+/*
+ Everything the semantic pass knows about the names of a single program, it lives on the tree
+ itself so two programs never get to see each other's functions
+*/
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    functions: FxHashMap<Rc<str>, FunctionSymbol>,
+    records: FxHashMap<Rc<str>, Rc<RecordSymbol>>,
+    enums: FxHashMap<Rc<str>, EnumSymbol>,
+}
+
+impl SymbolTable {
+    pub fn define_function(
+        &mut self,
+        name: Rc<str>,
+        symbol: FunctionSymbol,
+    ) -> Option<FunctionSymbol> {
+        self.functions.insert(name, symbol)
+    }
+
+    pub fn get_function(&self, name: &str) -> Option<&FunctionSymbol> {
+        self.functions.get(name)
+    }
+
+    pub fn is_function(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+
+    pub fn define_record(&mut self, name: Rc<str>, symbol: RecordSymbol) -> Option<Rc<RecordSymbol>> {
+        self.records.insert(name, Rc::new(symbol))
+    }
+
+    pub fn get_record(&self, name: &str) -> Option<&Rc<RecordSymbol>> {
+        self.records.get(name)
+    }
+
+    pub fn is_record(&self, name: &str) -> bool {
+        self.records.contains_key(name)
+    }
+
+    pub fn define_enum(&mut self, name: Rc<str>, symbol: EnumSymbol) -> Option<EnumSymbol> {
+        self.enums.insert(name, symbol)
+    }
+
+    pub fn get_enum(&self, name: &str) -> Option<&EnumSymbol> {
+        self.enums.get(name)
+    }
+
+    pub fn is_enum(&self, name: &str) -> bool {
+        self.enums.contains_key(name)
+    }
+
+    pub fn enum_type(&self, name: &str) -> Option<MascalType> {
+        self.enums
+            .get(name)
+            .map(|symbol| MascalType::Enum(Arc::from(name), symbol.variants.clone()))
+    }
+
+    /*
+     The parser can't tell records and enums apart by their name alone, so every named type is
+     first read as a record and the ones naming an enum get swapped out here
+    */
+    pub fn resolve_type(&self, mascal_type: MascalType) -> MascalType {
+        match mascal_type {
+            MascalType::Record(name) => self.enum_type(&name).unwrap_or(MascalType::Record(name)),
+            MascalType::Map(key_type, value_type) => MascalType::Map(
+                Box::new(self.resolve_type(*key_type)),
+                Box::new(self.resolve_type(*value_type)),
+            ),
+            MascalType::DynamicArray(inner) => {
+                MascalType::DynamicArray(Box::new(self.resolve_type(*inner)))
+            }
+            MascalType::StaticArray(inner) => {
+                MascalType::StaticArray(Box::new(self.resolve_type(*inner)))
+            }
+            other => other,
+        }
+    }
+
+    pub fn functions(&self) -> impl Iterator<Item = (&Rc<str>, &FunctionSymbol)> {
+        self.functions.iter()
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = (&Rc<str>, &Rc<RecordSymbol>)> {
+        self.records.iter()
+    }
+
+    pub fn enums(&self) -> impl Iterator<Item = (&Rc<str>, &EnumSymbol)> {
+        self.enums.iter()
+    }
+}
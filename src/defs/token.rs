@@ -74,6 +74,8 @@ pub enum TokenType {
     Comma,
     #[token(":")]
     Colon,
+    #[token(".")]
+    Dot,
 
     #[token("->")]
     ReturnIndicator,
@@ -91,12 +93,16 @@ pub enum TokenType {
     If,
     #[regex(r"Else|else|ELSE", priority=10)]
     Else,
+    #[regex(r"THEN|then|Then", priority=10)]
+    Then,
     #[regex(r"ELIF|Elif|elif|Else if| ELSE IF| Else If", priority=10)]
     ElseIf,
     #[regex(r"const|CONST|Const", priority=10)]
     Const,
     #[regex(r"VARIABLES|Variables|variables", priority=10)]
     Variables,
+    #[regex(r"ERRORS|Errors|errors", priority=10)]
+    Errors,
     #[regex(r"String|STRING|string", priority=10)]
     String,
     #[regex(r"INTEGER|integer|Integer", priority=10)]
@@ -107,20 +113,43 @@ pub enum TokenType {
     Dynamic,
     #[regex(r"BOOLEAN|boolean|Boolean", priority=10)]
     Boolean,
+    #[regex(r"FUNCTION|Function|function", priority=10)]
+    Function,
+    /* The opening arrow is part of the keyword so the MAP builtins can still be called as Map(...) */
+    #[regex(r"(MAP|Map|map)<", priority=10)]
+    Map,
     #[regex(r"DEFINE_FUNCTION|Define_Function|define_function", priority=10)]
     DefineFunction,
     #[regex(r"DEFINE_PROGRAM|Define_Program|define_program", priority=10)]
     DefineProgram,
+    #[regex(r"DEFINE_RECORD|Define_Record|define_record", priority=10)]
+    DefineRecord,
+    #[regex(r"DEFINE_ENUM|Define_Enum|define_enum", priority=10)]
+    DefineEnum,
+    #[regex(r"IMPORT|Import|import", priority=10)]
+    Import,
     #[regex(r"IMPLEMENTATION|implementation|Implementation", priority=10)]
     Implementation,
     #[regex(r"WHILE|while|While", priority=10)]
     While,
+    #[regex(r"REPEAT|repeat|Repeat", priority=10)]
+    Repeat,
+    #[regex(r"UNTIL|until|Until", priority=10)]
+    Until,
+    #[regex(r"DO|do|Do", priority=10)]
+    Do,
     #[regex(r"FROM|from|From", priority=10)]
     From,
     #[regex(r"To|to|TO", priority=10)]
     To,
+    #[regex(r"DOWNTO|downto|DownTo", priority=10)]
+    DownTo,
     #[regex(r"WITH_STEP|with_step|With_Step", priority=10)]
     WithStep,
+    #[regex(r"EACH|each|Each", priority=10)]
+    Each,
+    #[regex(r"IN|in|In", priority=10)]
+    In,
     #[regex(r"MUT|mut|Mut", priority=10)]
     Mutable,
     #[regex(r"TYPE|type|Type", priority=10)]
@@ -142,6 +171,30 @@ pub enum TokenType {
     #[regex(r"Continue|continue|CONTINUE", priority=10)]
     Continue,
 
+    #[regex(r"RETURN|return|Return", priority=10)]
+    Return,
+
+    #[regex(r"TRY|try|Try", priority=10)]
+    Try,
+
+    #[regex(r"CATCH|catch|Catch", priority=10)]
+    Catch,
+
+    #[regex(r"FINALLY|finally|Finally", priority=10)]
+    Finally,
+
+    #[regex(r"AS|as|As", priority=10)]
+    As,
+
+    #[regex(r"MATCH|match|Match", priority=10)]
+    Match,
+
+    #[regex(r"CASE|case|Case", priority=10)]
+    Case,
+
+    #[regex(r"DEFAULT|default|Default", priority=10)]
+    Default,
+
     #[token("<<")]
     OpenDynamicArray,
     #[token(">>")]
@@ -164,7 +217,10 @@ pub static SCOPABLE_TOKEN_TYPES: Lazy<HashSet<TokenType>> = Lazy::new(|| {
             TokenType::DefineProgram,
             TokenType::Implementation,
             TokenType::Variables,
+            TokenType::Errors,
             TokenType::DefineFunction,
+            TokenType::DefineRecord,
+            TokenType::DefineEnum,
             TokenType::Implementation,
         ]
         .iter()
@@ -179,4 +235,5 @@ pub struct Token<'a> {
     pub value: &'a str,
     pub start: usize,
     pub line: usize,
+    pub file: usize,
 }
@@ -1,6 +1,7 @@
 use crate::defs::errors::MascalError;
 use crate::defs::token::TokenType;
 use crate::runtime::values::MascalValue;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MascalUnprocessedType {
@@ -10,6 +11,9 @@ pub enum MascalUnprocessedType {
     String,
     Dynamic,
     Type,
+    Function,
+    Record(Arc<str>),
+    Map(Box<MascalUnprocessedType>, Box<MascalUnprocessedType>),
     DynamicArray(Box<MascalUnprocessedType>),
     StaticArray(Box<MascalUnprocessedType>),
 }
@@ -40,15 +44,20 @@ impl MascalType {
             }
         }
 
-        let mut s: String = String::from(match ty {
-            MascalType::String => "STRING",
-            MascalType::Integer => "INTEGER",
-            MascalType::Float => "FLOAT",
-            MascalType::Boolean => "BOOLEAN",
-            MascalType::Dynamic => "DYNAMIC",
-            MascalType::Type => "TYPE",
+        let mut s: String = match ty {
+            MascalType::String => String::from("STRING"),
+            MascalType::Integer => String::from("INTEGER"),
+            MascalType::Float => String::from("FLOAT"),
+            MascalType::Boolean => String::from("BOOLEAN"),
+            MascalType::Dynamic => String::from("DYNAMIC"),
+            MascalType::Type => String::from("TYPE"),
+            MascalType::Function => String::from("FUNCTION"),
+            MascalType::Record(name) | MascalType::Enum(name, _) => name.to_string(),
+            MascalType::Map(key_type, value_type) => {
+                format!("MAP<{}, {}>", key_type.as_string(), value_type.as_string())
+            }
             _ => unreachable!(),
-        });
+        };
 
         for &m in &modifiers {
             s.push_str(m);
@@ -65,6 +74,7 @@ pub fn token_type_to_atom_mascal_type(tt: &TokenType) -> Option<MascalUnprocesse
         TokenType::String => Some(MascalUnprocessedType::String),
         TokenType::Dynamic => Some(MascalUnprocessedType::Dynamic),
         TokenType::Type => Some(MascalUnprocessedType::Type),
+        TokenType::Function => Some(MascalUnprocessedType::Function),
         _ => None,
     }
 }
@@ -77,6 +87,12 @@ pub fn to_processed_type(unprocessed: MascalUnprocessedType) -> Result<MascalTyp
         MascalUnprocessedType::String => Ok(MascalType::String),
         MascalUnprocessedType::Dynamic => Ok(MascalType::Dynamic),
         MascalUnprocessedType::Type => Ok(MascalType::Type),
+        MascalUnprocessedType::Function => Ok(MascalType::Function),
+        MascalUnprocessedType::Record(name) => Ok(MascalType::Record(name)),
+        MascalUnprocessedType::Map(key_type, value_type) => Ok(MascalType::Map(
+            Box::new(to_processed_type(*key_type)?),
+            Box::new(to_processed_type(*value_type)?),
+        )),
         MascalUnprocessedType::DynamicArray(array_type) => Ok(MascalType::DynamicArray(Box::new(
             to_processed_type(*array_type)?,
         ))),
@@ -94,6 +110,11 @@ pub enum MascalType {
     String,
     Dynamic,
     Type,
+    Function,
+    Record(Arc<str>),
+    /* Parsed as a record type, the symbol table turns it into an enum with its variants later on */
+    Enum(Arc<str>, Arc<[Arc<str>]>),
+    Map(Box<MascalType>, Box<MascalType>),
     DynamicArray(Box<MascalType>),
     StaticArray(Box<MascalType>),
 }
@@ -108,6 +129,8 @@ pub enum MascalTypeKind {
     String,
     Dynamic,
     Type,
+    Function,
+    Map,
     DynamicArray,
     StaticArray,
 }
@@ -121,7 +144,9 @@ impl MascalTypeKind {
             (MascalTypeKind::Float, MascalValue::Float(..)) => true,
             (MascalTypeKind::String, MascalValue::String(..)) => true,
             (MascalTypeKind::Type, MascalValue::Type(..)) => true,
+            (MascalTypeKind::Function, MascalValue::Function(..)) => true,
             (MascalTypeKind::Boolean, MascalValue::Boolean(..)) => true,
+            (MascalTypeKind::Map, MascalValue::Map(..)) => true,
             (MascalTypeKind::StaticArray, MascalValue::StaticArray { .. }) => true,
             (MascalTypeKind::DynamicArray, MascalValue::DynamicArray { .. }) => true,
             _ => false,
@@ -138,6 +163,8 @@ impl MascalTypeKind {
             MascalTypeKind::StaticArray => String::from("STATIC_ARRAY"),
             MascalTypeKind::Dynamic => String::from("DYNAMIC"),
             MascalTypeKind::Type => String::from("TYPE"),
+            MascalTypeKind::Function => String::from("FUNCTION"),
+            MascalTypeKind::Map => String::from("MAP"),
         }
     }
 }
@@ -0,0 +1,202 @@
+use crate::defs::errors::{MascalError, MascalWarning};
+use crate::defs::token::TokenType;
+use logos::Logos;
+use std::io::IsTerminal;
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const CYAN: &str = "\x1b[1;36m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/*
+ A diagnostic is just a MascalError paired with any extra hints the caller wants to show, the
+ source text is only needed once rendering, so the same diagnostic can be rendered against
+ whatever copy of the file the embedder has laying around
+*/
+#[derive(Debug)]
+pub struct Diagnostic<'a> {
+    pub error: &'a MascalError,
+    pub helps: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+impl<'a> Diagnostic<'a> {
+    pub fn new(error: &'a MascalError) -> Self {
+        Diagnostic {
+            error,
+            helps: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.helps.push(help.into());
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn render(&self, source: &str, colored: bool) -> String {
+        let paint = |color: &str, text: &str| paint(color, text, colored);
+        let gutter: String = " ".repeat(
+            self.error
+                .span
+                .map_or(1, |span| (span.line + 1).to_string().len()),
+        );
+
+        let mut rendered: String = format!(
+            "{}{}\n",
+            paint(RED, &format!("error[{}]", self.error.error_type)),
+            paint(BOLD, &format!(": {}", self.error.source))
+        );
+        /* Nothing claimed an error raised outside of any node, so there is no line to show */
+        if let Some(span) = self.error.span {
+            rendered.push_str(&render_snippet(
+                source,
+                span.line,
+                span.character,
+                RED,
+                colored,
+            ));
+        }
+
+        if !self.helps.is_empty() || !self.notes.is_empty() {
+            rendered.push_str(&format!("{} {}\n", gutter, paint(BLUE, "|")));
+        }
+        for help in &self.helps {
+            rendered.push_str(&format!(
+                "{} {} {}\n",
+                gutter,
+                paint(BLUE, "="),
+                paint(CYAN, &format!("help: {}", help))
+            ));
+        }
+        for note in &self.notes {
+            rendered.push_str(&format!(
+                "{} {} {}\n",
+                gutter,
+                paint(BLUE, "="),
+                paint(BOLD, &format!("note: {}", note))
+            ));
+        }
+
+        rendered
+    }
+}
+
+fn paint(color: &str, text: &str, colored: bool) -> String {
+    if colored {
+        format!("{}{}{}", color, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+/* The location line along with the source line itself and the carets underneath it */
+fn render_snippet(
+    source: &str,
+    line: usize,
+    character: usize,
+    underline_color: &str,
+    colored: bool,
+) -> String {
+    let paint = |color: &str, text: &str| paint(color, text, colored);
+
+    let offset: usize = clamp_to_char_boundary(source, character);
+    let line_start: usize = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line_end: usize = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |index| offset + index);
+    let line_text: &str = source[line_start..line_end].trim_end_matches('\r');
+    let column: usize = source[line_start..offset].chars().count();
+    let line_number: String = (line + 1).to_string();
+    let gutter: String = " ".repeat(line_number.len());
+
+    let mut rendered: String = format!(
+        "{}{} {}:{}\n",
+        gutter,
+        paint(BLUE, "-->"),
+        line + 1,
+        column + 1
+    );
+    rendered.push_str(&format!("{} {}\n", gutter, paint(BLUE, "|")));
+    rendered.push_str(&format!(
+        "{} {} {}\n",
+        paint(BLUE, &line_number),
+        paint(BLUE, "|"),
+        line_text
+    ));
+
+    /*
+     Tabs are kept as-is in the padding so the caret lines up with whatever width the
+     terminal decides to give them on the source line above
+    */
+    let padding: String = line_text
+        .chars()
+        .take(column)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let underline_length: usize = underline_length(source, offset, line_end);
+    rendered.push_str(&format!(
+        "{} {} {}{}\n",
+        gutter,
+        paint(BLUE, "|"),
+        padding,
+        paint(underline_color, &"^".repeat(underline_length))
+    ));
+    rendered
+}
+
+fn clamp_to_char_boundary(source: &str, offset: usize) -> usize {
+    let mut offset: usize = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/*
+ Errors only remember where they start, so the underline is as wide as the token sitting at
+ that position. Anything the lexer does not recognise (or the end of the file) gets a single caret
+*/
+fn underline_length(source: &str, offset: usize, line_end: usize) -> usize {
+    let mut lexer = TokenType::lexer(&source[offset..line_end]);
+    match lexer.next() {
+        Some(Ok(_)) if lexer.span().start == 0 => source[offset..offset + lexer.span().end]
+            .chars()
+            .count()
+            .max(1),
+        _ => 1,
+    }
+}
+
+pub fn should_use_color() -> bool {
+    std::io::stdout().is_terminal()
+}
+
+pub fn render_error(source: &str, error: &MascalError, colored: bool) -> String {
+    Diagnostic::new(error).render(source, colored)
+}
+
+pub fn render_warning(source: &str, warning: &MascalWarning, colored: bool) -> String {
+    let mut rendered: String = format!(
+        "{}{}\n",
+        paint(YELLOW, "warning", colored),
+        paint(BOLD, &format!(": {}", warning.source), colored)
+    );
+    if let Some(span) = warning.span {
+        rendered.push_str(&render_snippet(
+            source,
+            span.line,
+            span.character,
+            YELLOW,
+            colored,
+        ));
+    }
+    rendered
+}
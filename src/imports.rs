@@ -0,0 +1,310 @@
+use crate::ast::{AbstractSyntaxTree, SourceFile, imported_file};
+use crate::defs::blocks::ScopedBlocks;
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
+use crate::defs::symbol_table::SymbolTable;
+use crate::defs::token::Token;
+use crate::lexer;
+use crate::parser::{ParsedModule, TokenSequence, parse_imports, parse_module};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::{env, fs};
+
+/*
+ Follows the IMPORT directives of a program and merges whatever the imported files define into
+ one syntax tree. Every file gets loaded once no matter how many others import it, so a file
+ shared by two imports is fine, while two different files defining the same name is not
+*/
+struct ImportResolver {
+    root: PathBuf,
+    loading: Vec<PathBuf>,
+    loaded: FxHashMap<PathBuf, Rc<FxHashSet<Rc<str>>>>,
+    blocks: Vec<ScopedBlocks>,
+    definitions: FxHashMap<String, (&'static str, PathBuf)>,
+    error_types: FxHashMap<Rc<str>, PathBuf>,
+    files: Vec<SourceFile>,
+}
+
+/*
+ Parses the entry file of a program along with everything it imports, paths are resolved relative
+ to the file doing the importing. A program that doesn't come from a file resolves them relative
+ to the current directory instead
+*/
+pub fn parse_with_imports(
+    source: &str,
+    entry_path: Option<&Path>,
+) -> Result<AbstractSyntaxTree, Vec<MascalError>> {
+    parse_after_files(source, entry_path, Vec::new())
+}
+
+/*
+ The same as above, only there were files read before this one (the REPL keeps every input it got
+ around), the last of them being the entry file itself. Its spans carry that file's number, and
+ whatever it imports gets numbered after it
+*/
+pub(crate) fn parse_after_files(
+    source: &str,
+    entry_path: Option<&Path>,
+    files: Vec<SourceFile>,
+) -> Result<AbstractSyntaxTree, Vec<MascalError>> {
+    let entry_path: PathBuf = match entry_path {
+        Some(entry_path) => entry_path
+            .canonicalize()
+            .unwrap_or_else(|_| entry_path.to_path_buf()),
+        None => env::current_dir().unwrap_or_default().join("<source>"),
+    };
+    let directory: &Path = entry_path.parent().unwrap_or(Path::new("."));
+    let mut resolver: ImportResolver = ImportResolver {
+        root: directory
+            .canonicalize()
+            .unwrap_or_else(|_| directory.to_path_buf()),
+        loading: vec![entry_path.clone()],
+        loaded: FxHashMap::default(),
+        blocks: Vec::new(),
+        definitions: FxHashMap::default(),
+        error_types: FxHashMap::default(),
+        files,
+    };
+
+    let entry_file: usize = resolver.files.len();
+    let (module, _) = resolver.parse_file(source, &entry_path, entry_file, true)?;
+    let mut blocks: Vec<ScopedBlocks> = module.blocks;
+    blocks.append(&mut resolver.blocks);
+    Ok(AbstractSyntaxTree {
+        blocks,
+        program_index: module.program_index.unwrap(),
+        symbol_table: SymbolTable::default(),
+        warnings: Vec::new(),
+        imported_files: resolver.files,
+    })
+}
+
+/* The tokens remember which file they came from, as do the spans made out of them */
+pub fn tokenize_source(source: &str, file: usize) -> Result<Vec<Token<'_>>, Vec<MascalError>> {
+    let mut tokens: Vec<Token> = lexer::tokenize(source).map_err(|(range, line, value)| {
+        vec![MascalError {
+            error_type: MascalErrorType::LexerError,
+            span: Some(Span {
+                line,
+                character: range.start,
+                file,
+            }),
+            source: format!("Unknown Character Sequence \"{}\"", value),
+        }]
+    })?;
+    for token in tokens.iter_mut() {
+        token.file = file;
+    }
+    Ok(tokens)
+}
+
+/*
+ Errors the semantic pass finds inside an imported file get pointed at the IMPORT directive that
+ led there, the same as the ones found while parsing it. The importing file might well have been
+ imported itself, so this goes on until the error sits in the entry file
+*/
+pub fn point_at_imports(mut error: MascalError, imported_files: &[SourceFile]) -> MascalError {
+    while let Some(span) = error.span
+        && let Some(file) = imported_file(imported_files, span)
+        && let Some(imported_at) = file.imported_at
+    {
+        error = pointed_at_import(error, &file.path, imported_at);
+    }
+    error
+}
+
+impl ImportResolver {
+    /* Hands back the parsed file along with every error type it can see, its own included */
+    fn parse_file(
+        &mut self,
+        source: &str,
+        path: &Path,
+        file: usize,
+        is_entry: bool,
+    ) -> Result<(ParsedModule, FxHashSet<Rc<str>>), Vec<MascalError>> {
+        let token_sequence: TokenSequence = TokenSequence::new(tokenize_source(source, file)?);
+        let directory: &Path = path.parent().unwrap_or(Path::new("."));
+        let mut imported_errors: FxHashSet<Rc<str>> = FxHashSet::default();
+        let mut errors: Vec<MascalError> = Vec::new();
+        for (import_path, span) in parse_imports(&token_sequence)? {
+            match self.load(&directory.join(import_path), span) {
+                Ok(exported_errors) => imported_errors.extend(exported_errors.iter().cloned()),
+                Err(import_errors) => errors.extend(import_errors),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let module: ParsedModule = parse_module(token_sequence, &imported_errors, is_entry)?;
+        self.register_definitions(&module, path)?;
+        imported_errors.extend(module.declared_errors.iter().map(|(name, _)| name.clone()));
+        Ok((module, imported_errors))
+    }
+
+    /*
+     Loads an imported file, handing back every error type it can see so the importing file can
+     use them too. Errors from inside the file are pointed at the IMPORT directive that led there
+    */
+    fn load(
+        &mut self,
+        path: &Path,
+        span: Span,
+    ) -> Result<Rc<FxHashSet<Rc<str>>>, Vec<MascalError>> {
+        let Ok(path) = path.canonicalize() else {
+            return Err(vec![import_error(
+                span,
+                format!(
+                    "The imported file {:?} could not be found",
+                    self.display(path)
+                ),
+            )]);
+        };
+        if let Some(position) = self.loading.iter().position(|loading| loading == &path) {
+            let cycle: Vec<String> = self.loading[position..]
+                .iter()
+                .chain(std::iter::once(&path))
+                .map(|loading| self.display(loading))
+                .collect();
+            return Err(vec![import_error(
+                span,
+                format!("Import cycle detected: {}", cycle.join(" -> ")),
+            )]);
+        }
+        if let Some(exported_errors) = self.loaded.get(&path) {
+            return Ok(Rc::clone(exported_errors));
+        }
+        let Ok(source) = fs::read_to_string(&path) else {
+            return Err(vec![import_error(
+                span,
+                format!(
+                    "The imported file {:?} could not be read",
+                    self.display(&path)
+                ),
+            )]);
+        };
+
+        self.files.push(SourceFile {
+            path: self.display(&path),
+            text: Rc::from(source.as_str()),
+            imported_at: Some(span),
+        });
+        let file_number: usize = self.files.len();
+        self.loading.push(path.clone());
+        let parsed = self.parse_file(&source, &path, file_number, false);
+        self.loading.pop();
+        /* A broken file only gets reported once, even when several files import it */
+        if parsed.is_err() {
+            self.loaded.insert(path.clone(), Rc::default());
+        }
+        let (module, visible_errors) = parsed.map_err(|errors| {
+            let file: String = self.display(&path);
+            errors
+                .into_iter()
+                .map(|err| pointed_at_import(err, &file, span))
+                .collect::<Vec<MascalError>>()
+        })?;
+
+        /* Error types reach every file further up the chain of imports, just like the blocks do */
+        let exported_errors: Rc<FxHashSet<Rc<str>>> = Rc::new(visible_errors);
+        self.loaded.insert(path, Rc::clone(&exported_errors));
+        self.blocks.extend(module.blocks);
+        Ok(exported_errors)
+    }
+
+    /*
+     Names only collide across files, a file defining the same name twice is already reported by
+     the parser and the semantic pass
+    */
+    fn register_definitions(
+        &mut self,
+        module: &ParsedModule,
+        path: &Path,
+    ) -> Result<(), Vec<MascalError>> {
+        let mut errors: Vec<MascalError> = Vec::new();
+        for block in module.blocks.iter() {
+            let (kind, name, span) = match block {
+                ScopedBlocks::Function { name, span, .. } => ("function", name, span),
+                ScopedBlocks::Record { name, span, .. } => ("record", name, span),
+                ScopedBlocks::Enum { name, span, .. } => ("enum", name, span),
+                ScopedBlocks::Program(..) => continue,
+            };
+            match self.definitions.get(name) {
+                Some((other_kind, origin)) if origin != path => {
+                    errors.push(import_error(
+                        *span,
+                        format!(
+                            "The {} {:?} collides with the {} of the same name from {:?}",
+                            kind,
+                            name,
+                            other_kind,
+                            self.display(origin)
+                        ),
+                    ));
+                }
+                Some(..) => continue,
+                None => {
+                    self.definitions
+                        .insert(name.clone(), (kind, path.to_path_buf()));
+                }
+            }
+        }
+        for (name, span) in module.declared_errors.iter() {
+            match self.error_types.get(name) {
+                Some(origin) if origin != path => {
+                    errors.push(import_error(
+                        *span,
+                        format!(
+                            "The error type {:?} collides with the error type of the same name from {:?}",
+                            name,
+                            self.display(origin)
+                        ),
+                    ));
+                }
+                Some(..) => continue,
+                None => {
+                    self.error_types.insert(name.clone(), path.to_path_buf());
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(())
+    }
+
+    /* Paths are shown relative to the entry file whenever possible, they get rather long otherwise */
+    fn display(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+}
+
+fn pointed_at_import(error: MascalError, file: &str, span: Span) -> MascalError {
+    MascalError {
+        error_type: error.error_type,
+        span: Some(span),
+        source: match error.span {
+            Some(error_span) => {
+                format!(
+                    "In {:?} at line {}: {}",
+                    file,
+                    error_span.line + 1,
+                    error.source
+                )
+            }
+            None => format!("In {:?}: {}", file, error.source),
+        },
+    }
+}
+
+fn import_error(span: Span, source: String) -> MascalError {
+    MascalError {
+        error_type: MascalErrorType::ParserError,
+        span: Some(span),
+        source,
+    }
+}
@@ -0,0 +1,305 @@
+use crate::ast::{AbstractSyntaxTree, SourceFile, imported_file};
+use crate::bytecode::BytecodeProgram;
+use crate::bytecode::compiler::compile_program;
+use crate::bytecode::vm::VirtualMachine;
+use crate::defs::builtins::builtin_functions::{
+    BuiltinFunction, NativeFunctionTable, is_builtin_function,
+};
+use crate::defs::errors::{MascalError, MascalErrorType, MascalWarning};
+use crate::defs::span::Span;
+use crate::defs::symbol_table::SymbolTable;
+use crate::defs::types::MascalTypeKind;
+use crate::imports::{parse_with_imports, point_at_imports};
+use crate::runtime::io_streams::{SharedIO, StandardIO};
+use crate::runtime::values::MascalValue;
+use crate::runtime::{ExecutionData, interpert};
+use crate::semantic_analysis::conduct_semantic_analysis;
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::fmt;
+use std::path::Path;
+use std::rc::Rc;
+
+/*
+ A program that made it through lexing, parsing and the semantic pass, it can be ran as many
+ times as wanted since every run works on its own copy of the tree
+*/
+#[derive(Debug, Clone)]
+pub struct Program {
+    abstract_syntax_tree: AbstractSyntaxTree,
+    bytecode: BytecodeCache,
+}
+
+/*
+ The bytecode a program got lowered into the last time, along with the native functions it got
+ lowered against since those end up baked into it
+*/
+#[derive(Clone, Default)]
+struct BytecodeCache(RefCell<Option<LoweredProgram>>);
+
+#[derive(Clone)]
+struct LoweredProgram {
+    native_functions: NativeFunctionTable,
+    bytecode_program: Result<Rc<BytecodeProgram>, MascalError>,
+}
+
+impl fmt::Debug for BytecodeCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BytecodeCache")
+    }
+}
+
+impl Program {
+    pub fn abstract_syntax_tree(&self) -> &AbstractSyntaxTree {
+        &self.abstract_syntax_tree
+    }
+
+    /* Whatever the semantic pass found suspicious without it being an outright error */
+    pub fn warnings(&self) -> &[MascalWarning] {
+        &self.abstract_syntax_tree.warnings
+    }
+
+    /*
+     The imported file a span points into, for showing runtime errors against the right text.
+     Spans in the entry file have nothing to hand back, the caller already has that one
+    */
+    pub fn imported_file(&self, span: Span) -> Option<&SourceFile> {
+        imported_file(&self.abstract_syntax_tree.imported_files, span)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunOutcome {
+    variables: FxHashMap<Rc<str>, MascalValue>,
+    backend: Backend,
+}
+
+impl RunOutcome {
+    /* The backend that really ran the program, which isn't always the one that was picked */
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    pub fn get_variable(&self, name: &str) -> Option<&MascalValue> {
+        self.variables.get(name)
+    }
+
+    pub fn variables(&self) -> &FxHashMap<Rc<str>, MascalValue> {
+        &self.variables
+    }
+}
+
+/*
+ What ends up running a program, the tree-walker evaluates the syntax tree as-is while the bytecode
+ backend compiles it first and runs it on a stack based VM with every variable resolved to a slot
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    #[default]
+    TreeWalker,
+    Bytecode,
+}
+
+pub struct Interpreter {
+    io: SharedIO,
+    native_functions: NativeFunctionTable,
+    backend: Backend,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new()
+    }
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Interpreter {
+            io: Rc::new(RefCell::new(StandardIO)),
+            native_functions: Rc::new(FxHashMap::default()),
+            backend: Backend::default(),
+        }
+    }
+
+    pub fn with_io(mut self, io: SharedIO) -> Self {
+        self.io = io;
+        self
+    }
+
+    pub fn with_backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /*
+     Registers a function implemented by the host, it gets called exactly like a builtin would be,
+     meaning the arguments are evaluated and checked against the given types before the closure
+     ever sees them. Names are case-insensitive and can't shadow the builtins of the language, a
+     program defining a function under the same name doesn't compile (or run) with this interpreter
+    */
+    pub fn register_function(
+        &mut self,
+        name: &str,
+        fixed_argument_types: Vec<Vec<MascalTypeKind>>,
+        execution: impl Fn(
+            Vec<MascalValue>,
+            &mut ExecutionData,
+        ) -> Result<Option<MascalValue>, MascalError>
+        + 'static,
+    ) -> Result<(), MascalError> {
+        if is_builtin_function(name) {
+            return Err(MascalError {
+                error_type: MascalErrorType::ArgumentError,
+                span: None,
+                source: format!(
+                    "Cannot register {:?} since a builtin function already goes by that name",
+                    name
+                ),
+            });
+        }
+        Rc::make_mut(&mut self.native_functions).insert(
+            name.to_lowercase(),
+            BuiltinFunction::new_value_based(false, fixed_argument_types, execution),
+        );
+        Ok(())
+    }
+
+    /* Any IMPORT in the source is resolved relative to the current directory */
+    pub fn compile(&self, source: &str) -> Result<Program, Vec<MascalError>> {
+        self.compile_program(source, None)
+    }
+
+    /* Compiles the contents of the file at the given path, its imports are relative to that file */
+    pub fn compile_file(&self, source: &str, path: &Path) -> Result<Program, Vec<MascalError>> {
+        self.compile_program(source, Some(path))
+    }
+
+    fn compile_program(
+        &self,
+        source: &str,
+        path: Option<&Path>,
+    ) -> Result<Program, Vec<MascalError>> {
+        let abstract_syntax_tree: AbstractSyntaxTree = parse_with_imports(source, path)?;
+        let imported_files: Vec<SourceFile> = abstract_syntax_tree.imported_files.clone();
+        let abstract_syntax_tree: AbstractSyntaxTree =
+            conduct_semantic_analysis(abstract_syntax_tree).map_err(|errors| {
+                errors
+                    .into_iter()
+                    .map(|error| point_at_imports(error, &imported_files))
+                    .collect::<Vec<MascalError>>()
+            })?;
+        let collisions: Vec<MascalError> =
+            self.native_function_collisions(&abstract_syntax_tree.symbol_table);
+        if !collisions.is_empty() {
+            return Err(collisions
+                .into_iter()
+                .map(|error| point_at_imports(error, &imported_files))
+                .collect());
+        }
+        Ok(Program {
+            abstract_syntax_tree,
+            bytecode: BytecodeCache::default(),
+        })
+    }
+
+    /*
+     Native functions are looked up before the ones of the program, so a DEFINE_FUNCTION going by
+     the name of one would never get called at all
+    */
+    fn native_function_collisions(&self, symbol_table: &SymbolTable) -> Vec<MascalError> {
+        let mut collisions: Vec<MascalError> = symbol_table
+            .functions()
+            .filter(|(name, _)| self.native_functions.contains_key(&name.to_lowercase()))
+            .map(|(name, symbol)| MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(symbol.span),
+                source: format!(
+                    "The function {:?} shares its name with a native function",
+                    name
+                ),
+            })
+            .collect();
+        collisions.sort_by_key(|error| {
+            error
+                .span
+                .map(|span| (span.file, span.line, span.character))
+        });
+        collisions
+    }
+
+    /*
+     Why the program can't run on the bytecode backend when that is the one picked, it runs through
+     the tree-walker instead whenever there is a reason
+    */
+    pub fn bytecode_fallback(&self, program: &Program) -> Option<MascalError> {
+        if self.backend != Backend::Bytecode {
+            return None;
+        }
+        self.bytecode(program).err()
+    }
+
+    /* Lowering a program only happens once, unless the native functions changed in the meantime */
+    fn bytecode(&self, program: &Program) -> Result<Rc<BytecodeProgram>, MascalError> {
+        let mut cache = program.bytecode.0.borrow_mut();
+        if let Some(lowered) = cache.as_ref()
+            && Rc::ptr_eq(&lowered.native_functions, &self.native_functions)
+        {
+            return lowered.bytecode_program.clone();
+        }
+        let bytecode_program: Result<Rc<BytecodeProgram>, MascalError> =
+            compile_program(&program.abstract_syntax_tree, &self.native_functions).map(Rc::new);
+        *cache = Some(LoweredProgram {
+            native_functions: self.native_functions.clone(),
+            bytecode_program: bytecode_program.clone(),
+        });
+        bytecode_program
+    }
+
+    pub fn run(&self, program: &Program) -> Result<RunOutcome, MascalError> {
+        /* The program might have been compiled by an interpreter without the same natives */
+        if let Some(collision) = self
+            .native_function_collisions(&program.abstract_syntax_tree.symbol_table)
+            .into_iter()
+            .next()
+        {
+            return Err(collision);
+        }
+        /*
+         Programs using something the bytecode compiler can't lower yet still run, they just go
+         through the tree-walker instead. The outcome tells which of the two it ended up being
+        */
+        if self.backend == Backend::Bytecode
+            && let Ok(bytecode_program) = self.bytecode(program)
+        {
+            let variables: FxHashMap<Rc<str>, MascalValue> = VirtualMachine::new(
+                &bytecode_program,
+                self.io.clone(),
+                self.native_functions.clone(),
+                Rc::new(program.abstract_syntax_tree.symbol_table.clone()),
+            )
+            .run()?;
+            return Ok(RunOutcome {
+                variables,
+                backend: Backend::Bytecode,
+            });
+        }
+        let variable_table = interpert(
+            program.abstract_syntax_tree.clone(),
+            self.io.clone(),
+            self.native_functions.clone(),
+        )?;
+        let variables: FxHashMap<Rc<str>, MascalValue> = variable_table
+            .borrow()
+            .iter()
+            .filter_map(|(name, data)| {
+                data.value
+                    .as_ref()
+                    .map(|value| (name.clone(), value.borrow().clone()))
+            })
+            .collect();
+        Ok(RunOutcome {
+            variables,
+            backend: Backend::TreeWalker,
+        })
+    }
+}
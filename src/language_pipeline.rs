@@ -1,52 +1,83 @@
-use logos::Span;
-use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::token::{Token};
-use crate::lexer;
-use crate::parser::{TokenSequence, parse};
-use crate::runtime::interpert;
-use crate::semantic_analysis::conduct_semantic_analysis;
+use crate::defs::errors::{MascalError, MascalWarning};
+use crate::diagnostics::{render_error, render_warning, should_use_color};
+use crate::interpreter::{Backend, Interpreter, Program};
+use std::path::Path;
 
-macro_rules! define_pipeline_step {
-    ($func: expr, $($args: expr)*) => {
-        match $func($($args),*) {
-            Ok(val) => Some(val),
-            Err(e) => {
-                println!("{}", e);
-                None
+pub fn trigger_pipeline(path: &Path, contents: String, backend: Backend) -> bool {
+    let interpreter: Interpreter = Interpreter::new().with_backend(backend);
+    let colored: bool = should_use_color();
+    let program: Program = match interpreter.compile_file(&contents, path) {
+        Ok(program) => program,
+        Err(errors) => {
+            for e in errors {
+                print!("{}", render_error(&contents, &e, colored));
             }
+            return false;
         }
     };
+    for warning in program.warnings() {
+        print!(
+            "{}",
+            render_located_warning(&program, &contents, warning, colored)
+        );
+    }
+    if let Some(reason) = interpreter.bytecode_fallback(&program) {
+        print!(
+            "{}",
+            render_located_warning(&program, &contents, &fallback_warning(reason), colored)
+        );
+    }
+    if let Err(e) = interpreter.run(&program) {
+        print!("{}", render_located_error(&program, &contents, &e, colored));
+        return false;
+    }
+    true
 }
 
-pub fn trigger_pipeline(contents: String) {
-    let tokens: Result<Vec<Token>, (Span, usize, &str)> = lexer::tokenize(&contents);
-    let token_sequence: TokenSequence = match tokens {
-        Ok(toks) => {
-            if toks.is_empty() {
-                return;
-            }
-            let token_sequence: TokenSequence = TokenSequence::new(toks);
-            token_sequence
-        }
-        
-        Err((range, line, value)) => {
-            println!(
-                "{}",
-                MascalError {
-                    error_type: MascalErrorType::LexerError,
-                    line,
-                    character: range.start,
-                    source: format!("Unknown Character Sequence \"{}\"", value)
-                }
-            );
-            return;
-        }
+/*
+ Whatever went wrong inside an imported file is shown against that file's own text, with the
+ message saying which file it was much like the errors pointed at an IMPORT do
+*/
+fn render_located_error(
+    program: &Program,
+    contents: &str,
+    error: &MascalError,
+    colored: bool,
+) -> String {
+    let Some(file) = error.span.and_then(|span| program.imported_file(span)) else {
+        return render_error(contents, error, colored);
+    };
+    let located: MascalError = MascalError {
+        span: error.span,
+        error_type: error.error_type.clone(),
+        source: format!("In {:?}: {}", file.path, error.source),
     };
-    let Some(tree) = define_pipeline_step!(parse, token_sequence) else {
-        return;
+    render_error(&file.text, &located, colored)
+}
+
+fn render_located_warning(
+    program: &Program,
+    contents: &str,
+    warning: &MascalWarning,
+    colored: bool,
+) -> String {
+    let Some(file) = warning.span.and_then(|span| program.imported_file(span)) else {
+        return render_warning(contents, warning, colored);
     };
-    let Some(tree) = define_pipeline_step!(conduct_semantic_analysis, tree) else {
-        return;
+    let located: MascalWarning = MascalWarning {
+        source: format!("In {:?}: {}", file.path, warning.source),
+        ..warning.clone()
     };
-    let _ = define_pipeline_step!(interpert, tree).is_none();
+    render_warning(&file.text, &located, colored)
+}
+
+/* Asking for the bytecode backend and silently getting the tree-walker would be rather confusing */
+fn fallback_warning(reason: MascalError) -> MascalWarning {
+    MascalWarning {
+        span: reason.span,
+        source: format!(
+            "{}, so the program runs on the tree-walker instead",
+            reason.source
+        ),
+    }
 }
@@ -1,7 +1,7 @@
 use logos::{Lexer, Logos, Span};
 use crate::defs::token::{Token, TokenType};
 
-pub fn tokenize(input: &str) -> Result<Vec<Token>, (Span, usize, &str)> {
+pub fn tokenize(input: &str) -> Result<Vec<Token<'_>>, (Span, usize, &str)> {
     let mut lexer: Lexer<TokenType> = TokenType::lexer(input);
     let mut tokens: Vec<Token> = Vec::new();
     lexer.extras = 0;
@@ -26,6 +26,7 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, (Span, usize, &str)> {
             token_type: unwrapped_kind,
             start: span.start,
             line,
+            file: 0,
             value
         });
     }
@@ -1,7 +1,12 @@
 pub mod runtime;
+pub mod bytecode;
 pub mod parser;
 pub mod semantic_analysis;
 pub mod lexer;
 pub mod defs;
+pub mod diagnostics;
+pub mod interpreter;
 pub mod language_pipeline;
-pub mod ast;
\ No newline at end of file
+pub mod repl;
+pub mod ast;
+pub mod imports;
\ No newline at end of file
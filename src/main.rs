@@ -2,7 +2,8 @@ use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use std::process::exit;
 use std::{env, fs};
-use mascal::language_pipeline;
+use mascal::interpreter::Backend;
+use mascal::{language_pipeline, repl};
 
 fn merge_paths(input_path: &Path, lang_dir: &Path) -> Option<PathBuf> {
     if input_path.is_absolute() {
@@ -21,11 +22,9 @@ fn merge_paths(input_path: &Path, lang_dir: &Path) -> Option<PathBuf> {
 fn main() {
     let args: Vec<String> = env::args().collect();
     let lang_path = env::current_exe();
-    if args.len() <= 1 {
-        println!(
-            "\x1b[1;31mPlease provide at least one argument (that being the file to run)\x1b[0m"
-        );
-        exit(64)
+    if args.len() <= 1 || args[1] == "repl" {
+        repl::start_repl();
+        return;
     }
     if lang_path.is_err() {
         println!("\x1b[1;31mUnexpected error when trying to get the executable's path\x1b[0m");
@@ -73,5 +72,12 @@ fn main() {
         return;
     }
     let contents = contents.unwrap();
-    language_pipeline::trigger_pipeline(contents);
+    let backend: Backend = if args.iter().skip(2).any(|arg| arg == "--bytecode") {
+        Backend::Bytecode
+    } else {
+        Backend::TreeWalker
+    };
+    if !language_pipeline::trigger_pipeline(&path, contents, backend) {
+        exit(1)
+    }
 }
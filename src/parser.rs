@@ -1,7 +1,11 @@
+mod parse_enum;
+mod parse_errors;
 mod parse_executable_block;
 mod parse_expression;
 mod parse_function;
+mod parse_import;
 mod parse_program;
+mod parse_record;
 mod parse_statement;
 mod parse_variable_decl;
 mod parse_variables;
@@ -10,10 +14,18 @@ mod utils;
 use crate::ast::AbstractSyntaxTree;
 use crate::defs::blocks::ScopedBlocks;
 use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
 use crate::defs::token::{Token, TokenType};
+use crate::defs::symbol_table::SymbolTable;
+use crate::parser::parse_enum::parse_enum;
+use crate::parser::parse_errors::parse_error_declarations;
 use crate::parser::parse_function::parse_function;
+pub use crate::parser::parse_import::parse_imports;
 use crate::parser::parse_program::parse_program;
+use crate::parser::parse_record::parse_record;
+use rustc_hash::FxHashSet;
 use std::ops::{Range, RangeFrom};
+use std::rc::Rc;
 
 pub struct TokenSequence<'a> {
     pub tokens: Vec<Token<'a>>,
@@ -24,19 +36,19 @@ impl<'a> TokenSequence<'a> {
         TokenSequence { tokens }
     }
 
-    pub fn get_token(&self, index: usize) -> Option<&Token> {
+    pub fn get_token(&self, index: usize) -> Option<&Token<'_>> {
         self.tokens.get(index)
     }
 
-    pub fn last_token(&self) -> &Token {
+    pub fn last_token(&self) -> &Token<'_> {
         self.acquire_token(self.tokens.len() - 1)
     }
 
-    pub fn first_token(&self) -> &Token {
+    pub fn first_token(&self) -> &Token<'_> {
         self.acquire_token(0)
     }
 
-    pub fn acquire_token(&self, index: usize) -> &Token {
+    pub fn acquire_token(&self, index: usize) -> &Token<'_> {
         &self.tokens[index]
     }
 
@@ -53,34 +65,132 @@ impl<'a> TokenSequence<'a> {
     }
 }
 
-pub fn parse(token_sequence: TokenSequence) -> Result<AbstractSyntaxTree, MascalError> {
+/*
+ Everything a single file defines, the program is only ever looked for in the entry file since
+ the files it imports can only bring along functions, records, enums and error types
+*/
+pub struct ParsedModule {
+    pub blocks: Vec<ScopedBlocks>,
+    pub program_index: Option<usize>,
+    pub declared_errors: Vec<(Rc<str>, Span)>,
+}
+
+pub fn parse(token_sequence: TokenSequence) -> Result<AbstractSyntaxTree, Vec<MascalError>> {
+    let module: ParsedModule = parse_module(token_sequence, &FxHashSet::default(), true)?;
+    let abstract_syntax_tree = AbstractSyntaxTree {
+        blocks: module.blocks,
+        program_index: module.program_index.unwrap(),
+        symbol_table: SymbolTable::default(),
+        warnings: Vec::new(),
+        imported_files: Vec::new(),
+    };
+    Ok(abstract_syntax_tree)
+}
+
+/*
+ Parses the blocks of one file, the IMPORT directives themselves are left for the caller to
+ resolve beforehand, which is also where the error types declared by the imports come from
+*/
+pub fn parse_module(
+    token_sequence: TokenSequence,
+    imported_errors: &FxHashSet<Rc<str>>,
+    is_entry: bool,
+) -> Result<ParsedModule, Vec<MascalError>> {
     let mut scoped_blocks: Vec<ScopedBlocks> = Vec::new();
     let mut program_index: Option<usize> = None;
+    let mut found_program: bool = false;
+    let mut errors: Vec<MascalError> = Vec::new();
+    let mut own_errors: Vec<(Rc<str>, Span)> = Vec::new();
+    let mut declared_errors: FxHashSet<Rc<str>> = imported_errors.clone();
+    let mut depth: usize = 0;
+
+    /* Error types can be used before their ERRORS block, so those get collected up front */
+    for (index, token) in token_sequence.tokens.iter().enumerate() {
+        match token.token_type {
+            TokenType::OpenBrace => depth += 1,
+            TokenType::CloseBrace => depth = depth.saturating_sub(1),
+            TokenType::Errors if depth == 0 => {
+                match parse_error_declarations(token_sequence.subsection_from(index + 1..)) {
+                    Ok(declarations) => {
+                        for (name, span) in declarations {
+                            if MascalErrorType::from_usable_name(&name).is_some()
+                                || !declared_errors.insert(name.clone())
+                            {
+                                errors.push(MascalError {
+                                    error_type: MascalErrorType::ParserError,
+                                    span: Some(span),
+                                    source: format!(
+                                        "The error type {:?} has already been defined",
+                                        name
+                                    ),
+                                });
+                                continue;
+                            }
+                            own_errors.push((name, span));
+                        }
+                    }
+                    Err(err) => errors.push(err),
+                }
+            }
+            _ => continue,
+        }
+    }
+
     for (index, token) in token_sequence.tokens.iter().enumerate() {
         match token.token_type {
             TokenType::DefineFunction => {
-                let func = parse_function(token_sequence.subsection_from(index + 1..))?;
-                scoped_blocks.push(func);
+                match parse_function(token_sequence.subsection_from(index + 1..), &declared_errors) {
+                    Ok(func) => scoped_blocks.push(func),
+                    Err(block_errors) => errors.extend(block_errors),
+                }
+            }
+            TokenType::DefineRecord => {
+                match parse_record(token_sequence.subsection_from(index + 1..)) {
+                    Ok(record) => scoped_blocks.push(record),
+                    Err(block_errors) => errors.extend(block_errors),
+                }
+            }
+            TokenType::DefineEnum => {
+                match parse_enum(token_sequence.subsection_from(index + 1..)) {
+                    Ok(enum_block) => scoped_blocks.push(enum_block),
+                    Err(block_errors) => errors.extend(block_errors),
+                }
+            }
+            TokenType::DefineProgram if !is_entry => {
+                errors.push(MascalError {
+                    error_type: MascalErrorType::ParserError,
+                    span: Some(Span::from(token)),
+                    source: String::from(
+                        "Only the entry file can define a program, imported files are limited to functions, records, enums and errors",
+                    ),
+                });
             }
             TokenType::DefineProgram => {
-                let program = parse_program(token_sequence.subsection_from(index + 1..))?;
-                program_index = Some(index);
-                scoped_blocks.push(program);
+                found_program = true;
+                match parse_program(token_sequence.subsection_from(index + 1..), &declared_errors) {
+                    Ok(program) => {
+                        program_index = Some(scoped_blocks.len());
+                        scoped_blocks.push(program);
+                    }
+                    Err(block_errors) => errors.extend(block_errors),
+                }
             }
             _ => continue,
         }
     }
-    if program_index.is_none() {
-        return Err(MascalError {
+    if is_entry && !found_program {
+        errors.push(MascalError {
             error_type: MascalErrorType::ParserError,
-            character: 0,
-            line: 0,
+            span: None,
             source: String::from("No program entrypoint has been defined"),
         });
     }
-    let abstract_syntax_tree = AbstractSyntaxTree {
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(ParsedModule {
         blocks: scoped_blocks,
-        program_index: program_index.unwrap(),
-    };
-    Ok(abstract_syntax_tree)
+        program_index,
+        declared_errors: own_errors,
+    })
 }
@@ -0,0 +1,87 @@
+use crate::defs::blocks::ScopedBlocks;
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
+use crate::defs::token::{Token, TokenType};
+use crate::parser::TokenSequence;
+use crate::parser::utils::extract_braced_block;
+
+/*
+DEFINE_ENUM Color {
+    RED, GREEN, BLUE
+}
+*/
+pub fn parse_enum(token_sequence: TokenSequence) -> Result<ScopedBlocks, Vec<MascalError>> {
+    if !token_sequence.is_of(TokenType::Identifier, 0) {
+        return Err(vec![MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(token_sequence.first_token())),
+            source: String::from("Expected a identifier for the enum name"),
+        }]);
+    }
+    let name: String = token_sequence.first_token().value.to_string();
+    let span: Span = Span::from(token_sequence.first_token());
+
+    let inner_token_sequence: TokenSequence =
+        extract_braced_block(token_sequence.subsection_from(1..), "DEFINE_ENUM", &[], &[])
+            .map_err(|err| vec![err])?;
+    if inner_token_sequence.tokens.is_empty() {
+        return Err(vec![MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(span),
+            source: format!("Expected at least one variant for the enum {:?}", name),
+        }]);
+    }
+
+    /* Variants are separated by commas, a trailing one after the last variant is fine too */
+    let mut variants: Vec<String> = Vec::new();
+    for (index, token) in inner_token_sequence.tokens.iter().enumerate() {
+        let expects_variant: bool = index % 2 == 0;
+        match token.token_type {
+            TokenType::Identifier if expects_variant => {
+                if variants.iter().any(|variant| variant == token.value) {
+                    return Err(vec![variant_error(
+                        token,
+                        format!(
+                            "The variant {:?} appears more than once in the enum {:?}",
+                            token.value, name
+                        ),
+                    )]);
+                }
+                variants.push(token.value.to_string());
+            }
+            TokenType::Comma if !expects_variant => {}
+            _ if expects_variant => {
+                return Err(vec![variant_error(
+                    token,
+                    format!(
+                        "Expected a variant name in the enum but got {:?}",
+                        token.value
+                    ),
+                )]);
+            }
+            _ => {
+                return Err(vec![variant_error(
+                    token,
+                    format!(
+                        "Expected a comma between the variants of the enum but got {:?}",
+                        token.value
+                    ),
+                )]);
+            }
+        }
+    }
+
+    Ok(ScopedBlocks::Enum {
+        name,
+        variants: variants.into_boxed_slice(),
+        span,
+    })
+}
+
+fn variant_error(token: &Token, source: String) -> MascalError {
+    MascalError {
+        error_type: MascalErrorType::ParserError,
+        span: Some(Span::from(token)),
+        source,
+    }
+}
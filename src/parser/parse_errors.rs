@@ -0,0 +1,143 @@
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
+use crate::defs::statements::{MascalStatement, MascalStatementKind};
+use crate::defs::token::{Token, TokenType};
+use crate::parser::TokenSequence;
+use crate::parser::utils::{extract_braced_block, run_per_statement};
+use rustc_hash::FxHashSet;
+use std::rc::Rc;
+
+/*
+ERRORS {
+    NotFoundError;
+    OutOfStockError;
+}
+*/
+pub fn parse_error_declarations(
+    token_sequence: TokenSequence,
+) -> Result<Vec<(Rc<str>, Span)>, MascalError> {
+    let inner_token_sequence: TokenSequence =
+        extract_braced_block(token_sequence, "ERRORS", &[], &[])?;
+    let mut declarations: Vec<(Rc<str>, Span)> = Vec::new();
+
+    let final_token_sequence: Vec<Token> =
+        run_per_statement(&inner_token_sequence, |token_sequence| {
+            match token_sequence.as_slice() {
+                [] => {}
+                [token, end]
+                    if token.token_type == TokenType::Identifier
+                        && end.token_type == TokenType::Semicolon =>
+                {
+                    declarations.push((Rc::from(token.value), Span::from(token)));
+                }
+                [token, ..] => {
+                    return Err(MascalError {
+                        error_type: MascalErrorType::ParserError,
+                        span: Some(Span::from(token)),
+                        source: format!(
+                            "Expected a single name for the error type but got {:?}",
+                            token.value
+                        ),
+                    });
+                }
+            }
+            Ok(())
+        })?;
+
+    if let Some(token) = final_token_sequence.first() {
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(token)),
+            source: String::from(
+                "Unexpected characters found inside errors block, perhaps forgot a semicolon?",
+            ),
+        });
+    }
+
+    Ok(declarations)
+}
+
+fn check_error_type(
+    error_type: &MascalErrorType,
+    declared_errors: &FxHashSet<Rc<str>>,
+    statement_name: &str,
+    span: Span,
+    errors: &mut Vec<MascalError>,
+) {
+    if let MascalErrorType::Custom(name) = error_type {
+        if declared_errors.contains(name) {
+            return;
+        }
+        errors.push(MascalError {
+            error_type: MascalErrorType::UndefinedErrorType,
+            span: Some(span),
+            source: format!(
+                "Use of an undefined usable error type in the {} statement (perhaps a typo?)",
+                statement_name
+            ),
+        });
+    }
+}
+
+/* Every THROW and CATCH naming an error type of its own has to find it in an ERRORS block */
+pub fn check_error_types(
+    statements: &[MascalStatement],
+    declared_errors: &FxHashSet<Rc<str>>,
+    errors: &mut Vec<MascalError>,
+) {
+    for statement in statements {
+        match &statement.kind {
+            MascalStatementKind::ConditionalStatement(branches) => {
+                for branch in branches {
+                    check_error_types(&branch.statements, declared_errors, errors);
+                }
+            }
+
+            MascalStatementKind::While { branch, .. } => {
+                check_error_types(&branch.statements, declared_errors, errors);
+            }
+
+            MascalStatementKind::For { statements, .. }
+            | MascalStatementKind::ForEach { statements, .. }
+            | MascalStatementKind::Repeat { statements, .. } => {
+                check_error_types(statements, declared_errors, errors);
+            }
+
+            MascalStatementKind::Match { cases, default, .. } => {
+                for case in cases {
+                    check_error_types(&case.statements, declared_errors, errors);
+                }
+                if let Some(default) = default {
+                    check_error_types(default, declared_errors, errors);
+                }
+            }
+
+            MascalStatementKind::Throw { error_type, .. } => {
+                check_error_type(error_type, declared_errors, "throw", statement.span, errors);
+            }
+
+            MascalStatementKind::Try {
+                statements,
+                catches,
+                finally,
+            } => {
+                check_error_types(statements, declared_errors, errors);
+                for catch in catches {
+                    if let Some(error_type) = &catch.error_type {
+                        check_error_type(error_type, declared_errors, "catch", catch.span, errors);
+                    }
+                    check_error_types(&catch.statements, declared_errors, errors);
+                }
+                if let Some(finally) = finally {
+                    check_error_types(finally, declared_errors, errors);
+                }
+            }
+
+            MascalStatementKind::ExpressionStatement(..)
+            | MascalStatementKind::Declaration { .. }
+            | MascalStatementKind::Break(..)
+            | MascalStatementKind::Continue(..)
+            | MascalStatementKind::Return(..) => {}
+        }
+    }
+}
@@ -1,37 +1,59 @@
 use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
 use crate::defs::statements::MascalStatement;
 use crate::defs::token::{Token, TokenType};
 use crate::parser::TokenSequence;
+use crate::parser::parse_errors::check_error_types;
 use crate::parser::parse_statement::parse_statement;
 use crate::parser::utils::{locate_block, run_per_statement};
+use rustc_hash::FxHashSet;
+use std::rc::Rc;
 
-pub fn parse_executable(inner_parser: TokenSequence) -> Result<Vec<MascalStatement>, MascalError> {
+pub fn parse_executable(
+    inner_parser: TokenSequence,
+    declared_errors: &FxHashSet<Rc<str>>,
+) -> Result<Vec<MascalStatement>, Vec<MascalError>> {
     let mut statements: Vec<MascalStatement> = Vec::new();
+    let mut errors: Vec<MascalError> = Vec::new();
     let program_parser = locate_block(
         inner_parser,
         TokenType::Implementation,
         "IMPLEMENTATION",
         &[],
         &[],
-    )?
+    )
+    .map_err(|err| vec![err])?
     .unwrap();
 
+    /*
+     A broken statement only takes itself down, parsing picks back up from the next semicolon
+     (or closing brace) so every independent mistake gets reported in one go
+    */
     let final_toks: Vec<Token> = run_per_statement(&program_parser, |token_sequence| {
-        let stmt: MascalStatement = parse_statement(token_sequence)?;
-        statements.push(stmt);
+        match parse_statement(token_sequence) {
+            Ok(stmt) => {
+                check_error_types(std::slice::from_ref(&stmt), declared_errors, &mut errors);
+                statements.push(stmt);
+            }
+            Err(err) => errors.push(err.located_at(Span::from(&token_sequence[0]))),
+        }
         Ok(())
-    })?;
+    })
+    .map_err(|err| vec![err])?;
 
     if !final_toks.is_empty() {
-        return Err(MascalError {
+        errors.push(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: final_toks[0].line,
-            character: final_toks[0].start,
+            span: Some(Span::from(&final_toks[0])),
             source: String::from(
                 "Unexpected characters found inside implementation block, perhaps forgot a semicolon?",
             ),
         });
     }
 
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
     Ok(statements)
 }
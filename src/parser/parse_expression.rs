@@ -1,15 +1,18 @@
 mod parse_binary_expression;
 mod parse_callable;
+mod parse_conditional;
 mod parse_indexing;
+mod parse_lambda;
 mod parse_primary;
 mod utils;
 
 use crate::define_parsing_step;
 use crate::defs::binding_power::{BindingPower, get_binding_power_from_psign};
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
 use crate::defs::loop_flags::LoopFlags;
 use crate::defs::operators::token_type_to_unary_operator;
+use crate::defs::span::Span;
 use crate::defs::token::{Token, TokenType};
 use crate::parser::parse_expression::parse_binary_expression::parse_binary_expression;
 use crate::parser::parse_expression::parse_callable::parse_callable;
@@ -27,6 +30,7 @@ pub fn parse_expression_internal(
         let curr: Option<&Token> = tokens.get(*pos);
         match curr.map(|t| &t.token_type) {
             Some(TokenType::Comma)
+            | Some(TokenType::Colon)
             | Some(TokenType::CloseBracket)
             | Some(TokenType::CloseDynamicArray)
             | Some(TokenType::CloseParen)
@@ -46,8 +50,7 @@ pub fn parse_expression_internal(
 pub fn parse_prefix(tokens: &[Token], pos: &mut usize) -> Result<MascalExpression, MascalError> {
     let tok = tokens.get(*pos).ok_or_else(|| MascalError {
         error_type: MascalErrorType::ParserError,
-        character: tokens.last().unwrap().start,
-        line: tokens.last().unwrap().line,
+        span: Some(Span::from(tokens.last().unwrap())),
         source: String::from("Abrupt ending in the expression"),
     })?;
 
@@ -55,10 +58,13 @@ pub fn parse_prefix(tokens: &[Token], pos: &mut usize) -> Result<MascalExpressio
         *pos += 1;
         let bp: BindingPower = get_binding_power_from_psign(op.clone());
         let rhs: MascalExpression = parse_expression_internal(tokens, pos, bp)?;
-        return Ok(MascalExpression::Unary {
-            operator: op,
-            value: Box::new(rhs),
-        });
+        return Ok(MascalExpression::new(
+            MascalExpressionKind::Unary {
+                operator: op,
+                value: Box::new(rhs),
+            },
+            Span::from(tok),
+        ));
     }
 
     parse_primary(tokens, pos)
@@ -68,8 +74,7 @@ pub fn parse_expression(token_sequence: &Vec<Token>) -> Result<MascalExpression,
     if token_sequence.is_empty() {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: 0,
-            character: 0,
+            span: None,
             source: String::from("Expected an expression to parse but got nothing")
         })
     }
@@ -82,11 +87,10 @@ pub fn parse_expression(token_sequence: &Vec<Token>) -> Result<MascalExpression,
             right_binding_power: 0,
         },
     )?;
-    if pos <= token_sequence.len() - 1 {
+    if pos < token_sequence.len() {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: token_sequence[pos].line,
-            character: token_sequence[pos].start,
+            span: Some(Span::from(&token_sequence[pos])),
             source: String::from("Unexpected character sequences found in a supposed expression")
         })
     }
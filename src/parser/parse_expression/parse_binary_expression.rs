@@ -1,9 +1,13 @@
 use crate::defs::binding_power::{BindingPower, get_binding_power_from_bsign};
-use crate::defs::errors::MascalError;
-use crate::defs::expressions::MascalExpression;
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
+use crate::defs::literal::MascalLiteral;
 use crate::defs::loop_flags::LoopFlags;
-use crate::defs::operators::{MascalBinaryOperators, token_type_to_binary_operator};
-use crate::defs::token::{Token};
+use crate::defs::operators::{
+    MascalBinaryOperators, MascalUnaryOperators, token_type_to_binary_operator,
+};
+use crate::defs::span::Span;
+use crate::defs::token::Token;
 use crate::parser::parse_expression::parse_expression_internal;
 
 #[inline(always)]
@@ -15,16 +19,16 @@ pub fn parse_binary_expression(
 ) -> Result<(LoopFlags, MascalExpression), MascalError> {
     let op_tok: &Token = if let Some(tok) = tokens.get(*pos) {
         tok
-    } else { return Ok((LoopFlags::Break, lhs)); }; // This feels a bit logically wrong but it works
+    } else { return Ok((LoopFlags::Break(None), lhs)); }; // This feels a bit logically wrong but it works
     let binop: Option<MascalBinaryOperators> = token_type_to_binary_operator(&op_tok.token_type);
     let bp: BindingPower = if let Some(op) = &binop {
         get_binding_power_from_bsign(op.clone())
     } else {
-        return Ok((LoopFlags::Break, lhs));
+        return Ok((LoopFlags::Break(None), lhs));
     };
 
     if bp.left_binding_power <= min_bp.left_binding_power {
-        return Ok((LoopFlags::Break, lhs));
+        return Ok((LoopFlags::Break(None), lhs));
     }
 
     *pos += 1;
@@ -37,12 +41,73 @@ pub fn parse_binary_expression(
             left_binding_power: bp.right_binding_power,
         },
     )?;
+    if matches!(op, MascalBinaryOperators::And | MascalBinaryOperators::Or) {
+        let operator_name: &str = if op == MascalBinaryOperators::And {
+            "AND"
+        } else {
+            "OR"
+        };
+        check_boolean_operand(&lhs, "left", operator_name)?;
+        check_boolean_operand(&rhs, "right", operator_name)?;
+    }
 
-    lhs = MascalExpression::Binary {
-        left: Box::new(lhs),
-        operator: op,
-        right: Box::new(rhs),
-    };
+    lhs = MascalExpression::new(
+        MascalExpressionKind::Binary {
+            left: Box::new(lhs),
+            operator: op,
+            right: Box::new(rhs),
+        },
+        Span::from(op_tok),
+    );
 
     Ok((LoopFlags::None, lhs))
 }
+
+/*
+ Only catches what can't be a boolean no matter what the variables hold, such as ``1 AND a``,
+ the semantic pass takes care of the rest once the variables are known
+*/
+fn check_boolean_operand(
+    operand: &MascalExpression,
+    side: &str,
+    operator_name: &str,
+) -> Result<(), MascalError> {
+    let found: &str = match &operand.kind {
+        MascalExpressionKind::Literal(MascalLiteral::Integer(..)) => "an integer",
+        MascalExpressionKind::Literal(MascalLiteral::Float(..)) => "a float",
+        MascalExpressionKind::Literal(MascalLiteral::String(..)) => "a string",
+        MascalExpressionKind::DynamicArray(..) | MascalExpressionKind::StaticArray(..) => {
+            "an array"
+        }
+        MascalExpressionKind::Map(..) => "a map",
+        MascalExpressionKind::Type(..)
+        | MascalExpressionKind::Unary {
+            operator: MascalUnaryOperators::Typeof,
+            ..
+        } => "a type",
+        MascalExpressionKind::Lambda { .. } => "a function",
+        MascalExpressionKind::Unary {
+            operator: MascalUnaryOperators::Minus,
+            ..
+        }
+        | MascalExpressionKind::Binary {
+            operator:
+                MascalBinaryOperators::Plus
+                | MascalBinaryOperators::Minus
+                | MascalBinaryOperators::Multiply
+                | MascalBinaryOperators::Divide
+                | MascalBinaryOperators::Modulo
+                | MascalBinaryOperators::Exponentiation,
+            ..
+        } => "an arithmetic expression",
+        _ => return Ok(()),
+    };
+    Err(MascalError {
+        error_type: MascalErrorType::ParserError,
+        span: Some(operand.span),
+        source: format!(
+            "Expected a boolean on the {} side of {} but got {}",
+            side, operator_name, found
+        ),
+    })
+}
@@ -1,7 +1,8 @@
 use crate::defs::binding_power::BindingPower;
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
 use crate::defs::loop_flags::LoopFlags;
+use crate::defs::span::Span;
 use crate::defs::token::{Token, TokenType};
 use crate::parser::parse_expression::parse_expression_internal;
 
@@ -40,8 +41,7 @@ pub fn parse_callable(
                     _ => {
                         return Err(MascalError {
                             error_type: MascalErrorType::ParserError,
-                            character: curr_tok.start,
-                            line: curr_tok.line,
+                            span: Some(Span::from(curr_tok)),
                             source: format!(
                                 "Expected a comma ',' or closing parenthesis ')' in the function call, but got {:?}",
                                 curr_tok.value
@@ -55,19 +55,22 @@ pub fn parse_callable(
         if tokens.get(*pos).map(|t| &t.token_type) != Some(&TokenType::CloseParen) {
             return Err(MascalError {
                 error_type: MascalErrorType::ParserError,
-                character: tokens.get(*pos).map_or(0, |t| t.start),
-                line: tokens.get(*pos).map_or(0, |t| t.line),
+                span: tokens.get(*pos).map(Span::from),
                 source: "Expected a closing parenthesis ')' to close the function call".into(),
             });
         }
         *pos += 1;
 
-        lhs = MascalExpression::Call {
-            function: Box::new(lhs),
-            arguments: args,
-        };
+        let span: Span = lhs.span;
+        lhs = MascalExpression::new(
+            MascalExpressionKind::Call {
+                function: Box::new(lhs),
+                arguments: args,
+            },
+            span,
+        );
 
-        return Ok((LoopFlags::Continue, lhs));
+        return Ok((LoopFlags::Continue(None), lhs));
     }
 
     Ok((LoopFlags::None, lhs))
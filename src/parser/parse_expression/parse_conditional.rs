@@ -0,0 +1,95 @@
+use crate::defs::binding_power::{BindingPower, get_binding_power_of_conditional};
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
+use crate::defs::span::Span;
+use crate::defs::token::{Token, TokenType};
+use crate::parser::parse_expression::parse_expression_internal;
+
+fn conditional_error(tokens: &[Token], pos: usize, source: String) -> MascalError {
+    let token: &Token = tokens.get(pos).unwrap_or_else(|| tokens.last().unwrap());
+    MascalError {
+        error_type: MascalErrorType::ParserError,
+        span: Some(Span::from(token)),
+        source,
+    }
+}
+
+fn found_token(tokens: &[Token], pos: usize) -> String {
+    tokens
+        .get(pos)
+        .map_or(String::from("nothing"), |t| format!("{:?}", t.value))
+}
+
+/* Parses whatever follows the keyword the position points at */
+fn parse_part(
+    tokens: &[Token],
+    pos: &mut usize,
+    keyword: &str,
+    part_name: &str,
+    bp: &BindingPower,
+) -> Result<MascalExpression, MascalError> {
+    *pos += 1;
+    if *pos >= tokens.len() {
+        return Err(conditional_error(
+            tokens,
+            *pos,
+            format!(
+                "Expected the {} of the IF expression after {} but got nothing",
+                part_name, keyword
+            ),
+        ));
+    }
+    parse_expression_internal(tokens, pos, bp.clone())
+}
+
+/*
+IF a > b THEN a ELSE b
+IF a > b THEN 1 ELSE IF a == b THEN 0 ELSE -1
+
+Only the branch that got picked is ever evaluated, so both of them are required. The ELSE branch
+takes everything up until whatever ends the expression around it just like the body of a lambda,
+the position points at the IF keyword
+*/
+pub fn parse_conditional(
+    tokens: &[Token],
+    pos: &mut usize,
+) -> Result<MascalExpressionKind, MascalError> {
+    let bp: BindingPower = get_binding_power_of_conditional();
+    let condition: MascalExpression = parse_part(tokens, pos, "IF", "condition", &bp)?;
+    if tokens.get(*pos).map(|t| &t.token_type) != Some(&TokenType::Then) {
+        return Err(conditional_error(
+            tokens,
+            *pos,
+            format!(
+                "Expected THEN after the condition of the IF expression but got {}",
+                found_token(tokens, *pos)
+            ),
+        ));
+    }
+    let then_branch: MascalExpression = parse_part(tokens, pos, "THEN", "THEN branch", &bp)?;
+
+    /* ELSE IF (or ELIF) chains another IF expression on as the ELSE branch */
+    let else_branch: MascalExpression = match tokens.get(*pos).map(|t| &t.token_type) {
+        Some(TokenType::ElseIf) => {
+            let span: Span = Span::from(&tokens[*pos]);
+            MascalExpression::new(parse_conditional(tokens, pos)?, span)
+        }
+        Some(TokenType::Else) => parse_part(tokens, pos, "ELSE", "ELSE branch", &bp)?,
+        _ => {
+            return Err(conditional_error(
+                tokens,
+                *pos,
+                format!(
+                    "Expected ELSE after the THEN branch of the IF expression but got {}",
+                    found_token(tokens, *pos)
+                ),
+            ));
+        }
+    };
+
+    Ok(MascalExpressionKind::Conditional {
+        condition: Box::new(condition),
+        then_branch: Box::new(then_branch),
+        else_branch: Box::new(else_branch),
+    })
+}
@@ -1,7 +1,8 @@
 use crate::defs::binding_power::BindingPower;
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
 use crate::defs::loop_flags::LoopFlags;
+use crate::defs::span::Span;
 use crate::defs::token::{Token, TokenType};
 use crate::parser::parse_expression::parse_expression_internal;
 
@@ -10,6 +11,7 @@ macro_rules! parse_inner_expr_of_array {
         $lhs: expr, $pos: expr, $tokens: expr, $open_tok: expr,
         $close_tok: expr, $close_tok_char: expr, $is_dynamic: expr
     ) => {
+        let span: Span = Span::from(&$tokens[*$pos]);
         *$pos += 1;
         let mut indexing_length: usize = 0;
         let mut has_found_closing: bool = false;
@@ -17,8 +19,7 @@ macro_rules! parse_inner_expr_of_array {
         if *$pos >= $tokens.len() {
             return Err(MascalError {
                 error_type: MascalErrorType::ParserError,
-                line: $tokens.last().unwrap().line,
-                character: $tokens.last().unwrap().start,
+                span: Some(Span::from($tokens.last().unwrap())),
                 source: format!("Expected {:?} after index expression", $close_tok_char)
             });
         }
@@ -43,8 +44,7 @@ macro_rules! parse_inner_expr_of_array {
         if !has_found_closing {
             return Err(MascalError {
                 error_type: MascalErrorType::ParserError,
-                line: $tokens[*$pos].line,
-                character: $tokens[*$pos].start,
+                span: Some(Span::from(&$tokens[*$pos])),
                 source: format!("Expected {:?} after index expression", $close_tok_char)
             });
         }
@@ -52,11 +52,14 @@ macro_rules! parse_inner_expr_of_array {
             &$tokens[*$pos..*$pos + indexing_length - 1], &mut 0usize, BindingPower::new(0)
         )?;
         *$pos += indexing_length;
-        $lhs = MascalExpression::Indexing {
-            array: Box::new($lhs),
-            index: Box::new(idx),
-            is_dynamic: $is_dynamic,
-        };
+        $lhs = MascalExpression::new(
+            MascalExpressionKind::Indexing {
+                array: Box::new($lhs),
+                index: Box::new(idx),
+                is_dynamic: $is_dynamic,
+            },
+            span,
+        );
     };
 }
 
@@ -83,11 +86,32 @@ pub fn parse_indexing_expression(
                 );
             }
 
+            Some(TokenType::Dot) => {
+                let span: Span = Span::from(curr.unwrap());
+                let field: &Token = match tokens.get(*pos + 1) {
+                    Some(token) if token.token_type == TokenType::Identifier => token,
+                    _ => {
+                        return Err(MascalError {
+                            error_type: MascalErrorType::ParserError,
+                            span: Some(Span::from(curr.unwrap())),
+                            source: String::from("Expected a field name after the \".\" of a record")
+                        })
+                    }
+                };
+                *pos += 2;
+                lhs = MascalExpression::new(
+                    MascalExpressionKind::FieldAccess {
+                        record: Box::new(lhs),
+                        field: field.value.to_string(),
+                    },
+                    span,
+                );
+            }
+
             Some(TokenType::CloseBracket) => {
                 return Err(MascalError {
                     error_type: MascalErrorType::ParserError,
-                    line: curr.unwrap().line,
-                    character: curr.unwrap().start,
+                    span: Some(Span::from(curr.unwrap())),
                     source: String::from("Expected an opening character \"[\" before closing an unopened static array")
                 })
             }
@@ -95,8 +119,7 @@ pub fn parse_indexing_expression(
             Some(TokenType::CloseDynamicArray) => {
                 return Err(MascalError {
                     error_type: MascalErrorType::ParserError,
-                    line: curr.unwrap().line,
-                    character: curr.unwrap().start,
+                    span: Some(Span::from(curr.unwrap())),
                     source: String::from("Expected an opening character \"<<\" before closing an unopened dynamic array")
                 })
             }
@@ -0,0 +1,101 @@
+use crate::defs::binding_power::BindingPower;
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
+use crate::defs::span::Span;
+use crate::defs::token::{Token, TokenType};
+use crate::parser::parse_expression::parse_expression_internal;
+use std::rc::Rc;
+
+fn lambda_error(tokens: &[Token], pos: usize, source: String) -> MascalError {
+    let token: &Token = tokens.get(pos).unwrap_or_else(|| tokens.last().unwrap());
+    MascalError {
+        error_type: MascalErrorType::ParserError,
+        span: Some(Span::from(token)),
+        source,
+    }
+}
+
+/*
+FUNCTION(a, b) -> a + b
+
+The body is a single expression which takes everything up until whatever ends the expression
+around it (a comma, a closing parenthesis... etc.), the position points at the FUNCTION keyword
+*/
+pub fn parse_lambda(
+    tokens: &[Token],
+    pos: &mut usize,
+) -> Result<MascalExpressionKind, MascalError> {
+    *pos += 2;
+    let mut parameters: Vec<Rc<str>> = Vec::new();
+    while tokens.get(*pos).map(|t| &t.token_type) != Some(&TokenType::CloseParen) {
+        let Some(parameter) = tokens
+            .get(*pos)
+            .filter(|t| t.token_type == TokenType::Identifier)
+        else {
+            return Err(lambda_error(
+                tokens,
+                *pos,
+                format!(
+                    "Expected a parameter name for the lambda but got {:?}",
+                    tokens.get(*pos).map_or("nothing", |t| t.value)
+                ),
+            ));
+        };
+        if parameters
+            .iter()
+            .any(|existing| **existing == *parameter.value)
+        {
+            return Err(lambda_error(
+                tokens,
+                *pos,
+                format!(
+                    "The parameter {:?} appears more than once in the lambda",
+                    parameter.value
+                ),
+            ));
+        }
+        parameters.push(Rc::from(parameter.value));
+        *pos += 1;
+        match tokens.get(*pos).map(|t| &t.token_type) {
+            Some(TokenType::Comma) => *pos += 1,
+            Some(TokenType::CloseParen) => {}
+            _ => {
+                return Err(lambda_error(
+                    tokens,
+                    *pos,
+                    String::from(
+                        "Expected a comma or a closing parenthesis after the parameter of the lambda",
+                    ),
+                ));
+            }
+        }
+    }
+    *pos += 1;
+    if tokens.get(*pos).map(|t| &t.token_type) != Some(&TokenType::ReturnIndicator) {
+        return Err(lambda_error(
+            tokens,
+            *pos,
+            String::from("Expected an arrow '->' followed by the body of the lambda"),
+        ));
+    }
+    *pos += 1;
+    if *pos >= tokens.len() {
+        return Err(lambda_error(
+            tokens,
+            *pos,
+            String::from("Expected an expression for the body of the lambda but got nothing"),
+        ));
+    }
+    let body: MascalExpression = parse_expression_internal(
+        tokens,
+        pos,
+        BindingPower {
+            left_binding_power: 0,
+            right_binding_power: 0,
+        },
+    )?;
+    Ok(MascalExpressionKind::Lambda {
+        parameters: parameters.into_boxed_slice(),
+        body: Box::new(body),
+    })
+}
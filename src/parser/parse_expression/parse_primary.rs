@@ -1,11 +1,20 @@
 use crate::defs::binding_power::BindingPower;
 use crate::defs::dynamic_int::IntegerNum;
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
 use crate::defs::literal::MascalLiteral;
+use crate::defs::span::Span;
 use crate::defs::token::{Token, TokenType};
 use crate::defs::types::token_type_to_atom_mascal_type;
+use crate::parser::parse_expression::parse_conditional::parse_conditional;
 use crate::parser::parse_expression::parse_expression_internal;
+use crate::parser::parse_expression::parse_lambda::parse_lambda;
+use crate::parser::utils::parse_map_type;
+
+const NO_BINDING_POWER: BindingPower = BindingPower {
+    left_binding_power: 0,
+    right_binding_power: 0,
+};
 
 fn parse_delimited_array(
     tokens: &[Token],
@@ -17,8 +26,7 @@ fn parse_delimited_array(
 ) -> Result<Vec<MascalExpression>, MascalError> {
     let start: &Token = tokens.get(*pos).ok_or_else(|| MascalError {
         error_type: MascalErrorType::ParserError,
-        line: tokens.first().unwrap().line,
-        character: tokens.first().unwrap().start,
+        span: Some(Span::from(tokens.first().unwrap())),
         source: format!(
             "Abrupt end of array, was expecting a opening character {:?} but got nothing",
             open_tok_chars
@@ -27,8 +35,7 @@ fn parse_delimited_array(
     if start.token_type != open_tok {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: start.line,
-            character: start.start,
+            span: Some(Span::from(start)),
             source: format!(
                 "Expected a opening character {:?} but got {:?}",
                 open_tok_chars, start.value
@@ -55,8 +62,7 @@ fn parse_delimited_array(
 
     let closing: &Token = tokens.get(*pos).ok_or_else(|| MascalError {
         error_type: MascalErrorType::ParserError,
-        line: tokens.last().unwrap().line,
-        character: tokens.last().unwrap().start,
+        span: Some(Span::from(tokens.last().unwrap())),
         source: format!(
             "Abrupt end of array, was expecting a closing character {:?} but got nothing",
             close_tok_chars
@@ -65,8 +71,7 @@ fn parse_delimited_array(
     if closing.token_type != close_tok {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: closing.line,
-            character: closing.start,
+            span: Some(Span::from(closing)),
             source: format!(
                 "Expected a closing character {:?} but got {:?}",
                 close_tok_chars, closing.value
@@ -78,58 +83,136 @@ fn parse_delimited_array(
     Ok(elems)
 }
 
+/* A bracket opens a map literal instead of a static array when its first element is followed by ":" */
+fn is_map_literal(tokens: &[Token], pos: usize) -> bool {
+    let mut depth: usize = 0;
+    for tok in &tokens[pos + 1..] {
+        match tok.token_type {
+            TokenType::Colon if depth == 0 => return true,
+            TokenType::Comma | TokenType::CloseBracket if depth == 0 => return false,
+            TokenType::OpenBracket | TokenType::OpenParen | TokenType::OpenDynamicArray => {
+                depth += 1
+            }
+            TokenType::CloseBracket | TokenType::CloseParen | TokenType::CloseDynamicArray => {
+                depth = depth.saturating_sub(1)
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+fn expect_map_literal_token(
+    tokens: &[Token],
+    pos: usize,
+    expected: TokenType,
+    expected_chars: &str,
+) -> Result<(), MascalError> {
+    let tok: &Token = tokens.get(pos).ok_or_else(|| MascalError {
+        error_type: MascalErrorType::ParserError,
+        span: Some(Span::from(tokens.last().unwrap())),
+        source: format!(
+            "Abrupt end of map, was expecting a {:?} but got nothing",
+            expected_chars
+        ),
+    })?;
+    if tok.token_type != expected {
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(tok)),
+            source: format!(
+                "Expected a {:?} in the map but got {:?}",
+                expected_chars, tok.value
+            ),
+        });
+    }
+    Ok(())
+}
+
+/* ["a": 1, "b": 2] or [:] for an empty one, the position points at the opening bracket */
+fn parse_map_literal(
+    tokens: &[Token],
+    pos: &mut usize,
+) -> Result<Vec<(MascalExpression, MascalExpression)>, MascalError> {
+    *pos += 1;
+    let mut entries: Vec<(MascalExpression, MascalExpression)> = Vec::new();
+    if tokens.get(*pos).map(|t| &t.token_type) == Some(&TokenType::Colon) {
+        expect_map_literal_token(tokens, *pos + 1, TokenType::CloseBracket, "]")?;
+        *pos += 2;
+        return Ok(entries);
+    }
+    loop {
+        let key: MascalExpression = parse_expression_internal(tokens, pos, NO_BINDING_POWER)?;
+        expect_map_literal_token(tokens, *pos, TokenType::Colon, ":")?;
+        *pos += 1;
+        let value: MascalExpression = parse_expression_internal(tokens, pos, NO_BINDING_POWER)?;
+        entries.push((key, value));
+        match tokens.get(*pos).map(|t| &t.token_type) {
+            Some(TokenType::Comma) => *pos += 1,
+            _ => {
+                expect_map_literal_token(tokens, *pos, TokenType::CloseBracket, "]")?;
+                *pos += 1;
+                return Ok(entries);
+            }
+        }
+    }
+}
+
 pub fn parse_primary(tokens: &[Token], pos: &mut usize) -> Result<MascalExpression, MascalError> {
     let tok: &Token = tokens.get(*pos).ok_or_else(|| MascalError {
         error_type: MascalErrorType::ParserError,
-        character: tokens.last().unwrap().start,
-        line: tokens.last().unwrap().line,
+        span: Some(Span::from(tokens.last().unwrap())),
         source: String::from("Abrupt ending in a primary expression"),
     })?;
 
-    if let Some(next_tok) = tokens.get(*pos + 1) {
-        if let (TokenType::IntegerLiteral | TokenType::FloatLiteral, TokenType::Identifier) =
+    if let Some(next_tok) = tokens.get(*pos + 1)
+        && let (TokenType::IntegerLiteral | TokenType::FloatLiteral, TokenType::Identifier) =
             (&tok.token_type, &next_tok.token_type)
-        {
-            return Err(MascalError {
-                error_type: MascalErrorType::ParserError,
-                character: tok.start,
-                line: tok.line,
-                source: format!(
-                    "Numbers cannot be directly followed by identifiers (found '{} {}')",
-                    tok.value, next_tok.value
-                ),
-            });
-        }
+    {
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(tok)),
+            source: format!(
+                "Numbers cannot be directly followed by identifiers (found '{} {}')",
+                tok.value, next_tok.value
+            ),
+        });
     }
 
-    match &tok.token_type {
+    let kind: MascalExpressionKind = match &tok.token_type {
         TokenType::IntegerLiteral => {
             *pos += 1;
-            Ok(MascalExpression::Literal(MascalLiteral::Integer(
-                IntegerNum::new(tok.value.parse::<i128>().unwrap()),
+            MascalExpressionKind::Literal(MascalLiteral::Integer(IntegerNum::new(
+                tok.value.parse::<i128>().unwrap(),
             )))
         }
 
         TokenType::FloatLiteral => {
             *pos += 1;
-            Ok(MascalExpression::Literal(MascalLiteral::Float(
-                tok.value.parse::<f64>().unwrap(),
-            )))
+            MascalExpressionKind::Literal(MascalLiteral::Float(tok.value.parse::<f64>().unwrap()))
         }
 
         TokenType::True => {
             *pos += 1;
-            Ok(MascalExpression::Literal(MascalLiteral::Boolean(true)))
+            MascalExpressionKind::Literal(MascalLiteral::Boolean(true))
         }
 
         TokenType::False => {
             *pos += 1;
-            Ok(MascalExpression::Literal(MascalLiteral::Boolean(false)))
+            MascalExpressionKind::Literal(MascalLiteral::Boolean(false))
         }
 
         TokenType::Null => {
             *pos += 1;
-            Ok(MascalExpression::Literal(MascalLiteral::Null))
+            MascalExpressionKind::Literal(MascalLiteral::Null)
+        }
+
+        TokenType::If => parse_conditional(tokens, pos)?,
+
+        TokenType::Function
+            if tokens.get(*pos + 1).map(|t| &t.token_type) == Some(&TokenType::OpenParen) =>
+        {
+            parse_lambda(tokens, pos)?
         }
 
         tt if tt == &TokenType::Integer
@@ -137,24 +220,21 @@ pub fn parse_primary(tokens: &[Token], pos: &mut usize) -> Result<MascalExpressi
             || tt == &TokenType::Float
             || tt == &TokenType::Boolean
             || tt == &TokenType::Type
+            || tt == &TokenType::Function
             || tt == &TokenType::Dynamic =>
         {
             *pos += 1;
-            Ok(MascalExpression::Type(Box::new(
-                token_type_to_atom_mascal_type(tt).unwrap(),
-            )))
+            MascalExpressionKind::Type(Box::new(token_type_to_atom_mascal_type(tt).unwrap()))
         }
 
         TokenType::StringLiteral => {
             *pos += 1;
-            Ok(MascalExpression::Literal(MascalLiteral::String(
-                tok.value.to_string(),
-            )))
+            MascalExpressionKind::Literal(MascalLiteral::String(tok.value.to_string()))
         }
 
         TokenType::Identifier => {
             *pos += 1;
-            Ok(MascalExpression::Symbolic(tok.value.to_string()))
+            MascalExpressionKind::Symbolic(tok.value.to_string())
         }
 
         TokenType::OpenParen => {
@@ -169,22 +249,26 @@ pub fn parse_primary(tokens: &[Token], pos: &mut usize) -> Result<MascalExpressi
             )?;
             let closing: &Token = tokens.get(*pos).ok_or_else(|| MascalError {
                 error_type: MascalErrorType::ParserError,
-                character: tok.start,
-                line: tok.line,
+                span: Some(Span::from(tok)),
                 source: "Unclosed parenthesis".into(),
             })?;
             if closing.token_type != TokenType::CloseParen {
                 return Err(MascalError {
                     error_type: MascalErrorType::ParserError,
-                    character: closing.start,
-                    line: closing.line,
+                    span: Some(Span::from(closing)),
                     source: "Expected a closing parenthesis ')'".into(),
                 });
             }
             *pos += 1;
-            Ok(expr)
+            return Ok(expr);
+        }
+
+        TokenType::OpenBracket if is_map_literal(tokens, *pos) => {
+            MascalExpressionKind::Map(parse_map_literal(tokens, pos)?.into_boxed_slice())
         }
 
+        TokenType::Map => MascalExpressionKind::Type(Box::new(parse_map_type(tokens, pos)?)),
+
         TokenType::OpenBracket => {
             let elems: Vec<MascalExpression> = parse_delimited_array(
                 tokens,
@@ -194,7 +278,7 @@ pub fn parse_primary(tokens: &[Token], pos: &mut usize) -> Result<MascalExpressi
                 TokenType::CloseBracket,
                 "]",
             )?;
-            Ok(MascalExpression::StaticArray(elems.into_boxed_slice()))
+            MascalExpressionKind::StaticArray(elems.into_boxed_slice())
         }
 
         TokenType::OpenDynamicArray => {
@@ -206,31 +290,36 @@ pub fn parse_primary(tokens: &[Token], pos: &mut usize) -> Result<MascalExpressi
                 TokenType::CloseDynamicArray,
                 ">>",
             )?;
-            Ok(MascalExpression::DynamicArray(elems.into_boxed_slice()))
+            MascalExpressionKind::DynamicArray(elems.into_boxed_slice())
         }
 
-        TokenType::CloseDynamicArray => Err(MascalError {
-            error_type: MascalErrorType::ParserError,
-            character: tok.start,
-            line: tok.line,
-            source: String::from("Expected an opening arrow '<<' in dynamic array literal"),
-        }),
+        TokenType::CloseDynamicArray => {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(tok)),
+                source: String::from("Expected an opening arrow '<<' in dynamic array literal"),
+            });
+        }
 
-        TokenType::CloseBracket => Err(MascalError {
-            error_type: MascalErrorType::ParserError,
-            character: tok.start,
-            line: tok.line,
-            source: String::from("Expected an opening bracket '[' in static array literal"),
-        }),
+        TokenType::CloseBracket => {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(tok)),
+                source: String::from("Expected an opening bracket '[' in static array literal"),
+            });
+        }
 
-        _ => Err(MascalError {
-            error_type: MascalErrorType::ParserError,
-            character: tok.start,
-            line: tok.line,
-            source: format!(
-                "Unexpected characters in primary expression: {:?}",
-                &tok.value
-            ),
-        }),
-    }
+        _ => {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(tok)),
+                source: format!(
+                    "Unexpected characters in primary expression: {:?}",
+                    &tok.value
+                ),
+            });
+        }
+    };
+
+    Ok(MascalExpression::new(kind, Span::from(tok)))
 }
@@ -5,8 +5,8 @@ macro_rules! define_parsing_step {
         $lhs_opt = new_lhs;
 
         match flag {
-            LoopFlags::Break => break,
-            LoopFlags::Continue => {
+            LoopFlags::Break(..) => break,
+            LoopFlags::Continue(..) => {
                 continue;
             }
             LoopFlags::None => {}
@@ -18,8 +18,8 @@ macro_rules! define_parsing_step {
         $lhs_opt = new_lhs;
 
         match flag {
-            LoopFlags::Break => break,
-            LoopFlags::Continue => {
+            LoopFlags::Break(..) => break,
+            LoopFlags::Continue(..) => {
                 continue;
             }
             LoopFlags::None => {}
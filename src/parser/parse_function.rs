@@ -1,12 +1,15 @@
 use crate::defs::blocks::{ExecutionBlock, MascalParameter, ScopedBlocks};
 use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
 use crate::defs::token::{Token, TokenType};
 use crate::defs::types::MascalUnprocessedType;
 use crate::parser::TokenSequence;
 use crate::parser::parse_executable_block::parse_executable;
 use crate::parser::parse_variables::parse_variable_block;
-use crate::parser::utils::{extract_braced_block, parse_array_type};
+use crate::parser::utils::{extract_braced_block, join_results, parse_array_type, parse_map_type};
+use rustc_hash::FxHashSet;
 use std::rc::Rc;
+use std::sync::Arc;
 
 #[inline(always)]
 fn get_parameters_of_func(
@@ -19,14 +22,13 @@ fn get_parameters_of_func(
         return Ok((Vec::new(), curr_index + 2));
     }
     let mut is_mutable: bool = false;
-    let mut parameter_name: Option<&str> = None;
+    let mut parameter_name: Option<&Token> = None;
     let mut parameters: Vec<MascalParameter> = Vec::new();
     while !token_sequence.is_of(TokenType::CloseParen, curr_index) {
         if curr_index >= token_sequence.tokens.len() {
             return Err(MascalError {
                 error_type: MascalErrorType::ParserError,
-                line: token_sequence.first_token().line,
-                character: token_sequence.first_token().start,
+                span: Some(Span::from(token_sequence.first_token())),
                 source: String::from("Parameters have not been closed with a parenthesis"),
             });
         }
@@ -36,13 +38,12 @@ fn get_parameters_of_func(
             .unwrap_or(curr_token)
             .token_type;
         if curr_token.token_type == TokenType::Identifier {
-            parameter_name = Some(curr_token.value);
+            parameter_name = Some(curr_token);
         } else if curr_token.token_type == TokenType::Mutable {
             if parameter_name.is_some() {
                 return Err(MascalError {
                     error_type: MascalErrorType::ParserError,
-                    line: curr_token.line,
-                    character: curr_token.start,
+                    span: Some(Span::from(curr_token)),
                     source: String::from(
                         "the keyword MUT should be followed before the variable's name",
                     ),
@@ -50,8 +51,7 @@ fn get_parameters_of_func(
             } else if is_mutable {
                 return Err(MascalError {
                     error_type: MascalErrorType::ParserError,
-                    line: curr_token.line,
-                    character: curr_token.start,
+                    span: Some(Span::from(curr_token)),
                     source: String::from("the MUT keyword cannot stack on top of one variable"),
                 });
             }
@@ -61,14 +61,15 @@ fn get_parameters_of_func(
             if parameter_name.is_none() {
                 return Err(MascalError {
                     error_type: MascalErrorType::ParserError,
-                    line: curr_token.line,
-                    character: curr_token.start,
+                    span: Some(Span::from(curr_token)),
                     source: String::from("expected to define parameter name"),
                 });
             }
+            let parameter_token: &Token = parameter_name.unwrap();
             parameters.push(MascalParameter {
-                name: Rc::from(parameter_name.unwrap()),
+                name: Rc::from(parameter_token.value),
                 is_mutable,
+                span: Span::from(parameter_token),
             });
             parameter_name = None;
             is_mutable = false;
@@ -79,22 +80,26 @@ fn get_parameters_of_func(
     Ok((parameters, curr_index + 1))
 }
 
-pub fn parse_function(token_sequence: TokenSequence) -> Result<ScopedBlocks, MascalError> {
+pub fn parse_function(
+    token_sequence: TokenSequence,
+    declared_errors: &FxHashSet<Rc<str>>,
+) -> Result<ScopedBlocks, Vec<MascalError>> {
     let mut curr_index: usize = 0;
     let name: String;
+    let span: Span = Span::from(token_sequence.first_token());
     if token_sequence.is_of(TokenType::Identifier, curr_index) {
         name = token_sequence.first_token().value.to_string();
         curr_index += 1;
     } else {
-        return Err(MascalError {
+        return Err(vec![MascalError {
             error_type: MascalErrorType::ParserError,
-            line: token_sequence.first_token().line,
-            character: token_sequence.first_token().start,
+            span: Some(Span::from(token_sequence.first_token())),
             source: String::from("Expected a identifier for the function name"),
-        });
+        }]);
     }
 
-    let returned_tuple = get_parameters_of_func(&token_sequence, curr_index)?;
+    let returned_tuple =
+        get_parameters_of_func(&token_sequence, curr_index).map_err(|err| vec![err])?;
     let parameters: Vec<MascalParameter> = returned_tuple.0;
     curr_index = returned_tuple.1;
 
@@ -103,19 +108,30 @@ pub fn parse_function(token_sequence: TokenSequence) -> Result<ScopedBlocks, Mas
         curr_index += 1;
         let curr_token: &Token = token_sequence.acquire_token(curr_index);
         return_type = Some(match curr_token.token_type {
+            TokenType::Map => {
+                let map_type: MascalUnprocessedType =
+                    parse_map_type(&token_sequence.tokens, &mut curr_index)
+                        .map_err(|err| vec![err])?;
+                /* Every other type is a single token which gets skipped right below */
+                curr_index -= 1;
+                map_type
+            }
             TokenType::Integer => MascalUnprocessedType::Integer,
             TokenType::Float => MascalUnprocessedType::Float,
             TokenType::Boolean => MascalUnprocessedType::Boolean,
             TokenType::Dynamic => MascalUnprocessedType::Dynamic,
             TokenType::String => MascalUnprocessedType::String,
             TokenType::Type => MascalUnprocessedType::Type,
+            TokenType::Function => MascalUnprocessedType::Function,
+            TokenType::Identifier => MascalUnprocessedType::Record(Arc::from(curr_token.value)),
             _ => {
-                return Err(MascalError {
+                return Err(vec![MascalError {
                     error_type: MascalErrorType::ParserError,
-                    line: curr_token.line,
-                    character: curr_token.start,
-                    source: String::from("Expected a specific type to be returned and got something else"),
-                });
+                    span: Some(Span::from(curr_token)),
+                    source: String::from(
+                        "Expected a specific type to be returned and got something else",
+                    ),
+                }]);
             }
         });
         curr_index += 1;
@@ -128,7 +144,8 @@ pub fn parse_function(token_sequence: TokenSequence) -> Result<ScopedBlocks, Mas
                 Ok(())
             },
             vec![TokenType::OpenBrace],
-        )?;
+        )
+        .map_err(|err| vec![err])?;
         if !is_dynamics.is_empty() {
             for is_dynamic in is_dynamics {
                 if is_dynamic {
@@ -149,9 +166,12 @@ pub fn parse_function(token_sequence: TokenSequence) -> Result<ScopedBlocks, Mas
         "DEFINE_FUNCTION",
         &[TokenType::Variables],
         &[TokenType::Implementation],
+    )
+    .map_err(|err| vec![err])?;
+    let (variable_block, program_body) = join_results(
+        parse_variable_block(&inner_token_sequence),
+        parse_executable(inner_token_sequence, declared_errors),
     )?;
-    let variable_block = parse_variable_block(&inner_token_sequence)?;
-    let program_body = parse_executable(inner_token_sequence)?;
 
     Ok(ScopedBlocks::Function {
         parameters: parameters.into_boxed_slice(),
@@ -161,5 +181,6 @@ pub fn parse_function(token_sequence: TokenSequence) -> Result<ScopedBlocks, Mas
             variables: variable_block,
             body: program_body.into_boxed_slice(),
         },
+        span,
     })
 }
@@ -0,0 +1,67 @@
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
+use crate::defs::token::{Token, TokenType};
+use crate::parser::TokenSequence;
+
+/*
+IMPORT "path/to/lib.mascal";
+*/
+pub fn parse_imports(
+    token_sequence: &TokenSequence,
+) -> Result<Vec<(String, Span)>, Vec<MascalError>> {
+    let mut imports: Vec<(String, Span)> = Vec::new();
+    let mut errors: Vec<MascalError> = Vec::new();
+    let mut depth: usize = 0;
+
+    for (index, token) in token_sequence.tokens.iter().enumerate() {
+        match token.token_type {
+            TokenType::OpenBrace => depth += 1,
+            TokenType::CloseBrace => depth = depth.saturating_sub(1),
+            TokenType::Import if depth > 0 => {
+                errors.push(import_error(
+                    token,
+                    String::from("Imports can only be placed at the top level of a file, outside of any block"),
+                ));
+            }
+            TokenType::Import => {
+                let Some(path) = token_sequence
+                    .get_token(index + 1)
+                    .filter(|path| path.token_type == TokenType::StringLiteral)
+                else {
+                    errors.push(import_error(
+                        token,
+                        String::from(
+                            "Expected the path of the imported file in quotes after IMPORT",
+                        ),
+                    ));
+                    continue;
+                };
+                if !token_sequence.is_of(TokenType::Semicolon, index + 2) {
+                    errors.push(import_error(
+                        path,
+                        format!(
+                            "Expected a semicolon after the imported path {:?}",
+                            path.value
+                        ),
+                    ));
+                    continue;
+                }
+                imports.push((path.value.to_string(), Span::from(token)));
+            }
+            _ => continue,
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(imports)
+}
+
+fn import_error(token: &Token, source: String) -> MascalError {
+    MascalError {
+        error_type: MascalErrorType::ParserError,
+        span: Some(Span::from(token)),
+        source,
+    }
+}
@@ -4,17 +4,25 @@ use crate::defs::token::TokenType;
 use crate::parser::TokenSequence;
 use crate::parser::parse_executable_block::parse_executable;
 use crate::parser::parse_variables::parse_variable_block;
-use crate::parser::utils::extract_braced_block;
+use crate::parser::utils::{extract_braced_block, join_results};
+use rustc_hash::FxHashSet;
+use std::rc::Rc;
 
-pub fn parse_program(token_sequence: TokenSequence) -> Result<ScopedBlocks, MascalError> {
+pub fn parse_program(
+    token_sequence: TokenSequence,
+    declared_errors: &FxHashSet<Rc<str>>,
+) -> Result<ScopedBlocks, Vec<MascalError>> {
     let inner_token_sequence = extract_braced_block(
         token_sequence,
         "DEFINE_PROGRAM",
         &[TokenType::Variables],
         &[TokenType::Implementation],
+    )
+    .map_err(|err| vec![err])?;
+    let (variable_block, program_body) = join_results(
+        parse_variable_block(&inner_token_sequence),
+        parse_executable(inner_token_sequence, declared_errors),
     )?;
-    let variable_block = parse_variable_block(&inner_token_sequence)?;
-    let program_body = parse_executable(inner_token_sequence)?;
 
     Ok(ScopedBlocks::Program(ExecutionBlock {
         variables: variable_block,
@@ -0,0 +1,44 @@
+use crate::defs::blocks::{ScopedBlocks, VariableBlock};
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
+use crate::defs::token::TokenType;
+use crate::parser::TokenSequence;
+use crate::parser::parse_variables::parse_variable_block;
+use crate::parser::utils::extract_braced_block;
+
+/*
+DEFINE_RECORD Point {
+    VARIABLES {
+        INTEGER {
+            x <- 0;
+            y <- 0;
+        }
+    }
+}
+*/
+pub fn parse_record(token_sequence: TokenSequence) -> Result<ScopedBlocks, Vec<MascalError>> {
+    if !token_sequence.is_of(TokenType::Identifier, 0) {
+        return Err(vec![MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(token_sequence.first_token())),
+            source: String::from("Expected a identifier for the record name"),
+        }]);
+    }
+    let name: String = token_sequence.first_token().value.to_string();
+    let span: Span = Span::from(token_sequence.first_token());
+
+    let inner_token_sequence: TokenSequence = extract_braced_block(
+        token_sequence.subsection_from(1..),
+        "DEFINE_RECORD",
+        &[TokenType::Variables],
+        &[TokenType::Variables],
+    )
+    .map_err(|err| vec![err])?;
+    let variables: VariableBlock = parse_variable_block(&inner_token_sequence)?;
+
+    Ok(ScopedBlocks::Record {
+        name,
+        variables,
+        span,
+    })
+}
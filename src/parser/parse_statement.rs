@@ -1,13 +1,19 @@
 use crate::define_statement_checkup;
 use crate::defs::dynamic_int::IntegerNum;
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
 use crate::defs::literal::MascalLiteral;
-use crate::defs::statements::{MascalConditionalBranch, MascalStatement};
+use crate::defs::operators::MascalUnaryOperators;
+use crate::defs::span::Span;
+use crate::defs::statements::{
+    MascalCaseBranch, MascalCasePattern, MascalCatchBranch, MascalConditionalBranch,
+    MascalStatement, MascalStatementKind,
+};
 use crate::defs::token::{Token, TokenType};
 use crate::parser::TokenSequence;
 use crate::parser::parse_expression::parse_expression;
 use crate::parser::utils::{extract_braced_block_from_tokens, run_per_statement};
+use std::rc::Rc;
 
 fn parse_branch(
     token_sequence: &[Token],
@@ -32,8 +38,7 @@ fn parse_branch(
     if token_sequence[open_brace_index].token_type != TokenType::OpenBrace {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: token_sequence[open_brace_index].line,
-            character: token_sequence[open_brace_index].start,
+            span: Some(Span::from(&token_sequence[open_brace_index])),
             source: String::from("Expected a opening brace for a conditional branch"),
         });
     }
@@ -56,8 +61,7 @@ fn parse_branch(
     if !final_toks.is_empty() {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: final_toks[0].line,
-            character: final_toks[0].start,
+            span: Some(Span::from(&final_toks[0])),
             source: String::from(
                 "Unexpected characters found inside conditional statement, perhaps forgot a semicolon?",
             ),
@@ -70,11 +74,24 @@ fn parse_branch(
     })
 }
 
-fn parse_conditional_statement(token_sequence: &[Token]) -> Result<MascalStatement, MascalError> {
+fn parse_conditional_statement(
+    token_sequence: &[Token],
+) -> Result<MascalStatementKind, MascalError> {
     let mut has_entered_else: bool = false;
     let mut branches: Vec<MascalConditionalBranch> = Vec::new();
+    let mut depth: usize = 0;
     for (index, token) in token_sequence.iter().enumerate() {
+        /*
+         A branch only starts the statement or right after the block of the one before it, anything
+         else is either nested inside a block or an IF expression in one of the conditions
+        */
+        let is_branch_start: bool = depth == 0
+            && (index == 0 || token_sequence[index - 1].token_type == TokenType::CloseBrace);
         match token.token_type {
+            TokenType::OpenBrace => depth += 1,
+            TokenType::CloseBrace => depth = depth.saturating_sub(1),
+            _ if !is_branch_start => {}
+
             TokenType::If => {
                 branches.push(parse_branch(&token_sequence[index + 1..], false)?);
             }
@@ -83,8 +100,7 @@ fn parse_conditional_statement(token_sequence: &[Token]) -> Result<MascalStateme
                 if has_entered_else {
                     return Err(MascalError {
                         error_type: MascalErrorType::ParserError,
-                        character: token.start,
-                        line: token.line,
+                        span: Some(Span::from(token)),
                         source: String::from("Cannot supply an ELIF condition after an ELSE condition without opening a new IF condition"),
                     })
                 }
@@ -100,7 +116,7 @@ fn parse_conditional_statement(token_sequence: &[Token]) -> Result<MascalStateme
         }
     }
 
-    Ok(MascalStatement::ConditionalStatement(branches))
+    Ok(MascalStatementKind::ConditionalStatement(branches))
 }
 
 fn locate_semicolon(tokens: &[Token]) -> Result<usize, MascalError> {
@@ -111,13 +127,21 @@ fn locate_semicolon(tokens: &[Token]) -> Result<usize, MascalError> {
     }
     Err(MascalError {
         error_type: MascalErrorType::ParserError,
-        line: tokens.last().unwrap().line,
-        character: tokens.last().unwrap().start,
+        span: Some(Span::from(tokens.last().unwrap())),
         source: String::from("Expected an ending semicolon to finish the statement"),
     })
 }
 
-fn parse_throw_statement(tokens: &[Token]) -> Result<MascalStatement, MascalError> {
+/*
+ Anything that isn't one of the builtin error types is taken as one declared in an ERRORS block,
+ whether it actually was declared only gets checked once the whole file has been parsed
+*/
+fn parse_error_type(token: &Token) -> MascalErrorType {
+    MascalErrorType::from_usable_name(token.value)
+        .unwrap_or_else(|| MascalErrorType::Custom(Rc::from(token.value)))
+}
+
+fn parse_throw_statement(tokens: &[Token]) -> Result<MascalStatementKind, MascalError> {
     let mut index: usize = 0;
     let mut curr: &Token;
     define_statement_checkup!(
@@ -128,26 +152,7 @@ fn parse_throw_statement(tokens: &[Token]) -> Result<MascalStatement, MascalErro
         String::from("Expected a error type to throw but got nothing"),
         |curr: &Token| { format!("Expected a error type to throw but got {:?}", curr.value) }
     );
-    let error_type: MascalErrorType = match curr.value {
-        "TypeError" => MascalErrorType::TypeError,
-        "RuntimeError" => MascalErrorType::RuntimeError,
-        "OverflowError" => MascalErrorType::OverflowError,
-        "UndefinedOperationError" => MascalErrorType::UndefinedOperation,
-        "IndexError" => MascalErrorType::IndexError,
-        "InputError" => MascalErrorType::InputError,
-        "ArgumentError" => MascalErrorType::ArgumentError,
-        "ValueError" => MascalErrorType::ValueError,
-        _ => {
-            return Err(MascalError {
-                error_type: MascalErrorType::UndefinedErrorType,
-                character: 0,
-                line: 0,
-                source: String::from(
-                    "Use of an undefined usable error type in the throw statement (perhaps a typo?)",
-                ),
-            });
-        }
-    };
+    let error_type: MascalErrorType = parse_error_type(curr);
     index += 1;
     define_statement_checkup!(
         index,
@@ -163,34 +168,16 @@ fn parse_throw_statement(tokens: &[Token]) -> Result<MascalStatement, MascalErro
         }
     );
     index += 1;
-    define_statement_checkup!(
-        index,
-        tokens,
-        curr,
-        TokenType::StringLiteral,
-        String::from("Expected a message for the throw statement but got nothing"),
-        |curr: &Token| {
-            format!(
-                "Expected a message for the throw statement but got {:?}",
-                curr.value
-            )
-        }
-    );
-    let message: String = curr.value.to_string();
-    index += 1;
-    if index < tokens.len() {
-        curr = &tokens[index];
+    if index >= tokens.len() {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: curr.line,
-            character: curr.start,
-            source: String::from(
-                "Unexpected tokens found during the parsing of the throw statement",
-            ),
+            span: Some(Span::from(curr)),
+            source: String::from("Expected a message for the throw statement but got nothing"),
         });
     }
+    let message: MascalExpression = parse_expression(&tokens[index..].to_vec())?;
 
-    Ok(MascalStatement::Throw {
+    Ok(MascalStatementKind::Throw {
         error_type,
         message,
     })
@@ -210,7 +197,13 @@ macro_rules! parse_expression_in_statement {
     }};
 }
 
-fn parse_for_loop_statement(tokens: &[Token]) -> Result<MascalStatement, MascalError> {
+fn parse_for_loop_statement(
+    tokens: &[Token],
+    label: Option<String>,
+) -> Result<MascalStatementKind, MascalError> {
+    if tokens.first().map(|token| &token.token_type) == Some(&TokenType::Each) {
+        return parse_for_each_statement(tokens, label);
+    }
     let mut index: usize = 0;
     let mut curr: &Token;
     define_statement_checkup!(
@@ -238,34 +231,53 @@ fn parse_for_loop_statement(tokens: &[Token]) -> Result<MascalStatement, MascalE
     );
     index += 1;
 
-    let from: MascalExpression = parse_expression_in_statement!(tokens, index, [TokenType::To]);
-
-    define_statement_checkup!(
-        index,
-        tokens,
-        curr,
-        TokenType::To,
-        String::from("Expected TO but got nothing"),
-        |curr: &Token| { format!("Expected TO but got {:?}", curr.value) }
-    );
+    let from: MascalExpression =
+        parse_expression_in_statement!(tokens, index, [TokenType::To, TokenType::DownTo]);
+
+    /* DOWNTO counts the other way, so the step gets a minus in front of it */
+    let is_descending: bool = tokens
+        .get(index)
+        .is_some_and(|token| token.token_type == TokenType::DownTo);
+    if !is_descending {
+        define_statement_checkup!(
+            index,
+            tokens,
+            curr,
+            TokenType::To,
+            String::from("Expected TO or DOWNTO but got nothing"),
+            |curr: &Token| { format!("Expected TO or DOWNTO but got {:?}", curr.value) }
+        );
+    }
     index += 1;
 
     let to: MascalExpression =
         parse_expression_in_statement!(tokens, index, [TokenType::WithStep, TokenType::OpenBrace]);
 
-    let with_step: MascalExpression = if tokens[index].token_type == TokenType::WithStep {
+    let mut with_step: MascalExpression = if tokens[index].token_type == TokenType::WithStep {
         index += 1;
 
         parse_expression_in_statement!(tokens, index, [TokenType::OpenBrace])
     } else {
-        MascalExpression::Literal(MascalLiteral::Integer(IntegerNum::I8(1)))
+        MascalExpression::new(
+            MascalExpressionKind::Literal(MascalLiteral::Integer(IntegerNum::I8(1))),
+            Span::from(&tokens[index]),
+        )
     };
+    if is_descending {
+        let span: Span = with_step.span;
+        with_step = MascalExpression::new(
+            MascalExpressionKind::Unary {
+                operator: MascalUnaryOperators::Minus,
+                value: Box::new(with_step),
+            },
+            span,
+        );
+    }
 
     if tokens[index].token_type != TokenType::OpenBrace {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: tokens[index].line,
-            character: tokens[index].start,
+            span: Some(Span::from(&tokens[index])),
             source: String::from("Expected a opening brace for a for loop block"),
         });
     }
@@ -284,24 +296,109 @@ fn parse_for_loop_statement(tokens: &[Token]) -> Result<MascalStatement, MascalE
     if !final_toks.is_empty() {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: final_toks[0].line,
-            character: final_toks[0].start,
+            span: Some(Span::from(&final_toks[0])),
             source: String::from(
                 "Unexpected characters found inside for loop statement, perhaps forgot a semicolon?",
             ),
         });
     }
 
-    Ok(MascalStatement::For {
+    Ok(MascalStatementKind::For {
         variable: variable_name,
         from,
         to,
         step: with_step,
         statements,
+        label,
     })
 }
 
-fn parse_while_loop_statement(tokens: &[Token]) -> Result<MascalStatement, MascalError> {
+/* The tokens start at EACH, the index variable (if there is one) comes before the element's */
+fn parse_for_each_statement(
+    tokens: &[Token],
+    label: Option<String>,
+) -> Result<MascalStatementKind, MascalError> {
+    let mut index: usize = 1;
+    let mut curr: &Token;
+    define_statement_checkup!(
+        index,
+        tokens,
+        curr,
+        TokenType::Identifier,
+        String::from("Expected a variable identifier to use but got nothing"),
+        |curr: &Token| {
+            format!(
+                "Expected a variable identifier to use but got {:?}",
+                curr.value
+            )
+        }
+    );
+    let mut variable_name: String = curr.value.to_string();
+    let mut index_variable: Option<String> = None;
+    index += 1;
+    if tokens.get(index).map(|token| &token.token_type) == Some(&TokenType::Comma) {
+        index += 1;
+        define_statement_checkup!(
+            index,
+            tokens,
+            curr,
+            TokenType::Identifier,
+            String::from("Expected a variable identifier for the element but got nothing"),
+            |curr: &Token| {
+                format!(
+                    "Expected a variable identifier for the element but got {:?}",
+                    curr.value
+                )
+            }
+        );
+        if curr.value == variable_name {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(curr)),
+                source: format!(
+                    "The variable {:?} cannot be both the index and the element",
+                    curr.value
+                ),
+            });
+        }
+        index_variable = Some(variable_name);
+        variable_name = curr.value.to_string();
+        index += 1;
+    }
+    define_statement_checkup!(
+        index,
+        tokens,
+        curr,
+        TokenType::In,
+        String::from("Expected IN but got nothing"),
+        |curr: &Token| { format!("Expected IN but got {:?}", curr.value) }
+    );
+    index += 1;
+
+    if tokens.get(index).map(|token| &token.token_type) == Some(&TokenType::OpenBrace) {
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(curr)),
+            source: String::from("Expected something to loop over after IN but got nothing"),
+        });
+    }
+    let iterable: MascalExpression =
+        parse_expression_in_statement!(tokens, index, [TokenType::OpenBrace]);
+    let (statements, _) = parse_block_statements(tokens, index, "For each loop")?;
+
+    Ok(MascalStatementKind::ForEach {
+        variable: variable_name,
+        index_variable,
+        iterable,
+        statements: statements.into_vec(),
+        label,
+    })
+}
+
+fn parse_while_loop_statement(
+    tokens: &[Token],
+    label: Option<String>,
+) -> Result<MascalStatementKind, MascalError> {
     let mut index: usize = 0;
 
     let condition_expression: MascalExpression =
@@ -310,8 +407,7 @@ fn parse_while_loop_statement(tokens: &[Token]) -> Result<MascalStatement, Masca
     if tokens[index].token_type != TokenType::OpenBrace {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: tokens[index].line,
-            character: tokens[index].start,
+            span: Some(Span::from(&tokens[index])),
             source: String::from("Expected a opening brace for a while loop block"),
         });
     }
@@ -330,88 +426,511 @@ fn parse_while_loop_statement(tokens: &[Token]) -> Result<MascalStatement, Masca
     if !final_toks.is_empty() {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: final_toks[0].line,
-            character: final_toks[0].start,
+            span: Some(Span::from(&final_toks[0])),
             source: String::from(
                 "Unexpected characters found inside while loop statement, perhaps forgot a semicolon?",
             ),
         });
     }
 
-    Ok(MascalStatement::While(MascalConditionalBranch {
-        condition: Some(condition_expression),
-        statements: statements.into_boxed_slice(),
-    }))
+    Ok(MascalStatementKind::While {
+        branch: MascalConditionalBranch {
+            condition: Some(condition_expression),
+            statements: statements.into_boxed_slice(),
+        },
+        label,
+    })
 }
 
-pub fn parse_statement(token_sequence: &Vec<Token>) -> Result<MascalStatement, MascalError> {
-    let first_token: &Token = token_sequence.first().unwrap();
-
-    match first_token.token_type {
-        TokenType::Throw => {
-            let index: usize = locate_semicolon(token_sequence)?;
-            let throw_statement: MascalStatement =
-                parse_throw_statement(&token_sequence[1..index])?;
-            Ok(throw_statement)
+/*
+ The condition only gets checked after the block, so the loop always runs at least once. REPEAT
+ stops as soon as its condition holds while DO keeps going for as long as it does
+*/
+fn parse_repeat_statement(
+    tokens: &[Token],
+    label: Option<String>,
+) -> Result<MascalStatementKind, MascalError> {
+    let is_until: bool = tokens[0].token_type == TokenType::Repeat;
+    let (block_name, keyword, condition_token): (&'static str, &str, TokenType) = if is_until {
+        ("Repeat loop", "UNTIL", TokenType::Until)
+    } else {
+        ("Do loop", "WHILE", TokenType::While)
+    };
+    let (statements, mut index) = parse_block_statements(tokens, 1, block_name)?;
+    match tokens.get(index) {
+        Some(token) if token.token_type == condition_token => index += 1,
+        token => {
+            let token: &Token = token.unwrap_or(tokens.last().unwrap());
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(token)),
+                source: format!(
+                    "Expected {} after the block of the loop but got {}",
+                    keyword,
+                    tokens
+                        .get(index)
+                        .map_or(String::from("nothing"), |token| format!("{:?}", token.value))
+                ),
+            });
         }
+    }
+    /* Searched from the keyword onwards, nothing might come after it */
+    let end: usize = index - 1 + locate_semicolon(&tokens[index - 1..])?;
+    if end == index {
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(&tokens[index - 1])),
+            source: format!("Expected a condition after {} but got nothing", keyword),
+        });
+    }
+    let condition: MascalExpression = parse_expression(&tokens[index..end].to_vec())?;
 
-        TokenType::If => {
-            let if_statement: MascalStatement = parse_conditional_statement(token_sequence)?;
-            Ok(if_statement)
-        }
+    Ok(MascalStatementKind::Repeat {
+        statements: statements.into_vec(),
+        condition,
+        is_until,
+        label,
+    })
+}
 
-        TokenType::For => {
-            let for_statement: MascalStatement = parse_for_loop_statement(&token_sequence[1..])?;
-            Ok(for_statement)
+/* The label goes on whichever loop comes right after it */
+fn parse_labeled_loop_statement(tokens: &[Token]) -> Result<MascalStatementKind, MascalError> {
+    let label: String = tokens[0].value.to_string();
+    match tokens.get(2).map(|token| &token.token_type) {
+        Some(TokenType::For) => parse_for_loop_statement(&tokens[3..], Some(label)),
+        Some(TokenType::While) => parse_while_loop_statement(&tokens[3..], Some(label)),
+        Some(TokenType::Repeat | TokenType::Do) => {
+            parse_repeat_statement(&tokens[2..], Some(label))
         }
-
-        TokenType::While => {
-            let for_statement: MascalStatement = parse_while_loop_statement(&token_sequence[1..])?;
-            Ok(for_statement)
+        _ => {
+            let token: &Token = tokens.get(2).unwrap_or(&tokens[1]);
+            Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(token)),
+                source: format!("Expected a loop to follow the label {:?}", label),
+            })
         }
+    }
+}
 
-        TokenType::ElseIf => Err(MascalError {
+/* BREAK and CONTINUE either stand alone or name the loop they are meant for */
+fn parse_loop_label(tokens: &[Token], statement_name: &str) -> Result<Option<String>, MascalError> {
+    let index: usize = locate_semicolon(tokens)?;
+    match &tokens[1..index] {
+        [] => Ok(None),
+        [label] if label.token_type == TokenType::Identifier => Ok(Some(label.value.to_string())),
+        [unexpected, ..] => Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            character: first_token.start,
-            line: first_token.line,
-            source: String::from("Expected an IF condition before this ELIF condition"),
+            span: Some(Span::from(unexpected)),
+            source: format!(
+                "Expected nothing else to be supplied but contents inside the {} statement",
+                statement_name
+            ),
         }),
+    }
+}
 
-        TokenType::Else => Err(MascalError {
+/*
+ Parses the braced block starting at the given index, handing back its statements along with the
+ index right after the closing brace
+*/
+fn parse_block_statements(
+    tokens: &[Token],
+    index: usize,
+    block_name: &'static str,
+) -> Result<(Box<[MascalStatement]>, usize), MascalError> {
+    if index >= tokens.len() || tokens[index].token_type != TokenType::OpenBrace {
+        let token: &Token = tokens.get(index).unwrap_or(tokens.last().unwrap());
+        return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            character: first_token.start,
-            line: first_token.line,
-            source: String::from("Expected an IF condition before this ELSE condition"),
-        }),
+            span: Some(Span::from(token)),
+            source: format!("Expected a opening brace for a {} block", block_name),
+        });
+    }
 
-        TokenType::Break => {
-            let index: usize = locate_semicolon(token_sequence)?;
-            if token_sequence[..index].len() > 1 {
+    let statements_parser: TokenSequence =
+        extract_braced_block_from_tokens(&tokens[index..], block_name, &[], &[])?;
+
+    let mut statements: Vec<MascalStatement> = Vec::new();
+
+    let final_toks: Vec<Token> = run_per_statement(&statements_parser, |token_sequence| {
+        let stmt = parse_statement(token_sequence)?;
+        statements.push(stmt);
+        Ok(())
+    })?;
+
+    if !final_toks.is_empty() {
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(&final_toks[0])),
+            source: format!(
+                "Unexpected characters found inside {} block, perhaps forgot a semicolon?",
+                block_name
+            ),
+        });
+    }
+
+    Ok((
+        statements.into_boxed_slice(),
+        index + statements_parser.tokens.len() + 2,
+    ))
+}
+
+fn parse_try_statement(tokens: &[Token]) -> Result<MascalStatementKind, MascalError> {
+    let (statements, mut index) = parse_block_statements(tokens, 1, "Try")?;
+    let mut catches: Vec<MascalCatchBranch> = Vec::new();
+    let mut finally: Option<Box<[MascalStatement]>> = None;
+
+    while index < tokens.len() {
+        let curr: &Token = &tokens[index];
+        match curr.token_type {
+            TokenType::Catch if finally.is_none() => {
+                let span: Span = Span::from(curr);
+                index += 1;
+                let mut error_type: Option<MascalErrorType> = None;
+                let mut variable: Option<String> = None;
+                if index < tokens.len() && tokens[index].token_type == TokenType::Identifier {
+                    error_type = Some(parse_error_type(&tokens[index]));
+                    index += 1;
+                }
+                if index < tokens.len() && tokens[index].token_type == TokenType::As {
+                    index += 1;
+                    match tokens.get(index) {
+                        Some(token) if token.token_type == TokenType::Identifier => {
+                            variable = Some(token.value.to_string());
+                            index += 1;
+                        }
+                        _ => {
+                            return Err(MascalError {
+                                error_type: MascalErrorType::ParserError,
+                                span: Some(Span::from(&tokens[index - 1])),
+                                source: String::from(
+                                    "Expected a variable name to hold the caught error after AS",
+                                ),
+                            });
+                        }
+                    }
+                }
+                let (catch_statements, next_index) =
+                    parse_block_statements(tokens, index, "Catch")?;
+                index = next_index;
+                catches.push(MascalCatchBranch {
+                    error_type,
+                    variable,
+                    statements: catch_statements,
+                    span,
+                });
+            }
+
+            TokenType::Finally if finally.is_none() => {
+                let (finally_statements, next_index) =
+                    parse_block_statements(tokens, index + 1, "Finally")?;
+                index = next_index;
+                finally = Some(finally_statements);
+            }
+
+            _ => {
                 return Err(MascalError {
                     error_type: MascalErrorType::ParserError,
-                    character: 0,
-                    line: 0,
+                    span: Some(Span::from(curr)),
                     source: String::from(
-                        "Expected nothing else to be supplied but contents inside the break statement",
+                        "Unexpected tokens found after the try statement, only CATCH blocks followed by a FINALLY block are allowed",
                     ),
                 });
             }
-            Ok(MascalStatement::Break)
         }
+    }
 
-        TokenType::Continue => {
-            let index: usize = locate_semicolon(token_sequence)?;
-            if token_sequence[..index].len() > 1 {
+    if catches.is_empty() && finally.is_none() {
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(&tokens[0])),
+            source: String::from("Expected at least one CATCH or FINALLY block for the try statement"),
+        });
+    }
+
+    Ok(MascalStatementKind::Try {
+        statements,
+        catches,
+        finally,
+    })
+}
+
+/*
+ Splits the patterns of a CASE on the commas that aren't nested inside of anything, array
+ literals and calls may have commas of their own. A CASE starting with TYPE takes every pattern
+ in it as a type, otherwise TYPE only applies to the one pattern it is in front of
+*/
+fn parse_case_patterns(
+    tokens: &[Token],
+    case_token: &Token,
+) -> Result<Vec<MascalCasePattern>, MascalError> {
+    let is_type_list: bool = tokens.len() > 1 && tokens[0].token_type == TokenType::Type;
+    let tokens: &[Token] = if is_type_list { &tokens[1..] } else { tokens };
+    let mut chunks: Vec<&[Token]> = Vec::new();
+    let mut depth: usize = 0;
+    let mut chunk_start: usize = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        match token.token_type {
+            TokenType::OpenParen | TokenType::OpenBracket | TokenType::OpenDynamicArray => {
+                depth += 1
+            }
+            TokenType::CloseParen | TokenType::CloseBracket | TokenType::CloseDynamicArray => {
+                depth = depth.saturating_sub(1)
+            }
+            TokenType::Comma if depth == 0 => {
+                chunks.push(&tokens[chunk_start..index]);
+                chunk_start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    chunks.push(&tokens[chunk_start..]);
+
+    let mut patterns: Vec<MascalCasePattern> = Vec::with_capacity(chunks.len());
+    for chunk in chunks {
+        let Some(first_token) = chunk.first() else {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(case_token)),
+                source: String::from("Expected a pattern for the case but got nothing"),
+            });
+        };
+        if is_type_list {
+            patterns.push(MascalCasePattern::Type(parse_expression(&chunk.to_vec())?));
+            continue;
+        }
+        if first_token.token_type == TokenType::Type && chunk.len() > 1 {
+            patterns.push(MascalCasePattern::Type(parse_expression(&chunk[1..].to_vec())?));
+            continue;
+        }
+        let mut depth: usize = 0;
+        let range_index: Option<usize> = chunk.iter().position(|token| {
+            match token.token_type {
+                TokenType::OpenParen | TokenType::OpenBracket | TokenType::OpenDynamicArray => {
+                    depth += 1
+                }
+                TokenType::CloseParen | TokenType::CloseBracket | TokenType::CloseDynamicArray => {
+                    depth = depth.saturating_sub(1)
+                }
+                _ => {}
+            }
+            depth == 0 && token.token_type == TokenType::To
+        });
+        let Some(range_index) = range_index else {
+            patterns.push(MascalCasePattern::Value(parse_expression(&chunk.to_vec())?));
+            continue;
+        };
+        if range_index == 0 || range_index == chunk.len() - 1 {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(&chunk[range_index])),
+                source: String::from(
+                    "Expected a value on both sides of TO for the range of the case",
+                ),
+            });
+        }
+        patterns.push(MascalCasePattern::Range {
+            from: parse_expression(&chunk[..range_index].to_vec())?,
+            to: parse_expression(&chunk[range_index + 1..].to_vec())?,
+        });
+    }
+    Ok(patterns)
+}
+
+/* The DEFAULT block goes last, it only runs when none of the cases matched */
+fn parse_match_statement(tokens: &[Token]) -> Result<MascalStatementKind, MascalError> {
+    let mut index: usize = 1;
+    if tokens.get(index).map(|token| &token.token_type) == Some(&TokenType::OpenBrace) {
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(&tokens[0])),
+            source: String::from("Expected a value to match against but got nothing"),
+        });
+    }
+    let subject: MascalExpression =
+        parse_expression_in_statement!(tokens, index, [TokenType::OpenBrace]);
+    if tokens.get(index).map(|token| &token.token_type) != Some(&TokenType::OpenBrace) {
+        let token: &Token = tokens.get(index).unwrap_or(tokens.last().unwrap());
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(token)),
+            source: String::from("Expected a opening brace for a Match block"),
+        });
+    }
+    /* A CASE TYPE pattern puts a type right before the opening brace of its block */
+    let block: TokenSequence = extract_braced_block_from_tokens(
+        &tokens[index..],
+        "Match",
+        &[
+            TokenType::Integer,
+            TokenType::Float,
+            TokenType::String,
+            TokenType::Boolean,
+            TokenType::Dynamic,
+            TokenType::Type,
+        ],
+        &[],
+    )?;
+    let block_tokens: &[Token] = &block.tokens;
+
+    let mut cases: Vec<MascalCaseBranch> = Vec::new();
+    let mut default: Option<Box<[MascalStatement]>> = None;
+    let mut index: usize = 0;
+    while index < block_tokens.len() {
+        let curr: &Token = &block_tokens[index];
+        match curr.token_type {
+            TokenType::Case | TokenType::Default if default.is_some() => {
                 return Err(MascalError {
                     error_type: MascalErrorType::ParserError,
-                    character: 0,
-                    line: 0,
-                    source: String::from(
-                        "Expected nothing else to be supplied but contents inside the continue statement",
+                    span: Some(Span::from(curr)),
+                    source: format!(
+                        "Expected the DEFAULT block to be the last one of the match statement but found {} after it",
+                        curr.value.to_uppercase()
                     ),
                 });
             }
-            Ok(MascalStatement::Continue)
+
+            TokenType::Case => {
+                let brace_index: usize = block_tokens[index..]
+                    .iter()
+                    .position(|token| token.token_type == TokenType::OpenBrace)
+                    .map_or(block_tokens.len(), |position| index + position);
+                let patterns: Vec<MascalCasePattern> =
+                    parse_case_patterns(&block_tokens[index + 1..brace_index], curr)?;
+                let (statements, next_index) =
+                    parse_block_statements(block_tokens, brace_index, "Case")?;
+                cases.push(MascalCaseBranch {
+                    patterns,
+                    statements,
+                    span: Span::from(curr),
+                });
+                index = next_index;
+            }
+
+            TokenType::Default => {
+                let (statements, next_index) =
+                    parse_block_statements(block_tokens, index + 1, "Default")?;
+                default = Some(statements);
+                index = next_index;
+            }
+
+            _ => {
+                return Err(MascalError {
+                    error_type: MascalErrorType::ParserError,
+                    span: Some(Span::from(curr)),
+                    source: format!(
+                        "Expected CASE or DEFAULT inside the match statement but got {:?}",
+                        curr.value
+                    ),
+                });
+            }
+        }
+    }
+
+    if cases.is_empty() && default.is_none() {
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(&tokens[0])),
+            source: String::from("Expected at least one CASE or DEFAULT for the match statement"),
+        });
+    }
+
+    Ok(MascalStatementKind::Match {
+        subject,
+        cases,
+        default,
+    })
+}
+
+pub fn parse_statement(token_sequence: &Vec<Token>) -> Result<MascalStatement, MascalError> {
+    let first_token: &Token = token_sequence.first().unwrap();
+
+    let kind: MascalStatementKind = match first_token.token_type {
+        TokenType::Throw => {
+            let index: usize = locate_semicolon(token_sequence)?;
+            parse_throw_statement(&token_sequence[1..index])?
+        }
+
+        TokenType::If => parse_conditional_statement(token_sequence)?,
+
+        TokenType::For => parse_for_loop_statement(&token_sequence[1..], None)?,
+
+        TokenType::While => parse_while_loop_statement(&token_sequence[1..], None)?,
+
+        TokenType::Repeat | TokenType::Do => parse_repeat_statement(token_sequence, None)?,
+
+        TokenType::Until => {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(first_token)),
+                source: String::from("Expected a REPEAT block before this UNTIL condition"),
+            });
+        }
+
+        TokenType::Identifier
+            if token_sequence.get(1).map(|token| &token.token_type) == Some(&TokenType::Colon) =>
+        {
+            parse_labeled_loop_statement(token_sequence)?
+        }
+
+        TokenType::Try => parse_try_statement(token_sequence)?,
+
+        TokenType::Match => parse_match_statement(token_sequence)?,
+
+        TokenType::Case | TokenType::Default => {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(first_token)),
+                source: format!(
+                    "Expected a MATCH statement around this {} block",
+                    first_token.value.to_uppercase()
+                ),
+            });
+        }
+
+        TokenType::Catch | TokenType::Finally => {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(first_token)),
+                source: format!(
+                    "Expected a TRY block before this {} block",
+                    first_token.value.to_uppercase()
+                ),
+            });
+        }
+
+        TokenType::ElseIf => {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(first_token)),
+                source: String::from("Expected an IF condition before this ELIF condition"),
+            });
+        }
+
+        TokenType::Else => {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(first_token)),
+                source: String::from("Expected an IF condition before this ELSE condition"),
+            });
+        }
+
+        TokenType::Break => MascalStatementKind::Break(parse_loop_label(token_sequence, "break")?),
+
+        TokenType::Continue => {
+            MascalStatementKind::Continue(parse_loop_label(token_sequence, "continue")?)
+        }
+
+        TokenType::Return => {
+            let index: usize = locate_semicolon(token_sequence)?;
+            if index == 1 {
+                MascalStatementKind::Return(None)
+            } else {
+                MascalStatementKind::Return(Some(parse_expression(
+                    &token_sequence[1..index].to_vec(),
+                )?))
+            }
         }
 
         _ => {
@@ -423,8 +942,7 @@ pub fn parse_statement(token_sequence: &Vec<Token>) -> Result<MascalStatement, M
                     if assignment_index.is_some() {
                         return Err(MascalError {
                             error_type: MascalErrorType::ParserError,
-                            line: tok.line,
-                            character: tok.start,
+                            span: Some(Span::from(tok)),
                             source: String::from("Cannot use more than one variable initializer per statement")
                         })
                     }
@@ -434,14 +952,15 @@ pub fn parse_statement(token_sequence: &Vec<Token>) -> Result<MascalStatement, M
             if let Some(unwrapped_assign_index) = assignment_index {
                 let target_assigne: MascalExpression =
                     parse_expression(&trunucated_token_seq[..unwrapped_assign_index].to_vec())?;
-                match target_assigne {
-                    MascalExpression::Indexing { .. } | MascalExpression::Symbolic(_) => {}
+                match target_assigne.kind {
+                    MascalExpressionKind::Indexing { .. }
+                    | MascalExpressionKind::Symbolic(_)
+                    | MascalExpressionKind::FieldAccess { .. } => {}
 
                     _ => {
                         return Err(MascalError {
                             error_type: MascalErrorType::ParserError,
-                            line: trunucated_token_seq.first().unwrap().line,
-                            character: trunucated_token_seq.first().unwrap().line,
+                            span: Some(Span::from(trunucated_token_seq.first().unwrap())),
                             source: String::from(
                                 "Expected either a index-based variable modification or variable assignment but got something else",
                             ),
@@ -450,13 +969,15 @@ pub fn parse_statement(token_sequence: &Vec<Token>) -> Result<MascalStatement, M
                 }
                 let value_assigned: MascalExpression =
                     parse_expression(&trunucated_token_seq[unwrapped_assign_index + 1..].to_vec())?;
-                return Ok(MascalStatement::Declaration {
+                MascalStatementKind::Declaration {
                     variable: target_assigne,
                     value: value_assigned,
-                });
+                }
+            } else {
+                MascalStatementKind::ExpressionStatement(parse_expression(&trunucated_token_seq)?)
             }
-            let expression_statement: MascalExpression = parse_expression(&trunucated_token_seq)?;
-            Ok(MascalStatement::ExpressionStatement(expression_statement))
         }
-    }
+    };
+
+    Ok(MascalStatement::new(kind, Span::from(first_token)))
 }
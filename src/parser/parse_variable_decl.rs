@@ -1,8 +1,9 @@
 use crate::defs::declerations::MascalVariableInitialDeclaration;
 use crate::defs::dynamic_int::IntegerNum;
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
 use crate::defs::literal::MascalLiteral;
+use crate::defs::span::Span;
 use crate::defs::token::{Token, TokenType};
 use crate::parser::parse_expression::parse_expression;
 use crate::parser::utils::parse_array_type;
@@ -13,6 +14,7 @@ pub fn parse_variable_decl<'a>(
     tokens: &'a Vec<Token<'a>>,
 ) -> Result<MascalVariableInitialDeclaration, MascalError> {
     let name: Rc<str>;
+    let span: Span;
     let mut is_constant: bool = false;
     let mut is_nullable: bool = false;
     let mut dimensions: Vec<MascalExpression> = Vec::new();
@@ -27,12 +29,12 @@ pub fn parse_variable_decl<'a>(
 
     if curr_index < tokens.len() && tokens[curr_index].token_type == TokenType::Identifier {
         name = Rc::from(tokens[curr_index].value);
+        span = Span::from(&tokens[curr_index]);
         curr_index += 1;
     } else {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: tokens[0].line,
-            character: tokens[0].start,
+            span: Some(Span::from(&tokens[0])),
             source: String::from("Expected a variable name"),
         });
     }
@@ -43,9 +45,10 @@ pub fn parse_variable_decl<'a>(
         |token_sequence, is_dynamic| {
             if is_dynamic {
                 if token_sequence.is_empty() {
-                    dimensions.push(MascalExpression::Literal(MascalLiteral::Integer(
-                        IntegerNum::I8(1),
-                    )));
+                    dimensions.push(MascalExpression::new(
+                        MascalExpressionKind::Literal(MascalLiteral::Integer(IntegerNum::I8(1))),
+                        span,
+                    ));
                     is_dynamic_array.push(is_dynamic);
                     return Ok(());
                 }
@@ -56,8 +59,7 @@ pub fn parse_variable_decl<'a>(
             if token_sequence.is_empty() {
                 return Err(MascalError {
                     error_type: MascalErrorType::ParserError,
-                    line: 0,
-                    character: 0,
+                    span: Some(span),
                     source: String::from(
                         "Static arrays cannot be omitted and must have a specified size",
                     ),
@@ -88,8 +90,7 @@ pub fn parse_variable_decl<'a>(
     if tokens[curr_index].token_type != TokenType::Semicolon {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: tokens[curr_index].line,
-            character: tokens[curr_index].start,
+            span: Some(Span::from(&tokens[curr_index])),
             source: String::from(
                 "Unexpected characters found during parsing of variable initialization",
             ),
@@ -103,5 +104,6 @@ pub fn parse_variable_decl<'a>(
         dimensions: dimensions.into_boxed_slice(),
         is_dynamic_array: is_dynamic_array.into_boxed_slice(),
         initial_value,
+        span,
     })
 }
@@ -1,16 +1,20 @@
-use crate::defs::blocks::VariableBlock;
+use crate::defs::blocks::{CustomTypeVariableBlock, VariableBlock};
 use crate::defs::declerations::MascalVariableInitialDeclaration;
 use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
 use crate::defs::token::{Token, TokenType};
+use crate::defs::types::{MascalUnprocessedType, to_processed_type};
 use crate::parser::TokenSequence;
 use crate::parser::parse_variable_decl::parse_variable_decl;
-use crate::parser::utils::{extract_braced_block, run_per_statement};
+use crate::parser::utils::{extract_braced_block, parse_map_type, run_per_statement};
 use std::rc::Rc;
+use std::sync::Arc;
 
 #[inline(always)]
 pub fn parse_variable_type_block(
     subsection_tokens: TokenSequence,
     var_inits: &mut Vec<MascalVariableInitialDeclaration>,
+    errors: &mut Vec<MascalError>,
 ) -> Result<(), MascalError> {
     let final_token_sequence: Vec<Token> =
         run_per_statement(&subsection_tokens, |token_sequence| {
@@ -24,20 +28,21 @@ pub fn parse_variable_type_block(
                     is_dynamic_array: Box::new([]),
                     dimensions: Box::new([]),
                     is_nullable: false,
+                    span: Span::from(&token_sequence[0]),
                 });
                 return Ok(());
             }
-            let variable_decl: MascalVariableInitialDeclaration =
-                parse_variable_decl(token_sequence)?;
-            var_inits.push(variable_decl);
+            match parse_variable_decl(token_sequence) {
+                Ok(variable_decl) => var_inits.push(variable_decl),
+                Err(err) => errors.push(err.located_at(Span::from(&token_sequence[0]))),
+            }
             Ok(())
         })?;
 
     if !final_token_sequence.is_empty() {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: final_token_sequence.first().unwrap().line,
-            character: final_token_sequence.first().unwrap().start,
+            span: Some(Span::from(final_token_sequence.first().unwrap())),
             source: String::from(
                 "Unexpected characters for variable declaration, perhaps it hasn't been closed with a semicolon?",
             ),
@@ -47,7 +52,10 @@ pub fn parse_variable_type_block(
     Ok(())
 }
 
-pub fn parse_variable_block(token_sequence: &TokenSequence) -> Result<VariableBlock, MascalError> {
+pub fn parse_variable_block(
+    token_sequence: &TokenSequence,
+) -> Result<VariableBlock, Vec<MascalError>> {
+    let mut errors: Vec<MascalError> = Vec::new();
     for (index, token) in token_sequence.tokens.iter().enumerate() {
         if token.token_type != TokenType::Variables {
             continue;
@@ -64,7 +72,8 @@ pub fn parse_variable_block(token_sequence: &TokenSequence) -> Result<VariableBl
                 TokenType::Type,
             ],
             &[],
-        )?;
+        )
+        .map_err(|err| vec![err])?;
 
         let mut pos: usize = index;
 
@@ -84,52 +93,145 @@ pub fn parse_variable_block(token_sequence: &TokenSequence) -> Result<VariableBl
             (TokenType::Type, "TYPE", &mut types),
         ];
 
+        let mut custom_types: Vec<CustomTypeVariableBlock> = vec![];
+
         let mut already_assigned: Vec<usize> = Vec::with_capacity(vartype_blocks.len());
         while pos < subset_token_sequence.tokens.len() {
             let curr: &Token = &subset_token_sequence.tokens[pos];
+
+            /*
+             A record's name followed by a block declares variables holding that record, the same
+             goes for a map type and functions
+            */
+            let custom_type: Option<(MascalUnprocessedType, &'static str)> = match curr.token_type {
+                TokenType::Identifier
+                    if subset_token_sequence.is_of(TokenType::OpenBrace, pos + 1) =>
+                {
+                    pos += 1;
+                    Some((
+                        MascalUnprocessedType::Record(Arc::from(curr.value)),
+                        "RECORD",
+                    ))
+                }
+                TokenType::Function => {
+                    pos += 1;
+                    Some((MascalUnprocessedType::Function, "FUNCTION"))
+                }
+                TokenType::Map => match parse_map_type(&subset_token_sequence.tokens, &mut pos) {
+                    Ok(map_type) => Some((map_type, "MAP")),
+                    Err(err) => {
+                        errors.push(err);
+                        return Err(errors);
+                    }
+                },
+                _ => None,
+            };
+            if let Some((variable_type, block_name)) = custom_type {
+                let extracted_custom_block: TokenSequence = match extract_braced_block(
+                    subset_token_sequence.subsection_from(pos..),
+                    block_name,
+                    &[],
+                    &[],
+                ) {
+                    Ok(extracted_custom_block) => extracted_custom_block,
+                    Err(err) => {
+                        errors.push(err);
+                        return Err(errors);
+                    }
+                };
+                pos += extracted_custom_block.tokens.len() + 2;
+                if custom_types
+                    .iter()
+                    .any(|custom_type| custom_type.variable_type == variable_type)
+                {
+                    errors.push(MascalError {
+                        error_type: MascalErrorType::ParserError,
+                        span: Some(Span::from(curr)),
+                        source: format!(
+                            "Found redefinition of the same variable type block {} declared before",
+                            to_processed_type(variable_type)
+                                .map_err(|err| vec![err])?
+                                .as_string()
+                        ),
+                    });
+                    continue;
+                }
+                let mut variables: Vec<MascalVariableInitialDeclaration> = vec![];
+                if let Err(err) =
+                    parse_variable_type_block(extracted_custom_block, &mut variables, &mut errors)
+                {
+                    errors.push(err);
+                }
+                custom_types.push(CustomTypeVariableBlock {
+                    variable_type,
+                    variables: variables.into_boxed_slice(),
+                    span: Span::from(curr),
+                });
+                continue;
+            }
+
             let index: Option<usize> = vartype_blocks
                 .iter()
                 .position(|(t, _, _)| t == &curr.token_type);
 
             if let Some(vartype_block_index) = index {
                 pos += 1;
-                let extracted_vartype_block: TokenSequence = extract_braced_block(
+                let extracted_vartype_block: TokenSequence = match extract_braced_block(
                     subset_token_sequence.subsection_from(pos..),
                     vartype_blocks[vartype_block_index].1,
                     &[],
                     &[],
-                )?;
+                ) {
+                    Ok(extracted_vartype_block) => extracted_vartype_block,
+                    Err(err) => {
+                        errors.push(err);
+                        return Err(errors);
+                    }
+                };
+                pos += extracted_vartype_block.tokens.len() + 2;
                 if already_assigned.contains(&vartype_block_index) {
-                    return Err(MascalError {
+                    errors.push(MascalError {
                         error_type: MascalErrorType::ParserError,
-                        line: curr.line,
-                        character: curr.start,
+                        span: Some(Span::from(curr)),
                         source: format!(
                             "Found redefinition of the same variable type block {} declared before",
                             curr.value
                         ),
                     });
+                    continue;
                 }
-                pos += extracted_vartype_block.tokens.len() + 2;
-                parse_variable_type_block(
+                if let Err(err) = parse_variable_type_block(
                     extracted_vartype_block,
                     vartype_blocks[vartype_block_index].2,
-                )?;
+                    &mut errors,
+                ) {
+                    errors.push(err);
+                }
                 already_assigned.push(vartype_block_index);
                 continue;
             }
-            return Err(MascalError {
+            errors.push(MascalError {
                 error_type: MascalErrorType::ParserError,
-                line: curr.line,
-                character: curr.start,
+                span: Some(Span::from(curr)),
                 source: String::from(
                     "Expected to define a variable type inside the variable block but got an unknown expression",
                 ),
             });
+            return Err(errors);
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
         }
 
         return Ok(VariableBlock::new(
-            integers, floats, booleans, strings, dynamics, types,
+            integers,
+            floats,
+            booleans,
+            strings,
+            dynamics,
+            types,
+            custom_types,
         ));
     }
 
@@ -140,5 +242,6 @@ pub fn parse_variable_block(token_sequence: &TokenSequence) -> Result<VariableBl
         vec![],
         vec![],
         vec![],
+        vec![],
     ))
 }
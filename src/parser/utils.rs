@@ -1,7 +1,10 @@
 use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
 use crate::defs::token::{SCOPABLE_TOKEN_TYPES, Token, TokenType};
+use crate::defs::types::MascalUnprocessedType;
 use crate::parser::TokenSequence;
 use std::collections::HashSet;
+use std::sync::Arc;
 
 pub fn extract_braced_block<'a>(
     token_sequence: TokenSequence<'a>,
@@ -12,8 +15,7 @@ pub fn extract_braced_block<'a>(
     if !token_sequence.is_of(TokenType::OpenBrace, 0) {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: token_sequence.acquire_token(0).line,
-            character: token_sequence.acquire_token(0).start,
+            span: Some(Span::from(token_sequence.acquire_token(0))),
             source: format!("{block_name} block must start with '{{'"),
         });
     }
@@ -36,8 +38,7 @@ pub fn extract_braced_block<'a>(
                     if !missing.is_empty() {
                         return Err(MascalError {
                             error_type: MascalErrorType::ParserError,
-                            line: token.line,
-                            character: token.start,
+                            span: Some(Span::from(token)),
                             source: format!("Missing required block(s): {}", missing.join(", ")),
                         });
                     }
@@ -45,7 +46,7 @@ pub fn extract_braced_block<'a>(
                 }
             }
 
-            ref tt if SCOPABLE_TOKEN_TYPES.contains(&tt) => {
+            ref tt if SCOPABLE_TOKEN_TYPES.contains(tt) => {
                 if token_sequence.tokens[index + 1].token_type != TokenType::OpenBrace {
                     continue;
                 }
@@ -57,8 +58,7 @@ pub fn extract_braced_block<'a>(
                 } else if depth == 1 && !is_optional_nest {
                     return Err(MascalError {
                         error_type: MascalErrorType::ParserError,
-                        line: token.line,
-                        character: token.start,
+                        span: Some(Span::from(token)),
                         source: format!(
                             "Token '{:?}' is not allowed in nested blocks of {block_name}",
                             tt
@@ -67,8 +67,7 @@ pub fn extract_braced_block<'a>(
                 } else if !is_required && (!is_optional_nest && depth == 1) {
                     return Err(MascalError {
                         error_type: MascalErrorType::ParserError,
-                        line: token.line,
-                        character: token.start,
+                        span: Some(Span::from(token)),
                         source: format!("Token '{:?}' is not allowed in {block_name} block", tt),
                     });
                 }
@@ -80,8 +79,7 @@ pub fn extract_braced_block<'a>(
 
     Err(MascalError {
         error_type: MascalErrorType::ParserError,
-        character: token_sequence.last_token().start,
-        line: token_sequence.last_token().line,
+        span: Some(Span::from(token_sequence.last_token())),
         source: format!("{block_name} block not properly closed"),
     })
 }
@@ -134,14 +132,34 @@ where
 {
     let mut statement_token_seq: Vec<Token> = Vec::new();
     let mut depth_counter: usize = 0;
-    let mut entered_conditional_stmt: bool = false;
+    /*
+     IF, TRY, REPEAT and DO statements carry on past their first block as long as a follow-up
+     comes next
+    */
+    let mut continuation_tokens: Option<&[TokenType]> = None;
     for (index, token) in token_sequence.tokens.iter().enumerate() {
         statement_token_seq.push(token.clone());
         match token.token_type {
-            TokenType::If => {
-                if depth_counter == 0 {
-                    entered_conditional_stmt = true;
-                }
+            TokenType::If if depth_counter == 0 && statement_token_seq.len() == 1 => {
+                continuation_tokens = Some(&[TokenType::ElseIf, TokenType::Else]);
+            }
+
+            TokenType::Try if depth_counter == 0 && statement_token_seq.len() == 1 => {
+                continuation_tokens = Some(&[TokenType::Catch, TokenType::Finally]);
+            }
+
+            /* The condition of these loops comes after their block, a label may come before them */
+            TokenType::Repeat | TokenType::Do
+                if depth_counter == 0
+                    && (statement_token_seq.len() == 1
+                        || (statement_token_seq.len() == 3
+                            && statement_token_seq[1].token_type == TokenType::Colon)) =>
+            {
+                continuation_tokens = Some(if token.token_type == TokenType::Repeat {
+                    &[TokenType::Until]
+                } else {
+                    &[TokenType::While]
+                });
             }
 
             TokenType::OpenBrace => {
@@ -158,15 +176,10 @@ where
                     .get(index + 1)
                     .map(|x| &x.token_type)
                     .unwrap_or(&TokenType::Null);
-                if !entered_conditional_stmt {
-                    func(&statement_token_seq)?;
-                    statement_token_seq.clear();
+                if continuation_tokens.is_some_and(|tokens| tokens.contains(lookahead)) {
                     continue;
                 }
-                if *lookahead == TokenType::ElseIf || *lookahead == TokenType::Else {
-                    continue;
-                }
-                entered_conditional_stmt = false;
+                continuation_tokens = None;
                 func(&statement_token_seq)?;
                 statement_token_seq.clear();
             }
@@ -175,6 +188,7 @@ where
                 if depth_counter > 0 {
                     continue;
                 }
+                continuation_tokens = None;
                 func(&statement_token_seq)?;
                 statement_token_seq.clear();
                 continue;
@@ -186,6 +200,24 @@ where
     Ok(statement_token_seq)
 }
 
+/*
+ Used by the block parsers so a broken variable block doesn't hide the problems inside the
+ implementation block (and vice versa), both sides get parsed and every error is kept
+*/
+pub fn join_results<A, B>(
+    first: Result<A, Vec<MascalError>>,
+    second: Result<B, Vec<MascalError>>,
+) -> Result<(A, B), Vec<MascalError>> {
+    match (first, second) {
+        (Ok(first), Ok(second)) => Ok((first, second)),
+        (first, second) => {
+            let mut errors: Vec<MascalError> = first.err().unwrap_or_default();
+            errors.extend(second.err().unwrap_or_default());
+            Err(errors)
+        }
+    }
+}
+
 pub fn parse_array_type<F>(
     tokens: &[Token],
     mut curr_index: usize,
@@ -260,15 +292,13 @@ where
     if bracket_depth != 0 {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: last_token.line,
-            character: last_token.start,
+            span: Some(Span::from(last_token)),
             source: String::from("Bracket has not been closed for array type"),
         });
     } else if arrow_depth != 0 {
         return Err(MascalError {
             error_type: MascalErrorType::ParserError,
-            line: last_token.line,
-            character: last_token.start,
+            span: Some(Span::from(last_token)),
             source: String::from("Arrow has not been closed for dynamic array type"),
         });
     }
@@ -276,6 +306,110 @@ where
     Ok(curr_index)
 }
 
+fn expect_map_token<'a>(
+    tokens: &'a [Token],
+    pos: usize,
+    expected: &str,
+) -> Result<&'a Token<'a>, MascalError> {
+    tokens.get(pos).ok_or_else(|| MascalError {
+        error_type: MascalErrorType::ParserError,
+        span: Some(Span::from(&tokens[pos - 1])),
+        source: format!("Abrupt ending of the map type, was expecting {}", expected),
+    })
+}
+
+fn parse_map_type_argument(
+    tokens: &[Token],
+    pos: &mut usize,
+    is_key: bool,
+) -> Result<(MascalUnprocessedType, bool), MascalError> {
+    let token: &Token = expect_map_token(tokens, *pos, "a type")?;
+    let argument_type: MascalUnprocessedType = match token.token_type {
+        TokenType::Integer => MascalUnprocessedType::Integer,
+        TokenType::Float => MascalUnprocessedType::Float,
+        TokenType::String => MascalUnprocessedType::String,
+        TokenType::Boolean => MascalUnprocessedType::Boolean,
+        TokenType::Type => MascalUnprocessedType::Type,
+        TokenType::Dynamic => MascalUnprocessedType::Dynamic,
+        TokenType::Function if !is_key => MascalUnprocessedType::Function,
+        TokenType::Identifier if !is_key => MascalUnprocessedType::Record(Arc::from(token.value)),
+        TokenType::Map if !is_key => return parse_map_type_inner(tokens, pos),
+        _ => {
+            return Err(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(Span::from(token)),
+                source: format!(
+                    "Expected a type for the {} of the map but got {:?}",
+                    if is_key { "keys" } else { "values" },
+                    token.value
+                ),
+            });
+        }
+    };
+    *pos += 1;
+    Ok((argument_type, false))
+}
+
+/*
+ The returned flag tells whether the closing ">>" of a nested map also closed the map around it,
+ the lexer sees the two arrows as one token
+*/
+fn parse_map_type_inner(
+    tokens: &[Token],
+    pos: &mut usize,
+) -> Result<(MascalUnprocessedType, bool), MascalError> {
+    *pos += 1;
+    let (key_type, _) = parse_map_type_argument(tokens, pos, true)?;
+    let comma: &Token = expect_map_token(tokens, *pos, "a comma")?;
+    if comma.token_type != TokenType::Comma {
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(comma)),
+            source: String::from("Expected a comma between the key and the value type of the map"),
+        });
+    }
+    *pos += 1;
+    let (value_type, is_closed) = parse_map_type_argument(tokens, pos, false)?;
+    let map_type: MascalUnprocessedType =
+        MascalUnprocessedType::Map(Box::new(key_type), Box::new(value_type));
+    if is_closed {
+        return Ok((map_type, false));
+    }
+    let closing: &Token = expect_map_token(tokens, *pos, "a closing '>'")?;
+    match closing.token_type {
+        TokenType::GreaterThan => {
+            *pos += 1;
+            Ok((map_type, false))
+        }
+        TokenType::CloseDynamicArray => {
+            *pos += 1;
+            Ok((map_type, true))
+        }
+        _ => Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(closing)),
+            source: String::from("Expected a closing '>' for the map type"),
+        }),
+    }
+}
+
+/* MAP<STRING, INTEGER>, the position is expected to point at the MAP keyword and ends past it */
+pub fn parse_map_type(
+    tokens: &[Token],
+    pos: &mut usize,
+) -> Result<MascalUnprocessedType, MascalError> {
+    let map_token: &Token = &tokens[*pos];
+    let (map_type, is_closed) = parse_map_type_inner(tokens, pos)?;
+    if is_closed {
+        return Err(MascalError {
+            error_type: MascalErrorType::ParserError,
+            span: Some(Span::from(map_token)),
+            source: String::from("Found an extra closing '>' for the map type"),
+        });
+    }
+    Ok(map_type)
+}
+
 #[macro_export]
 macro_rules! define_statement_checkup {
     ( $index: expr, $tokens: expr, $curr: expr, $target: expr, $message_for_nothing: expr, $message_for_wrong: expr) => {{
@@ -283,8 +417,7 @@ macro_rules! define_statement_checkup {
             let first_token: &Token = $tokens.first().unwrap();
             return Err(MascalError {
                 error_type: MascalErrorType::ParserError,
-                character: first_token.start,
-                line: first_token.line,
+                span: Some(Span::from(first_token)),
                 source: $message_for_nothing,
             });
         }
@@ -292,8 +425,7 @@ macro_rules! define_statement_checkup {
         if $curr.token_type != $target {
             return Err(MascalError {
                 error_type: MascalErrorType::ParserError,
-                character: $curr.start,
-                line: $curr.line,
+                span: Some(Span::from($curr)),
                 source: $message_for_wrong($curr),
             });
         }
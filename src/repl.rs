@@ -0,0 +1,364 @@
+use crate::ast::{AbstractSyntaxTree, SourceFile, imported_file};
+use crate::defs::blocks::{ExecutionBlock, ScopedBlocks};
+use crate::defs::errors::MascalError;
+use crate::defs::span::Span;
+use crate::defs::symbol_table::SymbolTable;
+use crate::defs::token::{Token, TokenType};
+use crate::diagnostics::{render_error, should_use_color};
+use crate::imports::{parse_after_files, point_at_imports};
+use crate::lexer;
+use crate::runtime::interpert_block;
+use crate::runtime::io_streams::{SharedIO, StandardIO};
+use crate::runtime::values::MascalValue;
+use crate::runtime::variable_table::VariableTable;
+use crate::semantic_analysis::conduct_semantic_analysis;
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::io;
+use std::io::{BufRead, Write};
+use std::rc::Rc;
+
+const PROMPT: &str = ">>> ";
+const CONTINUATION_PROMPT: &str = "... ";
+
+/*
+ Everything typed into the REPL is wrapped into a regular program before being compiled, the
+ prefix always sits on the same line as the input so only the character offsets need fixing up
+*/
+struct WrappedInput {
+    source: String,
+    prefix_length: usize,
+}
+
+impl WrappedInput {
+    fn new(prefix: &str, input: &str, suffix: &str) -> Self {
+        WrappedInput {
+            source: format!("{}{}{}", prefix, input, suffix),
+            prefix_length: prefix.len(),
+        }
+    }
+}
+
+/*
+ A REPL session keeps the variables and the functions alive in between inputs, each input is
+ still its own tiny program though so the usual parser and semantic checks apply to it
+*/
+pub struct ReplSession {
+    variable_table: Rc<RefCell<VariableTable>>,
+    functions: Vec<ScopedBlocks>,
+    symbol_table: SymbolTable,
+    files: Vec<SourceFile>,
+    io: SharedIO,
+}
+
+impl Default for ReplSession {
+    fn default() -> Self {
+        ReplSession::new()
+    }
+}
+
+impl ReplSession {
+    pub fn new() -> Self {
+        ReplSession {
+            variable_table: Rc::new(RefCell::new(FxHashMap::default())),
+            functions: Vec::new(),
+            symbol_table: SymbolTable::default(),
+            files: Vec::new(),
+            io: Rc::new(RefCell::new(StandardIO)),
+        }
+    }
+
+    pub fn with_io(mut self, io: SharedIO) -> Self {
+        self.io = io;
+        self
+    }
+
+    pub fn get_variable(&self, name: &str) -> Option<MascalValue> {
+        self.variable_table
+            .borrow()
+            .get(name)
+            .and_then(|data| data.value.as_ref().map(|value| value.borrow().clone()))
+    }
+
+    pub fn symbol_table(&self) -> &SymbolTable {
+        &self.symbol_table
+    }
+
+    /*
+     Input is complete once every opened brace got closed, anything the lexer chokes on counts as
+     complete too so the error gets reported right away instead of waiting for more lines
+    */
+    pub fn is_input_complete(input: &str) -> bool {
+        let Ok(tokens) = lexer::tokenize(input) else {
+            return true;
+        };
+        let mut depth: isize = 0;
+        for token in &tokens {
+            match token.token_type {
+                TokenType::OpenBrace => depth += 1,
+                TokenType::CloseBrace => depth -= 1,
+                _ => {}
+            }
+        }
+        depth <= 0
+    }
+
+    /*
+     Evaluates one complete input, when it ends with an expression its value is returned already
+     formatted for echoing (NULL is not worth echoing, so it counts as no value)
+    */
+    pub fn evaluate(&mut self, input: &str) -> Result<Option<String>, Vec<MascalError>> {
+        let Some(wrapped_input) = wrap_input(input) else {
+            return Ok(None);
+        };
+        /* Every input is kept around, whatever it defines can still fail long after it was typed */
+        let input_number: usize = self
+            .files
+            .iter()
+            .filter(|file| file.imported_at.is_none())
+            .count()
+            + 1;
+        self.files.push(SourceFile {
+            path: format!("<input {}>", input_number),
+            text: Rc::from(input),
+            imported_at: None,
+        });
+        let input_file: usize = self.files.len();
+        let abstract_syntax_tree: AbstractSyntaxTree = match self.compile(&wrapped_input.source) {
+            Ok(abstract_syntax_tree) => abstract_syntax_tree,
+            Err(errors) => {
+                let errors: Vec<MascalError> = errors
+                    .into_iter()
+                    .map(|error| self.relocate(error, input_file))
+                    .collect();
+                self.files.pop();
+                return Err(errors);
+            }
+        };
+        self.files = abstract_syntax_tree.imported_files.clone();
+
+        let mut program_block: Option<ExecutionBlock> = None;
+        for block in abstract_syntax_tree.blocks {
+            match block {
+                ScopedBlocks::Program(exec_block) => program_block = Some(exec_block),
+                ScopedBlocks::Function { ref name, .. } => {
+                    let symbol = abstract_syntax_tree
+                        .symbol_table
+                        .get_function(name)
+                        .unwrap();
+                    self.symbol_table
+                        .define_function(Rc::from(name.as_str()), symbol.clone());
+                    self.functions.retain(|function| {
+                        !matches!(function, ScopedBlocks::Function { name: existing, .. } if existing == name)
+                    });
+                    self.functions.push(block);
+                }
+                ScopedBlocks::Record { ref name, .. } => {
+                    let symbol = abstract_syntax_tree.symbol_table.get_record(name).unwrap();
+                    self.symbol_table
+                        .define_record(Rc::from(name.as_str()), (**symbol).clone());
+                }
+                ScopedBlocks::Enum { ref name, .. } => {
+                    let symbol = abstract_syntax_tree.symbol_table.get_enum(name).unwrap();
+                    self.symbol_table
+                        .define_enum(Rc::from(name.as_str()), symbol.clone());
+                }
+            }
+        }
+
+        let value: Option<MascalValue> = interpert_block(
+            program_block.unwrap(),
+            self.variable_table.clone(),
+            self.functions.clone(),
+            self.io.clone(),
+            Rc::new(FxHashMap::default()),
+            Rc::new(self.symbol_table.clone()),
+        )
+        .map_err(|error| vec![self.relocate(error, input_file)])?;
+        match value {
+            None | Some(MascalValue::Null) => Ok(None),
+            Some(value) => value
+                .as_string_inner(true)
+                .map(Some)
+                .map_err(|error| vec![self.relocate(error, input_file)]),
+        }
+    }
+
+    /*
+     The earlier input (or imported file) a span points into, spans pointing into the input that
+     was just evaluated have nothing to hand back, the caller already has that one
+    */
+    pub fn source_file(&self, span: Span) -> Option<&SourceFile> {
+        imported_file(&self.files, span)
+    }
+
+    /* Shows the error against whichever text it points into, much like a program's errors are */
+    pub fn render_error(&self, input: &str, error: &MascalError, colored: bool) -> String {
+        let Some(file) = error.span.and_then(|span| self.source_file(span)) else {
+            return render_error(input, error, colored);
+        };
+        let located: MascalError = MascalError {
+            span: error.span,
+            error_type: error.error_type.clone(),
+            source: format!("In {:?}: {}", file.path, error.source),
+        };
+        render_error(&file.text, &located, colored)
+    }
+
+    /*
+     Records and enums defined by earlier inputs are known up front, unless this input happens to
+     define them all over again
+    */
+    fn compile(&self, source: &str) -> Result<AbstractSyntaxTree, Vec<MascalError>> {
+        let mut abstract_syntax_tree: AbstractSyntaxTree =
+            parse_after_files(source, None, self.files.clone())?;
+        let defined_names: Vec<&str> = abstract_syntax_tree
+            .blocks
+            .iter()
+            .filter_map(|block| match block {
+                ScopedBlocks::Record { name, .. } | ScopedBlocks::Enum { name, .. } => {
+                    Some(name.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        let mut symbol_table: SymbolTable = SymbolTable::default();
+        for (name, symbol) in self.symbol_table.records() {
+            if !defined_names.contains(&&**name) {
+                symbol_table.define_record(name.clone(), (**symbol).clone());
+            }
+        }
+        for (name, symbol) in self.symbol_table.enums() {
+            if !defined_names.contains(&&**name) {
+                symbol_table.define_enum(name.clone(), symbol.clone());
+            }
+        }
+        abstract_syntax_tree.symbol_table = symbol_table;
+        let imported_files: Vec<SourceFile> = abstract_syntax_tree.imported_files.clone();
+        conduct_semantic_analysis(abstract_syntax_tree).map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|error| point_at_imports(error, &imported_files))
+                .collect()
+        })
+    }
+
+    /*
+     Spans count characters from the start of the wrapped up input rather than the input as it was
+     typed, which goes for the earlier inputs as well. The ones pointing into the input that was
+     just evaluated lose their file number so they get shown against it
+    */
+    fn relocate(&self, mut error: MascalError, input_file: usize) -> MascalError {
+        if let Some(span) = error.span.as_mut()
+            && let Some(file) = imported_file(&self.files, *span)
+            && file.imported_at.is_none()
+        {
+            let prefix_length: usize =
+                wrap_input(&file.text).map_or(0, |wrapped_input| wrapped_input.prefix_length);
+            span.character = span
+                .character
+                .saturating_sub(prefix_length)
+                .min(file.text.len());
+            if span.file == input_file {
+                span.file = 0;
+            }
+        }
+        error
+    }
+}
+
+fn wrap_input(input: &str) -> Option<WrappedInput> {
+    let tokens: Vec<Token> = lexer::tokenize(input).unwrap_or_default();
+    let (first_token, last_token) = match (tokens.first(), tokens.last()) {
+        (Some(first_token), Some(last_token)) => (first_token, last_token),
+        _ if input.trim().is_empty() => return None,
+        /* The lexer failed, compiling it as-is is the easiest way to get that error reported */
+        _ => return Some(WrappedInput::new("", input, "")),
+    };
+    /* A record or enum name followed by a brace declares variables of that type, as does a type */
+    let is_variable_type: bool = matches!(
+        first_token.token_type,
+        TokenType::Integer
+            | TokenType::Float
+            | TokenType::String
+            | TokenType::Boolean
+            | TokenType::Dynamic
+            | TokenType::Type
+            | TokenType::Function
+            | TokenType::Identifier
+    );
+    Some(match first_token.token_type {
+        TokenType::DefineFunction
+        | TokenType::DefineRecord
+        | TokenType::DefineEnum
+        | TokenType::Import => {
+            WrappedInput::new("", input, "\nDEFINE_PROGRAM { IMPLEMENTATION { } }")
+        }
+        TokenType::Variables => {
+            WrappedInput::new("DEFINE_PROGRAM { ", input, "\nIMPLEMENTATION { }\n}")
+        }
+        /* A map type is a handful of tokens long, only its group of variables ends with a brace */
+        _ if (is_variable_type
+            && tokens.get(1).map(|t| &t.token_type) == Some(&TokenType::OpenBrace))
+            || (first_token.token_type == TokenType::Map
+                && last_token.token_type == TokenType::CloseBrace) =>
+        {
+            WrappedInput::new(
+                "DEFINE_PROGRAM { VARIABLES { ",
+                input,
+                "\n}\nIMPLEMENTATION { }\n}",
+            )
+        }
+        _ => {
+            /* Forgetting the semicolon after a quick expression is fine in here */
+            let suffix: &str = match last_token.token_type {
+                TokenType::Semicolon | TokenType::CloseBrace => "\n}\n}",
+                _ => ";\n}\n}",
+            };
+            WrappedInput::new("DEFINE_PROGRAM { IMPLEMENTATION { ", input, suffix)
+        }
+    })
+}
+
+pub fn start_repl() {
+    let mut session: ReplSession = ReplSession::new();
+    let colored: bool = should_use_color();
+    let mut buffer: String = String::new();
+    println!("Mascal REPL, type :quit or press Ctrl+D to leave");
+    loop {
+        print!(
+            "{}",
+            if buffer.is_empty() {
+                PROMPT
+            } else {
+                CONTINUATION_PROMPT
+            }
+        );
+        if io::stdout().flush().is_err() {
+            break;
+        }
+        let mut line: String = String::new();
+        match io::stdin().lock().read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        if buffer.is_empty() && line.trim() == ":quit" {
+            break;
+        }
+        buffer.push_str(&line);
+        if !ReplSession::is_input_complete(&buffer) {
+            continue;
+        }
+        let input: String = std::mem::take(&mut buffer);
+        match session.evaluate(&input) {
+            Ok(Some(echo)) => println!("{}", echo),
+            Ok(None) => {}
+            Err(errors) => {
+                for error in errors {
+                    print!("{}", session.render_error(&input, &error, colored));
+                }
+            }
+        }
+    }
+    println!();
+}
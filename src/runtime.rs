@@ -1,54 +1,123 @@
-mod execute_binary_expression;
-mod execute_builtin_function;
-mod execute_declaration_statement;
+pub(crate) mod execute_binary_expression;
+pub(crate) mod execute_builtin_function;
+pub(crate) mod execute_declaration_statement;
 pub mod execute_expression;
-mod execute_function_expression;
-mod execute_statement;
+pub(crate) mod execute_function_expression;
+pub(crate) mod execute_statement;
 pub mod execute_typecast;
-mod execute_unary_expression;
+pub(crate) mod execute_unary_expression;
+pub mod io_streams;
 pub mod utils;
 pub mod values;
-mod variable_table;
+pub mod variable_table;
 
 use crate::ast::AbstractSyntaxTree;
 use crate::defs::blocks::{ExecutionBlock, ScopedBlocks};
+use crate::defs::builtins::builtin_functions::NativeFunctionTable;
 use crate::defs::errors::MascalError;
+use crate::defs::symbol_table::SymbolTable;
+use crate::defs::statements::{MascalStatement, MascalStatementKind};
+use crate::runtime::execute_expression::execute_expression;
 use crate::runtime::execute_statement::{SemanticContext, execute_statement};
+use crate::runtime::io_streams::SharedIO;
+use crate::runtime::values::MascalValue;
 use crate::runtime::variable_table::{VariableTable, create_variable_table};
+use rustc_hash::FxHashMap;
 use std::cell::RefCell;
-use std::collections::HashSet;
 use std::rc::Rc;
-use std::sync::{Arc, LazyLock, Mutex};
 
 pub struct ExecutionData<'a> {
     pub variable_table: Option<Rc<RefCell<VariableTable>>>,
     pub scoped_blocks: &'a Vec<ScopedBlocks>,
+    pub io: SharedIO,
+    pub native_functions: NativeFunctionTable,
+    pub symbol_table: Rc<SymbolTable>,
 }
 
-pub static FUNCTION_HASHSET: LazyLock<Mutex<HashSet<Arc<str>>>> =
-    LazyLock::new(|| Mutex::new(HashSet::new()));
-
-pub fn interpert(abstract_syntax_tree: AbstractSyntaxTree) -> Result<(), MascalError> {
+pub fn interpert(
+    abstract_syntax_tree: AbstractSyntaxTree,
+    io: SharedIO,
+    native_functions: NativeFunctionTable,
+) -> Result<Rc<RefCell<VariableTable>>, MascalError> {
     let mut scoped_blocks: Vec<ScopedBlocks> = abstract_syntax_tree.blocks;
     let program_block: ScopedBlocks = scoped_blocks.remove(abstract_syntax_tree.program_index);
-    let mut exec_block: ExecutionBlock = match program_block {
+    let exec_block: ExecutionBlock = match program_block {
         ScopedBlocks::Program(exec_block) => exec_block,
-        ScopedBlocks::Function { .. } => {
+        ScopedBlocks::Function { .. } | ScopedBlocks::Record { .. } | ScopedBlocks::Enum { .. } => {
             unreachable!()
         }
     };
-    let scoped_variable_table: Rc<RefCell<VariableTable>>;
-    (scoped_variable_table, exec_block) = create_variable_table(exec_block)?;
-    let containerized_scoped_blocks: Rc<RefCell<Vec<ScopedBlocks>>> =
-        Rc::new(RefCell::new(scoped_blocks));
+    let variable_table: Rc<RefCell<VariableTable>> = Rc::new(RefCell::new(FxHashMap::default()));
+    interpert_block(
+        exec_block,
+        variable_table.clone(),
+        scoped_blocks,
+        io,
+        native_functions,
+        Rc::new(abstract_syntax_tree.symbol_table),
+    )?;
+    Ok(variable_table)
+}
+
+/*
+ Runs a program block on top of a variable table that may outlive it, this is what lets the REPL
+ remember variables from one input to the next. Whatever the block declares gets merged into the
+ table, and the value of a trailing expression statement is handed back so it can be echoed
+*/
+pub fn interpert_block(
+    exec_block: ExecutionBlock,
+    variable_table: Rc<RefCell<VariableTable>>,
+    scoped_blocks: Vec<ScopedBlocks>,
+    io: SharedIO,
+    native_functions: NativeFunctionTable,
+    symbol_table: Rc<SymbolTable>,
+) -> Result<Option<MascalValue>, MascalError> {
+    let (declared_variable_table, exec_block) = create_variable_table(
+        exec_block,
+        io.clone(),
+        native_functions.clone(),
+        symbol_table.clone(),
+    )?;
+    variable_table
+        .borrow_mut()
+        .extend(declared_variable_table.borrow_mut().drain());
     let semantic_context: Rc<SemanticContext> = Rc::new(SemanticContext {
-        variable_table: scoped_variable_table.clone(),
-        scoped_blocks: containerized_scoped_blocks.clone(),
+        variable_table,
+        scoped_blocks: Rc::new(RefCell::new(scoped_blocks)),
         in_loop: false,
         function_name: None,
+        io,
+        native_functions,
+        symbol_table,
     });
-    for statement in exec_block.body.into_iter() {
+    let mut body: Vec<MascalStatement> = exec_block.body.into_vec();
+    let trailing_statement: Option<MascalStatement> = match body.last() {
+        Some(MascalStatement {
+            kind: MascalStatementKind::ExpressionStatement(..),
+            ..
+        }) => body.pop(),
+        _ => None,
+    };
+    for statement in body.into_iter() {
         execute_statement(statement, semantic_context.clone())?;
     }
-    Ok(())
+    let Some(MascalStatement {
+        kind: MascalStatementKind::ExpressionStatement(expression),
+        span,
+    }) = trailing_statement
+    else {
+        return Ok(None);
+    };
+    execute_expression(
+        expression,
+        &mut ExecutionData {
+            variable_table: Some(semantic_context.variable_table.clone()),
+            scoped_blocks: &semantic_context.scoped_blocks.borrow(),
+            io: semantic_context.io.clone(),
+            native_functions: semantic_context.native_functions.clone(),
+            symbol_table: semantic_context.symbol_table.clone(),
+        },
+    )
+    .map(Some)
+    .map_err(|err| err.located_at(span))
 }
@@ -13,8 +13,19 @@ pub fn execute_binary_expression(
     exec_data: &mut ExecutionData,
 ) -> Result<MascalValue, MascalError> {
     let left_value: MascalValue = execute_expression(left, exec_data)?;
+    if settles_without_right(&operator, &left_value) {
+        return Ok(left_value);
+    }
     let right_value: MascalValue = execute_expression(right, exec_data)?;
+    apply_binary_operator(operator, left_value, right_value)
+}
 
+#[inline(always)]
+pub(crate) fn apply_binary_operator(
+    operator: MascalBinaryOperators,
+    left_value: MascalValue,
+    right_value: MascalValue,
+) -> Result<MascalValue, MascalError> {
     match operator {
         MascalBinaryOperators::Plus => MascalValue::add(left_value, right_value),
         MascalBinaryOperators::Minus => MascalValue::sub(left_value, right_value),
@@ -38,3 +49,19 @@ pub fn execute_binary_expression(
         MascalBinaryOperators::Or => MascalValue::or(&left_value, &right_value),
     }
 }
+
+/*
+ AND and OR leave their right side alone once the left one already settled the outcome, so
+ ``i < LEN(a) AND a[i] == x`` never looks past the end of the array
+*/
+#[inline(always)]
+pub(crate) fn settles_without_right(
+    operator: &MascalBinaryOperators,
+    left_value: &MascalValue,
+) -> bool {
+    matches!(
+        (operator, left_value),
+        (MascalBinaryOperators::And, MascalValue::Boolean(false))
+            | (MascalBinaryOperators::Or, MascalValue::Boolean(true))
+    )
+}
@@ -1,10 +1,88 @@
 use crate::defs::builtins::builtin_functions::BuiltinFunction;
 use crate::defs::errors::{MascalError, MascalErrorType};
 use crate::defs::expressions::MascalExpression;
+use crate::defs::span::Span;
+use crate::defs::types::MascalTypeKind;
 use crate::runtime::ExecutionData;
 use crate::runtime::execute_expression::execute_expression;
 use crate::runtime::values::MascalValue;
 
+pub(crate) fn check_builtin_argument_count(
+    fixed_argument_types: &[Vec<MascalTypeKind>],
+    argument_count: usize,
+) -> Result<(), MascalError> {
+    if argument_count < fixed_argument_types.len() {
+        return Err(MascalError {
+            error_type: MascalErrorType::ArgumentError,
+            span: None,
+            source: format!(
+                "Expected at least {} argument(s) but got {} argument(s) instead",
+                fixed_argument_types.len(),
+                argument_count
+            ),
+        });
+    }
+    Ok(())
+}
+
+/*
+ Checks an already evaluated argument of a value based function, the bytecode VM evaluates every
+ argument up front so this lives apart from the tree-walker's own loop
+*/
+pub(crate) fn check_builtin_argument(
+    fixed_argument_types: &[Vec<MascalTypeKind>],
+    supports_dynamic_arguments: bool,
+    index: usize,
+    argument_count: usize,
+    result: &MascalValue,
+    span: Option<Span>,
+) -> Result<(), MascalError> {
+    if index >= fixed_argument_types.len() {
+        if !supports_dynamic_arguments {
+            return Err(MascalError {
+                error_type: MascalErrorType::RuntimeError,
+                span,
+                source: format!(
+                    "Expected only {:?} parameter(s) but got {:?} parameter(s)",
+                    argument_count, index
+                ),
+            });
+        }
+        return Ok(());
+    }
+    let arg_types = &fixed_argument_types[index];
+    if arg_types
+        .iter()
+        .any(|arg_type| arg_type.is_type_of_for_value(result))
+    {
+        return Ok(());
+    }
+    if arg_types.len() == 1 {
+        return Err(MascalError {
+            error_type: MascalErrorType::TypeError,
+            span,
+            source: format!(
+                "Expected a type of {:?} but got {:?}",
+                arg_types.first().unwrap().as_string(),
+                result.as_type_string()?
+            ),
+        });
+    }
+    Err(MascalError {
+        error_type: MascalErrorType::TypeError,
+        span,
+        source: format!(
+            "Expected at least one of the types {} but got {:?}",
+            arg_types
+                .iter()
+                .map(|x| format!("{:?}", x.as_string()))
+                .collect::<Vec<String>>()
+                .join(", "),
+            result.as_type_string()?
+        ),
+    })
+}
+
 #[inline(always)]
 pub fn execute_builtin_function(
     built_in_func: &BuiltinFunction,
@@ -17,58 +95,18 @@ pub fn execute_builtin_function(
             supports_dynamic_arguments,
             execution,
         } => {
+            check_builtin_argument_count(fixed_argument_types, arguments.len())?;
             let mut args: Vec<MascalValue> = Vec::with_capacity(arguments.len());
-            for (index, arg) in arguments.iter().enumerate() {
+            for arg in arguments.iter() {
                 let result: MascalValue = execute_expression(arg.clone(), exec_data)?;
-                if index < fixed_argument_types.len() {
-                    let arg_types = &fixed_argument_types[index];
-                    let mut is_atleast_one_type: bool = false;
-                    for arg_type in arg_types {
-                        if arg_type.is_type_of_for_value(&result) {
-                            is_atleast_one_type = true;
-                            break;
-                        }
-                    }
-                    if !is_atleast_one_type {
-                        if arg_types.len() == 1 {
-                            return Err(MascalError {
-                                error_type: MascalErrorType::TypeError,
-                                line: 0,
-                                character: 0,
-                                source: format!(
-                                    "Expected a type of {:?} but got {:?}",
-                                    arg_types.first().unwrap().as_string(),
-                                    result.as_type_string()?
-                                ),
-                            });
-                        }
-                        return Err(MascalError {
-                            error_type: MascalErrorType::TypeError,
-                            line: 0,
-                            character: 0,
-                            source: format!(
-                                "Expected at least one of the types {} but got {:?}",
-                                arg_types
-                                    .iter()
-                                    .map(|x| format!("{:?}", x.as_string()))
-                                    .collect::<Vec<String>>()
-                                    .join(", "),
-                                result.as_type_string()?
-                            ),
-                        });
-                    }
-                } else if !supports_dynamic_arguments {
-                    return Err(MascalError {
-                        error_type: MascalErrorType::RuntimeError,
-                        line: 0,
-                        character: 0,
-                        source: format!(
-                            "Expected only {:?} parameter(s) but got {:?} parameter(s)",
-                            arguments.len(),
-                            args.len()
-                        ),
-                    });
-                }
+                check_builtin_argument(
+                    fixed_argument_types,
+                    *supports_dynamic_arguments,
+                    args.len(),
+                    arguments.len(),
+                    &result,
+                    Some(arg.span),
+                )?;
                 args.push(result);
             }
             let val: Option<MascalValue> = execution(args, exec_data)?;
@@ -85,8 +123,7 @@ pub fn execute_builtin_function(
                 if args.len() > fixed_argument_types.len() && !supports_dynamic_arguments {
                     return Err(MascalError {
                         error_type: MascalErrorType::RuntimeError,
-                        line: 0,
-                        character: 0,
+                        span: Some(arg.span),
                         source: format!(
                             "Expected only {:?} parameter(s) but got {:?} parameter(s)",
                             arguments.len(),
@@ -101,3 +138,42 @@ pub fn execute_builtin_function(
         }
     }
 }
+
+/*
+ Calls a builtin with arguments that were already evaluated, which is how it goes when it gets
+ called through a FUNCTION value. The expression based ones need to see the expressions themselves
+*/
+pub fn execute_builtin_function_with_values(
+    built_in_func: &BuiltinFunction,
+    fn_name: &str,
+    arguments: Vec<MascalValue>,
+    span: Option<Span>,
+    exec_data: &mut ExecutionData,
+) -> Result<MascalValue, MascalError> {
+    let BuiltinFunction::ValueBased {
+        fixed_argument_types,
+        supports_dynamic_arguments,
+        execution,
+    } = built_in_func
+    else {
+        return Err(MascalError {
+            error_type: MascalErrorType::ArgumentError,
+            span,
+            source: format!("The builtin {:?} can only be called by its name", fn_name),
+        });
+    };
+    check_builtin_argument_count(fixed_argument_types, arguments.len())
+        .map_err(|err| err.located_at(span))?;
+    for (index, argument) in arguments.iter().enumerate() {
+        check_builtin_argument(
+            fixed_argument_types,
+            *supports_dynamic_arguments,
+            index,
+            arguments.len(),
+            argument,
+            span,
+        )?;
+    }
+    let val: Option<MascalValue> = execution(arguments, exec_data)?;
+    Ok(val.unwrap_or(MascalValue::Null))
+}
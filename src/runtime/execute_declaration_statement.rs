@@ -1,108 +1,71 @@
 mod check_array_assignment;
-mod execute_index_based_decleration;
+pub(crate) mod execute_index_based_decleration;
 mod extract_target_area;
 mod extract_variable_data;
 mod unwrap_index_layers;
 
-use crate::defs::blocks::ScopedBlocks;
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
+use crate::defs::span::Span;
 use crate::defs::loop_flags::LoopFlags;
+use crate::defs::types::MascalType;
 use crate::runtime::execute_declaration_statement::execute_index_based_decleration::execute_index_based_decleration;
+use crate::runtime::execute_declaration_statement::extract_variable_data::extract_record;
 use crate::runtime::execute_expression::execute_expression;
 use crate::runtime::execute_statement::StatementResults;
-use crate::runtime::values::MascalValue;
+use crate::runtime::values::{MascalRecord, MascalValue};
 use crate::runtime::variable_table::{VariableData, VariableTable};
-use crate::runtime::{ExecutionData, FUNCTION_HASHSET};
+use crate::runtime::ExecutionData;
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::Arc;
 
+/*
+ Assigning to the name of the function that is running returns from it, as long as none of its
+ variables or parameters goes by that name. Everywhere else (the program block included) it is
+ just an ordinary assignment
+*/
 pub fn execute_declaration_statement(
     variable: MascalExpression,
     value: MascalExpression,
-    variable_table: Rc<RefCell<VariableTable>>,
-    scoped_blocks: &mut Vec<ScopedBlocks>,
+    function_name: Option<&str>,
+    exec_data: &mut ExecutionData,
 ) -> Result<StatementResults, MascalError> {
-    match variable {
-        MascalExpression::Symbolic(varname) => {
-            if FUNCTION_HASHSET
-                .lock()
-                .unwrap()
-                .get(varname.as_str())
-                .is_some()
+    let variable_table: Rc<RefCell<VariableTable>> = exec_data.variable_table.clone().unwrap();
+    let span: Span = variable.span;
+    match variable.kind {
+        MascalExpressionKind::Symbolic(varname) => {
+            if function_name == Some(varname.as_str())
+                && !variable_table.borrow().contains_key(varname.as_str())
             {
                 return Ok(StatementResults {
-                    return_value: Some(execute_expression(
-                        value,
-                        &mut ExecutionData {
-                            variable_table: Some(variable_table.clone()),
-                            scoped_blocks,
-                        },
-                    )?),
-                    loop_flag: LoopFlags::None,
-                });
-            }
-            let variable_table_borrow = variable_table.borrow();
-            if let Some(vardata) = variable_table_borrow.get(varname.as_str()) {
-                let is_constant = vardata.is_constant;
-                let is_nullable = vardata.is_nullable;
-                let array_dimensions = vardata.array_dimensions.clone();
-                let is_dynamic_array = vardata.is_dynamic_array.clone();
-                let atomic_variable_type = Arc::clone(&vardata.atomic_variable_type);
-                if is_constant {
-                    return Err(MascalError {
-                        line: 0,
-                        character: 0,
-                        error_type: MascalErrorType::RuntimeError,
-                        source: format!(
-                            "Cannot assign a new value to the constant variable called {:?}",
-                            varname
-                        ),
-                    });
-                }
-
-                drop(variable_table_borrow);
-                let value: MascalValue = execute_expression(
-                    value,
-                    &mut ExecutionData {
-                        variable_table: Some(variable_table.clone()),
-                        scoped_blocks,
-                    },
-                )?;
-
-                value.is_expected_array(array_dimensions.clone(), is_dynamic_array.clone())?;
-
-                let mut vartable_mutable_borrow = variable_table.borrow_mut();
-                let owned_data = VariableData {
-                    value: Some(Rc::new(RefCell::new(value))),
-                    is_constant,
-                    is_nullable,
-                    array_dimensions,
-                    is_dynamic_array,
-                    atomic_variable_type,
-                };
-
-                vartable_mutable_borrow.insert(Rc::from(varname), owned_data);
-                return Ok(StatementResults {
-                    return_value: None,
+                    return_value: Some(Some(execute_expression(value, exec_data)?)),
                     loop_flag: LoopFlags::None,
                 });
             }
+            assign_value(&variable_table, varname, span, || {
+                execute_expression(value, exec_data)
+            })?;
+            Ok(StatementResults {
+                return_value: None,
+                loop_flag: LoopFlags::None,
+            })
+        }
 
-            Err(MascalError {
-                line: 0,
-                character: 0,
-                error_type: MascalErrorType::RuntimeError,
-                source: format!(
-                    "Expected a variable name, however got an unknown one called {:?}",
-                    varname
-                ),
+        MascalExpressionKind::FieldAccess { record, field } => {
+            let record: MascalRecord = extract_record(*record, &field, exec_data)?;
+            assign_value(&record.fields, field, span, || {
+                execute_expression(value, exec_data)
+            })?;
+            Ok(StatementResults {
+                return_value: None,
+                loop_flag: LoopFlags::None,
             })
         }
 
-        MascalExpression::Indexing { .. } => {
-            execute_index_based_decleration(variable, value, variable_table, scoped_blocks)?;
+        kind @ MascalExpressionKind::Indexing { .. } => {
+            execute_index_based_decleration(MascalExpression::new(kind, span), value, exec_data)
+            .map_err(|err| err.located_at(span))?;
             Ok(StatementResults {
                 return_value: None,
                 loop_flag: LoopFlags::None,
@@ -114,3 +77,122 @@ pub fn execute_declaration_statement(
         }
     }
 }
+
+/*
+ Assigns to a variable living inside the given table, that being either the one of the current
+ scope or the fields of a record. The value is only produced once the variable turned out to be
+ assignable, FOR EACH loops go through here as well for every element
+*/
+pub(crate) fn assign_value(
+    variable_table: &Rc<RefCell<VariableTable>>,
+    varname: String,
+    span: Span,
+    value: impl FnOnce() -> Result<MascalValue, MascalError>,
+) -> Result<(), MascalError> {
+    let variable_table_borrow = variable_table.borrow();
+    if let Some(vardata) = variable_table_borrow.get(varname.as_str()) {
+        let is_constant = vardata.is_constant;
+        let is_nullable = vardata.is_nullable;
+        let array_dimensions = vardata.array_dimensions.clone();
+        let is_dynamic_array = vardata.is_dynamic_array.clone();
+        let atomic_variable_type = Arc::clone(&vardata.atomic_variable_type);
+        if is_constant {
+            return Err(MascalError {
+                span: Some(span),
+                error_type: MascalErrorType::RuntimeError,
+                source: format!(
+                    "Cannot assign a new value to the constant variable called {:?}",
+                    varname
+                ),
+            });
+        }
+
+        drop(variable_table_borrow);
+        let value: MascalValue = value()?;
+
+        value.is_expected_array(array_dimensions.clone(), is_dynamic_array.clone())?;
+        let value: MascalValue =
+            conform_assigned_value(&varname, &array_dimensions, &atomic_variable_type, value)
+                .map_err(|err| err.located_at(span))?;
+
+        let mut vartable_mutable_borrow = variable_table.borrow_mut();
+        let owned_data = VariableData {
+            value: Some(Rc::new(RefCell::new(value))),
+            is_constant,
+            is_nullable,
+            array_dimensions,
+            is_dynamic_array,
+            atomic_variable_type,
+        };
+
+        vartable_mutable_borrow.insert(Rc::from(varname), owned_data);
+        return Ok(());
+    }
+
+    Err(MascalError {
+        span: Some(span),
+        error_type: MascalErrorType::RuntimeError,
+        source: format!(
+            "Expected a variable name, however got an unknown one called {:?}",
+            varname
+        ),
+    })
+}
+
+/*
+ The checks a value goes through on its way into a variable (on top of the array dimensions),
+ the bytecode VM stores into its slots through here as well
+*/
+pub(crate) fn conform_assigned_value(
+    varname: &str,
+    array_dimensions: &[usize],
+    atomic_variable_type: &MascalType,
+    value: MascalValue,
+) -> Result<MascalValue, MascalError> {
+    /*
+     Records and enums are told apart by their name alone, so a variable can't take in another
+     record or a variant of another enum
+    */
+    if let MascalType::Record(type_name) | MascalType::Enum(type_name, _) = atomic_variable_type
+        && array_dimensions.is_empty()
+        && !matches!(value, MascalValue::Null)
+        && !value.is_atomic_type_of(atomic_variable_type)?
+    {
+        return Err(MascalError {
+            span: None,
+            error_type: MascalErrorType::TypeError,
+            source: format!(
+                "Expected a value of the {} {} for {:?} but got {}",
+                if matches!(atomic_variable_type, MascalType::Enum(..)) {
+                    "enum"
+                } else {
+                    "record"
+                },
+                type_name,
+                varname,
+                value.as_type_string()?
+            ),
+        });
+    }
+
+    /* Maps keep the key and value types of the variable they end up in */
+    if let MascalType::Map(..) | MascalType::Function = atomic_variable_type
+        && array_dimensions.is_empty()
+        && !matches!(value, MascalValue::Null)
+    {
+        if !value.is_atomic_type_of(atomic_variable_type)? {
+            return Err(MascalError {
+                span: None,
+                error_type: MascalErrorType::TypeError,
+                source: format!(
+                    "Expected a value of type {} for {:?} but got {}",
+                    atomic_variable_type.as_string(),
+                    varname,
+                    value.as_type_string()?
+                ),
+            });
+        }
+        return Ok(value.conform_to_type(atomic_variable_type));
+    }
+    Ok(value)
+}
@@ -17,8 +17,7 @@ pub fn check_array_assignment(
             return match unwrapped_assignment_value {
                 MascalValue::StaticArray(_) | MascalValue::DynamicArray(_) => Err(MascalError {
                     error_type: MascalErrorType::TypeError,
-                    line: 0,
-                    character: 0,
+                    span: None,
                     source: String::from("The current array type is deeper than initialized to be"),
                 }),
                 _ => Ok(()),
@@ -1,5 +1,4 @@
-use crate::defs::blocks::ScopedBlocks;
-use crate::defs::errors::MascalError;
+use crate::defs::errors::{MascalError, MascalErrorType};
 use crate::defs::expressions::MascalExpression;
 use crate::runtime::ExecutionData;
 use crate::runtime::execute_declaration_statement::check_array_assignment::check_array_assignment;
@@ -8,40 +7,95 @@ use crate::runtime::execute_declaration_statement::extract_variable_data::extrac
 use crate::runtime::execute_declaration_statement::unwrap_index_layers::unwrap_index_layers;
 use crate::runtime::execute_expression::execute_expression;
 use crate::runtime::values::MascalValue;
-use crate::runtime::variable_table::VariableTable;
+use crate::runtime::values::value_map::MascalMap;
+use crate::runtime::variable_table::{VariableData, VariableTable};
 use std::cell::RefCell;
 use std::rc::Rc;
 
 pub fn execute_index_based_decleration(
     variable: MascalExpression,
     value: MascalExpression,
-    variable_table: Rc<RefCell<VariableTable>>,
-    scoped_blocks: &mut Vec<ScopedBlocks>,
+    exec_data: &mut ExecutionData,
 ) -> Result<(), MascalError> {
-    let (base, layers) =
-        unwrap_index_layers(variable, variable_table.clone(), scoped_blocks)?;
-    let (varname, vardata) = extract_variable_data(base, variable_table.clone(), &layers)?;
+    let variable_table: Rc<RefCell<VariableTable>> = exec_data.variable_table.clone().unwrap();
+    let (base, layers) = unwrap_index_layers(variable, exec_data)?;
+    let (varname, vardata, variable_table) =
+        extract_variable_data(base, variable_table, &layers, exec_data)?;
 
-    let rhs: MascalValue = execute_expression(
-        value,
-        &mut ExecutionData {
-            variable_table: Some(variable_table.clone()),
-            scoped_blocks,
-        },
-    )?;
+    let rhs: MascalValue = execute_expression(value, exec_data)?;
+
+    let map_value: Option<MascalValue> = vardata
+        .value
+        .as_ref()
+        .map(|value| value.borrow().clone())
+        .filter(|value| matches!(value, MascalValue::Map(_)));
+    if let Some(MascalValue::Map(map)) = map_value {
+        return assign_into_map(map, &layers, rhs);
+    }
+
+    assign_into_array(&varname, &vardata, &layers, rhs)?;
+    variable_table
+        .borrow_mut()
+        .insert(Rc::from(varname), vardata);
+    Ok(())
+}
 
-    let layers_len: usize = layers.len();
+/*
+ Inserts the right hand-side under the last key, every key before it has to lead to another map.
+ Entries are shared with the variable's own value just like array cells are
+*/
+fn assign_into_map(
+    mut map: MascalMap,
+    layers: &[(MascalValue, bool)],
+    rhs: MascalValue,
+) -> Result<(), MascalError> {
+    let Some(((last_key, _), inner_layers)) = layers.split_last() else {
+        unreachable!()
+    };
+    if layers.iter().any(|(_, is_dynamic)| *is_dynamic) {
+        return Err(MascalError {
+            error_type: MascalErrorType::TypeError,
+            span: None,
+            source: String::from("Maps can only be indexed with brackets '[' and ']'"),
+        });
+    }
+    for (key, _) in inner_layers {
+        map = match map.get(key.clone())? {
+            MascalValue::Map(inner_map) => inner_map,
+            other => {
+                return Err(MascalError {
+                    error_type: MascalErrorType::IndexError,
+                    span: None,
+                    source: format!(
+                        "Expected the key {} to hold a map but it holds a value of type {}",
+                        key.as_string_inner(true)?,
+                        other.as_type_string()?
+                    ),
+                });
+            }
+        };
+    }
+    map.insert(last_key.clone(), rhs)
+}
+
+/*
+ Writes the right hand-side into the element the index layers point at, the element cells are
+ shared with the variable's own value so the change is visible without storing it back
+*/
+pub(crate) fn assign_into_array(
+    varname: &String,
+    vardata: &VariableData,
+    layers: &[(MascalValue, bool)],
+    rhs: MascalValue,
+) -> Result<(), MascalError> {
     let target_value: Rc<RefCell<Option<MascalValue>>> =
-        extract_target_area(&varname, &vardata, &layers)?;
+        extract_target_area(varname, vardata, layers)?;
     check_array_assignment(
         target_value.clone(),
         Rc::new(RefCell::new(Some(rhs.clone()))),
-        &vardata,
-        layers_len,
+        vardata,
+        layers.len(),
     )?;
     *target_value.borrow_mut() = Some(rhs);
-    variable_table
-        .borrow_mut()
-        .insert(Rc::from(varname), vardata);
     Ok(())
 }
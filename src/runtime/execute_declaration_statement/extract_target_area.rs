@@ -21,8 +21,7 @@ pub fn extract_target_area(
         if *is_dynamic != expected_dynamic {
             return Err(MascalError {
                 error_type: MascalErrorType::TypeError,
-                line: 0,
-                character: 0,
+                span: None,
                 source: format!(
                     "Expected to index via a {} array, but indexed via a {} array instead",
                     if expected_dynamic {
@@ -40,8 +39,7 @@ pub fn extract_target_area(
             _ => {
                 return Err(MascalError {
                     error_type: MascalErrorType::TypeError,
-                    line: 0,
-                    character: 0,
+                    span: None,
                     source: format!(
                         "Expected integer index, got {}",
                         curr_index.as_type_string()?
@@ -59,8 +57,7 @@ pub fn extract_target_area(
         if normalized_index < 0 || normalized_index >= expected_array_size as i128 {
             return Err(MascalError {
                 error_type: MascalErrorType::IndexError,
-                line: 0,
-                character: 0,
+                span: None,
                 source: format!("Index out of bounds for array {}", varname),
             });
         }
@@ -74,8 +71,7 @@ pub fn extract_target_area(
                     None => {
                         return Err(MascalError {
                             error_type: MascalErrorType::IndexError,
-                            line: 0,
-                            character: 0,
+                            span: None,
                             source: format!(
                                 "Index {} out of bounds in a dynamic array {}",
                                 normalized_index, varname
@@ -88,8 +84,7 @@ pub fn extract_target_area(
                     None => {
                         return Err(MascalError {
                             error_type: MascalErrorType::IndexError,
-                            line: 0,
-                            character: 0,
+                            span: None,
                             source: format!(
                                 "Index {} out of bounds in a static array {}",
                                 normalized_index, varname
@@ -100,8 +95,7 @@ pub fn extract_target_area(
                 _ => {
                     return Err(MascalError {
                         error_type: MascalErrorType::TypeError,
-                        line: 0,
-                        character: 0,
+                        span: None,
                         source: format!(
                             "Trying to index a non-array value (type: {}) in variable {}",
                             target_borrow.as_type_string()?,
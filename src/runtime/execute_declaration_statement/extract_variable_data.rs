@@ -1,36 +1,71 @@
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
-use crate::runtime::values::MascalValue;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
+use crate::defs::span::Span;
+use crate::runtime::ExecutionData;
+use crate::runtime::execute_expression::execute_expression;
+use crate::runtime::values::{MascalRecord, MascalValue};
 use crate::runtime::variable_table::{VariableData, VariableTable};
 use std::cell::RefCell;
 use std::rc::Rc;
 
+/* The record a field assignment points at, its fields table is where the new value ends up */
+pub fn extract_record(
+    record: MascalExpression,
+    field: &str,
+    exec_data: &mut ExecutionData,
+) -> Result<MascalRecord, MascalError> {
+    let span: Span = record.span;
+    let record_value: MascalValue = execute_expression(record, exec_data)?;
+    let MascalValue::Record(record) = record_value else {
+        return Err(MascalError {
+            error_type: MascalErrorType::TypeError,
+            span: Some(span),
+            source: format!(
+                "Expected a record to assign the field {:?} to but got {}",
+                field,
+                record_value.as_type_string()?
+            ),
+        });
+    };
+    if !record.fields.borrow().contains_key(field) {
+        return Err(MascalError {
+            error_type: MascalErrorType::RuntimeError,
+            span: Some(span),
+            source: format!("The record {} has no field named {:?}", record.name, field),
+        });
+    }
+    Ok(record)
+}
+
 #[inline(always)]
 pub fn extract_variable_data(
     base: MascalExpression,
-    variable_table: Rc<RefCell<VariableTable>>,
+    mut variable_table: Rc<RefCell<VariableTable>>,
     layers: &[(MascalValue, bool)],
-) -> Result<(String, VariableData), MascalError> {
-    let vartable_borrow = variable_table.borrow();
-
-    let varname: String = match base {
-        MascalExpression::Symbolic(name) => name,
+    exec_data: &mut ExecutionData,
+) -> Result<(String, VariableData, Rc<RefCell<VariableTable>>), MascalError> {
+    let span: Span = base.span;
+    let varname: String = match base.kind {
+        MascalExpressionKind::Symbolic(name) => name,
+        MascalExpressionKind::FieldAccess { record, field } => {
+            variable_table = extract_record(*record, &field, exec_data)?.fields;
+            field
+        }
         other => {
             return Err(MascalError {
                 error_type: MascalErrorType::RuntimeError,
-                line: 0,
-                character: 0,
+                span: Some(span),
                 source: format!("Left hand-side must be a variable, but got {:?}", other),
             });
         }
     };
 
+    let vartable_borrow = variable_table.borrow();
     let vardata = vartable_borrow
         .get(varname.as_str())
         .ok_or_else(|| MascalError {
             error_type: MascalErrorType::RuntimeError,
-            line: 0,
-            character: 0,
+            span: Some(span),
             source: format!(
                 "Expected a defined variable but got an unknown called {}",
                 varname
@@ -41,8 +76,7 @@ pub fn extract_variable_data(
     if vardata.is_constant {
         return Err(MascalError {
             error_type: MascalErrorType::RuntimeError,
-            character: 0,
-            line: 0,
+            span: Some(span),
             source: format!("Cannot assign an array to a constant variable {}", varname),
         });
     }
@@ -50,8 +84,7 @@ pub fn extract_variable_data(
     if vardata.value.is_none() && !layers.is_empty() {
         return Err(MascalError {
             error_type: MascalErrorType::RuntimeError,
-            character: 0,
-            line: 0,
+            span: Some(span),
             source: format!(
                 "Cannot assign an array to a uninitialized variable {}",
                 varname
@@ -59,5 +92,6 @@ pub fn extract_variable_data(
         });
     }
 
-    Ok((varname, vardata))
+    drop(vartable_borrow);
+    Ok((varname, vardata, variable_table))
 }
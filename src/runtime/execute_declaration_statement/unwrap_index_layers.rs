@@ -1,34 +1,23 @@
-use crate::defs::blocks::ScopedBlocks;
 use crate::defs::errors::MascalError;
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
 use crate::runtime::ExecutionData;
 use crate::runtime::execute_expression::execute_expression;
 use crate::runtime::values::MascalValue;
-use crate::runtime::variable_table::VariableTable;
-use std::cell::RefCell;
-use std::rc::Rc;
 
 #[inline(always)]
 pub fn unwrap_index_layers(
     variable: MascalExpression,
-    variable_table: Rc<RefCell<VariableTable>>,
-    scoped_blocks: &mut Vec<ScopedBlocks>,
+    exec_data: &mut ExecutionData,
 ) -> Result<(MascalExpression, Vec<(MascalValue, bool)>), MascalError> {
     let mut layers: Vec<(MascalValue, bool)> = Vec::new();
     let mut base: MascalExpression = variable;
-    while let MascalExpression::Indexing {
+    while let MascalExpressionKind::Indexing {
         array,
         index,
         is_dynamic,
-    } = base
+    } = base.kind
     {
-        let index_val = execute_expression(
-            *index,
-            &mut ExecutionData {
-                variable_table: Some(variable_table.clone()),
-                scoped_blocks,
-            },
-        )?;
+        let index_val = execute_expression(*index, exec_data)?;
         layers.push((index_val, is_dynamic));
         base = *array;
     }
@@ -1,15 +1,17 @@
-use crate::defs::dynamic_int::IntegerNum;
+use crate::define_array_expression_exec;
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
 use crate::defs::literal::MascalLiteral;
+use crate::defs::span::Span;
 use crate::defs::types::{MascalType, to_processed_type};
 use crate::runtime::ExecutionData;
 use crate::runtime::execute_binary_expression::execute_binary_expression;
-use crate::runtime::execute_function_expression::execute_function_call;
+use crate::runtime::execute_function_expression::{execute_function_call, is_function_name};
 use crate::runtime::execute_unary_expression::execute_unary_expression;
-use crate::runtime::values::MascalValue;
+use crate::runtime::utils::index_array;
+use crate::runtime::values::value_map::MascalMap;
+use crate::runtime::values::{MascalEnumValue, MascalFunction, MascalLambda, MascalValue};
 use crate::runtime::variable_table::{VariableData, VariableTable};
-use crate::{define_array_expression_exec, index_array_impl, uninit_cell_error};
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -18,9 +20,18 @@ use std::sync::Arc;
 pub fn execute_expression(
     expression: MascalExpression,
     exec_data: &mut ExecutionData,
+) -> Result<MascalValue, MascalError> {
+    let span: Span = expression.span;
+    execute_expression_kind(expression.kind, span, exec_data).map_err(|err| err.located_at(span))
+}
+
+fn execute_expression_kind(
+    expression: MascalExpressionKind,
+    span: Span,
+    exec_data: &mut ExecutionData,
 ) -> Result<MascalValue, MascalError> {
     match expression {
-        MascalExpression::Literal(value) => {
+        MascalExpressionKind::Literal(value) => {
             let mv = match value {
                 MascalLiteral::String(s) => MascalValue::String(Arc::from(s)),
                 MascalLiteral::Integer(i) => MascalValue::Integer(i),
@@ -31,7 +42,7 @@ pub fn execute_expression(
             Ok(mv)
         }
 
-        MascalExpression::Symbolic(symbolic_expr) => {
+        MascalExpressionKind::Symbolic(symbolic_expr) => {
             let var_table_option: Option<Rc<RefCell<VariableTable>>> =
                 exec_data.variable_table.clone();
             let unwrapped_var_table: Rc<RefCell<VariableTable>> = match var_table_option {
@@ -39,8 +50,7 @@ pub fn execute_expression(
                 None => {
                     return Err(MascalError {
                         error_type: MascalErrorType::RuntimeError,
-                        character: 0,
-                        line: 0,
+                        span: Some(span),
                         source: format!(
                             "Variables are unavailable for this specific context (tried accessing {:?})",
                             symbolic_expr
@@ -60,26 +70,79 @@ pub fn execute_expression(
                     .map(|v| v.borrow().clone())
                     .ok_or_else(|| MascalError {
                         error_type: MascalErrorType::RuntimeError,
-                        character: 0,
-                        line: 0,
+                        span: Some(span),
                         source: format!("Variable {:?} not initialized", symbolic_expr),
                     }),
+                None if exec_data.symbol_table.is_record(&symbolic_expr) => Ok(MascalValue::Type(
+                    MascalType::Record(Arc::from(symbolic_expr.as_str())),
+                )),
+                None if exec_data.symbol_table.is_enum(&symbolic_expr) => Ok(MascalValue::Type(
+                    exec_data.symbol_table.enum_type(&symbolic_expr).unwrap(),
+                )),
+                None if is_function_name(&symbolic_expr, exec_data) => Ok(MascalValue::Function(
+                    MascalFunction::Named(Arc::from(symbolic_expr.as_str())),
+                )),
                 None => Err(MascalError {
                     error_type: MascalErrorType::RuntimeError,
-                    character: 0,
-                    line: 0,
+                    span: Some(span),
                     source: format!("Unknown expression {:?} found", symbolic_expr),
                 }),
             }
         }
 
-        MascalExpression::Indexing {
+        MascalExpressionKind::FieldAccess { record, field } => {
+            let record_value: MascalValue = execute_expression(*record, exec_data)?;
+            /* Color.RED picks a variant out of the enum */
+            if let MascalValue::Type(MascalType::Enum(name, variants)) = &record_value {
+                let index: usize = variants
+                    .iter()
+                    .position(|variant| **variant == *field)
+                    .ok_or_else(|| MascalError {
+                        error_type: MascalErrorType::RuntimeError,
+                        span: Some(span),
+                        source: format!("The enum {} has no variant named {:?}", name, field),
+                    })?;
+                return Ok(MascalValue::Enum(MascalEnumValue {
+                    name: name.clone(),
+                    variants: variants.clone(),
+                    index,
+                }));
+            }
+            let MascalValue::Record(record) = record_value else {
+                return Err(MascalError {
+                    error_type: MascalErrorType::TypeError,
+                    span: Some(span),
+                    source: format!(
+                        "Expected a record to access the field {:?} from but got {}",
+                        field,
+                        record_value.as_type_string()?
+                    ),
+                });
+            };
+            if !record.fields.borrow().contains_key(field.as_str()) {
+                return Err(MascalError {
+                    error_type: MascalErrorType::RuntimeError,
+                    span: Some(span),
+                    source: format!("The record {} has no field named {:?}", record.name, field),
+                });
+            }
+            record.get_field(&field).ok_or_else(|| MascalError {
+                error_type: MascalErrorType::RuntimeError,
+                span: Some(span),
+                source: format!(
+                    "Field {:?} of the record {} not initialized",
+                    field, record.name
+                ),
+            })
+        }
+
+        MascalExpressionKind::Indexing {
             index,
             array,
             is_dynamic,
         } => {
             let arr_expr: MascalExpression = *array;
-            let is_atomic_type_expr: bool = matches!(arr_expr, MascalExpression::Type(_));
+            let is_atomic_type_expr: bool = matches!(arr_expr.kind, MascalExpressionKind::Type(_));
             let arr_value: MascalValue = execute_expression(arr_expr, exec_data)?;
             if is_atomic_type_expr {
                 let MascalValue::Type(extract_type) = arr_value else {
@@ -94,67 +157,85 @@ pub fn execute_expression(
                     extract_type,
                 ))));
             }
-            if !arr_value.is_array() {
-                return Err(MascalError {
-                    error_type: MascalErrorType::TypeError,
-                    line: 0,
-                    character: 0,
-                    source: String::from("Expected an array type but found instead an atomic type"),
-                });
-            }
             let index_value: MascalValue = execute_expression(*index, exec_data)?;
-            let num: &IntegerNum = match &index_value {
-                MascalValue::Integer(i) => Ok(i),
-
-                _ => {
-                    return Err(MascalError {
-                        error_type: MascalErrorType::TypeError,
-                        line: 0,
-                        character: 0,
-                        source: format!(
-                            "Expected an index type (integer) but got {:?}",
-                            index_value.as_type_string()?
-                        ),
-                    });
-                }
-            }?;
-            let mut num_val: i128 = num.to_i128();
-            match arr_value {
-                MascalValue::DynamicArray(elements) => {
-                    index_array_impl!(elements, is_dynamic, num_val, true);
-                }
-                MascalValue::StaticArray(elements) => {
-                    index_array_impl!(elements, is_dynamic, num_val, false);
-                }
-                _ => unreachable!(),
-            }
+            index_array(arr_value, index_value, is_dynamic, span)
         }
 
-        MascalExpression::DynamicArray(array) => {
+        MascalExpressionKind::DynamicArray(array) => {
             let arr = define_array_expression_exec!(array, exec_data);
             Ok(MascalValue::DynamicArray(arr))
         }
 
-        MascalExpression::StaticArray(array) => {
+        MascalExpressionKind::StaticArray(array) => {
             let arr = define_array_expression_exec!(array, exec_data);
             Ok(MascalValue::StaticArray(arr.into()))
         }
 
-        MascalExpression::Type(type_expr) => Ok(MascalValue::Type(to_processed_type(*type_expr)?)),
+        MascalExpressionKind::Map(entries) => {
+            let map: MascalMap = MascalMap::new(MascalType::Dynamic, MascalType::Dynamic);
+            for (key, value) in entries {
+                let key: MascalValue = execute_expression(key, exec_data)?;
+                let value: MascalValue = execute_expression(value, exec_data)?;
+                map.insert(key, value)?;
+            }
+            Ok(MascalValue::Map(map))
+        }
+
+        MascalExpressionKind::Type(type_expr) => Ok(MascalValue::Type(
+            exec_data
+                .symbol_table
+                .resolve_type(to_processed_type(*type_expr)?),
+        )),
 
-        MascalExpression::Unary { value, operator } => {
+        MascalExpressionKind::Unary { value, operator } => {
             execute_unary_expression(*value, operator, exec_data)
         }
 
-        MascalExpression::Binary {
+        MascalExpressionKind::Binary {
             left,
             operator,
             right,
         } => execute_binary_expression(*left, operator, *right, exec_data),
 
-        MascalExpression::Call {
+        MascalExpressionKind::Call {
             arguments,
             function,
-        } => execute_function_call(*function, arguments, exec_data),
+        } => execute_function_call(*function, arguments, span, exec_data),
+
+        MascalExpressionKind::Lambda { parameters, body } => {
+            let captured_variables: VariableTable = exec_data
+                .variable_table
+                .as_ref()
+                .map(|variable_table| variable_table.borrow().clone())
+                .unwrap_or_default();
+            Ok(MascalValue::Function(MascalFunction::Lambda(Rc::new(
+                MascalLambda {
+                    parameters,
+                    body: *body,
+                    captured_variables,
+                },
+            ))))
+        }
+
+        /* The branch that didn't get picked is never evaluated, whatever it would have thrown */
+        MascalExpressionKind::Conditional {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            let condition_span: Span = condition.span;
+            match execute_expression(*condition, exec_data)? {
+                MascalValue::Boolean(true) => execute_expression(*then_branch, exec_data),
+                MascalValue::Boolean(false) => execute_expression(*else_branch, exec_data),
+                value => Err(MascalError {
+                    error_type: MascalErrorType::RuntimeError,
+                    span: Some(condition_span),
+                    source: format!(
+                        "Expected a boolean variable on the condition but got {:?}",
+                        value.as_string()?
+                    ),
+                }),
+            }
+        }
     }
 }
@@ -1,18 +1,26 @@
 use crate::defs::blocks::{ExecutionBlock, MascalParameter, ScopedBlocks};
-use crate::defs::builtins::builtin_functions::BUILT_IN_FUNCTION_TABLE;
+use crate::defs::builtins::builtin_functions::{
+    BUILT_IN_FUNCTION_TABLE, BuiltinFunction, NativeFunctionTable,
+};
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
+use crate::defs::span::Span;
 use crate::defs::types::{MascalType, MascalUnprocessedType, to_processed_type};
 use crate::runtime::ExecutionData;
-use crate::runtime::execute_builtin_function::execute_builtin_function;
+use crate::runtime::execute_builtin_function::{
+    execute_builtin_function, execute_builtin_function_with_values,
+};
 use crate::runtime::execute_expression::execute_expression;
 use crate::runtime::execute_statement::{SemanticContext, StatementResults, execute_statement};
 use crate::runtime::execute_typecast::{execute_processed_typecast, execute_typecast};
-use crate::runtime::values::MascalValue;
-use crate::runtime::variable_table::{VariableData, VariableTable, create_variable_table};
+use crate::runtime::values::{MascalFunction, MascalLambda, MascalValue};
+use crate::runtime::variable_table::{
+    VariableData, VariableTable, create_variable_table, instantiate_record,
+};
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::str::Chars;
+use std::sync::Arc;
 
 fn notify_mutable_params(
     mutable_parameters: Vec<(Rc<str>, Rc<str>)>,
@@ -31,28 +39,224 @@ fn notify_mutable_params(
     }
 }
 
+/* Every word between underscores counts on its own, so both Len and Has_Key are titlecase */
 fn is_titlecase(s: &str) -> bool {
-    let mut chars: Chars = s.chars();
-    if let Some(first) = chars.next() {
-        first.is_uppercase() && chars.clone().all(|c| c.is_lowercase())
-    } else {
-        false
+    s.split('_').all(|word| {
+        let mut chars: Chars = word.chars();
+        if let Some(first) = chars.next() {
+            first.is_uppercase() && chars.clone().all(|c| c.is_lowercase())
+        } else {
+            false
+        }
+    })
+}
+
+/*
+ Builtins and native functions can be called as all uppercase, all lowercase or titlecase, this
+ gives back the key to look them up with (or nothing if the casing doesn't qualify)
+*/
+pub(crate) fn builtin_key(fn_name: &str) -> Option<String> {
+    let lowercased: String = fn_name.to_lowercase();
+    if fn_name == fn_name.to_uppercase() || fn_name == lowercased || is_titlecase(fn_name) {
+        return Some(lowercased);
+    }
+    None
+}
+
+/*
+ Whatever a function hands back is checked against its return type, running off the end of the
+ body is treated exactly like a bare RETURN. Calls that return nothing still evaluate to NULL
+*/
+pub(crate) fn check_returned_value(
+    value: Option<MascalValue>,
+    return_type: &Option<MascalType>,
+    span: Option<Span>,
+) -> Result<MascalValue, MascalError> {
+    let Some(value) = value else {
+        return match return_type {
+            None => Ok(MascalValue::Null),
+            Some(_) => Err(MascalError {
+                error_type: MascalErrorType::RuntimeError,
+                span,
+                source: String::from("Expected a value to be returned, but nothing was returned"),
+            }),
+        };
+    };
+    match return_type {
+        None => Err(MascalError {
+            error_type: MascalErrorType::RuntimeError,
+            span,
+            source: format!(
+                "Expected no value to be returned, but returned {:?}",
+                value.as_string()?
+            ),
+        }),
+        Some(return_type) => {
+            if return_type.get_atomic_type() != MascalType::Dynamic && !value.is_type_of(return_type)
+            {
+                return Err(MascalError {
+                    error_type: MascalErrorType::RuntimeError,
+                    span,
+                    source: format!(
+                        "Expected value of type {} to be returned, but returned {}",
+                        return_type.as_string(),
+                        value.as_type_string()?
+                    ),
+                });
+            }
+            Ok(value)
+        }
+    }
+}
+
+/* Whether the name belongs to something callable, be it a user defined, builtin or native function */
+pub(crate) fn is_function_name(name: &str, exec_data: &ExecutionData) -> bool {
+    if exec_data.symbol_table.is_function(name) {
+        return true;
     }
+    builtin_key(name).is_some_and(|key| {
+        BUILT_IN_FUNCTION_TABLE.with(|table| table.contains_key(&key))
+            || exec_data.native_functions.contains_key(&key)
+    })
+}
+
+/*
+ A function gets its arguments either as the expressions written at the call (which mutable
+ parameters need to know what to write back into) or as values someone already evaluated
+*/
+enum CallArguments {
+    Expressions(Vec<MascalExpression>),
+    Values(Vec<MascalValue>),
+}
+
+impl CallArguments {
+    fn len(&self) -> usize {
+        match self {
+            CallArguments::Expressions(arguments) => arguments.len(),
+            CallArguments::Values(arguments) => arguments.len(),
+        }
+    }
+}
+
+fn evaluate_arguments(
+    arguments: Vec<MascalExpression>,
+    exec_data: &mut ExecutionData,
+) -> Result<Vec<MascalValue>, MascalError> {
+    arguments
+        .into_iter()
+        .map(|argument| execute_expression(argument, exec_data))
+        .collect()
+}
+
+/* Calls whatever a FUNCTION value stands for with arguments that have already been evaluated */
+pub fn call_function_value(
+    function: &MascalFunction,
+    arguments: Vec<MascalValue>,
+    span: Option<Span>,
+    exec_data: &mut ExecutionData,
+) -> Result<MascalValue, MascalError> {
+    let fn_name: &Arc<str> = match function {
+        MascalFunction::Lambda(lambda) => return call_lambda(lambda, arguments, span, exec_data),
+        MascalFunction::Named(fn_name) => fn_name,
+    };
+    if let Some(lowercased) = &builtin_key(fn_name) {
+        let builtin_function: Option<BuiltinFunction> = BUILT_IN_FUNCTION_TABLE
+            .with(|table| table.get(lowercased).cloned())
+            .or_else(|| exec_data.native_functions.get(lowercased).cloned());
+        if let Some(builtin_function) = builtin_function {
+            return execute_builtin_function_with_values(
+                &builtin_function,
+                fn_name,
+                arguments,
+                span,
+                exec_data,
+            );
+        }
+    }
+    call_user_function(fn_name, CallArguments::Values(arguments), span, exec_data)
+}
+
+fn call_lambda(
+    lambda: &MascalLambda,
+    arguments: Vec<MascalValue>,
+    span: Option<Span>,
+    exec_data: &mut ExecutionData,
+) -> Result<MascalValue, MascalError> {
+    if lambda.parameters.len() != arguments.len() {
+        return Err(MascalError {
+            error_type: MascalErrorType::ArgumentError,
+            span,
+            source: format!(
+                "Expected {} argument(s) but got {} argument(s) instead",
+                lambda.parameters.len(),
+                arguments.len()
+            ),
+        });
+    }
+    /* The parameters can hold anything, much like a DYNAMIC variable would */
+    let mut variable_table: VariableTable = lambda.captured_variables.clone();
+    for (parameter, value) in lambda.parameters.iter().zip(arguments) {
+        variable_table.insert(
+            parameter.clone(),
+            VariableData {
+                value: Some(Rc::new(RefCell::new(value))),
+                is_constant: false,
+                is_nullable: true,
+                array_dimensions: Rc::from([]),
+                is_dynamic_array: Rc::from([]),
+                atomic_variable_type: Arc::new(MascalType::Dynamic),
+            },
+        );
+    }
+    execute_expression(
+        lambda.body.clone(),
+        &mut ExecutionData {
+            variable_table: Some(Rc::new(RefCell::new(variable_table))),
+            scoped_blocks: exec_data.scoped_blocks,
+            io: exec_data.io.clone(),
+            native_functions: exec_data.native_functions.clone(),
+            symbol_table: exec_data.symbol_table.clone(),
+        },
+    )
+}
+
+fn get_variable_value(name: &str, exec_data: &ExecutionData) -> Option<MascalValue> {
+    let variable_table = exec_data.variable_table.as_ref()?.borrow();
+    variable_table
+        .get(name)?
+        .value
+        .as_ref()
+        .map(|value| value.borrow().clone())
 }
 
 #[allow(dead_code)]
 pub fn execute_function_call(
     function: MascalExpression,
     arguments: Vec<MascalExpression>,
+    span: Span,
     exec_data: &mut ExecutionData,
 ) -> Result<MascalValue, MascalError> {
-    let fn_name: String = match function {
-        MascalExpression::Symbolic(target_name) => target_name,
-        MascalExpression::Type(t) => {
+    let fn_name: String = match function.kind {
+        MascalExpressionKind::Symbolic(target_name) => {
+            /* A variable holding a function comes first, even if a function goes by the same name */
+            if let Some(MascalValue::Function(function)) =
+                get_variable_value(&target_name, exec_data)
+            {
+                let arguments: Vec<MascalValue> = evaluate_arguments(arguments, exec_data)?;
+                return call_function_value(&function, arguments, Some(span), exec_data);
+            }
+            target_name
+        }
+        MascalExpressionKind::Type(t) => {
             return execute_typecast(t, arguments, exec_data);
         }
-        expr => {
-            let value: MascalValue = execute_expression(expr, exec_data)?;
+        kind => {
+            let value: MascalValue =
+                execute_expression(MascalExpression::new(kind, function.span), exec_data)?;
+            if let MascalValue::Function(function) = value {
+                let arguments: Vec<MascalValue> = evaluate_arguments(arguments, exec_data)?;
+                return call_function_value(&function, arguments, Some(span), exec_data);
+            }
             if value.is_type_of(&MascalType::Type) {
                 let MascalValue::Type(extracted_type) = value else {
                     unreachable!()
@@ -60,47 +264,103 @@ pub fn execute_function_call(
                 if arguments.len() != 1 {
                     return Err(MascalError {
                         error_type: MascalErrorType::ArgumentError,
-                        line: 0,
-                        character: 0,
+                        span: Some(span),
                         source: String::from("Expected one value but got none or multiple values"),
                     });
                 }
                 let value: MascalValue = execute_expression(arguments[0].clone(), exec_data)?;
-                return execute_processed_typecast(extracted_type, value);
+                return execute_processed_typecast(extracted_type, value)
+                    .map_err(|err| err.located_at(arguments[0].span));
             }
             return Err(MascalError {
                 error_type: MascalErrorType::TypeError,
-                line: 0,
-                character: 0,
+                span: Some(function.span),
                 source: String::from(
                     "Expected an identifier for the function call but got a expression",
                 ),
             });
         }
     };
-    let lowercased: &String = &fn_name.to_lowercase();
-    if fn_name == fn_name.to_uppercase() || &fn_name == lowercased || is_titlecase(&fn_name) {
-        if let Some(built_in_func) = BUILT_IN_FUNCTION_TABLE.get(lowercased) {
-            return execute_builtin_function(built_in_func, arguments, exec_data);
+    if let Some(lowercased) = &builtin_key(&fn_name) {
+        if BUILT_IN_FUNCTION_TABLE.with(|table| table.contains_key(lowercased)) {
+            return BUILT_IN_FUNCTION_TABLE.with(|table| {
+                execute_builtin_function(&table[lowercased], arguments, exec_data)
+            });
+        }
+        /*
+         Functions the host registered on the interpreter come right after the language ones, they
+         follow the exact same casing rules so scripts can't tell them apart from the builtins
+        */
+        let native_functions: NativeFunctionTable = exec_data.native_functions.clone();
+        if let Some(native_func) = native_functions.get(lowercased) {
+            return execute_builtin_function(native_func, arguments, exec_data);
+        }
+    }
+    if exec_data.symbol_table.is_record(&fn_name) {
+        if !arguments.is_empty() {
+            return Err(MascalError {
+                error_type: MascalErrorType::ArgumentError,
+                span: Some(span),
+                source: format!(
+                    "Expected no arguments when creating the record {:?} but got {} argument(s)",
+                    fn_name,
+                    arguments.len()
+                ),
+            });
         }
+        return instantiate_record(
+            &Arc::from(fn_name.as_str()),
+            &exec_data.io,
+            &exec_data.native_functions,
+            &exec_data.symbol_table,
+        )
+        .map_err(|err| err.located_at(span));
     }
+    if let Some(enum_type) = exec_data.symbol_table.enum_type(&fn_name) {
+        if arguments.len() != 1 {
+            return Err(MascalError {
+                error_type: MascalErrorType::ArgumentError,
+                span: Some(span),
+                source: String::from("Expected one value but got none or multiple values"),
+            });
+        }
+        let value: MascalValue = execute_expression(arguments[0].clone(), exec_data)?;
+        return execute_processed_typecast(enum_type, value)
+            .map_err(|err| err.located_at(arguments[0].span));
+    }
+    call_user_function(
+        &fn_name,
+        CallArguments::Expressions(arguments),
+        Some(span),
+        exec_data,
+    )
+}
+
+fn call_user_function(
+    fn_name: &str,
+    arguments: CallArguments,
+    span: Option<Span>,
+    exec_data: &mut ExecutionData,
+) -> Result<MascalValue, MascalError> {
     let mut func_parameters: &[MascalParameter] = &Vec::new();
     let mut func_return_type: Option<MascalUnprocessedType> = None;
     let mut wrapped_func_exec_block: Option<ExecutionBlock> = None;
     {
-        let scope_blocks: &Vec<ScopedBlocks> = &*exec_data.scoped_blocks;
+        let scope_blocks: &Vec<ScopedBlocks> = exec_data.scoped_blocks;
         for scoped_block in scope_blocks.iter() {
             match scoped_block {
                 ScopedBlocks::Program(..) => {
                     unreachable!()
                 }
+                ScopedBlocks::Record { .. } | ScopedBlocks::Enum { .. } => {}
                 ScopedBlocks::Function {
                     name,
                     parameters,
                     return_type,
                     execution_block,
+                    ..
                 } => {
-                    if name == &fn_name {
+                    if name == fn_name {
                         func_return_type = return_type.clone();
                         func_parameters = parameters;
                         wrapped_func_exec_block = Some(execution_block.clone());
@@ -113,16 +373,14 @@ pub fn execute_function_call(
     if wrapped_func_exec_block.is_none() {
         return Err(MascalError {
             error_type: MascalErrorType::RuntimeError,
-            character: 0,
-            line: 0,
+            span,
             source: format!("Unidentified function with the name of {:?}", fn_name),
         });
     }
     if func_parameters.len() != arguments.len() {
         return Err(MascalError {
             error_type: MascalErrorType::ArgumentError,
-            line: 0,
-            character: 0,
+            span,
             source: format!(
                 "Expected {} argument(s) but got {} argument(s) instead",
                 func_parameters.len(),
@@ -132,22 +390,43 @@ pub fn execute_function_call(
     }
     let mut func_exec_block: ExecutionBlock = wrapped_func_exec_block.unwrap();
     let scoped_variable_table: Rc<RefCell<VariableTable>>;
-    (scoped_variable_table, func_exec_block) = create_variable_table(func_exec_block)?;
+    (scoped_variable_table, func_exec_block) = create_variable_table(
+        func_exec_block,
+        exec_data.io.clone(),
+        exec_data.native_functions.clone(),
+        exec_data.symbol_table.clone(),
+    )?;
     let mut borrowed_mut_vartable = scoped_variable_table.borrow_mut();
     let mut mutable_parameters: Vec<(Rc<str>, Rc<str>)> = Vec::with_capacity(func_parameters.len());
     for (index, parameter) in func_parameters.iter().enumerate() {
         let data: &mut VariableData = borrowed_mut_vartable.get_mut(&parameter.name).unwrap();
+        let arguments: &[MascalExpression] = match &arguments {
+            CallArguments::Values(..) if parameter.is_mutable => {
+                return Err(MascalError {
+                    error_type: MascalErrorType::ArgumentError,
+                    span,
+                    source: format!(
+                        "The function {:?} has mutable parameters, so it can only be called by its name",
+                        fn_name
+                    ),
+                });
+            }
+            CallArguments::Values(values) => {
+                data.value = Some(Rc::new(RefCell::new(values[index].clone())));
+                continue;
+            }
+            CallArguments::Expressions(arguments) => arguments,
+        };
         if parameter.is_mutable {
-            match &arguments[index] {
-                MascalExpression::Symbolic(varname) => {
+            match &arguments[index].kind {
+                MascalExpressionKind::Symbolic(varname) => {
                     mutable_parameters.push((parameter.name.clone(), Rc::from(varname.as_str())));
                 }
 
                 _ => {
                     return Err(MascalError {
                         error_type: MascalErrorType::ArgumentError,
-                        line: 0,
-                        character: 0,
+                        span: Some(arguments[index].span),
                         source: String::from(
                             "Expected a variable name in order to update to, but got something else",
                         ),
@@ -160,7 +439,7 @@ pub fn execute_function_call(
         data.value = Some(Rc::new(RefCell::new(result)));
     }
     let processed_return_type: Option<MascalType> = if let Some(return_type) = func_return_type {
-        Some(to_processed_type(return_type)?)
+        Some(exec_data.symbol_table.resolve_type(to_processed_type(return_type)?))
     } else {
         None
     };
@@ -171,52 +450,21 @@ pub fn execute_function_call(
             Rc::new(SemanticContext {
                 variable_table: scoped_variable_table.clone(),
                 scoped_blocks: Rc::new(RefCell::new(exec_data.scoped_blocks.clone())),
-                function_name: Some(Rc::from(fn_name.clone())),
+                function_name: Some(Rc::from(fn_name)),
                 in_loop: false,
+                io: exec_data.io.clone(),
+                native_functions: exec_data.native_functions.clone(),
+                symbol_table: exec_data.symbol_table.clone(),
             }),
         )?;
         if let Some(value) = statement_results.return_value {
-            if processed_return_type.is_none() {
-                return Err(MascalError {
-                    error_type: MascalErrorType::RuntimeError,
-                    character: 0,
-                    line: 0,
-                    source: format!(
-                        "Expected no value to be returned, but returned {:?}",
-                        value.as_string()?
-                    ),
-                });
-            }
-            let unwrapped_processed_return_type: MascalType =
-                processed_return_type.clone().unwrap();
-            if unwrapped_processed_return_type.get_atomic_type() != MascalType::Dynamic
-                && !value.is_type_of(&unwrapped_processed_return_type)
-            {
-                return Err(MascalError {
-                    error_type: MascalErrorType::RuntimeError,
-                    character: 0,
-                    line: 0,
-                    source: format!(
-                        "Expected value of type {} to be returned, but returned {}",
-                        &processed_return_type.unwrap().as_string(),
-                        value.as_type_string()?
-                    ),
-                });
-            }
+            let value: MascalValue = check_returned_value(value, &processed_return_type, span)?;
             notify_mutable_params(mutable_parameters, scoped_variable_table, exec_data);
             return Ok(value);
         }
     }
-    if processed_return_type.clone().is_some() {
-        return Err(MascalError {
-            error_type: MascalErrorType::RuntimeError,
-            character: 0,
-            line: 0,
-            source: String::from("Expected a value to be returned, but nothing was returned"),
-        });
-    }
-
+    let value: MascalValue = check_returned_value(None, &processed_return_type, span)?;
     notify_mutable_params(mutable_parameters, scoped_variable_table, exec_data);
 
-    Ok(MascalValue::Null)
+    Ok(value)
 }
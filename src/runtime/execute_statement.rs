@@ -1,16 +1,24 @@
 use crate::defs::blocks::ScopedBlocks;
+use crate::defs::builtins::builtin_functions::NativeFunctionTable;
 use crate::defs::dynamic_int::IntegerNum;
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::expressions::MascalExpression;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
 use crate::defs::literal::MascalLiteral;
 use crate::defs::loop_flags::LoopFlags;
-use crate::defs::statements::MascalStatement;
+use crate::defs::operators::MascalUnaryOperators;
+use crate::defs::span::Span;
+use crate::defs::statements::{
+    MascalCaseBranch, MascalCasePattern, MascalStatement, MascalStatementKind,
+};
+use crate::defs::symbol_table::SymbolTable;
 use crate::defs::types::MascalType;
 use crate::runtime::ExecutionData;
-use crate::runtime::execute_declaration_statement::execute_declaration_statement;
+use crate::runtime::execute_declaration_statement::{assign_value, execute_declaration_statement};
 use crate::runtime::execute_expression::execute_expression;
+use crate::runtime::io_streams::SharedIO;
 use crate::runtime::values::MascalValue;
 use crate::runtime::variable_table::{VariableData, VariableTable};
+use crate::uninit_cell_error;
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -20,6 +28,9 @@ pub struct SemanticContext {
     pub scoped_blocks: Rc<RefCell<Vec<ScopedBlocks>>>,
     pub function_name: Option<Rc<str>>,
     pub in_loop: bool,
+    pub io: SharedIO,
+    pub native_functions: NativeFunctionTable,
+    pub symbol_table: Rc<SymbolTable>,
 }
 
 impl SemanticContext {
@@ -32,6 +43,9 @@ impl SemanticContext {
             variable_table: semantic_context.variable_table.clone(),
             scoped_blocks: semantic_context.scoped_blocks.clone(),
             in_loop: semantic_context.in_loop,
+            io: semantic_context.io.clone(),
+            native_functions: semantic_context.native_functions.clone(),
+            symbol_table: semantic_context.symbol_table.clone(),
         })
     }
 
@@ -44,50 +58,77 @@ impl SemanticContext {
             variable_table: semantic_context.variable_table.clone(),
             scoped_blocks: semantic_context.scoped_blocks.clone(),
             in_loop: true,
+            io: semantic_context.io.clone(),
+            native_functions: semantic_context.native_functions.clone(),
+            symbol_table: semantic_context.symbol_table.clone(),
         })
     }
 }
 
 pub struct StatementResults {
-    pub return_value: Option<MascalValue>,
+    /* Set once the function returns, a bare RETURN is the only one leaving it without a value */
+    pub return_value: Option<Option<MascalValue>>,
     pub loop_flag: LoopFlags,
 }
 
-#[inline(always)]
-fn error_check_expression(
-    variable_table: Rc<RefCell<VariableTable>>,
-    mut val: MascalExpression,
-    variable_data: &VariableData,
-    variable: &String,
-    scoped_blocks: &mut Vec<ScopedBlocks>,
-) -> Result<MascalValue, MascalError> {
-    if let MascalExpression::Literal(literal) = &val {
-        if let MascalLiteral::Float(v) = literal {
-            if *variable_data.atomic_variable_type == MascalType::Integer {
-                val = MascalExpression::Literal(MascalLiteral::Integer(IntegerNum::new(
+/*
+ Literal bounds of a FOR loop get converted to the loop variable's numeric type beforehand, so
+ writing 1 for a FLOAT variable (or 1.0 for an INTEGER one) is fine
+*/
+pub(crate) fn coerce_for_bound(
+    val: MascalExpression,
+    atomic_variable_type: &MascalType,
+) -> MascalExpression {
+    let span: Span = val.span;
+    match &val.kind {
+        MascalExpressionKind::Literal(MascalLiteral::Float(v))
+            if *atomic_variable_type == MascalType::Integer =>
+        {
+            MascalExpression::new(
+                MascalExpressionKind::Literal(MascalLiteral::Integer(IntegerNum::new(
                     v.round() as i128
-                )));
-            }
-        } else if let MascalLiteral::Integer(i) = literal {
-            if *variable_data.atomic_variable_type == MascalType::Float {
-                val = MascalExpression::Literal(MascalLiteral::Float(i.as_f64()));
-            }
+                ))),
+                span,
+            )
+        }
+
+        MascalExpressionKind::Literal(MascalLiteral::Integer(i))
+            if *atomic_variable_type == MascalType::Float =>
+        {
+            MascalExpression::new(
+                MascalExpressionKind::Literal(MascalLiteral::Float(i.as_f64())),
+                span,
+            )
         }
+
+        /* Counting down is written as a minus in front of the step, DOWNTO puts one there too */
+        MascalExpressionKind::Unary {
+            operator: MascalUnaryOperators::Minus,
+            value,
+        } => MascalExpression::new(
+            MascalExpressionKind::Unary {
+                operator: MascalUnaryOperators::Minus,
+                value: Box::new(coerce_for_bound(*value.clone(), atomic_variable_type)),
+            },
+            span,
+        ),
+
+        _ => val,
     }
-    let val_num: MascalValue = execute_expression(
-        val,
-        &mut ExecutionData {
-            variable_table: Some(variable_table),
-            scoped_blocks,
-        },
-    )?;
-    match &val_num {
+}
+
+pub(crate) fn check_for_bound(
+    val_num: &MascalValue,
+    atomic_variable_type: &MascalType,
+    variable: &str,
+    span: Span,
+) -> Result<(), MascalError> {
+    match val_num {
         MascalValue::Integer(_) => {
-            if *variable_data.atomic_variable_type != MascalType::Integer {
+            if *atomic_variable_type != MascalType::Integer {
                 return Err(MascalError {
                     error_type: MascalErrorType::TypeError,
-                    character: 0,
-                    line: 0,
+                    span: Some(span),
                     source: format!(
                         "Variable {:?} is not assigned as an integer(dynamics not supported)",
                         variable
@@ -96,12 +137,25 @@ fn error_check_expression(
             }
         }
 
+        MascalValue::Enum(enum_value) => {
+            if !matches!(atomic_variable_type, MascalType::Enum(name, _) if *name == enum_value.name)
+            {
+                return Err(MascalError {
+                    error_type: MascalErrorType::TypeError,
+                    span: Some(span),
+                    source: format!(
+                        "Variable {:?} is not assigned as the enum {}",
+                        variable, enum_value.name
+                    ),
+                });
+            }
+        }
+
         MascalValue::Float(_) => {
-            if *variable_data.atomic_variable_type != MascalType::Float {
+            if *atomic_variable_type != MascalType::Float {
                 return Err(MascalError {
                     error_type: MascalErrorType::TypeError,
-                    character: 0,
-                    line: 0,
+                    span: Some(span),
                     source: format!(
                         "Variable {:?} is not assigned as an float(dynamics not supported)",
                         variable
@@ -113,8 +167,7 @@ fn error_check_expression(
         _ => {
             return Err(MascalError {
                 error_type: MascalErrorType::TypeError,
-                character: 0,
-                line: 0,
+                span: Some(span),
                 source: format!(
                     "Unsupported type used in for loop statement(variable {:?})",
                     variable
@@ -122,30 +175,395 @@ fn error_check_expression(
             });
         }
     }
+    Ok(())
+}
 
+#[inline(always)]
+fn error_check_expression(
+    val: MascalExpression,
+    bound_type: &MascalType,
+    variable: &str,
+    semantic_context: &SemanticContext,
+) -> Result<MascalValue, MascalError> {
+    let val: MascalExpression = coerce_for_bound(val, bound_type);
+    let span: Span = val.span;
+    let val_num: MascalValue = evaluate_in_context(val, semantic_context)?;
+    check_for_bound(&val_num, bound_type, variable, span)?;
     Ok(val_num)
 }
 
+/* A step of zero would never get the loop anywhere, so it is refused before the first iteration */
+pub(crate) fn zero_step_error(span: Span) -> MascalError {
+    MascalError {
+        error_type: MascalErrorType::ValueError,
+        span: Some(span),
+        source: String::from("The step of a FOR loop cannot be zero"),
+    }
+}
+
+/* Runs a block of statements, stopping early once one returns or breaks out of a loop */
+fn execute_block(
+    statements: Box<[MascalStatement]>,
+    semantic_context: &Rc<SemanticContext>,
+) -> Result<StatementResults, MascalError> {
+    for stmt in statements {
+        let statement_results: StatementResults = execute_statement(
+            stmt,
+            SemanticContext::create_from(
+                semantic_context.clone(),
+                semantic_context.function_name.clone(),
+            ),
+        )?;
+        if statement_results.return_value.is_some()
+            || statement_results.loop_flag != LoopFlags::None
+        {
+            return Ok(statement_results);
+        }
+    }
+    Ok(StatementResults {
+        return_value: None,
+        loop_flag: LoopFlags::None,
+    })
+}
+
+/*
+ The elements are gathered before the loop starts, so pushing to the array from inside the loop
+ doesn't make it go on forever. Strings are looped over one character at a time
+*/
+fn execute_for_each(
+    variable: String,
+    index_variable: Option<String>,
+    iterable: MascalExpression,
+    statements: Vec<MascalStatement>,
+    label: Option<&str>,
+    span: Span,
+    semantic_context: &Rc<SemanticContext>,
+) -> Result<StatementResults, MascalError> {
+    let iterable_span: Span = iterable.span;
+    let value: MascalValue = evaluate_in_context(iterable, semantic_context)?;
+    let elements: Vec<Rc<RefCell<Option<MascalValue>>>> = match value {
+        MascalValue::StaticArray(cells) => cells.into_vec(),
+        MascalValue::DynamicArray(cells) => cells,
+        MascalValue::String(string) => string
+            .chars()
+            .map(|char| {
+                Rc::new(RefCell::new(Some(MascalValue::String(Arc::from(
+                    char.to_string(),
+                )))))
+            })
+            .collect(),
+        other => {
+            return Err(MascalError {
+                error_type: MascalErrorType::TypeError,
+                span: Some(iterable_span),
+                source: format!(
+                    "Expected an array or a string to loop over but got {}",
+                    other.as_type_string()?
+                ),
+            });
+        }
+    };
+
+    for (index, element) in elements.iter().enumerate() {
+        let Some(element) = element.borrow().clone() else {
+            uninit_cell_error!();
+        };
+        assign_value(
+            &semantic_context.variable_table,
+            variable.clone(),
+            span,
+            || Ok(element),
+        )?;
+        if let Some(index_variable) = &index_variable {
+            assign_value(
+                &semantic_context.variable_table,
+                index_variable.clone(),
+                span,
+                || Ok(MascalValue::Integer(IntegerNum::new(index as i128))),
+            )?;
+        }
+        for statement in &statements {
+            let statement_results: StatementResults = execute_statement(
+                statement.clone(),
+                SemanticContext::create_for_loop_from(
+                    semantic_context.clone(),
+                    semantic_context.function_name.clone(),
+                ),
+            )?;
+            match statement_results.loop_flag {
+                LoopFlags::Break(..) if statement_results.loop_flag.is_meant_for(label) => {
+                    return Ok(StatementResults {
+                        return_value: None,
+                        loop_flag: LoopFlags::None,
+                    });
+                }
+                LoopFlags::Continue(..) if statement_results.loop_flag.is_meant_for(label) => {
+                    break;
+                }
+                LoopFlags::None if statement_results.return_value.is_none() => {}
+                _ => return Ok(statement_results),
+            }
+        }
+    }
+    Ok(StatementResults {
+        return_value: None,
+        loop_flag: LoopFlags::None,
+    })
+}
+
+/* CONTINUE skips straight to the condition, it gets checked once the block has ran every time */
+fn execute_repeat(
+    statements: Vec<MascalStatement>,
+    condition: MascalExpression,
+    is_until: bool,
+    label: Option<&str>,
+    semantic_context: &Rc<SemanticContext>,
+) -> Result<StatementResults, MascalError> {
+    loop {
+        for statement in &statements {
+            let statement_results: StatementResults = execute_statement(
+                statement.clone(),
+                SemanticContext::create_for_loop_from(
+                    semantic_context.clone(),
+                    semantic_context.function_name.clone(),
+                ),
+            )?;
+            match statement_results.loop_flag {
+                LoopFlags::Break(..) if statement_results.loop_flag.is_meant_for(label) => {
+                    return Ok(StatementResults {
+                        return_value: None,
+                        loop_flag: LoopFlags::None,
+                    });
+                }
+                LoopFlags::Continue(..) if statement_results.loop_flag.is_meant_for(label) => {
+                    break;
+                }
+                LoopFlags::None if statement_results.return_value.is_none() => {}
+                _ => return Ok(statement_results),
+            }
+        }
+        let condition_span: Span = condition.span;
+        match evaluate_in_context(condition.clone(), semantic_context)? {
+            MascalValue::Boolean(value) if value == is_until => break,
+            MascalValue::Boolean(..) => {}
+            value => {
+                return Err(MascalError {
+                    span: Some(condition_span),
+                    error_type: MascalErrorType::RuntimeError,
+                    source: format!(
+                        "Expected a boolean variable on the condition but got {:?}",
+                        value.as_string()?
+                    ),
+                });
+            }
+        }
+    }
+    Ok(StatementResults {
+        return_value: None,
+        loop_flag: LoopFlags::None,
+    })
+}
+
+/* Hands the context over to code that works on execution data rather than a semantic context */
+fn with_execution_data<T>(
+    semantic_context: &SemanticContext,
+    action: impl FnOnce(&mut ExecutionData) -> Result<T, MascalError>,
+) -> Result<T, MascalError> {
+    action(&mut ExecutionData {
+        variable_table: Some(semantic_context.variable_table.clone()),
+        scoped_blocks: &semantic_context.scoped_blocks.borrow(),
+        io: semantic_context.io.clone(),
+        native_functions: semantic_context.native_functions.clone(),
+        symbol_table: semantic_context.symbol_table.clone(),
+    })
+}
+
+fn evaluate_in_context(
+    expression: MascalExpression,
+    semantic_context: &SemanticContext,
+) -> Result<MascalValue, MascalError> {
+    with_execution_data(semantic_context, |exec_data| {
+        execute_expression(expression, exec_data)
+    })
+}
+
+/*
+ The value being matched is only worked out once, the cases are then tried from top to bottom
+ and only the first one with a fitting pattern gets to run
+*/
+fn execute_match(
+    subject: MascalExpression,
+    cases: Vec<MascalCaseBranch>,
+    default: Option<Box<[MascalStatement]>>,
+    semantic_context: &Rc<SemanticContext>,
+) -> Result<StatementResults, MascalError> {
+    let subject: MascalValue = evaluate_in_context(subject, semantic_context)?;
+    for case in cases {
+        let mut is_matching: bool = false;
+        for pattern in case.patterns {
+            if matches_case_pattern(&subject, pattern, semantic_context)? {
+                is_matching = true;
+                break;
+            }
+        }
+        if is_matching {
+            return execute_block(case.statements, semantic_context);
+        }
+    }
+    if let Some(default) = default {
+        return execute_block(default, semantic_context);
+    }
+    Ok(StatementResults {
+        return_value: None,
+        loop_flag: LoopFlags::None,
+    })
+}
+
+fn matches_case_pattern(
+    subject: &MascalValue,
+    pattern: MascalCasePattern,
+    semantic_context: &Rc<SemanticContext>,
+) -> Result<bool, MascalError> {
+    match pattern {
+        MascalCasePattern::Value(value) => {
+            let value: MascalValue = evaluate_in_context(value, semantic_context)?;
+            Ok(matches!(
+                MascalValue::equals(subject, &value)?,
+                MascalValue::Boolean(true)
+            ))
+        }
+
+        /* A value that can't be compared with the ends of the range simply doesn't fall in it */
+        MascalCasePattern::Range { from, to } => {
+            let from: MascalValue = evaluate_in_context(from, semantic_context)?;
+            let to: MascalValue = evaluate_in_context(to, semantic_context)?;
+            Ok(matches!(
+                (
+                    MascalValue::less_than_or_equal(&from, subject),
+                    MascalValue::less_than_or_equal(subject, &to)
+                ),
+                (
+                    Ok(MascalValue::Boolean(true)),
+                    Ok(MascalValue::Boolean(true))
+                )
+            ))
+        }
+
+        /* The type is compared just like TYPEOF would see it, DYNAMIC lets anything through */
+        MascalCasePattern::Type(pattern_type) => {
+            let span: Span = pattern_type.span;
+            match evaluate_in_context(pattern_type, semantic_context)? {
+                MascalValue::Type(MascalType::Dynamic) => Ok(true),
+                MascalValue::Type(pattern_type) => Ok(subject
+                    .as_mascal_type()
+                    .is_ok_and(|subject_type| subject_type == pattern_type)),
+                other => Err(MascalError {
+                    error_type: MascalErrorType::TypeError,
+                    span: Some(span),
+                    source: format!(
+                        "Expected a type for the case to check against but got {}",
+                        other.as_type_string()?
+                    ),
+                }),
+            }
+        }
+    }
+}
+
+/* Stores the message of a caught error into the variable named on the CATCH branch */
+fn bind_caught_error(
+    variable: &str,
+    error: &MascalError,
+    span: Span,
+    semantic_context: &Rc<SemanticContext>,
+) -> Result<(), MascalError> {
+    let mut borrowed_vartable = semantic_context.variable_table.borrow_mut();
+    let variable_data: &mut VariableData =
+        borrowed_vartable
+            .get_mut(variable)
+            .ok_or_else(|| MascalError {
+                error_type: MascalErrorType::RuntimeError,
+                span: Some(span),
+                source: format!("Variable {:?} not found", variable),
+            })?;
+    if variable_data.is_constant {
+        return Err(MascalError {
+            error_type: MascalErrorType::RuntimeError,
+            span: Some(span),
+            source: format!(
+                "Cannot store the caught error in {:?} as it is a constant",
+                variable
+            ),
+        });
+    }
+    let accepts_string: bool = matches!(
+        *variable_data.atomic_variable_type,
+        MascalType::String | MascalType::Dynamic
+    );
+    if !accepts_string || !variable_data.array_dimensions.is_empty() {
+        return Err(MascalError {
+            error_type: MascalErrorType::TypeError,
+            span: Some(span),
+            source: format!(
+                "Expected {:?} to be a STRING or DYNAMIC variable to hold the caught error",
+                variable
+            ),
+        });
+    }
+    variable_data.value = Some(Rc::new(RefCell::new(MascalValue::String(Arc::from(
+        error.source.as_str(),
+    )))));
+    Ok(())
+}
+
+/* Builds the error a THROW raises, the message it was given has to be a string */
+pub(crate) fn thrown_error(
+    error_type: MascalErrorType,
+    message: MascalValue,
+    span: Span,
+    message_span: Span,
+) -> MascalError {
+    match message {
+        MascalValue::String(message) => MascalError {
+            error_type,
+            span: Some(span),
+            source: message.to_string(),
+        },
+        other => MascalError {
+            error_type: MascalErrorType::TypeError,
+            span: Some(message_span),
+            source: format!(
+                "Expected a string message for the throw statement but got {:?}",
+                other.as_string().unwrap_or_default()
+            ),
+        },
+    }
+}
+
 pub fn execute_statement(
     statement: MascalStatement,
     semantic_context: Rc<SemanticContext>,
+) -> Result<StatementResults, MascalError> {
+    let span: Span = statement.span;
+    execute_statement_kind(statement.kind, span, semantic_context)
+        .map_err(|err| err.located_at(span))
+}
+
+fn execute_statement_kind(
+    statement: MascalStatementKind,
+    span: Span,
+    semantic_context: Rc<SemanticContext>,
 ) -> Result<StatementResults, MascalError> {
     match statement {
-        MascalStatement::ConditionalStatement(branches) => {
+        MascalStatementKind::ConditionalStatement(branches) => {
             for branch in branches {
                 let cond: bool = if let Some(cond) = branch.condition {
-                    let value: MascalValue = execute_expression(
-                        cond,
-                        &mut ExecutionData {
-                            variable_table: Some(semantic_context.variable_table.clone()),
-                            scoped_blocks: semantic_context.scoped_blocks.borrow_mut().as_mut(),
-                        },
-                    )?;
+                    let cond_span: Span = cond.span;
+                    let value: MascalValue = evaluate_in_context(cond, &semantic_context)?;
                     match value {
                         MascalValue::Boolean(b) => Ok(b),
                         _ => Err(MascalError {
-                            line: 0,
-                            character: 0,
+                            span: Some(cond_span),
                             error_type: MascalErrorType::RuntimeError,
                             source: format!(
                                 "Expected a boolean variable on the condition but got {:?}",
@@ -178,7 +596,10 @@ pub fn execute_statement(
                 break;
             }
         }
-        MascalStatement::While(condition) => {
+        MascalStatementKind::While {
+            branch: condition,
+            label,
+        } => {
             let cond_expr: MascalExpression = condition.condition.unwrap();
             while {
                 /*
@@ -186,22 +607,15 @@ pub fn execute_statement(
                  However, if I try to use references or even smart pointers, it would require sweeping
                  changes to the entire codebase which I am not willing to do
                 */
-                let value: MascalValue = execute_expression(
-                    cond_expr.clone(),
-                    &mut ExecutionData {
-                        variable_table: Some(semantic_context.variable_table.clone()),
-                        scoped_blocks: semantic_context.scoped_blocks.borrow_mut().as_mut(),
-                    },
-                )?;
+                let value: MascalValue = evaluate_in_context(cond_expr.clone(), &semantic_context)?;
                 match value {
                     MascalValue::Boolean(b) => Ok(b),
                     _ => Err(MascalError {
-                        line: 0,
-                        character: 0,
+                        span: Some(cond_expr.span),
                         error_type: MascalErrorType::RuntimeError,
                         source: format!(
                             "Expected a boolean variable on the condition but got {:?}",
-                            value
+                            value.as_string()?
                         ),
                     }),
                 }
@@ -214,26 +628,33 @@ pub fn execute_statement(
                             semantic_context.function_name.clone(),
                         ),
                     )?;
-                    if statement_results.loop_flag == LoopFlags::Break {
-                        return Ok(StatementResults {
-                            return_value: None,
-                            loop_flag: LoopFlags::None,
-                        });
-                    } else if statement_results.loop_flag == LoopFlags::Continue {
-                        break;
-                    }
-                    if statement_results.return_value.is_some() {
-                        return Ok(statement_results);
+                    match statement_results.loop_flag {
+                        LoopFlags::Break(..)
+                            if statement_results.loop_flag.is_meant_for(label.as_deref()) =>
+                        {
+                            return Ok(StatementResults {
+                                return_value: None,
+                                loop_flag: LoopFlags::None,
+                            });
+                        }
+                        LoopFlags::Continue(..)
+                            if statement_results.loop_flag.is_meant_for(label.as_deref()) =>
+                        {
+                            break;
+                        }
+                        LoopFlags::None if statement_results.return_value.is_none() => {}
+                        _ => return Ok(statement_results),
                     }
                 }
             }
         }
-        MascalStatement::For {
+        MascalStatementKind::For {
             variable,
             from,
             to,
             step,
             statements,
+            label,
         } => {
             let variable_metadata = {
                 let borrowed_vartable = semantic_context.variable_table.borrow();
@@ -242,8 +663,7 @@ pub fn execute_statement(
                         .get(variable.as_str())
                         .ok_or_else(|| MascalError {
                             error_type: MascalErrorType::RuntimeError,
-                            character: 0,
-                            line: 0,
+                            span: Some(span),
                             source: format!("Variable {:?} not found", variable),
                         })?;
                 (
@@ -256,53 +676,52 @@ pub fn execute_statement(
                 )
             };
             let (
-                variable_data,
+                _,
                 is_constant,
                 is_nullable,
                 array_dimensions,
                 is_dynamic_array,
                 atomic_variable_type,
             ) = variable_metadata;
-            let from_num: MascalValue = error_check_expression(
-                semantic_context.variable_table.clone(),
-                from,
-                &variable_data,
-                &variable,
-                semantic_context.scoped_blocks.borrow_mut().as_mut(),
-            )?;
+            let from_num: MascalValue =
+                error_check_expression(from, &atomic_variable_type, &variable, &semantic_context)?;
 
-            let to_num: MascalValue = error_check_expression(
-                semantic_context.variable_table.clone(),
-                to,
-                &variable_data,
-                &variable,
-                semantic_context.scoped_blocks.borrow_mut().as_mut(),
-            )?;
+            let to_num: MascalValue =
+                error_check_expression(to, &atomic_variable_type, &variable, &semantic_context)?;
 
-            let step_num: MascalValue = error_check_expression(
-                semantic_context.variable_table.clone(),
-                step,
-                &variable_data,
-                &variable,
-                semantic_context.scoped_blocks.borrow_mut().as_mut(),
-            )?;
+            /* Looping over an enum walks its variants, the step still counts them as integers */
+            let step_type: &MascalType = match &*atomic_variable_type {
+                MascalType::Enum(..) => &MascalType::Integer,
+                atomic_type => atomic_type,
+            };
+            let step_span: Span = step.span;
+            let step_num: MascalValue =
+                error_check_expression(step, step_type, &variable, &semantic_context)?;
 
             match (&from_num, &to_num, &step_num) {
-                (MascalValue::Integer(..), ..) => {
+                (MascalValue::Integer(..) | MascalValue::Enum(..), ..) => {
                     let int_to_num: i128 = to_num.extract_as_int().unwrap();
                     let int_step_num: i128 = step_num.extract_as_int().unwrap();
+                    if int_step_num == 0 {
+                        return Err(zero_step_error(step_span));
+                    }
                     let mut curr: i128 = from_num.extract_as_int().unwrap();
                     let varname: Rc<str> = Rc::from(variable);
-                    while curr <= int_to_num {
+                    while (int_step_num > 0 && curr <= int_to_num)
+                        || (int_step_num < 0 && curr >= int_to_num)
+                    {
                         {
                             let mut mutable_borrow_vartable =
                                 semantic_context.variable_table.borrow_mut();
                             mutable_borrow_vartable.insert(
                                 varname.clone(),
                                 VariableData {
-                                    value: Some(Rc::new(RefCell::new(MascalValue::Integer(
-                                        IntegerNum::new(curr),
-                                    )))),
+                                    value: Some(Rc::new(RefCell::new(match &from_num {
+                                        MascalValue::Enum(enum_value) => {
+                                            MascalValue::Enum(enum_value.with_index(curr as usize))
+                                        }
+                                        _ => MascalValue::Integer(IntegerNum::new(curr)),
+                                    }))),
                                     is_constant,
                                     is_nullable,
                                     array_dimensions: array_dimensions.clone(),
@@ -319,17 +738,27 @@ pub fn execute_statement(
                                     semantic_context.function_name.clone(),
                                 ),
                             )?;
-                            if statement_results.loop_flag == LoopFlags::Break {
-                                return Ok(StatementResults {
-                                    return_value: None,
-                                    loop_flag: LoopFlags::None,
-                                });
-                            } else if statement_results.loop_flag == LoopFlags::Continue {
-                                break;
+                            match statement_results.loop_flag {
+                                LoopFlags::Break(..)
+                                    if statement_results
+                                        .loop_flag
+                                        .is_meant_for(label.as_deref()) =>
+                                {
+                                    return Ok(StatementResults {
+                                        return_value: None,
+                                        loop_flag: LoopFlags::None,
+                                    });
+                                }
+                                LoopFlags::Continue(..)
+                                    if statement_results
+                                        .loop_flag
+                                        .is_meant_for(label.as_deref()) =>
+                                {
+                                    break;
+                                }
+                                LoopFlags::None if statement_results.return_value.is_none() => {}
+                                _ => return Ok(statement_results),
                             }
-                            if statement_results.return_value.is_some() {
-                                return Ok(statement_results);
-                            };
                         }
                         curr += int_step_num;
                     }
@@ -338,9 +767,14 @@ pub fn execute_statement(
                 (MascalValue::Float(..), ..) => {
                     let float_to_num: f64 = to_num.extract_as_float().unwrap();
                     let float_step_num: f64 = step_num.extract_as_float().unwrap();
+                    if float_step_num == 0.0 {
+                        return Err(zero_step_error(step_span));
+                    }
                     let mut curr: f64 = from_num.extract_as_float().unwrap();
                     let varname: Rc<str> = Rc::from(variable);
-                    while curr <= float_to_num {
+                    while (float_step_num > 0.0 && curr <= float_to_num)
+                        || (float_step_num < 0.0 && curr >= float_to_num)
+                    {
                         {
                             let mut mutable_borrow_vartable =
                                 semantic_context.variable_table.borrow_mut();
@@ -364,9 +798,27 @@ pub fn execute_statement(
                                     semantic_context.function_name.clone(),
                                 ),
                             )?;
-                            if statement_results.return_value.is_some() {
-                                return Ok(statement_results);
-                            };
+                            match statement_results.loop_flag {
+                                LoopFlags::Break(..)
+                                    if statement_results
+                                        .loop_flag
+                                        .is_meant_for(label.as_deref()) =>
+                                {
+                                    return Ok(StatementResults {
+                                        return_value: None,
+                                        loop_flag: LoopFlags::None,
+                                    });
+                                }
+                                LoopFlags::Continue(..)
+                                    if statement_results
+                                        .loop_flag
+                                        .is_meant_for(label.as_deref()) =>
+                                {
+                                    break;
+                                }
+                                LoopFlags::None if statement_results.return_value.is_none() => {}
+                                _ => return Ok(statement_results),
+                            }
                         }
                         curr += float_step_num;
                     }
@@ -381,40 +833,108 @@ pub fn execute_statement(
                 loop_flag: LoopFlags::None,
             });
         }
-        MascalStatement::ExpressionStatement(expression) => {
-            execute_expression(
-                expression,
-                &mut ExecutionData {
-                    variable_table: Some(semantic_context.variable_table.clone()),
-                    scoped_blocks: &*semantic_context.scoped_blocks.borrow(),
-                },
-            )?;
-        }
-        MascalStatement::Declaration { variable, value } => {
-            return execute_declaration_statement(
+        MascalStatementKind::ForEach {
+            variable,
+            index_variable,
+            iterable,
+            statements,
+            label,
+        } => {
+            return execute_for_each(
                 variable,
-                value,
-                semantic_context.variable_table.clone(),
-                semantic_context.scoped_blocks.borrow_mut().as_mut(),
+                index_variable,
+                iterable,
+                statements,
+                label.as_deref(),
+                span,
+                &semantic_context,
+            );
+        }
+        MascalStatementKind::Repeat {
+            statements,
+            condition,
+            is_until,
+            label,
+        } => {
+            return execute_repeat(
+                statements,
+                condition,
+                is_until,
+                label.as_deref(),
+                &semantic_context,
             );
         }
-        MascalStatement::Throw {
+        MascalStatementKind::Match {
+            subject,
+            cases,
+            default,
+        } => {
+            return execute_match(subject, cases, default, &semantic_context);
+        }
+        MascalStatementKind::ExpressionStatement(expression) => {
+            evaluate_in_context(expression, &semantic_context)?;
+        }
+        MascalStatementKind::Declaration { variable, value } => {
+            return with_execution_data(&semantic_context, |exec_data| {
+                execute_declaration_statement(
+                    variable,
+                    value,
+                    semantic_context.function_name.as_deref(),
+                    exec_data,
+                )
+            });
+        }
+        MascalStatementKind::Try {
+            statements,
+            catches,
+            finally,
+        } => {
+            let mut outcome: Result<StatementResults, MascalError> =
+                execute_block(statements, &semantic_context);
+            if let Err(err) = &outcome {
+                let caught = catches.into_iter().find(|catch| match &catch.error_type {
+                    Some(error_type) => *error_type == err.error_type,
+                    None => true,
+                });
+                if let Some(catch) = caught {
+                    /* Failing to store the error still has to leave FINALLY a chance to run */
+                    outcome = match &catch.variable {
+                        Some(variable) => {
+                            bind_caught_error(variable, err, catch.span, &semantic_context)
+                        }
+                        None => Ok(()),
+                    }
+                    .and_then(|_| execute_block(catch.statements, &semantic_context));
+                }
+            }
+
+            /*
+             The cleanup code always gets to run, and whatever it ends with (an error, a return
+             or a break) takes over from what the try or catch blocks ended with
+            */
+            if let Some(finally) = finally {
+                let finally_results: StatementResults = execute_block(finally, &semantic_context)?;
+                if finally_results.return_value.is_some()
+                    || finally_results.loop_flag != LoopFlags::None
+                {
+                    return Ok(finally_results);
+                }
+            }
+            return outcome;
+        }
+        MascalStatementKind::Throw {
             error_type,
             message,
         } => {
-            return Err(MascalError {
-                error_type,
-                character: 0,
-                line: 0,
-                source: message,
-            });
+            let message_span: Span = message.span;
+            let value: MascalValue = evaluate_in_context(message, &semantic_context)?;
+            return Err(thrown_error(error_type, value, span, message_span));
         }
-        MascalStatement::Break => {
+        MascalStatementKind::Break(label) => {
             if !semantic_context.in_loop {
                 return Err(MascalError {
                     error_type: MascalErrorType::ContextError,
-                    character: 0,
-                    line: 0,
+                    span: Some(span),
                     source: String::from(
                         "Break statement is not allowed outside of a loop statement",
                     ),
@@ -422,16 +942,15 @@ pub fn execute_statement(
             }
             return Ok(StatementResults {
                 return_value: None,
-                loop_flag: LoopFlags::Break,
+                loop_flag: LoopFlags::Break(label),
             });
         }
 
-        MascalStatement::Continue => {
+        MascalStatementKind::Continue(label) => {
             if !semantic_context.in_loop {
                 return Err(MascalError {
                     error_type: MascalErrorType::ContextError,
-                    character: 0,
-                    line: 0,
+                    span: Some(span),
                     source: String::from(
                         "Continue statement is not allowed outside of a loop statement",
                     ),
@@ -439,7 +958,25 @@ pub fn execute_statement(
             }
             return Ok(StatementResults {
                 return_value: None,
-                loop_flag: LoopFlags::Continue,
+                loop_flag: LoopFlags::Continue(label),
+            });
+        }
+
+        MascalStatementKind::Return(value) => {
+            if semantic_context.function_name.is_none() {
+                return Err(MascalError {
+                    error_type: MascalErrorType::ContextError,
+                    span: Some(span),
+                    source: String::from("Return statement is not allowed outside of a function"),
+                });
+            }
+            let return_value: Option<MascalValue> = match value {
+                Some(expression) => Some(evaluate_in_context(expression, &semantic_context)?),
+                None => None,
+            };
+            return Ok(StatementResults {
+                return_value: Some(return_value),
+                loop_flag: LoopFlags::None,
             });
         }
     };
@@ -4,7 +4,7 @@ use crate::defs::expressions::MascalExpression;
 use crate::defs::types::{MascalType, MascalUnprocessedType, to_processed_type};
 use crate::runtime::ExecutionData;
 use crate::runtime::execute_expression::execute_expression;
-use crate::runtime::values::MascalValue;
+use crate::runtime::values::{MascalEnumValue, MascalValue};
 use crate::{from_string_to_array_impl, type_cast_array_impl};
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -17,7 +17,10 @@ pub fn execute_typecast(
     exec_data: &mut ExecutionData,
 ) -> Result<MascalValue, MascalError> {
     let value: MascalValue = execute_expression(arguments[0].clone(), exec_data)?;
-    execute_processed_typecast(to_processed_type(*function.clone())?, value)
+    let target_type: MascalType = exec_data
+        .symbol_table
+        .resolve_type(to_processed_type(*function.clone())?);
+    execute_processed_typecast(target_type, value)
 }
 
 #[inline(always)]
@@ -32,6 +35,47 @@ pub fn execute_processed_typecast(
 
         (MascalType::Float, MascalValue::Integer(i)) => Ok(MascalValue::Float(i.as_f64())),
 
+        (MascalType::Integer, MascalValue::Enum(enum_value)) => Ok(MascalValue::Integer(
+            IntegerNum::new(enum_value.index as i128),
+        )),
+
+        /* Enum variants are counted from zero, in the order they got declared in */
+        (MascalType::Enum(name, variants), MascalValue::Integer(i)) => {
+            let index: i128 = i.to_i128();
+            if index < 0 || index >= variants.len() as i128 {
+                return Err(MascalError {
+                    error_type: MascalErrorType::ValueError,
+                    span: None,
+                    source: format!(
+                        "The enum {} has no variant at the position {} (it only has {})",
+                        name,
+                        index,
+                        variants.len()
+                    ),
+                });
+            }
+            Ok(MascalValue::Enum(MascalEnumValue {
+                name,
+                variants,
+                index: index as usize,
+            }))
+        }
+
+        (MascalType::Enum(name, variants), MascalValue::String(s)) => {
+            match variants.iter().position(|variant| **variant == *s) {
+                Some(index) => Ok(MascalValue::Enum(MascalEnumValue {
+                    name,
+                    variants,
+                    index,
+                })),
+                None => Err(MascalError {
+                    error_type: MascalErrorType::ValueError,
+                    span: None,
+                    source: format!("The enum {} has no variant named {:?}", name, s),
+                }),
+            }
+        }
+
         (MascalType::String, v) => Ok(MascalValue::String(Arc::from(v.as_string()?))),
 
         (MascalType::Integer, MascalValue::Boolean(b)) => {
@@ -76,8 +120,7 @@ pub fn execute_processed_typecast(
             }
             Err(MascalError {
                 error_type: MascalErrorType::TypeError,
-                line: 0,
-                character: 0,
+                span: None,
                 source: format!(
                     "Unable to cast {:?} into the type {:?}",
                     v.as_string()?,
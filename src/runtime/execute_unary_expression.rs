@@ -13,7 +13,14 @@ pub fn execute_unary_expression(
     exec_data: &mut ExecutionData,
 ) -> Result<MascalValue, MascalError> {
     let target_value: MascalValue = execute_expression(target, exec_data)?;
+    apply_unary_operator(operator, target_value)
+}
 
+#[inline(always)]
+pub(crate) fn apply_unary_operator(
+    operator: MascalUnaryOperators,
+    target_value: MascalValue,
+) -> Result<MascalValue, MascalError> {
     match operator {
         MascalUnaryOperators::Not => MascalValue::not(&target_value),
         MascalUnaryOperators::Minus => MascalValue::negate(&target_value),
@@ -0,0 +1,89 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io;
+use std::io::{BufRead, Write};
+use std::rc::Rc;
+
+/*
+ Everything WRITE and READ do goes through here, so whoever runs the program decides where the
+ text actually ends up (the terminal, a buffer in memory, a socket... etc.)
+*/
+pub trait MascalIO {
+    fn write(&mut self, text: &str) -> io::Result<()>;
+
+    fn read_line(&mut self) -> io::Result<String>;
+}
+
+pub type SharedIO = Rc<RefCell<dyn MascalIO>>;
+
+#[derive(Debug, Default)]
+pub struct StandardIO;
+
+impl MascalIO for StandardIO {
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(text.as_bytes())?;
+        stdout.flush()
+    }
+
+    /*
+     Running out of input is an error just like it is for the BufferedIO, otherwise READ would
+     happily hand back an empty line once stdin is closed
+    */
+    fn read_line(&mut self) -> io::Result<String> {
+        let mut input: String = String::new();
+        if io::stdin().lock().read_line(&mut input)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "no more input lines to read",
+            ));
+        }
+        if input.ends_with('\n') {
+            input.pop();
+            if input.ends_with('\r') {
+                input.pop();
+            }
+        }
+        Ok(input)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BufferedIO {
+    input: VecDeque<String>,
+    output: String,
+}
+
+impl BufferedIO {
+    pub fn new(input: &str) -> Self {
+        BufferedIO {
+            input: input.lines().map(String::from).collect(),
+            output: String::new(),
+        }
+    }
+
+    pub fn push_input(&mut self, line: &str) {
+        self.input.push_back(line.to_string());
+    }
+
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    pub fn take_output(&mut self) -> String {
+        std::mem::take(&mut self.output)
+    }
+}
+
+impl MascalIO for BufferedIO {
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        self.output.push_str(text);
+        Ok(())
+    }
+
+    fn read_line(&mut self) -> io::Result<String> {
+        self.input.pop_front().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::UnexpectedEof, "no more input lines to read")
+        })
+    }
+}
@@ -1,6 +1,8 @@
 use crate::defs::dynamic_int::IntegerNum;
-use crate::defs::errors::MascalError;
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
 use crate::runtime::values::MascalValue;
+use crate::{index_array_impl, uninit_cell_error};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -30,6 +32,59 @@ pub fn make_array(dims: &[usize], dyns: &[bool]) -> MascalValue {
     MascalValue::StaticArray(slots.into_boxed_slice())
 }
 
+/*
+ Reads a single element out of an array value, kept apart from the expression evaluation so both
+ the tree-walker and the bytecode VM index arrays the exact same way
+*/
+pub fn index_array(
+    arr_value: MascalValue,
+    index_value: MascalValue,
+    is_dynamic: bool,
+    span: Span,
+) -> Result<MascalValue, MascalError> {
+    if let MascalValue::Map(map) = &arr_value {
+        if is_dynamic {
+            return Err(MascalError {
+                error_type: MascalErrorType::TypeError,
+                span: Some(span),
+                source: String::from("Maps can only be indexed with brackets '[' and ']'"),
+            });
+        }
+        return map.get(index_value).map_err(|err| err.located_at(span));
+    }
+    if !arr_value.is_array() {
+        return Err(MascalError {
+            error_type: MascalErrorType::TypeError,
+            span: Some(span),
+            source: String::from("Expected an array type but found instead an atomic type"),
+        });
+    }
+    let num: &IntegerNum = match &index_value {
+        MascalValue::Integer(i) => i,
+
+        _ => {
+            return Err(MascalError {
+                error_type: MascalErrorType::TypeError,
+                span: Some(span),
+                source: format!(
+                    "Expected an index type (integer) but got {:?}",
+                    index_value.as_type_string()?
+                ),
+            });
+        }
+    };
+    let mut num_val: i128 = num.to_i128();
+    match arr_value {
+        MascalValue::DynamicArray(elements) => {
+            index_array_impl!(elements, is_dynamic, num_val, true);
+        }
+        MascalValue::StaticArray(elements) => {
+            index_array_impl!(elements, is_dynamic, num_val, false);
+        }
+        _ => unreachable!(),
+    }
+}
+
 pub fn get_dimensions(
     val: &Option<MascalValue>,
     dimension: usize,
@@ -85,8 +140,7 @@ macro_rules! uninit_cell_error {
     () => {
         return Err(MascalError {
             error_type: MascalErrorType::ValueError,
-            line: 0,
-            character: 0,
+            span: None,
             source: String::from("Uninitialized cell in an array has been detected"),
         })
     };
@@ -200,8 +254,7 @@ macro_rules! index_array_impl {
         if $is_dynamic != $expected_is_dynamic {
             return Err(MascalError {
                 error_type: MascalErrorType::IndexError,
-                line: 0,
-                character: 0,
+                span: None,
                 source: format!(
                     "Attempting to access a {} array when it is a {} one",
                     if $is_dynamic { "dynamic" } else { "static" },
@@ -216,8 +269,7 @@ macro_rules! index_array_impl {
         if $num_val < 0 || $num_val >= $values.len() as i128 {
             return Err(MascalError {
                 error_type: MascalErrorType::IndexError,
-                line: 0,
-                character: 0,
+                span: None,
                 source: format!("Index is out of bounds for array size of {}", $values.len()),
             });
         }
@@ -248,8 +300,7 @@ macro_rules! array_check_assignment_impl {
         if $values.len() != expected_array_size {
             return Err(MascalError {
                 error_type: MascalErrorType::TypeError,
-                line: 0,
-                character: 0,
+                span: None,
                 source: format!(
                     "Mismatch between element size, expected an array of {} element(s) but got an array of {} element(s)",
                     expected_array_size,
@@ -260,8 +311,7 @@ macro_rules! array_check_assignment_impl {
         if expected_dynamic != $is_dynamic {
             return Err(MascalError {
                 error_type: MascalErrorType::TypeError,
-                line: 0,
-                character: 0,
+                span: None,
                 source: format!(
                     "Expected a {} array, but got a {} array instead",
                     if expected_dynamic {"dynamic"} else {"static"},
@@ -287,8 +337,7 @@ macro_rules! type_cast_array_impl {
                 if !is_dynamic_type && unwrapped_val.is_type_of(&deref_array_type) {
                     return Err(MascalError {
                         error_type: MascalErrorType::TypeError,
-                        character: 0,
-                        line: 0,
+                        span: None,
                         source: format!(
                             "Expected an array with type {:?} but got {:?}",
                             $array_type.as_string(),
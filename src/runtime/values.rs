@@ -1,11 +1,15 @@
 pub mod value_arithmetic_operations;
 pub mod value_boolean_operations;
 pub mod value_comparision_operations;
+pub mod value_map;
 mod value_utils;
 
 use crate::defs::dynamic_int::IntegerNum;
 use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::expressions::MascalExpression;
 use crate::defs::types::MascalType;
+use crate::runtime::values::value_map::MascalMap;
+use crate::runtime::variable_table::VariableTable;
 use crate::{
     as_mascal_atomic_type_array_impl, as_mascal_type_array_impl, as_string_array_impl,
     as_type_string_array_impl, atomic_type_array_impl, uninit_cell_error,
@@ -25,6 +29,96 @@ pub enum MascalValue {
     StaticArray(Box<[Rc<RefCell<Option<MascalValue>>>]>),
     DynamicArray(Vec<Rc<RefCell<Option<MascalValue>>>>),
     Type(MascalType),
+    Record(MascalRecord),
+    Map(MascalMap),
+    Enum(MascalEnumValue),
+    Function(MascalFunction),
+}
+
+/*
+ An instance of a record, its fields live in a variable table of their own which copies of the
+ value share (much like the cells of an array are shared)
+*/
+#[derive(Clone, Debug)]
+pub struct MascalRecord {
+    pub name: Arc<str>,
+    pub field_names: Rc<[Rc<str>]>,
+    pub fields: Rc<RefCell<VariableTable>>,
+}
+
+impl MascalRecord {
+    pub fn get_field(&self, field: &str) -> Option<MascalValue> {
+        self.fields
+            .borrow()
+            .get(field)
+            .and_then(|data| data.value.as_ref().map(|value| value.borrow().clone()))
+    }
+}
+
+/* A single variant of an enum, it carries every variant along so it can be converted on its own */
+#[derive(Clone, Debug)]
+pub struct MascalEnumValue {
+    pub name: Arc<str>,
+    pub variants: Arc<[Arc<str>]>,
+    pub index: usize,
+}
+
+impl MascalEnumValue {
+    pub fn variant(&self) -> &str {
+        &self.variants[self.index]
+    }
+
+    pub fn enum_type(&self) -> MascalType {
+        MascalType::Enum(self.name.clone(), self.variants.clone())
+    }
+
+    pub fn with_index(&self, index: usize) -> MascalEnumValue {
+        MascalEnumValue {
+            name: self.name.clone(),
+            variants: self.variants.clone(),
+            index,
+        }
+    }
+}
+
+/*
+ A function used as a value, either one referred to by its name (which covers the builtins and the
+ host's native functions too) or a lambda written inline
+*/
+#[derive(Clone, Debug)]
+pub enum MascalFunction {
+    Named(Arc<str>),
+    Lambda(Rc<MascalLambda>),
+}
+
+/*
+ The variables around a lambda are captured once it gets created, so it keeps seeing them as they
+ were back then no matter where it ends up being called from
+*/
+#[derive(Debug)]
+pub struct MascalLambda {
+    pub parameters: Box<[Rc<str>]>,
+    pub body: MascalExpression,
+    pub captured_variables: VariableTable,
+}
+
+impl MascalFunction {
+    pub fn is_equal(&self, other: &MascalFunction) -> bool {
+        match (self, other) {
+            (MascalFunction::Named(name1), MascalFunction::Named(name2)) => name1 == name2,
+            (MascalFunction::Lambda(lambda1), MascalFunction::Lambda(lambda2)) => {
+                Rc::ptr_eq(lambda1, lambda2)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn as_string(&self) -> String {
+        match self {
+            MascalFunction::Named(name) => name.to_string(),
+            MascalFunction::Lambda(lambda) => format!("FUNCTION({})", lambda.parameters.join(", ")),
+        }
+    }
 }
 
 fn is_expected_array_internal(
@@ -40,8 +134,7 @@ fn is_expected_array_internal(
                     if dynamics[curr] {
                         return Err(MascalError {
                             error_type: MascalErrorType::TypeError,
-                            line: 0,
-                            character: 0,
+                            span: None,
                             source: format!(
                                 "Expected a dynamic array with size {} element(s) but got a static array with size {} element(s)",
                                 sizes[curr],
@@ -51,8 +144,7 @@ fn is_expected_array_internal(
                     } else if sizes[curr] != values.len() {
                         return Err(MascalError {
                             error_type: MascalErrorType::TypeError,
-                            line: 0,
-                            character: 0,
+                            span: None,
                             source: format!(
                                 "Expected a static array with size {} element(s) but got a static array with size {} element(s)",
                                 sizes[curr],
@@ -63,8 +155,7 @@ fn is_expected_array_internal(
                 } else {
                     return Err(MascalError {
                         error_type: MascalErrorType::TypeError,
-                        line: 0,
-                        character: 0,
+                        span: None,
                         source: String::from(
                             "The current array type is deeper than initialized to be",
                         ),
@@ -85,8 +176,7 @@ fn is_expected_array_internal(
                     if !dynamics[curr] {
                         return Err(MascalError {
                             error_type: MascalErrorType::TypeError,
-                            line: 0,
-                            character: 0,
+                            span: None,
                             source: format!(
                                 "Expected a static array with size {} element(s) but got a dynamic array with size {} element(s)",
                                 sizes[curr],
@@ -97,8 +187,7 @@ fn is_expected_array_internal(
                 } else {
                     return Err(MascalError {
                         error_type: MascalErrorType::TypeError,
-                        line: 0,
-                        character: 0,
+                        span: None,
                         source: String::from(
                             "The current array type is deeper than initialized to be",
                         ),
@@ -118,8 +207,7 @@ fn is_expected_array_internal(
                 if sizes.is_empty() && curr >= 1 {
                     return Err(MascalError {
                         error_type: MascalErrorType::TypeError,
-                        line: 0,
-                        character: 0,
+                        span: None,
                         source: String::from(
                             "Cannot assign an array to a value that hasn't initialized an array size",
                         ),
@@ -128,8 +216,7 @@ fn is_expected_array_internal(
                 if curr < sizes.len() {
                     return Err(MascalError {
                         error_type: MascalErrorType::TypeError,
-                        line: 0,
-                        character: 0,
+                        span: None,
                         source: format!("Expected a {} but got an atomic type instead", {
                             let next_idx: usize = usize::min(curr + 1, sizes.len() - 1);
                             let current_size: usize = sizes[next_idx];
@@ -158,6 +245,23 @@ impl MascalValue {
             (MascalValue::String(s1), MascalValue::String(s2)) => s1.eq(s2),
             (MascalValue::Type(t1), MascalValue::Type(t2)) => *t1 == *t2,
             (MascalValue::Null, MascalValue::Null) => true,
+            (MascalValue::Map(map1), MascalValue::Map(map2)) => map1.is_equal(map2),
+            (MascalValue::Record(record1), MascalValue::Record(record2)) => {
+                record1.name == record2.name
+                    && record1.field_names.iter().all(|field| {
+                        match (record1.get_field(field), record2.get_field(field)) {
+                            (Some(value1), Some(value2)) => value1.is_equal(&value2),
+                            (None, None) => true,
+                            _ => false,
+                        }
+                    })
+            }
+            (MascalValue::Enum(enum1), MascalValue::Enum(enum2)) => {
+                enum1.name == enum2.name && enum1.index == enum2.index
+            }
+            (MascalValue::Function(function1), MascalValue::Function(function2)) => {
+                function1.is_equal(function2)
+            }
             (MascalValue::DynamicArray(values1), MascalValue::DynamicArray(values2)) => {
                 if values1.len() != values2.len() {
                     return false;
@@ -202,8 +306,7 @@ impl MascalValue {
                 if !sizes.is_empty() {
                     return Err(MascalError {
                         error_type: MascalErrorType::TypeError,
-                        line: 0,
-                        character: 0,
+                        span: None,
                         source: format!("Expected a {} but got an atomic type instead", {
                             let current_size: usize = *sizes.first().unwrap();
                             let is_dynamic_current: bool = *dynamics.first().unwrap();
@@ -227,6 +330,13 @@ impl MascalValue {
             MascalValue::Float(_) => Ok(MascalType::Float),
             MascalValue::Boolean(_) => Ok(MascalType::Boolean),
             MascalValue::Type(_) => Ok(MascalType::Type),
+            MascalValue::Record(record) => Ok(MascalType::Record(record.name.clone())),
+            MascalValue::Enum(enum_value) => Ok(enum_value.enum_type()),
+            MascalValue::Function(..) => Ok(MascalType::Function),
+            MascalValue::Map(map) => Ok(MascalType::Map(
+                Box::new(map.key_type.clone()),
+                Box::new(map.value_type.clone()),
+            )),
             MascalValue::StaticArray(values) => {
                 as_mascal_atomic_type_array_impl!(values);
             }
@@ -236,8 +346,7 @@ impl MascalValue {
 
             MascalValue::Null => Err(MascalError {
                 error_type: MascalErrorType::TypeError,
-                line: 0,
-                character: 0,
+                span: None,
                 source: String::from("NULL is not a type in of itself"),
             }),
         }
@@ -250,6 +359,13 @@ impl MascalValue {
             MascalValue::Float(_) => Ok(MascalType::Float),
             MascalValue::Boolean(_) => Ok(MascalType::Boolean),
             MascalValue::Type(_) => Ok(MascalType::Type),
+            MascalValue::Record(record) => Ok(MascalType::Record(record.name.clone())),
+            MascalValue::Enum(enum_value) => Ok(enum_value.enum_type()),
+            MascalValue::Function(..) => Ok(MascalType::Function),
+            MascalValue::Map(map) => Ok(MascalType::Map(
+                Box::new(map.key_type.clone()),
+                Box::new(map.value_type.clone()),
+            )),
             MascalValue::StaticArray(values) => {
                 as_mascal_type_array_impl!(values, false);
             }
@@ -259,8 +375,7 @@ impl MascalValue {
 
             MascalValue::Null => Err(MascalError {
                 error_type: MascalErrorType::TypeError,
-                line: 0,
-                character: 0,
+                span: None,
                 source: String::from("NULL is not a type in of itself"),
             }),
         }
@@ -298,6 +413,34 @@ impl MascalValue {
                 as_string_array_impl!(values, "[", "]");
             }
             MascalValue::Type(t) => Ok(t.as_string()),
+            MascalValue::Record(record) => {
+                let mut fields: Vec<String> = Vec::with_capacity(record.field_names.len());
+                for field in record.field_names.iter() {
+                    let value: String = match record.get_field(field) {
+                        Some(value) => value.as_string_inner(true)?,
+                        None => String::from("NULL"),
+                    };
+                    fields.push(format!("{}: {}", field, value));
+                }
+                Ok(format!("{}({})", record.name, fields.join(", ")))
+            }
+            MascalValue::Enum(enum_value) => Ok(enum_value.variant().to_string()),
+            MascalValue::Function(function) => Ok(function.as_string()),
+            MascalValue::Map(map) => {
+                let entries = map.entries.borrow();
+                if entries.is_empty() {
+                    return Ok(String::from("[:]"));
+                }
+                let mut formatted_entries: Vec<String> = Vec::with_capacity(entries.len());
+                for (key, value) in entries.iter() {
+                    formatted_entries.push(format!(
+                        "{}: {}",
+                        key.as_string_inner(true)?,
+                        value.as_string_inner(true)?
+                    ));
+                }
+                Ok(format!("[{}]", formatted_entries.join(", ")))
+            }
         }
     }
 
@@ -315,6 +458,10 @@ impl MascalValue {
                 as_type_string_array_impl!(values);
             }
             MascalValue::Type(t) => Ok(t.as_string()),
+            MascalValue::Record(record) => Ok(record.name.to_string()),
+            MascalValue::Enum(enum_value) => Ok(enum_value.name.to_string()),
+            MascalValue::Function(..) => Ok(String::from("FUNCTION")),
+            MascalValue::Map(..) => Ok(self.as_mascal_type()?.as_string()),
         }
     }
 
@@ -332,6 +479,7 @@ impl MascalValue {
         match self {
             MascalValue::Integer(i) => Some(i.to_i128()),
             MascalValue::Float(f) => Some(f.round() as i128),
+            MascalValue::Enum(enum_value) => Some(enum_value.index as i128),
             _ => None,
         }
     }
@@ -352,6 +500,12 @@ impl MascalValue {
             (MascalValue::Boolean(..), MascalType::Boolean) => true,
             (MascalValue::Null, _) => true,
             (MascalValue::Type(..), MascalType::Type) => true,
+            (MascalValue::Function(..), MascalType::Function) => true,
+            (MascalValue::Record(record), MascalType::Record(name)) => record.name == *name,
+            (MascalValue::Enum(enum_value), MascalType::Enum(name, _)) => enum_value.name == *name,
+            (MascalValue::Map(map), MascalType::Map(key_type, value_type)) => {
+                map.fits_types(key_type, value_type).unwrap_or(false)
+            }
             (MascalValue::StaticArray(..), MascalType::StaticArray(array_type)) => {
                 self.is_type_of(array_type)
             }
@@ -371,6 +525,14 @@ impl MascalValue {
             (MascalValue::Null, _) => Ok(true),
             (_, MascalType::Dynamic) => Ok(true),
             (MascalValue::Type(..), MascalType::Type) => Ok(true),
+            (MascalValue::Function(..), MascalType::Function) => Ok(true),
+            (MascalValue::Record(record), MascalType::Record(name)) => Ok(record.name == *name),
+            (MascalValue::Enum(enum_value), MascalType::Enum(name, _)) => {
+                Ok(enum_value.name == *name)
+            }
+            (MascalValue::Map(map), MascalType::Map(key_type, value_type)) => {
+                map.fits_types(key_type, value_type)
+            }
             (MascalValue::StaticArray(values), _) => {
                 atomic_type_array_impl!(values, &value_type);
             }
@@ -380,4 +542,17 @@ impl MascalValue {
             _ => Ok(false),
         }
     }
+
+    /*
+     A map that ends up in a variable (or inside another map) takes on the key and value types of
+     it, anything else is left as is
+    */
+    pub fn conform_to_type(self, value_type: &MascalType) -> MascalValue {
+        match (self, value_type) {
+            (MascalValue::Map(map), MascalType::Map(key_type, value_type)) => MascalValue::Map(
+                map.with_types((**key_type).clone(), (**value_type).clone()),
+            ),
+            (value, _) => value,
+        }
+    }
 }
@@ -5,6 +5,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::Arc;
 
+#[allow(clippy::should_implement_trait)]
 impl MascalValue {
     pub fn add(left: MascalValue, right: MascalValue) -> Result<MascalValue, MascalError> {
         define_arithmetic_fn!(Self::add, left, right, add, +,
@@ -41,8 +42,7 @@ impl MascalValue {
             (MascalValue::Float(l), MascalValue::Float(r)) => {
                 if r == 0f64 {
                     return Err(MascalError {
-                        character: 0,
-                        line: 0,
+                        span: None,
                         error_type: MascalErrorType::UndefinedOperation,
                         source: String::from("Cannot divide by zero"),
                     });
@@ -75,8 +75,7 @@ impl MascalValue {
             (MascalValue::Float(l), MascalValue::Float(r)) => {
                 if *l <= 0f64 {
                     return Err(MascalError {
-                        character: 0,
-                        line: 0,
+                        span: None,
                         error_type: MascalErrorType::UndefinedOperation,
                         source: String::from(
                             "Cannot perform exponentation with a negative or zero base",
@@ -109,8 +108,7 @@ impl MascalValue {
                 if *r == 0f64 {
                     return Err(MascalError {
                         error_type: MascalErrorType::UndefinedOperation,
-                        line: 0,
-                        character: 0,
+                        span: None,
                         source: String::from("Cannot modulo by zero"),
                     });
                 }
@@ -128,8 +126,7 @@ impl MascalValue {
 
             _ => Err(MascalError {
                 error_type: MascalErrorType::UndefinedOperation,
-                line: 0,
-                character: 0,
+                span: None,
                 source: String::from("Cannot perform the negation operation on a non-numeric type"),
             }),
         }
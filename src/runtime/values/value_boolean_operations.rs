@@ -29,8 +29,7 @@ impl MascalValue {
 
             _ => Err(MascalError {
                 error_type: MascalErrorType::UndefinedOperation,
-                line: 0,
-                character: 0,
+                span: None,
                 source: String::from(
                     "Cannot use the operation \"not\" on a type other than boolean",
                 ),
@@ -41,6 +41,18 @@ impl MascalValue {
                     Ok(MascalValue::Boolean(t1.eq(&t2)))
                 }
 
+                (record1 @ MascalValue::Record(..), record2 @ MascalValue::Record(..)) => {
+                    Ok(MascalValue::Boolean(record1.is_equal(&record2)))
+                }
+
+                (MascalValue::Map(map1), MascalValue::Map(map2)) => {
+                    Ok(MascalValue::Boolean(map1.is_equal(&map2)))
+                }
+
+                (MascalValue::Function(function1), MascalValue::Function(function2)) => {
+                    Ok(MascalValue::Boolean(function1.is_equal(&function2)))
+                }
+
                 (MascalValue::Boolean(b1), MascalValue::Boolean(b2)) => {
                     Ok(MascalValue::Boolean(b1.eq(&b2)))
                 }
@@ -67,6 +79,18 @@ impl MascalValue {
                     Ok(MascalValue::Boolean(t1.ne(&t2)))
                 }
 
+                (record1 @ MascalValue::Record(..), record2 @ MascalValue::Record(..)) => {
+                    Ok(MascalValue::Boolean(!record1.is_equal(&record2)))
+                }
+
+                (MascalValue::Map(map1), MascalValue::Map(map2)) => {
+                    Ok(MascalValue::Boolean(!map1.is_equal(&map2)))
+                }
+
+                (MascalValue::Function(function1), MascalValue::Function(function2)) => {
+                    Ok(MascalValue::Boolean(!function1.is_equal(&function2)))
+                }
+
                 (MascalValue::Boolean(b1), MascalValue::Boolean(b2)) => {
                     Ok(MascalValue::Boolean(b1.ne(&b2)))
                 }
@@ -83,8 +107,7 @@ impl MascalValue {
         comparison_arms!(left, right, gt, |v1: MascalValue, v2: MascalValue| {
             Err(MascalError {
                 error_type: MascalErrorType::UndefinedOperation,
-                character: 0,
-                line: 0,
+                span: None,
                 source: format!(
                     "Cannot operate the \"greater than(>)\" operation between the types {:?} and {:?}",
                     v1.as_type_string()?,
@@ -98,8 +121,7 @@ impl MascalValue {
         comparison_arms!(left, right, lt, |v1: MascalValue, v2: MascalValue| {
             Err(MascalError {
                 error_type: MascalErrorType::UndefinedOperation,
-                character: 0,
-                line: 0,
+                span: None,
                 source: format!(
                     "Cannot operate the \"less than(<)\" operation between the types {:?} and {:?}",
                     v1.as_type_string()?,
@@ -116,8 +138,7 @@ impl MascalValue {
         comparison_arms!(left, right, le, |v1: MascalValue, v2: MascalValue| {
             Err(MascalError {
                 error_type: MascalErrorType::UndefinedOperation,
-                character: 0,
-                line: 0,
+                span: None,
                 source: format!(
                     "Cannot operate the \"less than or equal(<=)\" operation between the types {:?} and {:?}",
                     v1.as_type_string()?,
@@ -134,8 +155,7 @@ impl MascalValue {
         comparison_arms!(left, right, ge, |v1: MascalValue, v2: MascalValue| {
             Err(MascalError {
                 error_type: MascalErrorType::UndefinedOperation,
-                character: 0,
-                line: 0,
+                span: None,
                 source: format!(
                     "Cannot operate the \"less than or equal(>=)\" operation between the types {:?} and {:?}",
                     v1.as_type_string()?,
@@ -0,0 +1,207 @@
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::types::MascalType;
+use crate::runtime::values::MascalValue;
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+/*
+ The key of a map entry, two keys are the same whenever MascalValue::is_equal says so. Only the
+ values that can't change under a map's feet (the atomic ones) are allowed as keys
+*/
+#[derive(Clone, Debug)]
+pub struct MapKey(MascalValue);
+
+impl MapKey {
+    pub fn new(value: MascalValue) -> Result<Self, MascalError> {
+        match value {
+            MascalValue::Integer(..)
+            | MascalValue::Float(..)
+            | MascalValue::String(..)
+            | MascalValue::Boolean(..)
+            | MascalValue::Type(..)
+            | MascalValue::Enum(..) => Ok(MapKey(value)),
+            _ => Err(MascalError {
+                error_type: MascalErrorType::TypeError,
+                span: None,
+                source: format!(
+                    "A value of type {} cannot be used as the key of a map",
+                    value.as_type_string()?
+                ),
+            }),
+        }
+    }
+
+    pub fn value(&self) -> &MascalValue {
+        &self.0
+    }
+}
+
+impl PartialEq for MapKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.is_equal(&other.0)
+    }
+}
+
+impl Eq for MapKey {}
+
+impl Hash for MapKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match &self.0 {
+            MascalValue::Integer(i) => i.to_i128().hash(state),
+            /* 0.0 and -0.0 are equal so they better end up with the same hash */
+            MascalValue::Float(f) => (if *f == 0.0 { 0.0f64 } else { *f }).to_bits().hash(state),
+            MascalValue::String(s) => s.hash(state),
+            MascalValue::Boolean(b) => b.hash(state),
+            MascalValue::Type(t) => t.as_string().hash(state),
+            MascalValue::Enum(enum_value) => (&enum_value.name, enum_value.index).hash(state),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/* The entries of a map, they are kept in the order they got inserted in */
+#[derive(Debug, Default)]
+pub struct MapEntries {
+    indices: FxHashMap<MapKey, usize>,
+    entries: Vec<(MapKey, MascalValue)>,
+}
+
+impl MapEntries {
+    pub fn get(&self, key: &MapKey) -> Option<&MascalValue> {
+        self.indices.get(key).map(|index| &self.entries[*index].1)
+    }
+
+    pub fn contains_key(&self, key: &MapKey) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    pub fn insert(&mut self, key: MapKey, value: MascalValue) {
+        if let Some(index) = self.indices.get(&key) {
+            self.entries[*index].1 = value;
+            return;
+        }
+        self.indices.insert(key.clone(), self.entries.len());
+        self.entries.push((key, value));
+    }
+
+    pub fn remove(&mut self, key: &MapKey) -> Option<MascalValue> {
+        let index: usize = self.indices.remove(key)?;
+        let (_, value) = self.entries.remove(index);
+        for later_index in self.indices.values_mut() {
+            if *later_index > index {
+                *later_index -= 1;
+            }
+        }
+        Some(value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&MascalValue, &MascalValue)> {
+        self.entries.iter().map(|(key, value)| (key.value(), value))
+    }
+}
+
+/*
+ A map value, its entries are shared between copies of the value just like the cells of an array
+ are. The key and value types are the ones of the variable holding it (DYNAMIC for a bare literal)
+*/
+#[derive(Clone, Debug)]
+pub struct MascalMap {
+    pub key_type: MascalType,
+    pub value_type: MascalType,
+    pub entries: Rc<RefCell<MapEntries>>,
+}
+
+impl MascalMap {
+    pub fn new(key_type: MascalType, value_type: MascalType) -> Self {
+        MascalMap {
+            key_type,
+            value_type,
+            entries: Rc::new(RefCell::new(MapEntries::default())),
+        }
+    }
+
+    /* The same entries seen through different key and value types */
+    pub fn with_types(&self, key_type: MascalType, value_type: MascalType) -> Self {
+        MascalMap {
+            key_type,
+            value_type,
+            entries: self.entries.clone(),
+        }
+    }
+
+    pub fn fits_types(
+        &self,
+        key_type: &MascalType,
+        value_type: &MascalType,
+    ) -> Result<bool, MascalError> {
+        for (key, value) in self.entries.borrow().iter() {
+            if !key.is_atomic_type_of(key_type)? || !value.is_atomic_type_of(value_type)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /* Inserts an entry while making sure it fits the key and value types of the map */
+    pub fn insert(&self, key: MascalValue, value: MascalValue) -> Result<(), MascalError> {
+        if !key.is_atomic_type_of(&self.key_type)? {
+            return Err(MascalError {
+                error_type: MascalErrorType::TypeError,
+                span: None,
+                source: format!(
+                    "Expected a key of type {} for the map but got {}",
+                    self.key_type.as_string(),
+                    key.as_type_string()?
+                ),
+            });
+        }
+        if !value.is_atomic_type_of(&self.value_type)? {
+            return Err(MascalError {
+                error_type: MascalErrorType::TypeError,
+                span: None,
+                source: format!(
+                    "Expected a value of type {} for the map but got {}",
+                    self.value_type.as_string(),
+                    value.as_type_string()?
+                ),
+            });
+        }
+        let value: MascalValue = value.conform_to_type(&self.value_type);
+        self.entries.borrow_mut().insert(MapKey::new(key)?, value);
+        Ok(())
+    }
+
+    pub fn get(&self, key: MascalValue) -> Result<MascalValue, MascalError> {
+        let key: MapKey = MapKey::new(key)?;
+        let entries = self.entries.borrow();
+        entries.get(&key).cloned().ok_or_else(|| MascalError {
+            error_type: MascalErrorType::IndexError,
+            span: None,
+            source: format!(
+                "The key {} is not present in the map",
+                key.value().as_string_inner(true).unwrap_or_default()
+            ),
+        })
+    }
+
+    pub fn is_equal(&self, other: &MascalMap) -> bool {
+        let entries = self.entries.borrow();
+        let other_entries = other.entries.borrow();
+        entries.len() == other_entries.len()
+            && entries.iter().all(|(key, value)| {
+                other_entries
+                    .get(&MapKey(key.clone()))
+                    .is_some_and(|other_value| value.is_equal(other_value))
+            })
+    }
+}
@@ -11,7 +11,7 @@ macro_rules! comparison_arms {
             }
 
             (MascalValue::Integer(i), MascalValue::Float(f)) => {
-                Ok(MascalValue::Boolean(f.$method(&i.as_f64())))
+                Ok(MascalValue::Boolean(i.as_f64().$method(f)))
             }
 
             (MascalValue::Float(f1), MascalValue::Float(f2)) => {
@@ -22,6 +22,11 @@ macro_rules! comparison_arms {
                 Ok(MascalValue::Boolean(s1.$method(s2)))
             }
 
+            /* Variants of the same enum compare by the order they got declared in */
+            (MascalValue::Enum(e1), MascalValue::Enum(e2)) if e1.name == e2.name => {
+                Ok(MascalValue::Boolean(e1.index.$method(&e2.index)))
+            }
+
             (v1, v2) => $fallback(v1.clone(), v2.clone()),
         }
     };
@@ -69,8 +74,7 @@ macro_rules! define_arithmetic_fn {
 macro_rules! error_float_overflow {
     ($l: expr, $r: expr) => {
         Err(MascalError {
-            character: 0,
-            line: 0,
+            span: None,
             error_type: MascalErrorType::OverflowError,
             source: format!(
                 "Float has been overflowed which was caused by the addition of {:?} and {:?}",
@@ -84,8 +88,7 @@ macro_rules! error_float_overflow {
 macro_rules! unsupported_operation_error {
     ($left: expr, $right: expr) => {
         Err(MascalError {
-            character: 0,
-            line: 0,
+            span: None,
             error_type: MascalErrorType::UndefinedOperation,
             source: format!(
                 "Cannot operate between the values {:?} and {:?}",
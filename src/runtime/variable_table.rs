@@ -1,10 +1,15 @@
-use crate::defs::blocks::ExecutionBlock;
+use crate::defs::blocks::{ExecutionBlock, VariableBlock};
+use crate::defs::builtins::builtin_functions::NativeFunctionTable;
 use crate::defs::errors::{MascalError, MascalErrorType};
-use crate::defs::types::MascalType;
+use crate::defs::span::Span;
+use crate::defs::symbol_table::{RecordSymbol, SymbolTable};
+use crate::defs::types::{MascalType, to_processed_type};
 use crate::runtime::ExecutionData;
 use crate::runtime::execute_expression::execute_expression;
+use crate::runtime::io_streams::SharedIO;
 use crate::runtime::utils::make_array;
-use crate::runtime::values::MascalValue;
+use crate::runtime::values::value_map::MascalMap;
+use crate::runtime::values::{MascalRecord, MascalValue};
 use rustc_hash::FxHashMap;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -29,29 +34,36 @@ pub struct VariableData {
 }
 
 macro_rules! create_variable_table_for_type {
-    ($variable_type: expr, $table: expr, $target_type: expr) => {
+    ($variable_type: expr, $table: expr, $target_type: expr, $io: expr, $native_functions: expr, $symbol_table: expr) => {
         for var in $variable_type {
             let has_defined_value = var.initial_value.is_some();
             let mut value: Option<Rc<RefCell<MascalValue>>> = if let Some(unwrapped_val) = var.initial_value {
+                let span: Span = unwrapped_val.span;
                 let val: MascalValue = execute_expression(unwrapped_val, &mut ExecutionData {
                     variable_table: Some($table.clone()),
-                    scoped_blocks: &mut Vec::new()
+                    scoped_blocks: &mut Vec::new(),
+                    io: $io.clone(),
+                    native_functions: $native_functions.clone(),
+                    symbol_table: $symbol_table.clone(),
                 })?;
                 if !(val.is_atomic_type_of(&*$target_type.clone())?) {
                     return Err(MascalError {
                         error_type: MascalErrorType::RuntimeError,
-                        line: 0,
-                        character: 0,
+                        span: Some(span),
                         source: format!("Evaluated value does not match its atomic type which is {:?}", $target_type.clone())
                     });
                 }
-                Some(Rc::new(RefCell::new(val)))
+                Some(Rc::new(RefCell::new(val.conform_to_type(&$target_type))))
             } else {None};
             let mut dimensions_val: Vec<usize> = Vec::new();
             for dimension in var.dimensions {
+                let span: Span = dimension.span;
                 let val: MascalValue = execute_expression(dimension, &mut ExecutionData {
                     variable_table: Some($table.clone()),
-                    scoped_blocks: &mut Vec::new()
+                    scoped_blocks: &mut Vec::new(),
+                    io: $io.clone(),
+                    native_functions: $native_functions.clone(),
+                    symbol_table: $symbol_table.clone(),
                 })?;
 
                 let size = match val {
@@ -59,8 +71,7 @@ macro_rules! create_variable_table_for_type {
                         if i.is_negative_or_zero() {
                             return Err(MascalError {
                                 error_type: MascalErrorType::TypeError,
-                                line: 0,
-                                character: 0,
+                                span: Some(span),
                                 source: String::from("Evaluated expression is not a positive non-zero integer to be used in specifying an array size")
                             });
                         }
@@ -68,8 +79,7 @@ macro_rules! create_variable_table_for_type {
                     }
                     _ => {return Err(MascalError {
                         error_type: MascalErrorType::TypeError,
-                        line: 0,
-                        character: 0,
+                        span: Some(span),
                         source: String::from("Evaluated expression is not of type integer to be used in specifying an array size")
                     })}
                 };
@@ -81,6 +91,13 @@ macro_rules! create_variable_table_for_type {
                 let dyns: Vec<bool> = var.is_dynamic_array.to_vec();
                 let arr = make_array(&dimensions_val, &dyns);
                 Some(Rc::new(RefCell::new(arr)))
+            } else if let (MascalType::Record(name), false) = (&*$target_type, has_defined_value) {
+                /* A record variable starts out as a fresh instance, that way its fields can be set right away */
+                let record: MascalValue = instantiate_record(name, &$io, &$native_functions, &$symbol_table)?;
+                Some(Rc::new(RefCell::new(record)))
+            } else if let (MascalType::Map(key_type, value_type), false) = (&*$target_type, has_defined_value) {
+                let map: MascalMap = MascalMap::new((**key_type).clone(), (**value_type).clone());
+                Some(Rc::new(RefCell::new(MascalValue::Map(map))))
             } else {value};
 
             let is_dynamic_array: Rc<[bool]> = var.is_dynamic_array.into();
@@ -110,6 +127,9 @@ macro_rules! create_variable_table_for_type {
 #[inline(always)]
 pub fn create_variable_table(
     mut block: ExecutionBlock,
+    io: SharedIO,
+    native_functions: NativeFunctionTable,
+    symbol_table: Rc<SymbolTable>,
 ) -> Result<(Rc<RefCell<VariableTable>>, ExecutionBlock), MascalError> {
     let table: Rc<RefCell<VariableTable>> = Rc::new(RefCell::new(FxHashMap::default()));
 
@@ -119,13 +139,88 @@ pub fn create_variable_table(
     let booleans = std::mem::take(&mut block.variables.booleans);
     let dynamics = std::mem::take(&mut block.variables.dynamics);
     let types = std::mem::take(&mut block.variables.types);
+    let custom_types = std::mem::take(&mut block.variables.custom_types);
 
-    create_variable_table_for_type!(integers, Rc::clone(&table), Arc::new(MascalType::Integer));
-    create_variable_table_for_type!(floats, Rc::clone(&table), Arc::new(MascalType::Float));
-    create_variable_table_for_type!(strings, Rc::clone(&table), Arc::new(MascalType::String));
-    create_variable_table_for_type!(booleans, Rc::clone(&table), Arc::new(MascalType::Boolean));
-    create_variable_table_for_type!(dynamics, Rc::clone(&table), Arc::new(MascalType::Dynamic));
-    create_variable_table_for_type!(types, Rc::clone(&table), Arc::new(MascalType::Type));
+    create_variable_table_for_type!(
+        integers,
+        Rc::clone(&table),
+        Arc::new(MascalType::Integer),
+        io,
+        native_functions,
+        symbol_table
+    );
+    create_variable_table_for_type!(floats, Rc::clone(&table), Arc::new(MascalType::Float), io, native_functions, symbol_table);
+    create_variable_table_for_type!(strings, Rc::clone(&table), Arc::new(MascalType::String), io, native_functions, symbol_table);
+    create_variable_table_for_type!(
+        booleans,
+        Rc::clone(&table),
+        Arc::new(MascalType::Boolean),
+        io,
+        native_functions,
+        symbol_table
+    );
+    create_variable_table_for_type!(
+        dynamics,
+        Rc::clone(&table),
+        Arc::new(MascalType::Dynamic),
+        io,
+        native_functions,
+        symbol_table
+    );
+    create_variable_table_for_type!(types, Rc::clone(&table), Arc::new(MascalType::Type), io, native_functions, symbol_table);
+    for custom_type in custom_types {
+        let variable_type: Arc<MascalType> =
+            Arc::new(symbol_table.resolve_type(to_processed_type(custom_type.variable_type)?));
+        create_variable_table_for_type!(
+            custom_type.variables,
+            Rc::clone(&table),
+            Arc::clone(&variable_type),
+            io,
+            native_functions,
+            symbol_table
+        );
+    }
 
     Ok((table, block))
 }
+
+/* Builds a brand new instance of a record, each field gets evaluated just like a variable would */
+pub fn instantiate_record(
+    name: &Arc<str>,
+    io: &SharedIO,
+    native_functions: &NativeFunctionTable,
+    symbol_table: &Rc<SymbolTable>,
+) -> Result<MascalValue, MascalError> {
+    let Some(record_symbol) = symbol_table.get_record(name) else {
+        return Err(MascalError {
+            error_type: MascalErrorType::RuntimeError,
+            span: None,
+            source: format!("The record {:?} has not been defined", name),
+        });
+    };
+    let record_symbol: Rc<RecordSymbol> = record_symbol.clone();
+    let fields: &VariableBlock = &record_symbol.fields;
+
+    let mut field_declarations = fields.iter_all();
+    field_declarations.sort_by_key(|declaration| (declaration.span.line, declaration.span.character));
+    let field_names: Rc<[Rc<str>]> = field_declarations
+        .into_iter()
+        .map(|declaration| declaration.name.clone())
+        .collect();
+
+    let (table, _) = create_variable_table(
+        ExecutionBlock {
+            variables: fields.clone(),
+            body: Box::new([]),
+        },
+        io.clone(),
+        native_functions.clone(),
+        symbol_table.clone(),
+    )?;
+
+    Ok(MascalValue::Record(MascalRecord {
+        name: name.clone(),
+        field_names,
+        fields: table,
+    }))
+}
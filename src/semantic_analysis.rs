@@ -1,46 +1,186 @@
+mod check_catch_variables;
+mod check_expression_types;
+mod check_loop_labels;
+mod check_match_statements;
 mod check_parameters_declaration;
+mod check_records;
+mod check_return_statements;
 mod variable_check_stage;
 
 use crate::ast::AbstractSyntaxTree;
 use crate::defs::blocks::{ScopedBlocks, VariableBlock};
-use crate::defs::errors::MascalError;
-use crate::runtime::FUNCTION_HASHSET;
+use crate::defs::errors::{MascalError, MascalErrorType, MascalWarning};
+use crate::defs::symbol_table::{EnumSymbol, FunctionSymbol, RecordSymbol, SymbolTable};
+use crate::semantic_analysis::check_catch_variables::check_catch_variables;
+use crate::semantic_analysis::check_expression_types::check_expression_types;
+use crate::semantic_analysis::check_loop_labels::check_loop_labels;
+use crate::semantic_analysis::check_match_statements::check_match_statements;
 use crate::semantic_analysis::check_parameters_declaration::check_for_param_declaration;
+use crate::semantic_analysis::check_records::{
+    check_record_return_type, check_record_variables, check_recursive_record,
+};
+use crate::semantic_analysis::check_return_statements::check_return_statements;
 use std::collections::HashSet;
 use std::rc::Rc;
 use std::sync::Arc;
 
 pub fn conduct_semantic_analysis(
-    abstract_syntax_tree: AbstractSyntaxTree,
-) -> Result<AbstractSyntaxTree, MascalError> {
+    mut abstract_syntax_tree: AbstractSyntaxTree,
+) -> Result<AbstractSyntaxTree, Vec<MascalError>> {
+    let mut errors: Vec<MascalError> = Vec::new();
+    let mut warnings: Vec<MascalWarning> = Vec::new();
+    /* Whatever the tree already knows of goes in first, the REPL hands in what came before */
+    let mut symbol_table: SymbolTable = std::mem::take(&mut abstract_syntax_tree.symbol_table);
+
+    /*
+     Records and enums can be used anywhere regardless of where they got defined, hence the
+     separate pass
+    */
+    for block in &abstract_syntax_tree.blocks {
+        match block {
+            ScopedBlocks::Record {
+                name,
+                variables,
+                span,
+            } => {
+                let previous_definition = symbol_table.define_record(
+                    Rc::from(name.as_str()),
+                    RecordSymbol {
+                        fields: variables.clone(),
+                        span: *span,
+                    },
+                );
+                if previous_definition.is_some() {
+                    errors.push(MascalError {
+                        error_type: MascalErrorType::ParserError,
+                        span: Some(*span),
+                        source: format!("The record {:?} has already been defined", name),
+                    });
+                }
+            }
+            ScopedBlocks::Enum {
+                name,
+                variants,
+                span,
+            } => {
+                let previous_definition = symbol_table.define_enum(
+                    Rc::from(name.as_str()),
+                    EnumSymbol {
+                        variants: variants.iter().map(|variant| Arc::from(variant.as_str())).collect(),
+                        span: *span,
+                    },
+                );
+                let source: Option<String> = if previous_definition.is_some() {
+                    Some(format!("The enum {:?} has already been defined", name))
+                } else if symbol_table.is_record(name) {
+                    Some(format!("The enum {:?} shares its name with a record", name))
+                } else {
+                    None
+                };
+                if let Some(source) = source {
+                    errors.push(MascalError {
+                        error_type: MascalErrorType::ParserError,
+                        span: Some(*span),
+                        source,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
     for block in &abstract_syntax_tree.blocks {
-        let varblock: &VariableBlock = &match block {
-            ScopedBlocks::Program(exec_block) => exec_block,
+        let varblock: &VariableBlock = match block {
+            ScopedBlocks::Program(exec_block) => {
+                check_return_statements(&exec_block.body, None, &mut errors);
+                check_loop_labels(&exec_block.body, &mut Vec::new(), &mut errors);
+                check_match_statements(&exec_block.body, &mut warnings);
+                check_expression_types(exec_block, None, &mut errors);
+                check_catch_variables(&exec_block.body, &exec_block.variables, &mut errors);
+                &exec_block.variables
+            }
             ScopedBlocks::Function {
                 execution_block,
                 parameters,
                 name,
-                ..
+                return_type,
+                span,
             } => {
-                let converted_name: Arc<str> = Arc::from(name.as_str());
-                FUNCTION_HASHSET.lock().unwrap().insert(converted_name);
-                check_for_param_declaration(execution_block, parameters)?;
-                execution_block
+                let previous_definition: Option<FunctionSymbol> = symbol_table.define_function(
+                    Rc::from(name.as_str()),
+                    FunctionSymbol {
+                        parameter_count: parameters.len(),
+                        return_type: return_type.clone(),
+                        span: *span,
+                    },
+                );
+                if previous_definition.is_some() {
+                    errors.push(MascalError {
+                        error_type: MascalErrorType::ParserError,
+                        span: Some(*span),
+                        source: format!("The function {:?} has already been defined", name),
+                    });
+                }
+                if symbol_table.is_record(name) {
+                    errors.push(MascalError {
+                        error_type: MascalErrorType::ParserError,
+                        span: Some(*span),
+                        source: format!("The function {:?} shares its name with a record", name),
+                    });
+                } else if symbol_table.is_enum(name) {
+                    errors.push(MascalError {
+                        error_type: MascalErrorType::ParserError,
+                        span: Some(*span),
+                        source: format!("The function {:?} shares its name with an enum", name),
+                    });
+                }
+                check_record_return_type(return_type, &symbol_table, *span, &mut errors);
+                check_for_param_declaration(execution_block, parameters, &mut errors);
+                check_return_statements(&execution_block.body, Some(return_type), &mut errors);
+                check_loop_labels(&execution_block.body, &mut Vec::new(), &mut errors);
+                check_match_statements(&execution_block.body, &mut warnings);
+                check_expression_types(execution_block, return_type.as_ref(), &mut errors);
+                check_catch_variables(
+                    &execution_block.body,
+                    &execution_block.variables,
+                    &mut errors,
+                );
+                &execution_block.variables
             }
-        }
-        .variables;
+            ScopedBlocks::Record {
+                name,
+                variables,
+                span,
+            } => {
+                check_recursive_record(name, variables, &symbol_table, *span, &mut errors);
+                variables
+            }
+            ScopedBlocks::Enum { .. } => continue,
+        };
+        check_record_variables(varblock, &symbol_table, &mut errors);
         let mut defined_var_names: HashSet<Rc<str>> = HashSet::new();
-        defined_var_names =
-            variable_check_stage::check_per_variable(&varblock.integers, defined_var_names)?;
-        defined_var_names =
-            variable_check_stage::check_per_variable(&varblock.floats, defined_var_names)?;
-        defined_var_names =
-            variable_check_stage::check_per_variable(&varblock.strings, defined_var_names)?;
-        defined_var_names =
-            variable_check_stage::check_per_variable(&varblock.booleans, defined_var_names)?;
-        defined_var_names =
-            variable_check_stage::check_per_variable(&varblock.dynamics, defined_var_names)?;
-        variable_check_stage::check_per_variable(&varblock.types, defined_var_names)?;
+        for variable_type in [
+            &varblock.integers,
+            &varblock.floats,
+            &varblock.strings,
+            &varblock.booleans,
+            &varblock.dynamics,
+            &varblock.types,
+        ]
+        .into_iter()
+        .chain(varblock.custom_types.iter().map(|custom_type| &custom_type.variables))
+        {
+            defined_var_names = variable_check_stage::check_per_variable(
+                variable_type,
+                defined_var_names,
+                &mut errors,
+            );
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
     }
+    abstract_syntax_tree.symbol_table = symbol_table;
+    abstract_syntax_tree.warnings = warnings;
     Ok(abstract_syntax_tree)
 }
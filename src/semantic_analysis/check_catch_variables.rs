@@ -0,0 +1,114 @@
+use crate::defs::blocks::VariableBlock;
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::statements::{MascalCatchBranch, MascalStatement, MascalStatementKind};
+
+/*
+ The variable a CATCH stores the error message in has to be able to hold a string, which is
+ better found out now than only once something finally gets caught. A variable that isn't
+ declared at all is left to the runtime, the REPL keeps its variables around from one line to the
+ next
+*/
+pub fn check_catch_variables(
+    statements: &[MascalStatement],
+    variables: &VariableBlock,
+    errors: &mut Vec<MascalError>,
+) {
+    for statement in statements {
+        match &statement.kind {
+            MascalStatementKind::ConditionalStatement(branches) => {
+                for branch in branches {
+                    check_catch_variables(&branch.statements, variables, errors);
+                }
+            }
+
+            MascalStatementKind::While { branch, .. } => {
+                check_catch_variables(&branch.statements, variables, errors);
+            }
+
+            MascalStatementKind::For { statements, .. }
+            | MascalStatementKind::ForEach { statements, .. }
+            | MascalStatementKind::Repeat { statements, .. } => {
+                check_catch_variables(statements, variables, errors);
+            }
+
+            MascalStatementKind::Match { cases, default, .. } => {
+                for case in cases {
+                    check_catch_variables(&case.statements, variables, errors);
+                }
+                if let Some(default) = default {
+                    check_catch_variables(default, variables, errors);
+                }
+            }
+
+            MascalStatementKind::Try {
+                statements,
+                catches,
+                finally,
+            } => {
+                check_catch_variables(statements, variables, errors);
+                for catch in catches {
+                    check_catch_variable(catch, variables, errors);
+                    check_catch_variables(&catch.statements, variables, errors);
+                }
+                if let Some(finally) = finally {
+                    check_catch_variables(finally, variables, errors);
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+fn check_catch_variable(
+    catch: &MascalCatchBranch,
+    variables: &VariableBlock,
+    errors: &mut Vec<MascalError>,
+) {
+    let Some(variable) = &catch.variable else {
+        return;
+    };
+    let declared = [
+        (&variables.strings, true),
+        (&variables.dynamics, true),
+        (&variables.integers, false),
+        (&variables.floats, false),
+        (&variables.booleans, false),
+        (&variables.types, false),
+    ]
+    .into_iter()
+    .chain(
+        variables
+            .custom_types
+            .iter()
+            .map(|custom_type| (&custom_type.variables, false)),
+    )
+    .find_map(|(declarations, holds_strings)| {
+        declarations
+            .iter()
+            .find(|declaration| *declaration.name == **variable)
+            .map(|declaration| (declaration, holds_strings))
+    });
+    let Some((declaration, holds_strings)) = declared else {
+        return;
+    };
+    if declaration.is_constant {
+        errors.push(MascalError {
+            error_type: MascalErrorType::RuntimeError,
+            span: Some(catch.span),
+            source: format!(
+                "Cannot store the caught error in {:?} as it is a constant",
+                variable
+            ),
+        });
+    } else if !holds_strings || !declaration.dimensions.is_empty() {
+        errors.push(MascalError {
+            error_type: MascalErrorType::TypeError,
+            span: Some(catch.span),
+            source: format!(
+                "Expected {:?} to be a STRING or DYNAMIC variable to hold the caught error",
+                variable
+            ),
+        });
+    }
+}
@@ -0,0 +1,519 @@
+use crate::defs::blocks::{ExecutionBlock, VariableBlock};
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
+use crate::defs::literal::MascalLiteral;
+use crate::defs::operators::{MascalBinaryOperators, MascalUnaryOperators};
+use crate::defs::statements::{MascalCasePattern, MascalStatement, MascalStatementKind};
+use crate::defs::types::MascalUnprocessedType;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/*
+ The type an expression is known to have before the program even runs, anything going through
+ arrays, records, calls or dynamic variables is left out since there is no telling what it ends up
+ being
+*/
+#[derive(Clone, Copy, PartialEq)]
+enum KnownType {
+    Integer,
+    Float,
+    Boolean,
+    String,
+}
+
+impl KnownType {
+    fn from_type(unprocessed_type: &MascalUnprocessedType) -> Option<KnownType> {
+        match unprocessed_type {
+            MascalUnprocessedType::Integer => Some(KnownType::Integer),
+            MascalUnprocessedType::Float => Some(KnownType::Float),
+            MascalUnprocessedType::Boolean => Some(KnownType::Boolean),
+            MascalUnprocessedType::String => Some(KnownType::String),
+            _ => None,
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            KnownType::Integer => "an integer",
+            KnownType::Float => "a float",
+            KnownType::Boolean => "a boolean",
+            KnownType::String => "a string",
+        }
+    }
+
+    /* Integers and floats get converted into one another on their own, other types don't */
+    fn fits_into(&self, target: &KnownType) -> bool {
+        self == target
+            || matches!(
+                (self, target),
+                (
+                    KnownType::Integer | KnownType::Float,
+                    KnownType::Integer | KnownType::Float
+                )
+            )
+    }
+}
+
+struct KnownVariables(HashMap<Rc<str>, KnownType>);
+
+impl KnownVariables {
+    /* Arrays are left out, only the plain variables of a block have a type worth knowing here */
+    fn from_block(variables: &VariableBlock) -> Self {
+        let mut known_variables: HashMap<Rc<str>, KnownType> = HashMap::new();
+        for (declarations, known_type) in [
+            (&variables.integers, KnownType::Integer),
+            (&variables.floats, KnownType::Float),
+            (&variables.booleans, KnownType::Boolean),
+            (&variables.strings, KnownType::String),
+        ] {
+            for declaration in declarations.iter() {
+                if declaration.dimensions.is_empty() {
+                    known_variables.insert(declaration.name.clone(), known_type);
+                }
+            }
+        }
+        KnownVariables(known_variables)
+    }
+}
+
+/*
+ AND and OR only work on booleans, the parser already turns away literals and the like so what is
+ left to catch here are variables (or whatever gets built from them) that are known not to be one.
+ Both branches of an IF expression also have to fit into the variable it gets assigned to (or be
+ what the function returns), even though only one of them ends up there. The return type is only
+ there for functions
+*/
+pub fn check_expression_types(
+    execution_block: &ExecutionBlock,
+    return_type: Option<&MascalUnprocessedType>,
+    errors: &mut Vec<MascalError>,
+) {
+    let variables: &VariableBlock = &execution_block.variables;
+    let known_variables: KnownVariables = KnownVariables::from_block(variables);
+    for declarations in [
+        &variables.integers,
+        &variables.floats,
+        &variables.strings,
+        &variables.booleans,
+        &variables.dynamics,
+        &variables.types,
+    ]
+    .into_iter()
+    .chain(
+        variables
+            .custom_types
+            .iter()
+            .map(|custom_type| &custom_type.variables),
+    ) {
+        for declaration in declarations.iter() {
+            let Some(initial_value) = &declaration.initial_value else {
+                continue;
+            };
+            check_expression(initial_value, &known_variables, errors);
+            if let Some(target_type) = known_variables.0.get(&declaration.name) {
+                check_branches(
+                    BranchTarget::Variable(&declaration.name),
+                    target_type,
+                    initial_value,
+                    &known_variables,
+                    errors,
+                );
+            }
+        }
+    }
+    check_statements(
+        &execution_block.body,
+        &known_variables,
+        return_type.and_then(KnownType::from_type),
+        errors,
+    );
+}
+
+fn check_statements(
+    statements: &[MascalStatement],
+    variables: &KnownVariables,
+    return_type: Option<KnownType>,
+    errors: &mut Vec<MascalError>,
+) {
+    for statement in statements {
+        match &statement.kind {
+            MascalStatementKind::ConditionalStatement(branches) => {
+                for branch in branches {
+                    if let Some(condition) = &branch.condition {
+                        check_expression(condition, variables, errors);
+                    }
+                    check_statements(&branch.statements, variables, return_type, errors);
+                }
+            }
+
+            MascalStatementKind::While { branch, .. } => {
+                if let Some(condition) = &branch.condition {
+                    check_expression(condition, variables, errors);
+                }
+                check_statements(&branch.statements, variables, return_type, errors);
+            }
+
+            MascalStatementKind::Repeat {
+                statements,
+                condition,
+                ..
+            } => {
+                check_statements(statements, variables, return_type, errors);
+                check_expression(condition, variables, errors);
+            }
+
+            MascalStatementKind::For {
+                from,
+                to,
+                step,
+                statements,
+                ..
+            } => {
+                for bound in [from, to, step] {
+                    check_expression(bound, variables, errors);
+                }
+                check_statements(statements, variables, return_type, errors);
+            }
+
+            MascalStatementKind::ForEach {
+                iterable,
+                statements,
+                ..
+            } => {
+                check_expression(iterable, variables, errors);
+                check_statements(statements, variables, return_type, errors);
+            }
+
+            MascalStatementKind::Match {
+                subject,
+                cases,
+                default,
+            } => {
+                check_expression(subject, variables, errors);
+                for case in cases {
+                    for pattern in case.patterns.iter() {
+                        match pattern {
+                            MascalCasePattern::Value(value) | MascalCasePattern::Type(value) => {
+                                check_expression(value, variables, errors);
+                            }
+                            MascalCasePattern::Range { from, to } => {
+                                check_expression(from, variables, errors);
+                                check_expression(to, variables, errors);
+                            }
+                        }
+                    }
+                    check_statements(&case.statements, variables, return_type, errors);
+                }
+                if let Some(default) = default {
+                    check_statements(default, variables, return_type, errors);
+                }
+            }
+
+            MascalStatementKind::Try {
+                statements,
+                catches,
+                finally,
+            } => {
+                check_statements(statements, variables, return_type, errors);
+                for catch in catches {
+                    check_statements(&catch.statements, variables, return_type, errors);
+                }
+                if let Some(finally) = finally {
+                    check_statements(finally, variables, return_type, errors);
+                }
+            }
+
+            MascalStatementKind::ExpressionStatement(expression)
+            | MascalStatementKind::Throw {
+                message: expression,
+                ..
+            } => {
+                check_expression(expression, variables, errors);
+            }
+
+            /* Unlike assignments, returning an integer from a FLOAT function is not converted */
+            MascalStatementKind::Return(Some(expression)) => {
+                check_expression(expression, variables, errors);
+                let Some(expected) = return_type else {
+                    continue;
+                };
+                if matches!(expression.kind, MascalExpressionKind::Conditional { .. }) {
+                    check_branches(
+                        BranchTarget::Return,
+                        &expected,
+                        expression,
+                        variables,
+                        errors,
+                    );
+                } else if let Some(found) = known_type(expression, variables)
+                    && found != expected
+                {
+                    errors.push(MascalError {
+                        error_type: MascalErrorType::TypeError,
+                        span: Some(expression.span),
+                        source: format!(
+                            "Expected {} to be returned but got {}",
+                            expected.describe(),
+                            found.describe()
+                        ),
+                    });
+                }
+            }
+
+            MascalStatementKind::Declaration { variable, value } => {
+                check_expression(variable, variables, errors);
+                check_expression(value, variables, errors);
+                if let MascalExpressionKind::Symbolic(name) = &variable.kind
+                    && let Some(target_type) = variables.0.get(name.as_str())
+                {
+                    check_branches(
+                        BranchTarget::Variable(name),
+                        target_type,
+                        value,
+                        variables,
+                        errors,
+                    );
+                }
+            }
+
+            MascalStatementKind::Break(..)
+            | MascalStatementKind::Continue(..)
+            | MascalStatementKind::Return(None) => {}
+        }
+    }
+}
+
+fn check_expression(
+    expression: &MascalExpression,
+    variables: &KnownVariables,
+    errors: &mut Vec<MascalError>,
+) {
+    match &expression.kind {
+        MascalExpressionKind::Binary {
+            left,
+            operator,
+            right,
+        } => {
+            check_expression(left, variables, errors);
+            check_expression(right, variables, errors);
+            let operator_name: &str = match operator {
+                MascalBinaryOperators::And => "AND",
+                MascalBinaryOperators::Or => "OR",
+                _ => return,
+            };
+            for (operand, side) in [(left, "left"), (right, "right")] {
+                if let Some(found) = known_type(operand, variables)
+                    && found != KnownType::Boolean
+                {
+                    errors.push(MascalError {
+                        error_type: MascalErrorType::TypeError,
+                        span: Some(operand.span),
+                        source: format!(
+                            "Expected a boolean on the {} side of {} but got {}",
+                            side,
+                            operator_name,
+                            found.describe()
+                        ),
+                    });
+                }
+            }
+        }
+
+        MascalExpressionKind::DynamicArray(elements)
+        | MascalExpressionKind::StaticArray(elements) => {
+            for element in elements.iter() {
+                check_expression(element, variables, errors);
+            }
+        }
+
+        MascalExpressionKind::Map(entries) => {
+            for (key, value) in entries.iter() {
+                check_expression(key, variables, errors);
+                check_expression(value, variables, errors);
+            }
+        }
+
+        MascalExpressionKind::Unary { value, .. } => check_expression(value, variables, errors),
+
+        MascalExpressionKind::Call {
+            function,
+            arguments,
+        } => {
+            check_expression(function, variables, errors);
+            for argument in arguments {
+                check_expression(argument, variables, errors);
+            }
+        }
+
+        MascalExpressionKind::Indexing { array, index, .. } => {
+            check_expression(array, variables, errors);
+            check_expression(index, variables, errors);
+        }
+
+        MascalExpressionKind::FieldAccess { record, .. } => {
+            check_expression(record, variables, errors)
+        }
+
+        /* The parameters of a lambda hide whatever variables outside share their names */
+        MascalExpressionKind::Lambda { parameters, body } => {
+            let mut inner_variables: HashMap<Rc<str>, KnownType> = variables.0.clone();
+            for parameter in parameters.iter() {
+                inner_variables.remove(parameter);
+            }
+            check_expression(body, &KnownVariables(inner_variables), errors);
+        }
+
+        MascalExpressionKind::Conditional {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            for part in [condition, then_branch, else_branch] {
+                check_expression(part, variables, errors);
+            }
+            if let Some(found) = known_type(condition, variables)
+                && found != KnownType::Boolean
+            {
+                errors.push(MascalError {
+                    error_type: MascalErrorType::TypeError,
+                    span: Some(condition.span),
+                    source: format!(
+                        "Expected a boolean on the condition of an IF expression but got {}",
+                        found.describe()
+                    ),
+                });
+            }
+        }
+
+        MascalExpressionKind::Literal(..)
+        | MascalExpressionKind::Symbolic(..)
+        | MascalExpressionKind::Type(..) => {}
+    }
+}
+
+/* Where the value of an IF expression ends up, returned values aren't converted like assigned ones */
+#[derive(Clone, Copy)]
+enum BranchTarget<'a> {
+    Variable(&'a str),
+    Return,
+}
+
+/* Goes through every branch an IF expression (nested ones included) could hand to its target */
+fn check_branches(
+    target: BranchTarget,
+    target_type: &KnownType,
+    value: &MascalExpression,
+    variables: &KnownVariables,
+    errors: &mut Vec<MascalError>,
+) {
+    let MascalExpressionKind::Conditional {
+        then_branch,
+        else_branch,
+        ..
+    } = &value.kind
+    else {
+        return;
+    };
+    for (branch, branch_name) in [(then_branch, "THEN"), (else_branch, "ELSE")] {
+        if matches!(branch.kind, MascalExpressionKind::Conditional { .. }) {
+            check_branches(target, target_type, branch, variables, errors);
+            continue;
+        }
+        let Some(found) = known_type(branch, variables) else {
+            continue;
+        };
+        let source: String = match target {
+            BranchTarget::Variable(name) if !found.fits_into(target_type) => format!(
+                "The {} branch gives {} but {:?} can only hold {}",
+                branch_name,
+                found.describe(),
+                name,
+                target_type.describe()
+            ),
+            BranchTarget::Return if found != *target_type => format!(
+                "The {} branch gives {} but {} has to be returned",
+                branch_name,
+                found.describe(),
+                target_type.describe()
+            ),
+            _ => continue,
+        };
+        errors.push(MascalError {
+            error_type: MascalErrorType::TypeError,
+            span: Some(branch.span),
+            source,
+        });
+    }
+}
+
+fn known_type(expression: &MascalExpression, variables: &KnownVariables) -> Option<KnownType> {
+    match &expression.kind {
+        MascalExpressionKind::Literal(MascalLiteral::Integer(..)) => Some(KnownType::Integer),
+        MascalExpressionKind::Literal(MascalLiteral::Float(..)) => Some(KnownType::Float),
+        MascalExpressionKind::Literal(MascalLiteral::Boolean(..)) => Some(KnownType::Boolean),
+        MascalExpressionKind::Literal(MascalLiteral::String(..)) => Some(KnownType::String),
+        MascalExpressionKind::Symbolic(name) => variables.0.get(name.as_str()).copied(),
+
+        MascalExpressionKind::Unary {
+            operator: MascalUnaryOperators::Not,
+            ..
+        } => Some(KnownType::Boolean),
+        MascalExpressionKind::Unary {
+            operator: MascalUnaryOperators::Minus,
+            value,
+        } => known_type(value, variables)
+            .filter(|found| matches!(found, KnownType::Integer | KnownType::Float)),
+
+        MascalExpressionKind::Binary {
+            left,
+            operator,
+            right,
+        } => match operator {
+            MascalBinaryOperators::Equals
+            | MascalBinaryOperators::NotEqual
+            | MascalBinaryOperators::GreaterThan
+            | MascalBinaryOperators::LessThan
+            | MascalBinaryOperators::GreaterThanOrEqual
+            | MascalBinaryOperators::LessThanOrEqual
+            | MascalBinaryOperators::And
+            | MascalBinaryOperators::Or => Some(KnownType::Boolean),
+
+            MascalBinaryOperators::Plus
+            | MascalBinaryOperators::Minus
+            | MascalBinaryOperators::Multiply
+            | MascalBinaryOperators::Divide
+            | MascalBinaryOperators::Modulo => {
+                match (known_type(left, variables)?, known_type(right, variables)?) {
+                    (KnownType::Integer, KnownType::Integer) => Some(KnownType::Integer),
+                    (
+                        KnownType::Integer | KnownType::Float,
+                        KnownType::Integer | KnownType::Float,
+                    ) => Some(KnownType::Float),
+                    (KnownType::String, KnownType::String)
+                        if *operator == MascalBinaryOperators::Plus =>
+                    {
+                        Some(KnownType::String)
+                    }
+                    _ => None,
+                }
+            }
+
+            MascalBinaryOperators::Exponentiation => None,
+        },
+
+        MascalExpressionKind::Conditional {
+            then_branch,
+            else_branch,
+            ..
+        } => match (
+            known_type(then_branch, variables)?,
+            known_type(else_branch, variables)?,
+        ) {
+            (then_type, else_type) if then_type == else_type => Some(then_type),
+            (then_type, else_type) if then_type.fits_into(&else_type) => Some(KnownType::Float),
+            _ => None,
+        },
+
+        _ => None,
+    }
+}
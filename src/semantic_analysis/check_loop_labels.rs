@@ -0,0 +1,109 @@
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::statements::{MascalStatement, MascalStatementKind};
+
+/*
+ A BREAK or CONTINUE naming a loop has to sit somewhere inside that loop, the labels of the loops
+ around the current statement are kept from the outermost to the innermost one
+*/
+pub fn check_loop_labels(
+    statements: &[MascalStatement],
+    labels: &mut Vec<String>,
+    errors: &mut Vec<MascalError>,
+) {
+    for statement in statements {
+        match &statement.kind {
+            MascalStatementKind::ConditionalStatement(branches) => {
+                for branch in branches {
+                    check_loop_labels(&branch.statements, labels, errors);
+                }
+            }
+
+            MascalStatementKind::While { branch, label } => {
+                check_loop(&branch.statements, label, statement, labels, errors);
+            }
+
+            MascalStatementKind::For {
+                statements, label, ..
+            }
+            | MascalStatementKind::ForEach {
+                statements, label, ..
+            }
+            | MascalStatementKind::Repeat {
+                statements, label, ..
+            } => {
+                check_loop(statements, label, statement, labels, errors);
+            }
+
+            MascalStatementKind::Match { cases, default, .. } => {
+                for case in cases {
+                    check_loop_labels(&case.statements, labels, errors);
+                }
+                if let Some(default) = default {
+                    check_loop_labels(default, labels, errors);
+                }
+            }
+
+            MascalStatementKind::Try {
+                statements,
+                catches,
+                finally,
+            } => {
+                check_loop_labels(statements, labels, errors);
+                for catch in catches {
+                    check_loop_labels(&catch.statements, labels, errors);
+                }
+                if let Some(finally) = finally {
+                    check_loop_labels(finally, labels, errors);
+                }
+            }
+
+            MascalStatementKind::Break(Some(label))
+            | MascalStatementKind::Continue(Some(label))
+                if !labels.contains(label) =>
+            {
+                errors.push(MascalError {
+                    error_type: MascalErrorType::ContextError,
+                    span: Some(statement.span),
+                    source: format!(
+                        "There is no loop labeled {:?} around this {} statement",
+                        label,
+                        if matches!(statement.kind, MascalStatementKind::Break(..)) {
+                            "break"
+                        } else {
+                            "continue"
+                        }
+                    ),
+                });
+            }
+
+            _ => {}
+        }
+    }
+}
+
+/* Reusing the label of a loop around it would leave BREAK guessing which of the two it meant */
+fn check_loop(
+    statements: &[MascalStatement],
+    label: &Option<String>,
+    loop_statement: &MascalStatement,
+    labels: &mut Vec<String>,
+    errors: &mut Vec<MascalError>,
+) {
+    let Some(label) = label else {
+        check_loop_labels(statements, labels, errors);
+        return;
+    };
+    if labels.contains(label) {
+        errors.push(MascalError {
+            error_type: MascalErrorType::ContextError,
+            span: Some(loop_statement.span),
+            source: format!(
+                "The label {:?} is already used by a loop around this one",
+                label
+            ),
+        });
+    }
+    labels.push(label.clone());
+    check_loop_labels(statements, labels, errors);
+    labels.pop();
+}
@@ -0,0 +1,201 @@
+use crate::defs::errors::MascalWarning;
+use crate::defs::expressions::{MascalExpression, MascalExpressionKind};
+use crate::defs::literal::MascalLiteral;
+use crate::defs::operators::MascalUnaryOperators;
+use crate::defs::statements::{MascalCasePattern, MascalStatement, MascalStatementKind};
+use crate::defs::types::MascalUnprocessedType;
+use std::sync::Arc;
+
+/*
+ What a pattern is known to match before the program even runs, anything depending on variables
+ or calls is left out since there is no telling what it ends up being
+*/
+enum KnownPattern {
+    Integer(f64),
+    Float(f64),
+    Number { from: f64, to: f64 },
+    Text { from: String, to: String },
+    Boolean(bool),
+    Type(MascalUnprocessedType),
+    Anything,
+}
+
+impl KnownPattern {
+    fn covers(&self, other: &KnownPattern) -> bool {
+        match (self, other) {
+            (KnownPattern::Anything, _) => true,
+            (KnownPattern::Integer(a), KnownPattern::Integer(b))
+            | (KnownPattern::Float(a), KnownPattern::Float(b)) => a == b,
+            (
+                KnownPattern::Number { from, to },
+                KnownPattern::Integer(value) | KnownPattern::Float(value),
+            ) => from <= value && value <= to,
+            (
+                KnownPattern::Number { from, to },
+                KnownPattern::Number {
+                    from: other_from,
+                    to: other_to,
+                },
+            ) => from <= other_from && other_to <= to,
+            (
+                KnownPattern::Text { from, to },
+                KnownPattern::Text {
+                    from: other_from,
+                    to: other_to,
+                },
+            ) => from <= other_from && other_to <= to,
+            (KnownPattern::Boolean(a), KnownPattern::Boolean(b)) => a == b,
+            (KnownPattern::Type(a), KnownPattern::Type(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /* A range going backwards can't match anything, so whatever came before already covers it */
+    fn is_empty(&self) -> bool {
+        match self {
+            KnownPattern::Number { from, to } => from > to,
+            KnownPattern::Text { from, to } => from > to,
+            _ => false,
+        }
+    }
+}
+
+/*
+ A CASE whose every pattern is already covered by the cases above it can never run, since only
+ the first fitting case ever does. That is not wrong per se, so it only gets a warning
+*/
+pub fn check_match_statements(statements: &[MascalStatement], warnings: &mut Vec<MascalWarning>) {
+    for statement in statements {
+        match &statement.kind {
+            MascalStatementKind::ConditionalStatement(branches) => {
+                for branch in branches {
+                    check_match_statements(&branch.statements, warnings);
+                }
+            }
+
+            MascalStatementKind::While { branch, .. } => {
+                check_match_statements(&branch.statements, warnings);
+            }
+
+            MascalStatementKind::For { statements, .. }
+            | MascalStatementKind::ForEach { statements, .. }
+            | MascalStatementKind::Repeat { statements, .. } => {
+                check_match_statements(statements, warnings);
+            }
+
+            MascalStatementKind::Try {
+                statements,
+                catches,
+                finally,
+            } => {
+                check_match_statements(statements, warnings);
+                for catch in catches {
+                    check_match_statements(&catch.statements, warnings);
+                }
+                if let Some(finally) = finally {
+                    check_match_statements(finally, warnings);
+                }
+            }
+
+            MascalStatementKind::Match { cases, default, .. } => {
+                let mut known_patterns: Vec<KnownPattern> = Vec::new();
+                for case in cases {
+                    let mut is_reachable: bool = false;
+                    for pattern in case.patterns.iter() {
+                        let Some(known_pattern) = known_pattern(pattern) else {
+                            is_reachable = true;
+                            continue;
+                        };
+                        if !known_pattern.is_empty()
+                            && !known_patterns
+                                .iter()
+                                .any(|known| known.covers(&known_pattern))
+                        {
+                            is_reachable = true;
+                            known_patterns.push(known_pattern);
+                        }
+                    }
+                    if !is_reachable {
+                        warnings.push(MascalWarning {
+                            span: Some(case.span),
+                            source: String::from(
+                                "This case can never be reached since the cases before it already cover everything it matches",
+                            ),
+                        });
+                    }
+                    check_match_statements(&case.statements, warnings);
+                }
+                if let Some(default) = default {
+                    check_match_statements(default, warnings);
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+fn known_pattern(pattern: &MascalCasePattern) -> Option<KnownPattern> {
+    match pattern {
+        MascalCasePattern::Value(value) => match known_literal(value)? {
+            MascalLiteral::Integer(integer) => Some(KnownPattern::Integer(integer.as_f64())),
+            MascalLiteral::Float(float) => Some(KnownPattern::Float(float)),
+            MascalLiteral::Boolean(boolean) => Some(KnownPattern::Boolean(boolean)),
+            MascalLiteral::String(text) => Some(KnownPattern::Text {
+                from: text.clone(),
+                to: text,
+            }),
+            MascalLiteral::Null => None,
+        },
+
+        MascalCasePattern::Range { from, to } => match (known_literal(from)?, known_literal(to)?) {
+            (MascalLiteral::String(from), MascalLiteral::String(to)) => {
+                Some(KnownPattern::Text { from, to })
+            }
+            (from, to) => Some(KnownPattern::Number {
+                from: known_number(&from)?,
+                to: known_number(&to)?,
+            }),
+        },
+
+        MascalCasePattern::Type(pattern_type) => match &pattern_type.kind {
+            MascalExpressionKind::Type(unprocessed_type)
+                if **unprocessed_type == MascalUnprocessedType::Dynamic =>
+            {
+                Some(KnownPattern::Anything)
+            }
+            MascalExpressionKind::Type(unprocessed_type) => {
+                Some(KnownPattern::Type(*unprocessed_type.clone()))
+            }
+            /* Records and enums are named like any variable would be */
+            MascalExpressionKind::Symbolic(name) => Some(KnownPattern::Type(
+                MascalUnprocessedType::Record(Arc::from(name.as_str())),
+            )),
+            _ => None,
+        },
+    }
+}
+
+/* Negative numbers are a minus in front of a literal, so those get folded in here */
+fn known_literal(expression: &MascalExpression) -> Option<MascalLiteral> {
+    match &expression.kind {
+        MascalExpressionKind::Literal(literal) => Some(literal.clone()),
+        MascalExpressionKind::Unary {
+            operator: MascalUnaryOperators::Minus,
+            value,
+        } => match known_literal(value)? {
+            MascalLiteral::Integer(integer) => integer.neg().ok().map(MascalLiteral::Integer),
+            MascalLiteral::Float(float) => Some(MascalLiteral::Float(-float)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn known_number(literal: &MascalLiteral) -> Option<f64> {
+    match literal {
+        MascalLiteral::Integer(integer) => Some(integer.as_f64()),
+        MascalLiteral::Float(float) => Some(*float),
+        _ => None,
+    }
+}
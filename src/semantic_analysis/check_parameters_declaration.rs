@@ -6,18 +6,18 @@ use std::rc::Rc;
 pub fn check_for_param_declaration(
     function_block: &ExecutionBlock,
     params: &[MascalParameter],
-) -> Result<(), MascalError> {
+    errors: &mut Vec<MascalError>,
+) {
     let param_names: HashSet<Rc<str>> = params.iter().map(|p| p.name.clone()).collect();
 
-    if function_block
+    for variable in function_block
         .variables
         .iter_all()
         .iter()
-        .any(|v| param_names.contains(&v.name) && v.initial_value.is_some())
+        .filter(|v| param_names.contains(&v.name) && v.initial_value.is_some())
     {
-        return Err(MascalError {
-            character: 0,
-            line: 0,
+        errors.push(MascalError {
+            span: Some(variable.span),
             error_type: MascalErrorType::ParserError,
             source: String::from(
                 "Cannot define an initial value for a parameter inside a function",
@@ -34,9 +34,8 @@ pub fn check_for_param_declaration(
 
     for param in params {
         if !declared.contains(&param.name) {
-            return Err(MascalError {
-                character: 0,
-                line: 0,
+            errors.push(MascalError {
+                span: Some(param.span),
                 error_type: MascalErrorType::ParserError,
                 source: format!(
                     "Parameter named {:?} has not been declared in the variables block",
@@ -45,6 +44,4 @@ pub fn check_for_param_declaration(
             });
         }
     }
-
-    Ok(())
 }
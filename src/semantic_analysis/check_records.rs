@@ -0,0 +1,112 @@
+use crate::defs::blocks::{CustomTypeVariableBlock, VariableBlock};
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::span::Span;
+use crate::defs::symbol_table::SymbolTable;
+use crate::defs::types::MascalUnprocessedType;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+fn undefined_record(name: &str, span: Span) -> MascalError {
+    MascalError {
+        error_type: MascalErrorType::ParserError,
+        span: Some(span),
+        source: format!(
+            "Use of an undefined record type {:?} (perhaps a typo?)",
+            name
+        ),
+    }
+}
+
+/* Any record named by a type (be it on its own, in an array or in a map) has to actually exist */
+fn check_record_type(
+    variable_type: &MascalUnprocessedType,
+    symbol_table: &SymbolTable,
+    span: Span,
+    errors: &mut Vec<MascalError>,
+) {
+    match variable_type {
+        MascalUnprocessedType::Record(name)
+            if !symbol_table.is_record(name) && !symbol_table.is_enum(name) =>
+        {
+            errors.push(undefined_record(name, span));
+        }
+        MascalUnprocessedType::DynamicArray(inner) | MascalUnprocessedType::StaticArray(inner) => {
+            check_record_type(inner, symbol_table, span, errors);
+        }
+        MascalUnprocessedType::Map(key_type, value_type) => {
+            check_record_type(key_type, symbol_table, span, errors);
+            check_record_type(value_type, symbol_table, span, errors);
+        }
+        _ => {}
+    }
+}
+
+pub fn check_record_variables(
+    variable_block: &VariableBlock,
+    symbol_table: &SymbolTable,
+    errors: &mut Vec<MascalError>,
+) {
+    for custom_type in &variable_block.custom_types {
+        check_record_type(
+            &custom_type.variable_type,
+            symbol_table,
+            custom_type.span,
+            errors,
+        );
+    }
+}
+
+pub fn check_record_return_type(
+    return_type: &Option<MascalUnprocessedType>,
+    symbol_table: &SymbolTable,
+    span: Span,
+    errors: &mut Vec<MascalError>,
+) {
+    if let Some(return_type) = return_type {
+        check_record_type(return_type, symbol_table, span, errors);
+    }
+}
+
+/*
+ A record holding itself (directly or through other records) outside an array could never finish
+ being created, since each instance creates its fields right away
+*/
+pub fn check_recursive_record(
+    name: &str,
+    variable_block: &VariableBlock,
+    symbol_table: &SymbolTable,
+    span: Span,
+    errors: &mut Vec<MascalError>,
+) {
+    let mut visited: HashSet<Rc<str>> = HashSet::new();
+    let mut pending: Vec<&CustomTypeVariableBlock> = variable_block.custom_types.iter().collect();
+    while let Some(record) = pending.pop() {
+        let MascalUnprocessedType::Record(record_name) = &record.variable_type else {
+            continue;
+        };
+        if !record
+            .variables
+            .iter()
+            .any(|field| field.dimensions.is_empty())
+        {
+            continue;
+        }
+        if record_name.as_ref() == name {
+            errors.push(MascalError {
+                error_type: MascalErrorType::ParserError,
+                span: Some(span),
+                source: format!(
+                    "The record {:?} contains itself, perhaps use an array of it instead?",
+                    name
+                ),
+            });
+            return;
+        }
+        if !visited.insert(Rc::from(record_name.as_ref())) {
+            continue;
+        }
+        if let Some(symbol) = symbol_table.get_record(record_name) {
+            pending.extend(symbol.fields.custom_types.iter());
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::defs::errors::{MascalError, MascalErrorType};
+use crate::defs::statements::{MascalStatement, MascalStatementKind};
+use crate::defs::types::MascalUnprocessedType;
+
+/*
+ The value itself can only be checked once it has been evaluated, but whether a RETURN carries one
+ at all is known ahead of time. The return type being None means we are inside the program block
+*/
+pub fn check_return_statements(
+    statements: &[MascalStatement],
+    return_type: Option<&Option<MascalUnprocessedType>>,
+    errors: &mut Vec<MascalError>,
+) {
+    for statement in statements {
+        match &statement.kind {
+            MascalStatementKind::ConditionalStatement(branches) => {
+                for branch in branches {
+                    check_return_statements(&branch.statements, return_type, errors);
+                }
+            }
+
+            MascalStatementKind::While { branch, .. } => {
+                check_return_statements(&branch.statements, return_type, errors);
+            }
+
+            MascalStatementKind::For { statements, .. }
+            | MascalStatementKind::ForEach { statements, .. }
+            | MascalStatementKind::Repeat { statements, .. } => {
+                check_return_statements(statements, return_type, errors);
+            }
+
+            MascalStatementKind::Match { cases, default, .. } => {
+                for case in cases {
+                    check_return_statements(&case.statements, return_type, errors);
+                }
+                if let Some(default) = default {
+                    check_return_statements(default, return_type, errors);
+                }
+            }
+
+            MascalStatementKind::Try {
+                statements,
+                catches,
+                finally,
+            } => {
+                check_return_statements(statements, return_type, errors);
+                for catch in catches {
+                    check_return_statements(&catch.statements, return_type, errors);
+                }
+                if let Some(finally) = finally {
+                    check_return_statements(finally, return_type, errors);
+                }
+            }
+
+            MascalStatementKind::Return(value) => {
+                let source: &str = match (return_type, value) {
+                    (None, _) => "Return statement is not allowed outside of a function",
+                    (Some(None), Some(_)) => {
+                        "Cannot return a value from a function without a return type"
+                    }
+                    (Some(Some(_)), None) => {
+                        "Expected a value to be returned from a function with a return type"
+                    }
+                    _ => continue,
+                };
+                errors.push(MascalError {
+                    error_type: if return_type.is_none() {
+                        MascalErrorType::ContextError
+                    } else {
+                        MascalErrorType::TypeError
+                    },
+                    span: Some(statement.span),
+                    source: String::from(source),
+                });
+            }
+
+            _ => {}
+        }
+    }
+}
@@ -7,18 +7,19 @@ use std::rc::Rc;
 pub(crate) fn check_per_variable(
     variable_type: &[MascalVariableInitialDeclaration],
     mut defined_var_names: HashSet<Rc<str>>,
-) -> Result<HashSet<Rc<str>>, MascalError> {
+    errors: &mut Vec<MascalError>,
+) -> HashSet<Rc<str>> {
     for var_decl in variable_type {
         let name: Rc<str> = var_decl.name.clone();
         if defined_var_names.contains(&*name) {
-            return Err(MascalError {
+            errors.push(MascalError {
                 error_type: MascalErrorType::ParserError,
-                line: 0,
-                character: 0,
+                span: Some(var_decl.span),
                 source: String::from("Cannot redeclare the same variable in a variable block"),
             });
+            continue;
         }
         defined_var_names.insert(name);
     }
-    Ok(defined_var_names)
+    defined_var_names
 }
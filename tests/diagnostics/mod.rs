@@ -0,0 +1,116 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType, MascalWarning};
+use mascal::defs::span::Span;
+use mascal::diagnostics::{Diagnostic, render_error, render_warning};
+
+macro_rules! make_error {
+    ($line: expr, $character: expr) => {
+        MascalError {
+            error_type: MascalErrorType::TypeError,
+            span: Some(Span::new($line, $character)),
+            source: String::from("Something went wrong"),
+        }
+    };
+}
+
+#[rstest(
+    source, line, character, expected_line, expected_underline,
+    case("a <- b + c;", 0, 5, "1 | a <- b + c;", "  |      ^"),
+    case("a <- 1;\nfoo <- bar;", 1, 15, "2 | foo <- bar;", "  |        ^^^"),
+    case("a <- 1;\n\tb <- \"text\";", 1, 14, "2 | \tb <- \"text\";", "  | \t     ^^^^^^"),
+    case("a <- 1;\nb <- 2;", 1, 14, "2 | b <- 2;", "  |       ^"),
+)]
+fn test_snippet(source: &str, line: usize, character: usize, expected_line: &str, expected_underline: &str) {
+    let error: MascalError = make_error!(line, character);
+    let rendered: String = render_error(source, &error, false);
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines[0], "error[TypeError]: Something went wrong");
+    assert_eq!(lines[3], expected_line);
+    assert_eq!(lines[4], expected_underline);
+}
+
+#[test]
+fn test_location_header() {
+    let error: MascalError = make_error!(1, 12);
+    let rendered: String = render_error("a <- 1;\nb <- c;", &error, false);
+    assert_eq!(rendered.lines().nth(1).unwrap(), " --> 2:5");
+}
+
+#[test]
+fn test_help_and_notes() {
+    let error: MascalError = make_error!(0, 0);
+    let rendered: String = Diagnostic::new(&error)
+        .with_help("try this instead")
+        .with_note("this is why")
+        .render("a <- 1;", false);
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[6], "  = help: try this instead");
+    assert_eq!(lines[7], "  = note: this is why");
+}
+
+#[rstest(colored, case(true), case(false))]
+fn test_color_mode(colored: bool) {
+    let error: MascalError = make_error!(0, 0);
+    let rendered: String = render_error("a <- 1;", &error, colored);
+    assert_eq!(rendered.contains("\x1b["), colored);
+}
+
+#[test]
+fn test_out_of_bounds_position() {
+    let error: MascalError = make_error!(0, 100);
+    let rendered: String = render_error("a <- 1;", &error, false);
+    assert_eq!(rendered.lines().nth(4).unwrap(), "  |        ^");
+}
+
+#[test]
+fn test_unlocated_error() {
+    let error: MascalError = MascalError {
+        error_type: MascalErrorType::ParserError,
+        span: None,
+        source: String::from("Something went wrong"),
+    };
+    let rendered: String = render_error("a <- 1;", &error, false);
+    assert_eq!(rendered, "error[ParserError]: Something went wrong\n");
+}
+
+#[rstest(
+    span, expected_span,
+    case(None, Span::new(2, 7)),
+    case(Some(Span::new(0, 0)), Span::new(0, 0)),
+    case(Some(Span::new(1, 4)), Span::new(1, 4)),
+)]
+fn test_located_at(span: Option<Span>, expected_span: Span) {
+    let error: MascalError = MascalError {
+        error_type: MascalErrorType::TypeError,
+        span,
+        source: String::from("Something went wrong"),
+    };
+    assert_eq!(error.located_at(Span::new(2, 7)).span, Some(expected_span));
+}
+
+#[test]
+fn test_warning() {
+    let warning: MascalWarning = MascalWarning {
+        span: Some(Span::new(1, 8)),
+        source: String::from("Something looks off"),
+    };
+    let rendered: String = render_warning("a <- 1;
+CASE 2 {}", &warning, false);
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "warning: Something looks off");
+    assert_eq!(lines[1], " --> 2:1");
+    assert_eq!(lines[3], "2 | CASE 2 {}");
+    assert_eq!(lines[4], "  | ^^^^");
+}
+
+#[test]
+fn test_unlocated_warning() {
+    let warning: MascalWarning = MascalWarning {
+        span: None,
+        source: String::from("Something looks off"),
+    };
+    let rendered: String = render_warning("a <- 1;", &warning, false);
+    assert_eq!(rendered, "warning: Something looks off\n");
+}
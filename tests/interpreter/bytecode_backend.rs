@@ -0,0 +1,259 @@
+use rstest::rstest;
+use mascal::bytecode::compiler::compile_program;
+use mascal::defs::dynamic_int::IntegerNum;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::span::Span;
+use mascal::interpreter::{Backend, Interpreter, Program, RunOutcome};
+use mascal::runtime::io_streams::BufferedIO;
+use mascal::runtime::values::MascalValue;
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::define_program_boilerplate;
+
+type Observation = (Result<Vec<(String, String)>, (MascalErrorType, Option<Span>, String)>, String);
+
+fn run_on(backend: Backend, source: &str) -> Observation {
+    let io: Rc<RefCell<BufferedIO>> = Rc::new(RefCell::new(BufferedIO::new("")));
+    let interpreter: Interpreter = Interpreter::new().with_io(io.clone()).with_backend(backend);
+    let program: Program = interpreter.compile(source).unwrap();
+    let outcome: Result<RunOutcome, MascalError> = interpreter.run(&program);
+    let observed = outcome
+        .map(|outcome| {
+            let mut variables: Vec<(String, String)> = outcome
+                .variables()
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.as_string().unwrap()))
+                .collect();
+            variables.sort();
+            variables
+        })
+        .map_err(|err| (err.error_type, err.span, err.source));
+    (observed, io.borrow_mut().take_output())
+}
+
+/* Runs the source on both backends, making sure the VM actually ran it rather than falling back */
+fn assert_same_behaviour(source: &str) -> Observation {
+    let program: Program = Interpreter::new().compile(source).unwrap();
+    assert!(compile_program(program.abstract_syntax_tree(), &FxHashMap::default()).is_ok());
+    let tree_walker: Observation = run_on(Backend::TreeWalker, source);
+    let bytecode: Observation = run_on(Backend::Bytecode, source);
+    assert_eq!(tree_walker, bytecode);
+    bytecode
+}
+
+const FIBONACCI: &str = "
+DEFINE_FUNCTION fib(n) -> INTEGER {
+    VARIABLES {
+        INTEGER {n;}
+    }
+
+    IMPLEMENTATION {
+        IF n < 2 {
+            RETURN n;
+        }
+        RETURN fib(n - 1) + fib(n - 2);
+    }
+}";
+
+const PASCAL_STYLE: &str = "
+DEFINE_FUNCTION square(x) -> FLOAT {
+    VARIABLES {
+        FLOAT {x;}
+    }
+
+    IMPLEMENTATION {
+        square <- x * x;
+    }
+}";
+
+const MUTABLE_PARAMETERS: &str = "
+DEFINE_FUNCTION double_into(mut total, amount) -> INTEGER {
+    VARIABLES {
+        INTEGER {total; amount;}
+    }
+
+    IMPLEMENTATION {
+        total <- amount * 2;
+        RETURN total;
+    }
+}";
+
+#[rstest(
+    variables, code,
+    case(vec!["INTEGER {a <- 0; i;}"], vec!["FOR i FROM 1 TO 100 {a <- a + i;}"]),
+    case(vec!["INTEGER {a <- 1;}"], vec!["WHILE a < 1000 {a <- a * 3;}", "WRITE(a);"]),
+    case(
+        vec!["INTEGER {a <- 0; i; j;}"],
+        vec!["FOR i FROM 1 TO 5 {FOR j FROM i TO 5 {IF j = 4 {BREAK;} a <- a + j;}}"]
+    ),
+    case(
+        vec!["INTEGER {i; total <- 0;}"],
+        vec!["FOR i FROM 1 TO 10 {IF i % 2 = 0 {CONTINUE;} total <- total + i;}"]
+    ),
+    case(vec!["INTEGER {i;}"], vec!["FOR i FROM 10 TO 1 {WRITE(i);}", "WRITE(\"done\");"]),
+    case(vec!["FLOAT {f; total <- 0.0;}"], vec!["FOR f FROM 0 TO 1 WITH_STEP 0.25 {total <- total + f;}"]),
+    case(
+        vec!["INTEGER {a <- 7;}", "STRING {s;}"],
+        vec!["IF a > 10 {s <- \"big\";} ELIF a > 5 {s <- \"medium\";} ELSE {s <- \"small\";}"]
+    ),
+    case(
+        vec!["INTEGER {grid[3][2]; i; j;}"],
+        vec!["FOR i FROM 0 TO 2 {FOR j FROM 0 TO 1 {grid[i][j] <- i * 10 + j;}}", "WRITE(grid);"]
+    ),
+    case(
+        vec!["DYNAMIC {xs <- <<1, 2, 3>>; t;}"],
+        vec!["WRITE(LEN(xs), xs<<1>>, <<4, 5>><<-1>>);", "t <- TYPEOF xs;"]
+    ),
+    case(
+        vec!["STRING {s <- \"\";}", "INTEGER {i;}"],
+        vec!["FOR i FROM 1 TO 3 {s <- s + STRING(i);}", "WRITE(FLOAT(i), INTEGER(2.6));"]
+    ),
+    case(vec!["BOOLEAN {b;}"], vec!["b <- NOT (1 < 2 AND 3 <= 3) OR 2 ^ 3 = 8;"]),
+    case(vec!["INTEGER {a <- 1; b <- 2;}"], vec!["SWAP(a, b);", "WRITE(a, b);"]),
+    case(vec!["INTEGER {a <- 4;}"], vec!["MATCH a {CASE 1, 2 {a <- 0;} CASE 3 TO 5 {a <- 1;} DEFAULT {a <- 2;}}"]),
+    case(vec!["STRING {s <- \"b\";}"], vec!["MATCH s {CASE 1 TO 2 {s <- \"n\";} CASE TYPE STRING {s <- \"t\";}}"]),
+    case(
+        vec!["INTEGER {a <- 1;}", "STRING {s <- \"x\";}"],
+        vec!["SWAP(a, s);", "SWAP(a, a);", "a <- a + \"y\";", "WRITE(a, s);"]
+    ),
+)]
+fn test_backends_agree(variables: Vec<&str>, code: Vec<&str>) {
+    let source: String = define_program_boilerplate!(variables, code);
+    let (outcome, _) = assert_same_behaviour(source.as_str());
+    assert!(outcome.is_ok());
+}
+
+#[rstest(n, expected, case(0, "0"), case(1, "1"), case(15, "610"))]
+fn test_backends_agree_on_recursion(n: i32, expected: &str) {
+    let source: String = define_program_boilerplate!(
+        ["INTEGER {a;}"],
+        [format!("a <- fib({});", n)]
+    ) + FIBONACCI;
+    let (outcome, _) = assert_same_behaviour(source.as_str());
+    assert!(outcome.unwrap().contains(&(String::from("a"), String::from(expected))));
+}
+
+#[test]
+fn test_backends_agree_on_pascal_style_returns() {
+    let source: String =
+        define_program_boilerplate!(["FLOAT {a;}"], ["a <- square(1.5);"]) + PASCAL_STYLE;
+    let (outcome, _) = assert_same_behaviour(source.as_str());
+    assert!(outcome.unwrap().contains(&(String::from("a"), String::from("2.25"))));
+}
+
+#[rstest(
+    code, expected,
+    case(vec!["b <- double_into(a, 5);"], vec![("a", "10"), ("b", "10")]),
+    case(vec!["b <- double_into(a, double_into(a, 1));"], vec![("a", "4"), ("b", "4")]),
+    case(vec!["f <- FUNCTION(x) -> x + 1;", "b <- double_into(a, f(a));", "WRITE(f(a));"], vec![("a", "4"), ("b", "4")]),
+)]
+fn test_backends_agree_on_mutable_parameters(code: Vec<&str>, expected: Vec<(&str, &str)>) {
+    let source: String = define_program_boilerplate!(["INTEGER {a <- 1; b;}", "FUNCTION {f;}"], code)
+        + MUTABLE_PARAMETERS;
+    let (outcome, _) = assert_same_behaviour(source.as_str());
+    let variables: Vec<(String, String)> = outcome.unwrap();
+    for (name, value) in expected {
+        assert!(variables.contains(&(String::from(name), String::from(value))));
+    }
+}
+
+#[rstest(
+    code, expected_type,
+    case(vec!["b <- double_into(a + 1, 5);"], MascalErrorType::ArgumentError),
+    case(vec!["f <- double_into;", "b <- f(a, 1);"], MascalErrorType::ArgumentError),
+    case(vec!["b <- double_into(a);"], MascalErrorType::ArgumentError),
+)]
+fn test_backends_agree_on_mutable_parameter_errors(code: Vec<&str>, expected_type: MascalErrorType) {
+    let source: String = define_program_boilerplate!(["INTEGER {a <- 1; b;}", "FUNCTION {f;}"], code)
+        + MUTABLE_PARAMETERS;
+    let (outcome, _) = assert_same_behaviour(source.as_str());
+    assert_eq!(outcome.unwrap_err().0, expected_type);
+}
+
+#[rstest(
+    variables, code, expected_type,
+    case(vec!["INTEGER {a;}"], vec!["THROW ValueError: \"nope\";"], MascalErrorType::ValueError),
+    case(vec!["INTEGER {a;}"], vec!["WRITE(\"before\");", "a <- a + 1;"], MascalErrorType::RuntimeError),
+    case(vec!["INTEGER {a <- 1;}"], vec!["WHILE a {a <- 0;}"], MascalErrorType::RuntimeError),
+    case(vec!["INTEGER {xs[2];}"], vec!["xs[5] <- 1;"], MascalErrorType::IndexError),
+    case(vec!["INTEGER {a;}"], vec!["a <- <<1, 2>><<7>>;"], MascalErrorType::IndexError),
+    case(vec!["INTEGER {CONST a <- 1;}"], vec!["a <- 2;"], MascalErrorType::RuntimeError),
+    case(vec!["INTEGER {a;}"], vec!["a <- 1 / 0;"], MascalErrorType::UndefinedOperation),
+    case(vec!["INTEGER {a;}"], vec!["a <- LEN(1);"], MascalErrorType::TypeError),
+    case(vec!["DYNAMIC {d <- \"x\";}"], vec!["FOR d FROM 1 TO 2 {}"], MascalErrorType::TypeError),
+    case(vec!["INTEGER {a <- 4;}"], vec!["THROW IndexError: \"bad \" + STRING(a);"], MascalErrorType::IndexError),
+    case(vec!["INTEGER {a <- 4;}"], vec!["THROW IndexError: a;"], MascalErrorType::TypeError),
+    case(vec!["INTEGER {a;}"], vec!["READ(a);"], MascalErrorType::InputError),
+    case(vec!["INTEGER {xs[2];}"], vec!["READ(xs);"], MascalErrorType::RuntimeError),
+    case(vec!["INTEGER {a;}"], vec!["READ(a + 1);"], MascalErrorType::RuntimeError),
+    case(vec!["INTEGER {a <- 1;}"], vec!["SWAP(a);"], MascalErrorType::ArgumentError),
+    case(vec!["INTEGER {a <- 1;}"], vec!["SWAP(a, 2);"], MascalErrorType::ArgumentError),
+    case(vec!["INTEGER {a <- 1;}"], vec!["a <- 2;", "SWAP(a, 2 * a);"], MascalErrorType::ArgumentError),
+    case(vec!["INTEGER {a;}"], vec!["FOR EACH a IN 5 {}"], MascalErrorType::TypeError),
+    case(vec!["INTEGER {a <- 1;}"], vec!["MATCH a {CASE 2 {} CASE TYPE a {}}"], MascalErrorType::TypeError),
+    case(vec!["INTEGER {a <- 1;}"], vec!["MATCH a {CASE 2 TO 3 {} CASE 1 {a <- a / 0;}}"], MascalErrorType::UndefinedOperation),
+    case(vec!["INTEGER {a; xs<<2>>;}"], vec!["FOR EACH a IN xs {}"], MascalErrorType::ValueError),
+    case(vec!["INTEGER {CONST a <- 1;}"], vec!["FOR EACH a IN \"\" {}", "FOR EACH a IN <<2>> {}"], MascalErrorType::RuntimeError),
+)]
+fn test_backends_agree_on_errors(variables: Vec<&str>, code: Vec<&str>, expected_type: MascalErrorType) {
+    let source: String = define_program_boilerplate!(variables, code);
+    let (outcome, _) = assert_same_behaviour(source.as_str());
+    assert_eq!(outcome.unwrap_err().0, expected_type);
+}
+
+#[test]
+fn test_unsupported_programs_fall_back() {
+    let source: String = define_program_boilerplate!(
+        ["INTEGER {a <- 1; b <- 2;}"],
+        ["TRY {a <- b; b <- 1;} CATCH {WRITE(\"never\");}"]
+    );
+    let program: Program = Interpreter::new().compile(source.as_str()).unwrap();
+    let err: MascalError = compile_program(program.abstract_syntax_tree(), &FxHashMap::default())
+        .err()
+        .unwrap();
+    assert_eq!(err.error_type, MascalErrorType::UndefinedOperation);
+
+    let (outcome, _) = run_on(Backend::Bytecode, source.as_str());
+    assert_eq!(
+        outcome.unwrap(),
+        vec![(String::from("a"), String::from("2")), (String::from("b"), String::from("1"))]
+    );
+
+    let interpreter: Interpreter = Interpreter::new().with_backend(Backend::Bytecode);
+    let program: Program = interpreter.compile(source.as_str()).unwrap();
+    let reason: MascalError = interpreter.bytecode_fallback(&program).unwrap();
+    assert_eq!(reason.error_type, MascalErrorType::UndefinedOperation);
+    assert_eq!(interpreter.run(&program).unwrap().backend(), Backend::TreeWalker);
+}
+
+#[rstest]
+#[case::tree_walker(Backend::TreeWalker)]
+#[case::bytecode(Backend::Bytecode)]
+fn test_outcome_reports_the_backend_that_ran(#[case] backend: Backend) {
+    let source: String = define_program_boilerplate!(["INTEGER {a <- 1;}"], ["a <- a + 1;"]);
+    let interpreter: Interpreter = Interpreter::new().with_backend(backend);
+    let program: Program = interpreter.compile(source.as_str()).unwrap();
+    assert!(interpreter.bytecode_fallback(&program).is_none());
+    assert_eq!(interpreter.run(&program).unwrap().backend(), backend);
+}
+
+/* The natives end up in the bytecode, a program ran by an interpreter with other ones gets lowered again */
+#[test]
+fn test_bytecode_follows_the_native_functions() {
+    let source: String = define_program_boilerplate!(["INTEGER {a;}"], ["a <- Answer();"]);
+    let plain_interpreter: Interpreter = Interpreter::new().with_backend(Backend::Bytecode);
+    let program: Program = plain_interpreter.compile(source.as_str()).unwrap();
+    assert!(plain_interpreter.bytecode_fallback(&program).is_none());
+    assert_eq!(plain_interpreter.run(&program).unwrap_err().error_type, MascalErrorType::RuntimeError);
+
+    let mut interpreter: Interpreter = Interpreter::new().with_backend(Backend::Bytecode);
+    interpreter
+        .register_function("Answer", vec![], |_args, _exec_data| {
+            Ok(Some(MascalValue::Integer(IntegerNum::new(42))))
+        })
+        .unwrap();
+    let outcome: RunOutcome = interpreter.run(&program).unwrap();
+    assert_eq!(outcome.backend(), Backend::Bytecode);
+    assert_eq!(outcome.get_variable("a").unwrap().as_string().unwrap(), "42");
+}
@@ -0,0 +1,100 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::{Backend, Interpreter};
+use crate::interpreter::{TOUCH, VARIABLES};
+use crate::{program_source, run_captured};
+
+#[rstest(
+    code, expected_output,
+    case(vec!["n <- IF i > 2 THEN 10 ELSE 20;", "WRITE(n);"], "10\n"),
+    case(vec!["n <- IF i > 5 THEN 10 ELSE 20 + 1;", "WRITE(n);"], "21\n"),
+    case(vec!["n <- 1 + (IF b THEN 1 ELSE 2) * 10;", "WRITE(n);"], "11\n"),
+    case(vec!["WRITE(IF i < LEN(xs) THEN xs<<i>> ELSE -1);"], "-1\n"),
+    case(vec!["WRITE(IF b THEN touch(1) ELSE touch(2));"], "touched 1\n1\n"),
+    case(vec!["WRITE(IF NOT b THEN touch(1) ELSE touch(2));"], "touched 2\n2\n"),
+    case(vec!["FOR n FROM 1 TO 3 {WRITE(IF n == 1 THEN \"one\" ELSE IF n == 2 THEN \"two\" ELSE \"many\");}"], "one\ntwo\nmany\n"),
+    case(vec!["f <- IF b THEN 1 ELSE 2.5;", "WRITE(f);"], "1\n"),
+    case(vec!["IF b {s <- IF i > 1 THEN \"big\" ELSE \"small\";} ELSE {s <- \"none\";}", "WRITE(s);"], "big\n"),
+    case(vec!["IF NOT b {IF b {s <- \"inner\";} ELSE {s <- \"inner else\";}} ELSE {s <- \"outer else\";}", "WRITE(s);"], "outer else\n"),
+)]
+fn test_conditional_expression(code: Vec<&str>, expected_output: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", TOUCH, VARIABLES, code.clone());
+        assert!(outcome.is_ok(), "{:?}", outcome);
+        assert_eq!(output, expected_output);
+    }
+}
+
+#[test]
+fn test_non_boolean_condition() {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", TOUCH, VARIABLES, ["d <- 1;", "WRITE(IF d THEN touch(1) ELSE touch(2));"]);
+        let err: MascalError = outcome.unwrap_err();
+        assert_eq!(err.error_type, MascalErrorType::RuntimeError);
+        assert_eq!(err.source, "Expected a boolean variable on the condition but got \"1\"");
+        assert_eq!(output, "");
+    }
+}
+
+#[rstest(
+    code, expected_source,
+    case(vec!["n <- IF b THEN 1 ELSE \"none\";"], "The ELSE branch gives a string but \"n\" can only hold an integer"),
+    case(vec!["s <- IF b THEN i ELSE \"none\";"], "The THEN branch gives an integer but \"s\" can only hold a string"),
+    case(vec!["b <- IF i > 0 THEN TRUE ELSE IF i < 0 THEN FALSE ELSE 0;"], "The ELSE branch gives an integer but \"b\" can only hold a boolean"),
+    case(vec!["f <- IF b THEN s ELSE 1.5;"], "The THEN branch gives a string but \"f\" can only hold a float"),
+)]
+fn test_incompatible_branches(code: Vec<&str>, expected_source: &str) {
+    let errors: Vec<MascalError> = Interpreter::new().compile(program_source!(TOUCH, VARIABLES, code).as_str()).unwrap_err();
+    assert_eq!(errors[0].error_type, MascalErrorType::TypeError);
+    assert_eq!(errors[0].source, expected_source);
+}
+
+#[rstest(
+    variables, code, expected_source,
+    case(vec!["BOOLEAN {b;}"], vec!["b <- IF 5 THEN 1 ELSE 2;"], "Expected a boolean on the condition of an IF expression but got an integer"),
+    case(vec!["STRING {s;}"], vec!["WRITE(IF s THEN 1 ELSE 2);"], "Expected a boolean on the condition of an IF expression but got a string"),
+    case(vec!["INTEGER {n <- IF 1.5 + 1 THEN 1 ELSE 2;}"], vec!["WRITE(n);"], "Expected a boolean on the condition of an IF expression but got a float"),
+)]
+fn test_known_non_boolean_condition(variables: Vec<&str>, code: Vec<&str>, expected_source: &str) {
+    let errors: Vec<MascalError> = Interpreter::new().compile(program_source!(TOUCH, variables, code).as_str()).unwrap_err();
+    assert_eq!(errors[0].error_type, MascalErrorType::TypeError);
+    assert_eq!(errors[0].source, expected_source);
+}
+
+#[rstest(
+    return_type, code, expected_source,
+    case("INTEGER", "RETURN IF x > 0 THEN x ELSE \"none\";", "The ELSE branch gives a string but an integer has to be returned"),
+    case("FLOAT", "RETURN IF x > 0 THEN 1.5 ELSE 0;", "The ELSE branch gives an integer but a float has to be returned"),
+    case("STRING", "RETURN IF x > 0 THEN \"up\" ELSE IF x < 0 THEN TRUE ELSE \"flat\";", "The THEN branch gives a boolean but a string has to be returned"),
+)]
+fn test_returned_branches(return_type: &str, code: &str, expected_source: &str) {
+    let function: String = format!(
+        "DEFINE_FUNCTION pick(x) -> {return_type} {{VARIABLES {{INTEGER {{x;}}}} IMPLEMENTATION {{{code}}}}}\n"
+    );
+    let errors: Vec<MascalError> = Interpreter::new()
+        .compile((function + &program_source!(TOUCH, VARIABLES, ["WRITE(pick(1));"])).as_str())
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error_type, MascalErrorType::TypeError);
+    assert_eq!(errors[0].source, expected_source);
+}
+
+#[test]
+fn test_incompatible_initial_value() {
+    let errors: Vec<MascalError> = Interpreter::new()
+        .compile(program_source!(TOUCH, ["STRING {t <- IF TRUE THEN 1 ELSE \"x\";}"], ["WRITE(t);"]).as_str())
+        .unwrap_err();
+    assert_eq!(errors[0].error_type, MascalErrorType::TypeError);
+    assert_eq!(errors[0].source, "The THEN branch gives an integer but \"t\" can only hold a string");
+}
+
+#[rstest(
+    code,
+    case(vec!["f <- IF b THEN 1 ELSE 2.5;"]),
+    case(vec!["n <- IF b THEN touch(1) ELSE xs<<0>>;"]),
+    case(vec!["d <- IF b THEN 1 ELSE \"one\";"]),
+    case(vec!["WRITE(IF b THEN 1 ELSE \"one\");"]),
+)]
+fn test_compatible_branches(code: Vec<&str>) {
+    assert!(Interpreter::new().compile(program_source!(TOUCH, VARIABLES, code).as_str()).is_ok());
+}
@@ -0,0 +1,85 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::{Backend, Interpreter};
+use crate::define_program_boilerplate;
+use crate::run_captured;
+
+const ENUMS: &str = "
+DEFINE_ENUM Color { RED, GREEN, BLUE }
+
+DEFINE_ENUM Shape {
+    SQUARE,
+    CIRCLE,
+}
+
+DEFINE_RECORD Pixel {
+    VARIABLES {
+        Color {color <- Color.BLUE;}
+    }
+}
+
+DEFINE_FUNCTION next(c) -> Color {
+    VARIABLES {
+        Color {c;}
+    }
+
+    IMPLEMENTATION {
+        RETURN Color((INTEGER(c) + 1) % 3);
+    }
+}
+";
+
+#[rstest(
+    code, expected_output,
+    case(vec!["c <- Color.GREEN;", "WRITE(c, TYPEOF(c));"], "GREEN Color\n"),
+    case(vec!["WRITE(Color.RED == Color.RED, Color.RED != Color.BLUE, Color.RED == Shape.SQUARE);"], "TRUE TRUE FALSE\n"),
+    case(vec!["WRITE(Color.RED < Color.GREEN, Color.BLUE <= Color.GREEN, Color.BLUE > Color.RED);"], "TRUE FALSE TRUE\n"),
+    case(vec!["WRITE(INTEGER(Color.BLUE), STRING(Color.BLUE));"], "2 BLUE\n"),
+    case(vec!["WRITE(Color(0), Color(\"GREEN\"), Color(Color.BLUE));"], "RED GREEN BLUE\n"),
+    case(vec!["FOR c FROM Color.RED TO Color.BLUE {", "WRITE(c, next(c));", "}"], "RED GREEN\nGREEN BLUE\nBLUE RED\n"),
+    case(vec!["FOR c FROM Color.RED TO Color.BLUE WITH_STEP 2 {", "WRITE(c);", "}"], "RED\nBLUE\n"),
+    case(vec!["FOR c FROM Color.BLUE DOWNTO Color.RED {", "WRITE(c);", "}"], "BLUE\nGREEN\nRED\n"),
+    case(vec!["WRITE(p.color);", "p.color <- Color.RED;", "WRITE(p.color);"], "BLUE\nRED\n"),
+    case(vec!["m <- [Color.RED: \"warm\", Shape.SQUARE: \"boxy\"];", "WRITE(m[Color(\"RED\")], m[Shape.SQUARE]);"], "warm boxy\n"),
+)]
+fn test_enums(code: Vec<&str>, expected_output: &str) {
+    let (outcome, output) = run_captured!(Backend::TreeWalker, "", ENUMS, ["Color {c;}", "Pixel {p;}", "DYNAMIC {m;}"], code);
+    assert!(outcome.is_ok());
+    assert_eq!(output, expected_output);
+}
+
+#[rstest(
+    code, expected_type, expected_source,
+    case(vec!["c <- 1;"], MascalErrorType::TypeError, "Expected a value of the enum Color for \"c\" but got INTEGER"),
+    case(vec!["c <- Shape.SQUARE;"], MascalErrorType::TypeError, "Expected a value of the enum Color for \"c\" but got Shape"),
+    case(vec!["WRITE(Color.PURPLE);"], MascalErrorType::RuntimeError, "The enum Color has no variant named \"PURPLE\""),
+    case(vec!["WRITE(Color(3));"], MascalErrorType::ValueError, "The enum Color has no variant at the position 3 (it only has 3)"),
+    case(vec!["WRITE(Color(\"PURPLE\"));"], MascalErrorType::ValueError, "The enum Color has no variant named \"PURPLE\""),
+    case(vec!["WRITE(Color());"], MascalErrorType::ArgumentError, "Expected one value but got none or multiple values"),
+    case(
+        vec!["WRITE(Color.RED < Shape.SQUARE);"],
+        MascalErrorType::UndefinedOperation,
+        "Cannot operate the \"less than(<)\" operation between the types \"Color\" and \"Shape\""
+    ),
+    case(vec!["FOR i FROM Color.RED TO Color.BLUE {}"], MascalErrorType::TypeError, "Variable \"i\" is not assigned as the enum Color"),
+)]
+fn test_enum_errors(code: Vec<&str>, expected_type: MascalErrorType, expected_source: &str) {
+    let (outcome, _) = run_captured!(Backend::TreeWalker, "", ENUMS, ["Color {c;}", "INTEGER {i;}"], code);
+    let err: MascalError = outcome.unwrap_err();
+    assert_eq!(err.error_type, expected_type);
+    assert_eq!(err.source, expected_source);
+}
+
+#[rstest(
+    input, expected_source,
+    case("DEFINE_ENUM Color { RED } DEFINE_ENUM Color { BLUE }", "The enum \"Color\" has already been defined"),
+    case("DEFINE_RECORD Color {VARIABLES {INTEGER {x;}}} DEFINE_ENUM Color { RED }", "The enum \"Color\" shares its name with a record"),
+    case("DEFINE_ENUM f { RED } DEFINE_FUNCTION f() {VARIABLES {} IMPLEMENTATION {}}", "The function \"f\" shares its name with an enum"),
+)]
+fn test_enum_semantic_errors(input: &str, expected_source: &str) {
+    let source: String = String::from(input)
+        + &define_program_boilerplate!(Vec::<String>::new(), Vec::<String>::new());
+    let errors: Vec<MascalError> = Interpreter::new().compile(source.as_str()).unwrap_err();
+    assert_eq!(errors[0].error_type, MascalErrorType::ParserError);
+    assert_eq!(errors[0].source, expected_source);
+}
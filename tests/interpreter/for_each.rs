@@ -0,0 +1,64 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::Backend;
+use crate::run_captured;
+
+const FIRST_EVEN: &str = "
+DEFINE_FUNCTION first_even(xs) -> INTEGER {
+    VARIABLES {
+        INTEGER {xs<<>>; x;}
+    }
+
+    IMPLEMENTATION {
+        FOR EACH x IN xs {
+            IF x % 2 == 0 {
+                RETURN x;
+            }
+        }
+        RETURN -1;
+    }
+}
+";
+
+const VARIABLES: [&str; 3] = [
+    "INTEGER {nums<<5>> <- <<3, 1, 4, 1, 5>>; n; i; total <- 0; CONST k <- 0;}",
+    "STRING {c;}",
+    "DYNAMIC {grow<<>> <- <<1, 2>>; row<<>>; cells<<2>>;}",
+];
+
+#[rstest(
+    code, expected_output,
+    case(vec!["FOR EACH n IN nums {total <- total + n;}", "WRITE(total, n);"], "14 5\n"),
+    case(vec!["FOR EACH n IN nums {IF n == 1 {CONTINUE;} IF n == 5 {BREAK;} WRITE(n);}"], "3\n4\n"),
+    case(vec!["FOR EACH i, c IN \"abc\" {WRITE(i, c);}"], "0 a\n1 b\n2 c\n"),
+    case(vec!["FOR EACH c IN \"\" {WRITE(c);}", "FOR EACH n IN <<>> {WRITE(n);}", "WRITE(\"done\");"], "done\n"),
+    case(vec!["FOR EACH n IN grow {grow <- Push(grow, n);}", "WRITE(grow);"], "<<1, 2, 1, 2>>\n"),
+    case(vec!["FOR EACH row IN <<<<1, 2>>, <<3>>>> {WRITE(row);}"], "<<1, 2>>\n<<3>>\n"),
+    case(vec!["FOR EACH i, n IN nums {FOR EACH c IN \"xy\" {IF c == \"y\" {BREAK;} total <- total + i;}}", "WRITE(total);"], "10\n"),
+    case(vec!["WRITE(first_even(<<1, 3, 8, 10>>), first_even(<<1>>));"], "8 -1\n"),
+    case(vec!["FOR EACH n IN Map(nums, FUNCTION(x) -> x * 10) {total <- total + n;}", "WRITE(total);"], "140\n"),
+)]
+fn test_for_each(code: Vec<&str>, expected_output: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", FIRST_EVEN, VARIABLES, code.clone());
+        assert!(outcome.is_ok());
+        assert_eq!(output, expected_output);
+    }
+}
+
+#[rstest(
+    code, expected_type, expected_source,
+    case(vec!["FOR EACH n IN 5 {}"], MascalErrorType::TypeError, "Expected an array or a string to loop over but got INTEGER"),
+    case(vec!["FOR EACH k IN nums {}"], MascalErrorType::RuntimeError, "Cannot assign a new value to the constant variable called \"k\""),
+    case(vec!["FOR EACH missing IN nums {}"], MascalErrorType::RuntimeError, "Expected a variable name, however got an unknown one called \"missing\""),
+    case(vec!["FOR EACH n IN cells {}"], MascalErrorType::ValueError, "Uninitialized cell in an array has been detected"),
+    case(vec!["FOR EACH n IN <<<<1>>>> {}"], MascalErrorType::TypeError, "Cannot assign an array to a value that hasn't initialized an array size"),
+)]
+fn test_for_each_errors(code: Vec<&str>, expected_type: MascalErrorType, expected_source: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, _) = run_captured!(backend, "", FIRST_EVEN, VARIABLES, code.clone());
+        let err: MascalError = outcome.unwrap_err();
+        assert_eq!(err.error_type, expected_type);
+        assert_eq!(err.source, expected_source);
+    }
+}
@@ -0,0 +1,46 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::Backend;
+use crate::run_captured;
+
+const VARIABLES: [&str; 2] = ["INTEGER {i; j; step <- 0;}", "FLOAT {f;}"];
+
+#[rstest(
+    code, expected_output,
+    case(vec!["FOR i FROM 3 TO 1 WITH_STEP -1 {WRITE(i);}"], "3\n2\n1\n"),
+    case(vec!["FOR i FROM 10 TO 1 WITH_STEP -4 {WRITE(i);}"], "10\n6\n2\n"),
+    case(vec!["FOR i FROM 3 DOWNTO 1 {WRITE(i);}"], "3\n2\n1\n"),
+    case(vec!["FOR i FROM 10 downto 0 WITH_STEP 5 {WRITE(i);}"], "10\n5\n0\n"),
+    case(vec!["FOR i FROM 1 DOWNTO 3 {WRITE(i);}", "WRITE(\"done\");"], "done\n"),
+    case(vec!["FOR i FROM 1 TO 3 WITH_STEP -1 {WRITE(i);}", "WRITE(\"done\");"], "done\n"),
+    case(vec!["FOR i FROM 5 DOWNTO 1 {IF i % 2 == 0 {CONTINUE;} IF i == 1 {BREAK;} WRITE(i);}"], "5\n3\n"),
+    case(vec!["FOR f FROM 1 DOWNTO 0 WITH_STEP 0.5 {WRITE(f);}"], "1.0\n0.5\n0.0\n"),
+    case(vec!["FOR f FROM 0 TO 1 WITH_STEP 0.25 {IF f == 0.5 {CONTINUE;} IF f > 0.7 {BREAK;} WRITE(f);}"], "0.0\n0.25\n"),
+    case(vec!["outer: FOR f FROM 0 TO 2 {FOR j FROM 1 TO 3 {IF j == 2 {CONTINUE outer;} WRITE(f, j);}}"], "0.0 1\n1.0 1\n2.0 1\n"),
+    case(vec!["outer: FOR i FROM 3 DOWNTO 1 {FOR f FROM 0 TO 1 WITH_STEP 0.5 {IF i == 2 {BREAK outer;} WRITE(i, f);}}"], "3 0.0\n3 0.5\n3 1.0\n"),
+)]
+fn test_for(code: Vec<&str>, expected_output: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", "", VARIABLES, code.clone());
+        assert!(outcome.is_ok(), "{:?}", outcome);
+        assert_eq!(output, expected_output);
+    }
+}
+
+#[rstest(
+    code,
+    case(vec!["FOR i FROM 1 TO 3 WITH_STEP 0 {WRITE(i);}"]),
+    case(vec!["FOR i FROM 1 TO 3 WITH_STEP step {WRITE(i);}"]),
+    case(vec!["FOR i FROM 3 DOWNTO 1 WITH_STEP step {WRITE(i);}"]),
+    case(vec!["FOR f FROM 0 TO 1 WITH_STEP 0.0 {WRITE(f);}"]),
+)]
+fn test_zero_step(code: Vec<&str>) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", "", VARIABLES, code.clone());
+        let err: MascalError = outcome.unwrap_err();
+        assert_eq!(err.error_type, MascalErrorType::ValueError);
+        assert_eq!(err.source, "The step of a FOR loop cannot be zero");
+        assert_eq!(err.span.unwrap().line, 7);
+        assert_eq!(output, "");
+    }
+}
@@ -0,0 +1,97 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::Backend;
+use crate::run_captured;
+
+const FUNCTIONS: &str = "
+DEFINE_FUNCTION square(x) -> INTEGER {
+    VARIABLES {
+        INTEGER {x;}
+    }
+
+    IMPLEMENTATION {
+        RETURN x * x;
+    }
+}
+
+DEFINE_FUNCTION apply(f, x) -> DYNAMIC {
+    VARIABLES {
+        FUNCTION {f;}
+        DYNAMIC {x;}
+    }
+
+    IMPLEMENTATION {
+        RETURN f(x);
+    }
+}
+
+DEFINE_FUNCTION make_adder(n) -> FUNCTION {
+    VARIABLES {
+        INTEGER {n;}
+    }
+
+    IMPLEMENTATION {
+        RETURN FUNCTION(x) -> x + n;
+    }
+}
+
+DEFINE_FUNCTION bump(mut x) {
+    VARIABLES {
+        INTEGER {x;}
+    }
+
+    IMPLEMENTATION {
+        x <- 1;
+    }
+}
+";
+
+#[rstest(
+    code, expected_output,
+    case(vec!["WRITE(f(3), f, TYPEOF(f));"], "9 square FUNCTION\n"),
+    case(vec!["g <- FUNCTION(a, b) -> a * b + n;", "WRITE(g(2, 3), g);"], "16 FUNCTION(a, b)\n"),
+    case(vec!["g <- FUNCTION() -> n;", "n <- 100;", "WRITE(g(), n);"], "10 100\n"),
+    case(vec!["WRITE(apply(square, 5), apply(FUNCTION(s) -> s + \"!\", \"hi\"));"], "25 hi!\n"),
+    case(vec!["g <- make_adder(7);", "WRITE(g(1), make_adder(2)(3));"], "8 5\n"),
+    case(vec!["fs <- <<square, Len, FUNCTION() -> \"none\">>;", "WRITE(fs<<0>>(4), fs<<1>>(<<1, 2>>), fs<<2>>());"], "16 2 none\n"),
+    case(vec!["g <- square;", "WRITE(f == g, f == make_adder(1), f != Len);"], "TRUE FALSE TRUE\n"),
+    case(vec!["g <- FUNCTION(x) -> x;", "h <- g;", "WRITE(g == h, g == FUNCTION(x) -> x);"], "TRUE FALSE\n"),
+    case(vec!["g <- FUNCTION(x) -> x;", "WRITE(TYPEOF(g), TYPEOF(Len));"], "FUNCTION FUNCTION\n"),
+)]
+fn test_function_values(code: Vec<&str>, expected_output: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(
+            backend,
+            "",
+            FUNCTIONS,
+            ["FUNCTION {f <- square; g; h; fs<<>>;}", "INTEGER {n <- 10;}"],
+            code.clone()
+        );
+        assert!(outcome.is_ok());
+        assert_eq!(output, expected_output);
+    }
+}
+
+#[rstest(
+    code, expected_type, expected_source,
+    case(vec!["f <- 3;"], MascalErrorType::TypeError, "Expected a value of type FUNCTION for \"f\" but got INTEGER"),
+    case(vec!["f <- FUNCTION(a) -> a;", "f(1, 2);"], MascalErrorType::ArgumentError, "Expected 1 argument(s) but got 2 argument(s) instead"),
+    case(vec!["f <- READ;", "f();"], MascalErrorType::ArgumentError, "The builtin \"READ\" can only be called by its name"),
+    case(vec!["f <- bump;", "f(n);"], MascalErrorType::ArgumentError, "The function \"bump\" has mutable parameters, so it can only be called by its name"),
+    case(vec!["f <- Len;", "f(1);"], MascalErrorType::TypeError, "Expected at least one of the types \"STATIC_ARRAY\", \"DYNAMIC_ARRAY\", \"MAP\" but got \"INTEGER\""),
+    case(vec!["d <- [f: 1];"], MascalErrorType::TypeError, "A value of type FUNCTION cannot be used as the key of a map"),
+)]
+fn test_function_value_errors(code: Vec<&str>, expected_type: MascalErrorType, expected_source: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, _) = run_captured!(
+            backend,
+            "",
+            FUNCTIONS,
+            ["FUNCTION {f <- square;}", "INTEGER {n <- 1;}", "DYNAMIC {d;}"],
+            code.clone()
+        );
+        let err: MascalError = outcome.unwrap_err();
+        assert_eq!(err.error_type, expected_type);
+        assert_eq!(err.source, expected_source);
+    }
+}
@@ -0,0 +1,76 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::Backend;
+use crate::run_captured;
+
+const FUNCTIONS: &str = "
+DEFINE_FUNCTION square(x) -> INTEGER {
+    VARIABLES {
+        INTEGER {x;}
+    }
+
+    IMPLEMENTATION {
+        RETURN x * x;
+    }
+}
+
+DEFINE_FUNCTION is_even(x) -> BOOLEAN {
+    VARIABLES {
+        INTEGER {x;}
+    }
+
+    IMPLEMENTATION {
+        RETURN x % 2 == 0;
+    }
+}
+";
+
+const VARIABLES: [&str; 2] = [
+    "INTEGER {nums<<5>> <- <<3, 1, 4, 1, 5>>;}",
+    "DYNAMIC {words<<>> <- <<\"pear\", \"fig\", \"apple\">>; sorted<<>>;}",
+];
+
+#[rstest(
+    code, expected_output,
+    case(vec!["WRITE(Map(nums, square), Map(words, To_Upper));"], "<<9, 1, 16, 1, 25>> <<\"PEAR\", \"FIG\", \"APPLE\">>\n"),
+    case(vec!["WRITE(MAP(<<1.5>>, FUNCTION(x) -> x * 2), map(<<>>, square));"], "<<3.0>> <<>>\n"),
+    case(vec!["WRITE(Filter(nums, FUNCTION(x) -> x > 2), Filter(nums, is_even));"], "<<3, 4, 5>> <<4>>\n"),
+    case(vec!["WRITE(Reduce(nums, FUNCTION(acc, x) -> acc + x, 0), Reduce(words, FUNCTION(acc, w) -> acc + w, \"\"));"], "14 pearfigapple\n"),
+    case(vec!["WRITE(Sort(nums), nums, Sort(words));"], "<<1, 1, 3, 4, 5>> <<3, 1, 4, 1, 5>> <<\"apple\", \"fig\", \"pear\">>\n"),
+    case(vec!["WRITE(Sort(<<2.5, 1, 0.5>>), 1 < 2.5, 2.5 < 1);"], "<<0.5, 1, 2.5>> TRUE FALSE\n"),
+    case(vec!["WRITE(Sort_By(words, FUNCTION(a, b) -> a > b), Sort_By(<<>>, FUNCTION(a, b) -> a < b));"], "<<\"pear\", \"fig\", \"apple\">> <<>>\n"),
+    case(
+        vec!["WRITE(Sort_By(<<<<2, 0>>, <<1, 1>>, <<2, 2>>, <<1, 3>>>>, FUNCTION(a, b) -> a<<0>> < b<<0>>));"],
+        "<<<<1, 1>>, <<1, 3>>, <<2, 0>>, <<2, 2>>>>\n"
+    ),
+    case(vec!["WRITE(Any(nums, is_even), Any(nums, FUNCTION(x) -> x > 5), Any(<<>>, is_even));"], "TRUE FALSE FALSE\n"),
+    case(vec!["WRITE(All(nums, FUNCTION(x) -> x > 0), All(nums, is_even), All(<<>>, is_even));"], "TRUE FALSE TRUE\n"),
+    case(vec!["WRITE(Find(words, FUNCTION(w) -> w == \"fig\"), Find(nums, FUNCTION(x) -> x > 10));"], "fig NULL\n"),
+    case(vec!["sorted <- Sort(nums);", "sorted<<0>> <- 99;", "WRITE(sorted, nums);"], "<<99, 1, 3, 4, 5>> <<3, 1, 4, 1, 5>>\n"),
+)]
+fn test_higher_order_builtins(code: Vec<&str>, expected_output: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", FUNCTIONS, VARIABLES, code.clone());
+        assert!(outcome.is_ok());
+        assert_eq!(output, expected_output);
+    }
+}
+
+#[rstest(
+    code, expected_type, expected_source,
+    case(vec!["Map(1, square);"], MascalErrorType::TypeError, "Expected at least one of the types \"STATIC_ARRAY\", \"DYNAMIC_ARRAY\" but got \"INTEGER\""),
+    case(vec!["Map(nums, 1);"], MascalErrorType::TypeError, "Expected a type of \"FUNCTION\" but got \"INTEGER\""),
+    case(vec!["Reduce(nums, FUNCTION(acc, x) -> acc + x);"], MascalErrorType::ArgumentError, "Expected at least 3 argument(s) but got 2 argument(s) instead"),
+    case(vec!["Filter(nums, square);"], MascalErrorType::TypeError, "Expected the function \"square\" to give back a BOOLEAN but got INTEGER"),
+    case(vec!["Any(nums, FUNCTION(x) -> x);"], MascalErrorType::TypeError, "Expected the function passed in to give back a BOOLEAN but got INTEGER"),
+    case(vec!["Map(nums, FUNCTION(a, b) -> a);"], MascalErrorType::ArgumentError, "Expected 2 argument(s) but got 1 argument(s) instead"),
+    case(vec!["Sort(<<1, \"a\">>);"], MascalErrorType::UndefinedOperation, "Cannot operate the \"less than(<)\" operation between the types \"STRING\" and \"INTEGER\""),
+)]
+fn test_higher_order_builtin_errors(code: Vec<&str>, expected_type: MascalErrorType, expected_source: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, _) = run_captured!(backend, "", FUNCTIONS, VARIABLES, code.clone());
+        let err: MascalError = outcome.unwrap_err();
+        assert_eq!(err.error_type, expected_type);
+        assert_eq!(err.source, expected_source);
+    }
+}
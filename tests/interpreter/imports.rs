@@ -0,0 +1,174 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::ast::SourceFile;
+use mascal::defs::span::Span;
+use mascal::interpreter::{Backend, Interpreter, Program};
+use mascal::runtime::io_streams::BufferedIO;
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/interpreter/imports").join(name)
+}
+
+fn compile_fixture_source(source: &str) -> Result<Program, Vec<MascalError>> {
+    Interpreter::new().compile_file(source, &fixture("entry.mascal"))
+}
+
+#[test]
+fn test_imported_blocks_are_merged() {
+    let path: PathBuf = fixture("main.mascal");
+    let source: String = fs::read_to_string(&path).unwrap();
+    let io: Rc<RefCell<BufferedIO>> = Rc::new(RefCell::new(BufferedIO::new("")));
+    let interpreter: Interpreter = Interpreter::new().with_io(io.clone());
+    let program: Program = interpreter.compile_file(&source, &path).unwrap();
+    assert!(interpreter.run(&program).is_ok());
+    assert_eq!(io.borrow_mut().take_output(), "SQUARE 9.0\n3 is odd\n");
+}
+
+#[test]
+fn test_imports_relative_to_current_directory() {
+    let source: &str = "
+    IMPORT \"tests/interpreter/imports/lib/math.mascal\";
+    DEFINE_PROGRAM {
+        VARIABLES {
+            INTEGER {a;}
+        }
+        IMPLEMENTATION {
+            a <- square(7);
+        }
+    }";
+    let interpreter: Interpreter = Interpreter::new();
+    let program: Program = interpreter.compile(source).unwrap();
+    let outcome = interpreter.run(&program).unwrap();
+    assert_eq!(outcome.get_variable("a").unwrap().as_string().unwrap(), "49");
+}
+
+#[rstest(
+    source, expected_line, expected_source,
+    case(
+        "IMPORT \"missing.mascal\";",
+        0,
+        "The imported file \"missing.mascal\" could not be found"
+    ),
+    case(
+        "\nIMPORT \"cycle/a.mascal\";",
+        1,
+        "In \"cycle/a.mascal\" at line 1: In \"cycle/b.mascal\" at line 1: Import cycle detected: cycle/a.mascal -> cycle/b.mascal -> cycle/a.mascal"
+    ),
+    case(
+        "IMPORT \"collision/one.mascal\";\nIMPORT \"collision/two.mascal\";",
+        1,
+        "In \"collision/two.mascal\" at line 1: The record \"helper\" collides with the function of the same name from \"collision/one.mascal\""
+    ),
+    case(
+        "IMPORT \"lib/math.mascal\";\nDEFINE_FUNCTION square() {VARIABLES {} IMPLEMENTATION {}}",
+        1,
+        "The function \"square\" collides with the function of the same name from \"lib/math.mascal\""
+    ),
+    case(
+        "IMPORT \"program/lib.mascal\";",
+        0,
+        "In \"program/lib.mascal\" at line 1: Only the entry file can define a program, imported files are limited to functions, records, enums and errors"
+    ),
+    case(
+        "IMPORT \"lib/math.mascal\";\nERRORS {OddError;}",
+        1,
+        "The error type \"OddError\" has already been defined"
+    ),
+    case("IMPORT lib;", 0, "Expected the path of the imported file in quotes after IMPORT"),
+    case("IMPORT \"lib/math.mascal\"", 0, "Expected a semicolon after the imported path \"lib/math.mascal\""),
+)]
+fn test_import_errors(source: &str, expected_line: usize, expected_source: &str) {
+    let source: String = String::from(source) + "\nDEFINE_PROGRAM {VARIABLES {} IMPLEMENTATION {}}";
+    let errors: Vec<MascalError> = compile_fixture_source(&source).unwrap_err();
+    assert_eq!(errors[0].error_type, MascalErrorType::ParserError);
+    assert_eq!(errors[0].span.unwrap().line, expected_line);
+    assert_eq!(errors[0].source, expected_source);
+}
+
+#[test]
+fn test_import_inside_block() {
+    let source: &str = "DEFINE_PROGRAM {VARIABLES {} IMPLEMENTATION {IMPORT \"lib/math.mascal\";}}";
+    let errors: Vec<MascalError> = compile_fixture_source(source).unwrap_err();
+    assert_eq!(errors[0].source, "Imports can only be placed at the top level of a file, outside of any block");
+}
+
+#[test]
+fn test_thrown_imported_error_type() {
+    let source: &str = "
+    IMPORT \"lib/shapes.mascal\";
+    DEFINE_PROGRAM {
+        VARIABLES {}
+        IMPLEMENTATION {
+            THROW OddError: \"reached through shapes\";
+        }
+    }";
+    let interpreter: Interpreter = Interpreter::new();
+    let program: Program = interpreter.compile_file(source, &fixture("entry.mascal")).unwrap();
+    let err: MascalError = interpreter.run(&program).unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::Custom("OddError".into()));
+    assert_eq!(err.source, "reached through shapes");
+}
+
+#[rstest]
+#[case::tree_walker(Backend::TreeWalker)]
+#[case::bytecode(Backend::Bytecode)]
+fn test_runtime_errors_point_into_the_imported_file(#[case] backend: Backend) {
+    let source: &str = "IMPORT \"lib/lists.mascal\";
+    DEFINE_PROGRAM {
+        VARIABLES {
+            INTEGER {a;}
+        }
+        IMPLEMENTATION {
+            a <- pick(7);
+        }
+    }";
+    let interpreter: Interpreter = Interpreter::new().with_backend(backend);
+    let program: Program = interpreter.compile_file(source, &fixture("entry.mascal")).unwrap();
+    let err: MascalError = interpreter.run(&program).unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::IndexError);
+    let span: Span = err.span.unwrap();
+    let file: &SourceFile = program.imported_file(span).unwrap();
+    assert_eq!(file.path, "lib/lists.mascal");
+    assert_eq!(span.line, 6);
+    assert_eq!(file.text.lines().nth(span.line).unwrap().trim(), "RETURN xs<<i>>;");
+    assert_eq!(file.imported_at, Some(Span::new(0, 0)));
+}
+
+#[test]
+fn test_entry_file_errors_are_not_imported() {
+    let source: &str = "IMPORT \"lib/lists.mascal\";
+    DEFINE_PROGRAM {
+        VARIABLES {}
+        IMPLEMENTATION {
+            THROW ValueError: \"from the entry file\";
+        }
+    }";
+    let interpreter: Interpreter = Interpreter::new();
+    let program: Program = interpreter.compile_file(source, &fixture("entry.mascal")).unwrap();
+    let err: MascalError = interpreter.run(&program).unwrap_err();
+    assert!(program.imported_file(err.span.unwrap()).is_none());
+}
+
+#[rstest(
+    source, expected_source,
+    case(
+        "\nIMPORT \"semantic/mistyped.mascal\";",
+        "In \"semantic/mistyped.mascal\" at line 5: Expected an integer to be returned but got a string"
+    ),
+    case(
+        "\nIMPORT \"semantic/through.mascal\";",
+        "In \"semantic/through.mascal\" at line 2: In \"semantic/mistyped.mascal\" at line 5: Expected an integer to be returned but got a string"
+    ),
+)]
+fn test_semantic_errors_point_at_the_import(source: &str, expected_source: &str) {
+    let source: String = String::from(source) + "\nDEFINE_PROGRAM {VARIABLES {} IMPLEMENTATION {}}";
+    let errors: Vec<MascalError> = compile_fixture_source(&source).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error_type, MascalErrorType::TypeError);
+    assert_eq!(errors[0].span, Some(Span::new(1, 1)));
+    assert_eq!(errors[0].source, expected_source);
+}
@@ -0,0 +1,91 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::{Backend, Interpreter, Program, RunOutcome};
+use mascal::runtime::io_streams::BufferedIO;
+use std::cell::RefCell;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+use std::rc::Rc;
+use crate::run_captured;
+
+#[rstest(
+    code, expected_output,
+    case(vec!["WRITE(\"hello\");"], "hello\n"),
+    case(vec!["WRITE(1, 2.5, TRUE, \"x\");"], "1 2.5 TRUE x\n"),
+    case(vec!["FOR i FROM 1 TO 3 {WRITE(i);}"], "1\n2\n3\n"),
+    case(vec!["WRITE(\"a\");", "WRITE(\"b\", \"c\");"], "a\nb c\n"),
+)]
+fn test_write_is_captured(code: Vec<&str>, expected_output: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", "", ["INTEGER {i;}"], code.clone());
+        assert!(outcome.is_ok());
+        assert_eq!(output, expected_output);
+    }
+}
+
+#[test]
+fn test_read_from_buffer() {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(
+            backend,
+            "42\n  3.5\nTRUE\nsome words",
+            "",
+            ["INTEGER {a;}", "FLOAT {b;}", "BOOLEAN {c;}", "STRING {d;}"],
+            ["READ(a, b);", "READ(c);", "READ(d);", "WRITE(a + 1, b, c, d);"]
+        );
+        let outcome: RunOutcome = outcome.unwrap();
+        assert_eq!(output, "43 3.5 TRUE some words\n");
+        assert_eq!(outcome.get_variable("a").unwrap().as_string().unwrap(), "42");
+    }
+}
+
+#[rstest(
+    input, expected_message,
+    case("", "Could not read user input"),
+    case("not a number", "The user input cannot be parsed as an integer"),
+)]
+fn test_read_errors(input: &str, expected_message: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, _) = run_captured!(backend, input, "", ["INTEGER {a;}"], ["READ(a);"]);
+        let err: MascalError = outcome.unwrap_err();
+        assert_eq!(err.error_type, MascalErrorType::InputError);
+        assert_eq!(err.source, expected_message);
+    }
+}
+
+/*
+ Running out of input has to look the same whether READ goes through a buffer or the actual
+ stdin, the latter is only reachable by running the binary itself
+*/
+#[test]
+fn test_end_of_input_on_every_io() {
+    let path: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/interpreter/io_streams/read_past_the_end.mascal");
+    let source: String = fs::read_to_string(&path).unwrap();
+
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let io: Rc<RefCell<BufferedIO>> = Rc::new(RefCell::new(BufferedIO::new("4\n")));
+        let interpreter: Interpreter = Interpreter::new().with_io(io.clone()).with_backend(backend);
+        let program: Program = interpreter.compile_file(&source, &path).unwrap();
+        assert!(interpreter.bytecode_fallback(&program).is_none());
+        let err: MascalError = interpreter.run(&program).unwrap_err();
+        assert_eq!(err.error_type, MascalErrorType::InputError);
+        assert_eq!(err.source, "Could not read user input");
+        assert_eq!(io.borrow_mut().take_output(), "5\n");
+    }
+
+    let mut child: Child = Command::new(env!("CARGO_BIN_EXE_mascal"))
+        .arg(&path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"4\n").unwrap();
+    let output: Output = child.wait_with_output().unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    assert!(!output.status.success());
+    assert!(stdout.starts_with("5\n"));
+    assert!(stdout.contains("Could not read user input"));
+}
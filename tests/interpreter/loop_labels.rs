@@ -0,0 +1,89 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::{Backend, Interpreter};
+use crate::interpreter::VARIABLES;
+use crate::{program_source, run_captured};
+
+const FIND_PAIR: &str = "
+DEFINE_FUNCTION find_pair(target) -> INTEGER {
+    VARIABLES {
+        INTEGER {target; a; b; found <- -1;}
+    }
+
+    IMPLEMENTATION {
+        search: FOR a FROM 1 TO 9 {
+            FOR b FROM a TO 9 {
+                IF a * b == target {
+                    found <- a * 10 + b;
+                    BREAK search;
+                }
+            }
+        }
+        RETURN found;
+    }
+}
+";
+
+#[rstest(
+    code, expected_output,
+    case(vec!["outer: FOR i FROM 1 TO 3 {FOR j FROM 1 TO 3 {IF j == 2 {BREAK outer;} WRITE(i, j);}}"], "1 1\n"),
+    case(vec!["outer: FOR i FROM 1 TO 3 {FOR j FROM 1 TO 3 {IF j == 2 {CONTINUE outer;} WRITE(i, j);}}"], "1 1\n2 1\n3 1\n"),
+    case(vec!["outer: FOR i FROM 1 TO 2 {FOR j FROM 1 TO 3 {IF j == 2 {BREAK;} WRITE(i, j);}}"], "1 1\n2 1\n"),
+    case(vec!["outer: FOR i FROM 1 TO 2 {inner: FOR j FROM 1 TO 3 {IF j == 2 {CONTINUE inner;} WRITE(i, j);}}"], "1 1\n1 3\n2 1\n2 3\n"),
+    case(vec!["i <- 0;", "outer: WHILE TRUE {i <- i + 1; WHILE TRUE {IF i == 3 {BREAK outer;} BREAK;}}", "WRITE(i);"], "3\n"),
+    case(vec!["WRITE(find_pair(12), find_pair(97));"], "26 -1\n"),
+    case(vec!["a: FOR i FROM 1 TO 3 {b: FOR j FROM 1 TO 3 {FOR EACH c IN \"xyz\" {IF c == \"y\" {CONTINUE b;} IF j == 2 {BREAK a;} WRITE(i, j, c);}}}"], "1 1 x\n"),
+    case(vec!["cells: FOR EACH i IN grid {FOR j FROM 1 TO 9 {total <- total + 1; IF j == i {CONTINUE cells;}}}", "WRITE(total);"], "7\n"),
+)]
+fn test_labeled_loops(code: Vec<&str>, expected_output: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", FIND_PAIR, VARIABLES, code.clone());
+        assert!(outcome.is_ok());
+        assert_eq!(output, expected_output);
+    }
+}
+
+/* TRY isn't lowered by the bytecode compiler yet, so this only runs on the tree-walker */
+#[rstest(
+    code, expected_output,
+    case(vec!["outer: FOR i FROM 1 TO 3 {TRY {FOR j FROM 1 TO 3 {BREAK outer;}} FINALLY {WRITE(\"cleanup\");}}", "WRITE(\"done\");"], "cleanup\ndone\n"),
+)]
+fn test_labeled_loops_on_the_tree_walker(code: Vec<&str>, expected_output: &str) {
+    let (outcome, output) = run_captured!(Backend::TreeWalker, "", FIND_PAIR, VARIABLES, code);
+    assert!(outcome.is_ok());
+    assert_eq!(output, expected_output);
+}
+
+#[rstest(
+    code, expected_source,
+    case(vec!["FOR i FROM 1 TO 3 {BREAK outer;}"], "There is no loop labeled \"outer\" around this break statement"),
+    case(vec!["outer: FOR i FROM 1 TO 3 {}", "FOR i FROM 1 TO 3 {CONTINUE outer;}"], "There is no loop labeled \"outer\" around this continue statement"),
+    case(vec!["BREAK outer;"], "There is no loop labeled \"outer\" around this break statement"),
+    case(vec!["outer: FOR i FROM 1 TO 3 {outer: WHILE TRUE {BREAK outer;}}"], "The label \"outer\" is already used by a loop around this one"),
+)]
+fn test_label_errors(code: Vec<&str>, expected_source: &str) {
+    let errors: Vec<MascalError> = Interpreter::new().compile(program_source!(FIND_PAIR, VARIABLES, code).as_str()).unwrap_err();
+    assert_eq!(errors[0].error_type, MascalErrorType::ContextError);
+    assert_eq!(errors[0].source, expected_source);
+}
+
+#[test]
+fn test_labels_are_scoped_to_their_block() {
+    let function: &str = "
+DEFINE_FUNCTION leave() {
+    VARIABLES {
+        INTEGER {k;}
+    }
+
+    IMPLEMENTATION {
+        FOR k FROM 1 TO 2 {
+            BREAK outer;
+        }
+    }
+}
+";
+    let errors: Vec<MascalError> = Interpreter::new()
+        .compile((String::from(function) + &program_source!(FIND_PAIR, VARIABLES, ["outer: FOR i FROM 1 TO 3 {leave();}"])).as_str())
+        .unwrap_err();
+    assert_eq!(errors[0].source, "There is no loop labeled \"outer\" around this break statement");
+}
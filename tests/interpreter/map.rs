@@ -0,0 +1,84 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::{Backend, Interpreter};
+use crate::define_program_boilerplate;
+use crate::run_captured;
+
+const COUNT_WORDS: &str = "
+DEFINE_FUNCTION count_words(words) -> MAP<STRING, INTEGER> {
+    VARIABLES {
+        STRING {words<<>>;}
+        INTEGER {i;}
+        MAP<STRING, INTEGER> {counts;}
+    }
+
+    IMPLEMENTATION {
+        FOR i FROM 0 TO LEN(words) - 1 {
+            IF Has_Key(counts, words<<i>>) {
+                counts[words<<i>>] <- counts[words<<i>>] + 1;
+            } ELSE {
+                counts[words<<i>>] <- 1;
+            }
+        }
+        RETURN counts;
+    }
+}
+";
+
+#[rstest(
+    code, expected_output,
+    case(
+        vec!["m <- count_words(<<\"the\", \"cat\", \"and\", \"the\", \"hat\">>);", "WRITE(m);"],
+        "[\"the\": 2, \"cat\": 1, \"and\": 1, \"hat\": 1]\n"
+    ),
+    case(vec!["WRITE(m, LEN(m));"], "[:] 0\n"),
+    case(vec!["m <- [\"a\": 1, \"b\": 2];", "WRITE(m[\"b\"], LEN(m));"], "2 2\n"),
+    case(vec!["m[\"a\"] <- 1;", "m[\"a\"] <- m[\"a\"] + 4;", "WRITE(m);"], "[\"a\": 5]\n"),
+    case(vec!["m <- [\"x\": 1, \"y\": 2];", "WRITE(Keys(m), Values(m));"], "<<\"x\", \"y\">> <<1, 2>>\n"),
+    case(vec!["m <- [\"x\": 1];", "WRITE(Has_Key(m, \"x\"), HAS_KEY(m, \"y\"));"], "TRUE FALSE\n"),
+    case(
+        vec!["m <- [\"x\": 1, \"y\": 2];", "WRITE(Remove_Key(m, \"x\"), Remove_Key(m, \"z\"), m);"],
+        "1 NULL [\"y\": 2]\n"
+    ),
+    case(vec!["WRITE(TYPEOF(m), TYPEOF(t), MAP<STRING, INTEGER>);"], "MAP<STRING, INTEGER> MAP<INTEGER, MAP<BOOLEAN, FLOAT>> MAP<STRING, INTEGER>\n"),
+    case(vec!["m <- [\"a\": 1, \"b\": 2];", "WRITE(m == [\"b\": 2, \"a\": 1], m != [\"a\": 1]);"], "TRUE TRUE\n"),
+    case(vec!["n <- m;", "n[\"shared\"] <- 3;", "WRITE(m);"], "[\"shared\": 3]\n"),
+    case(vec!["t[1] <- [TRUE: 0.5];", "t[1][FALSE] <- 1.5;", "WRITE(t[1][FALSE], t);"], "1.5 [1: [TRUE: 0.5, FALSE: 1.5]]\n"),
+    case(vec!["m[\"a\"] <- 1;", "Remove_Key(m, \"a\");", "m[\"b\"] <- 2;", "m[\"a\"] <- 3;", "WRITE(Keys(m));"], "<<\"b\", \"a\">>\n"),
+)]
+fn test_maps(code: Vec<&str>, expected_output: &str) {
+    let (outcome, output) = run_captured!(
+        Backend::TreeWalker,
+        "",
+        COUNT_WORDS,
+        ["MAP<STRING, INTEGER> {m; n;}", "MAP<INTEGER, MAP<BOOLEAN, FLOAT>> {t;}"],
+        code
+    );
+    assert!(outcome.is_ok());
+    assert_eq!(output, expected_output);
+}
+
+#[rstest(
+    code, expected_type, expected_source,
+    case(vec!["WRITE(m[\"missing\"]);"], MascalErrorType::IndexError, "The key \"missing\" is not present in the map"),
+    case(vec!["m[1] <- 1;"], MascalErrorType::TypeError, "Expected a key of type STRING for the map but got INTEGER"),
+    case(vec!["m[\"a\"] <- \"b\";"], MascalErrorType::TypeError, "Expected a value of type INTEGER for the map but got STRING"),
+    case(vec!["m <- [\"a\": 1.5];"], MascalErrorType::TypeError, "Expected a value of type MAP<STRING, INTEGER> for \"m\" but got MAP<DYNAMIC, DYNAMIC>"),
+    case(vec!["d <- [[1: 1]: 1];"], MascalErrorType::TypeError, "A value of type MAP<DYNAMIC, DYNAMIC> cannot be used as the key of a map"),
+    case(vec!["m[\"a\"] <- 1;", "m[\"a\"][\"b\"] <- 1;"], MascalErrorType::IndexError, "Expected the key \"a\" to hold a map but it holds a value of type INTEGER"),
+    case(vec!["m[\"a\"] <- 1;", "WRITE(m<<\"a\">>);"], MascalErrorType::TypeError, "Maps can only be indexed with brackets '[' and ']'"),
+)]
+fn test_map_errors(code: Vec<&str>, expected_type: MascalErrorType, expected_source: &str) {
+    let (outcome, _) = run_captured!(Backend::TreeWalker, "", COUNT_WORDS, ["MAP<STRING, INTEGER> {m;}", "DYNAMIC {d;}"], code);
+    let err: MascalError = outcome.unwrap_err();
+    assert_eq!(err.error_type, expected_type);
+    assert_eq!(err.source, expected_source);
+}
+
+#[test]
+fn test_map_of_undefined_record() {
+    let source: String = define_program_boilerplate!(["MAP<STRING, Shape> {m;}"], Vec::<String>::new());
+    let errors: Vec<MascalError> = Interpreter::new().compile(source.as_str()).unwrap_err();
+    assert_eq!(errors[0].error_type, MascalErrorType::ParserError);
+    assert_eq!(errors[0].source, "Use of an undefined record type \"Shape\" (perhaps a typo?)");
+}
@@ -0,0 +1,111 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType, MascalWarning};
+use mascal::interpreter::{Backend, Interpreter, Program};
+use crate::define_program_boilerplate;
+use crate::run_captured;
+
+const DESCRIBE: &str = "
+DEFINE_RECORD Point {
+    VARIABLES {
+        INTEGER {x <- 0;}
+    }
+}
+
+DEFINE_FUNCTION describe(value) -> STRING {
+    VARIABLES {
+        DYNAMIC {value;}
+    }
+
+    IMPLEMENTATION {
+        MATCH value {
+            CASE TYPE INTEGER, FLOAT {
+                RETURN \"number\";
+            }
+            CASE TYPE STRING {
+                RETURN \"text\";
+            }
+            CASE TYPE Point {
+                RETURN \"point\";
+            }
+        }
+        RETURN \"something else\";
+    }
+}
+";
+
+const VARIABLES: [&str; 5] = [
+    "INTEGER {n <- 5; i; total <- 0;}",
+    "FLOAT {f <- 2.5;}",
+    "STRING {s <- \"m\";}",
+    "TYPE {t <- INTEGER;}",
+    "Point {p;}",
+];
+
+/* Hands back the line the given code starts at as well, to check where the warnings point */
+fn compile_match(code: Vec<&str>) -> (Program, usize) {
+    let source: String = String::from(DESCRIBE) + &define_program_boilerplate!(VARIABLES, code);
+    let first_line: usize = source[..source.rfind("IMPLEMENTATION").unwrap()].matches('\n').count() + 1;
+    (Interpreter::new().compile(source.as_str()).unwrap(), first_line)
+}
+
+#[rstest(
+    code, expected_output,
+    case(vec!["MATCH n {CASE 1, 2 {WRITE(\"small\");} CASE 3 TO 9 {WRITE(\"medium\");} DEFAULT {WRITE(\"big\");}}"], "medium\n"),
+    case(vec!["MATCH n * 10 {CASE 1, 2 {WRITE(\"small\");} CASE 3 TO 9 {WRITE(\"medium\");} DEFAULT {WRITE(\"big\");}}"], "big\n"),
+    case(vec!["MATCH n {CASE 5 {WRITE(\"first\");} CASE 1 TO 9 {WRITE(\"second\");}}"], "first\n"),
+    case(vec!["MATCH n {CASE 6 {WRITE(\"six\");}}", "WRITE(\"done\");"], "done\n"),
+    case(vec!["MATCH f {CASE 2 TO 3 {WRITE(\"in\");} DEFAULT {WRITE(\"out\");}}"], "in\n"),
+    case(vec!["MATCH -n {CASE -9 TO -1 {WRITE(\"negative\");}}"], "negative\n"),
+    case(vec!["MATCH s {CASE \"a\" TO \"f\" {WRITE(\"early\");} CASE \"g\" TO \"z\" {WRITE(\"late\");}}"], "late\n"),
+    case(vec!["MATCH s {CASE 1 TO 9 {WRITE(\"number\");} CASE \"m\" {WRITE(\"text\");}}"], "text\n"),
+    case(vec!["MATCH n {CASE n - 1, n {WRITE(\"itself\");}}"], "itself\n"),
+    case(vec!["WRITE(describe(1), describe(2.5), describe(\"a\"), describe(p), describe(TRUE));"], "number number text point something else\n"),
+    case(vec!["MATCH n {CASE TYPE t {WRITE(\"integer\");}}"], "integer\n"),
+    case(vec!["MATCH p {CASE TYPE INTEGER {WRITE(\"integer\");} CASE TYPE DYNAMIC {WRITE(\"anything\");}}"], "anything\n"),
+    case(vec!["FOR i FROM 0 TO 5 {MATCH i {CASE 1 {CONTINUE;} CASE 3 {BREAK;}} WRITE(i);}"], "0\n2\n"),
+    case(vec!["FOR i FROM 1 TO 4 {MATCH i % 2 {CASE 0 {total <- total + i;} DEFAULT {total <- total - i;}}}", "WRITE(total);"], "2\n"),
+)]
+fn test_match(code: Vec<&str>, expected_output: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", DESCRIBE, VARIABLES, code.clone());
+        assert!(outcome.is_ok());
+        assert_eq!(output, expected_output);
+    }
+}
+
+#[rstest(
+    code, expected_type, expected_source,
+    case(vec!["MATCH n {CASE TYPE 5 {}}"], MascalErrorType::TypeError, "Expected a type for the case to check against but got INTEGER"),
+    case(vec!["MATCH n {CASE missing {}}"], MascalErrorType::RuntimeError, "Unknown expression \"missing\" found"),
+)]
+fn test_match_errors(code: Vec<&str>, expected_type: MascalErrorType, expected_source: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, _) = run_captured!(backend, "", DESCRIBE, VARIABLES, code.clone());
+        let err: MascalError = outcome.unwrap_err();
+        assert_eq!(err.error_type, expected_type);
+        assert_eq!(err.source, expected_source);
+    }
+}
+
+#[rstest(
+    code, expected_lines,
+    case(vec!["MATCH n {CASE 1 TO 9 {}", "CASE 3, 4 {}}"], vec![1]),
+    case(vec!["MATCH n {CASE 1 {}", "CASE 1 {}", "CASE 1, 2 {}}"], vec![1]),
+    case(vec!["MATCH n {CASE -2 {}", "CASE -3 TO -1 {}", "CASE -2 {}}"], vec![2]),
+    case(vec!["MATCH n {CASE 1 TO 10 {}", "CASE 2 TO 5 {}", "CASE 0 TO 5 {}}"], vec![1]),
+    case(vec!["MATCH n {CASE 9 TO 1 {}}"], vec![0]),
+    case(vec!["MATCH s {CASE \"a\" TO \"z\" {}", "CASE \"m\" {}}"], vec![1]),
+    case(vec!["MATCH n {CASE TYPE INTEGER {}", "CASE TYPE FLOAT, INTEGER {}", "CASE TYPE INTEGER {}}"], vec![2]),
+    case(vec!["MATCH p {CASE TYPE DYNAMIC {}", "CASE 1 {}", "CASE TYPE Point {}}"], vec![1, 2]),
+    case(vec!["IF n > 0 {MATCH n {CASE TRUE {}", "CASE TRUE {}}}"], vec![1]),
+    case(vec!["MATCH n {CASE 1 {}", "CASE 1.0 {}", "CASE n {}", "CASE n {}}"], vec![]),
+    case(vec!["MATCH n {CASE 1 TO 5 {}", "CASE 0 TO 5, 7 {}}"], vec![]),
+)]
+fn test_unreachable_case_warnings(code: Vec<&str>, expected_lines: Vec<usize>) {
+    let (program, first_line) = compile_match(code);
+    let lines: Vec<usize> = program.warnings().iter().map(|warning| warning.span.unwrap().line).collect();
+    assert_eq!(lines, expected_lines.iter().map(|line| line + first_line).collect::<Vec<usize>>());
+    assert!(program.warnings().iter().all(|warning: &MascalWarning| {
+        warning.source == "This case can never be reached since the cases before it already cover everything it matches"
+    }));
+}
@@ -0,0 +1,147 @@
+mod bytecode_backend;
+mod conditional_expression;
+mod enumeration;
+mod for_each;
+mod for_loop;
+mod function_value;
+mod higher_order_builtins;
+mod imports;
+mod io_streams;
+mod loop_labels;
+mod map;
+mod match_statement;
+mod native_functions;
+mod record;
+mod repeat_loop;
+mod return_statement;
+mod short_circuit;
+mod symbol_table;
+mod throw_statement;
+mod try_statement;
+
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::{Interpreter, Program, RunOutcome};
+use mascal::runtime::values::MascalValue;
+use crate::define_program_boilerplate;
+
+#[macro_export]
+macro_rules! run_program {
+    ($variables: expr, $code: expr) => {{
+        let input: String = define_program_boilerplate!($variables, $code);
+        let interpreter: Interpreter = Interpreter::new();
+        let program: Program = interpreter.compile(input.as_str()).unwrap();
+        interpreter.run(&program)
+    }};
+}
+
+/*
+ A function for the tests to tell whether something got evaluated, it writes out what it was given
+ and hands it right back
+*/
+pub const TOUCH: &str = "
+DEFINE_FUNCTION touch(result) -> DYNAMIC {
+    VARIABLES {
+        DYNAMIC {result;}
+    }
+
+    IMPLEMENTATION {
+        WRITE(\"touched\", result);
+        RETURN result;
+    }
+}
+";
+
+/* Variables of every type for the tests that just need something to work with */
+pub const VARIABLES: [&str; 5] = [
+    "INTEGER {xs<<>> <- <<1, 2, 3>>; i <- 3; j; n <- 0; total <- 0; grid<<2>> <- <<3, 4>>;}",
+    "FLOAT {f;}",
+    "STRING {s <- \"\"; c;}",
+    "BOOLEAN {b <- TRUE; found <- FALSE;}",
+    "DYNAMIC {d;}",
+];
+
+/* The whole source of a program, the prefix goes before it for the functions, records and such */
+#[macro_export]
+macro_rules! program_source {
+    ($prefix: expr, $variables: expr, $code: expr) => {{
+        String::from($prefix) + &$crate::define_program_boilerplate!($variables, $code)
+    }};
+}
+
+/*
+ Runs the program on the given backend with the given input, handing back the outcome along with
+ everything it wrote. The prefix goes before the program, for the functions, records and such it
+ relies on. A successful run has to have happened on the backend asked for, rather than quietly
+ falling back to the tree-walker
+*/
+#[macro_export]
+macro_rules! run_captured {
+    ($backend: expr, $input: expr, $prefix: expr, $variables: expr, $code: expr) => {{
+        let source: String = $crate::program_source!($prefix, $variables, $code);
+        let io: std::rc::Rc<std::cell::RefCell<mascal::runtime::io_streams::BufferedIO>> =
+            std::rc::Rc::new(std::cell::RefCell::new(mascal::runtime::io_streams::BufferedIO::new($input)));
+        let interpreter: mascal::interpreter::Interpreter = mascal::interpreter::Interpreter::new()
+            .with_io(io.clone())
+            .with_backend($backend);
+        let program: mascal::interpreter::Program = interpreter.compile(source.as_str()).unwrap();
+        let outcome: Result<mascal::interpreter::RunOutcome, mascal::defs::errors::MascalError> =
+            interpreter.run(&program);
+        if let Ok(outcome) = &outcome {
+            assert_eq!(outcome.backend(), $backend);
+        }
+        (outcome, io.borrow_mut().take_output())
+    }};
+}
+
+#[rstest(
+    code, variable, expected,
+    case(vec!["a <- 1 + 2;"], "a", "3"),
+    case(vec!["a <- 2;", "WHILE a < 100 {a <- a * 2;}"], "a", "128"),
+    case(vec!["FOR i FROM 1 TO 4 {a <- a + i;}"], "a", "10"),
+    case(vec!["a <- 5;", "IF a > 3 {a <- 0;} ELSE {a <- 1;}"], "a", "0"),
+)]
+fn test_run_outcome(code: Vec<&str>, variable: &str, expected: &str) {
+    let outcome: RunOutcome = run_program!(["INTEGER {a <- 0; i;}"], code).unwrap();
+    let value: &MascalValue = outcome.get_variable(variable).unwrap();
+    assert_eq!(value.as_string().unwrap(), expected);
+}
+
+#[test]
+fn test_uninitialized_variables_are_absent() {
+    let outcome: RunOutcome = run_program!(["INTEGER {a; b <- 2;}"], ["b <- b + 1;"]).unwrap();
+    assert!(outcome.get_variable("a").is_none());
+    assert_eq!(outcome.get_variable("b").unwrap().as_string().unwrap(), "3");
+}
+
+#[test]
+fn test_program_can_run_repeatedly() {
+    let input: String = define_program_boilerplate!(["INTEGER {a <- 1;}"], ["a <- a + 1;"]);
+    let interpreter: Interpreter = Interpreter::new();
+    let program: Program = interpreter.compile(input.as_str()).unwrap();
+    for _ in 0..3 {
+        let outcome: RunOutcome = interpreter.run(&program).unwrap();
+        assert_eq!(outcome.get_variable("a").unwrap().as_string().unwrap(), "2");
+    }
+}
+
+#[rstest(
+    input, expected_type,
+    case("DEFINE_PROGRAM { IMPLEMENTATION { a <- $; } }", MascalErrorType::LexerError),
+    case("DEFINE_PROGRAM { IMPLEMENTATION { a <- ; } }", MascalErrorType::ParserError),
+    case("", MascalErrorType::ParserError),
+)]
+fn test_compile_errors(input: &str, expected_type: MascalErrorType) {
+    let errors: Vec<MascalError> = Interpreter::new().compile(input).unwrap_err();
+    assert_eq!(errors[0].error_type, expected_type);
+}
+
+#[test]
+fn test_runtime_error() {
+    let err: MascalError = run_program!(
+        ["INTEGER {a;}"],
+        ["THROW IndexError: \"out of reach\";"]
+    ).unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::IndexError);
+    assert_eq!(err.source, "out of reach");
+}
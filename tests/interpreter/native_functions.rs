@@ -0,0 +1,128 @@
+use rstest::rstest;
+use mascal::defs::dynamic_int::IntegerNum;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::types::MascalTypeKind;
+use mascal::interpreter::{Interpreter, Program, RunOutcome};
+use mascal::runtime::values::MascalValue;
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::define_program_boilerplate;
+
+macro_rules! run_with_interpreter {
+    ($interpreter: expr, $variables: expr, $code: expr) => {{
+        let source: String = define_program_boilerplate!($variables, $code);
+        let program: Program = $interpreter.compile(source.as_str()).unwrap();
+        $interpreter.run(&program)
+    }};
+}
+
+fn interpreter_with_double() -> Interpreter {
+    let mut interpreter: Interpreter = Interpreter::new();
+    interpreter
+        .register_function("Double", vec![vec![MascalTypeKind::Integer]], |args, _exec_data| {
+            let MascalValue::Integer(i) = &args[0] else {
+                unreachable!()
+            };
+            Ok(Some(MascalValue::Integer(IntegerNum::new(i.to_i128() * 2))))
+        })
+        .unwrap();
+    interpreter
+}
+
+#[rstest(
+    code, expected,
+    case(vec!["a <- Double(21);"], "42"),
+    case(vec!["a <- DOUBLE(2) + double(3);"], "10"),
+    case(vec!["a <- Double(Double(1));"], "4"),
+)]
+fn test_native_function_call(code: Vec<&str>, expected: &str) {
+    let interpreter: Interpreter = interpreter_with_double();
+    let outcome: RunOutcome = run_with_interpreter!(interpreter, ["INTEGER {a;}"], code).unwrap();
+    assert_eq!(outcome.get_variable("a").unwrap().as_string().unwrap(), expected);
+}
+
+#[rstest(
+    code, expected_type,
+    case(vec!["a <- Double(\"21\");"], MascalErrorType::TypeError),
+    case(vec!["a <- Double();"], MascalErrorType::ArgumentError),
+    case(vec!["a <- Double(1, 2);"], MascalErrorType::RuntimeError),
+    case(vec!["a <- DoUbLe(1);"], MascalErrorType::RuntimeError),
+)]
+fn test_native_function_argument_errors(code: Vec<&str>, expected_type: MascalErrorType) {
+    let interpreter: Interpreter = interpreter_with_double();
+    let err: MascalError = run_with_interpreter!(interpreter, ["INTEGER {a;}"], code).unwrap_err();
+    assert_eq!(err.error_type, expected_type);
+}
+
+#[test]
+fn test_native_function_captures_host_state() {
+    let calls: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+    let recorded_calls: Rc<RefCell<Vec<String>>> = calls.clone();
+    let mut interpreter: Interpreter = Interpreter::new();
+    interpreter
+        .register_function("Record", vec![vec![MascalTypeKind::String]], move |args, _exec_data| {
+            recorded_calls.borrow_mut().push(args[0].as_string()?);
+            Ok(None)
+        })
+        .unwrap();
+    run_with_interpreter!(
+        interpreter,
+        ["INTEGER {i;}"],
+        ["FOR i FROM 1 TO 3 {Record(\"tick\");}", "Record(\"done\");"]
+    )
+    .unwrap();
+    assert_eq!(*calls.borrow(), vec!["tick", "tick", "tick", "done"]);
+}
+
+#[test]
+fn test_native_function_errors_propagate() {
+    let mut interpreter: Interpreter = Interpreter::new();
+    interpreter
+        .register_function("Lookup", vec![vec![MascalTypeKind::String]], |_args, _exec_data| {
+            Err(MascalError {
+                error_type: MascalErrorType::ValueError,
+                span: None,
+                source: String::from("No such record"),
+            })
+        })
+        .unwrap();
+    let err: MascalError =
+        run_with_interpreter!(interpreter, ["STRING {s;}"], ["s <- Lookup(\"x\");"]).unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::ValueError);
+    assert_eq!(err.span.unwrap().line, 6);
+}
+
+#[test]
+fn test_native_functions_are_per_interpreter() {
+    let interpreter: Interpreter = interpreter_with_double();
+    assert!(run_with_interpreter!(interpreter, ["INTEGER {a;}"], ["a <- Double(1);"]).is_ok());
+    let plain_interpreter: Interpreter = Interpreter::new();
+    let err: MascalError =
+        run_with_interpreter!(plain_interpreter, ["INTEGER {a;}"], ["a <- Double(1);"]).unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::RuntimeError);
+}
+
+#[rstest(name, case("Write"), case("LEN"), case("sqrt"))]
+fn test_cannot_shadow_builtins(name: &str) {
+    let err: MascalError = Interpreter::new()
+        .register_function(name, vec![], |_args, _exec_data| Ok(None))
+        .unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::ArgumentError);
+}
+
+#[rstest(name, case("Double"), case("double"), case("DOUBLE"))]
+fn test_program_functions_cannot_share_a_native_name(name: &str) {
+    let source: String = format!(
+        "DEFINE_FUNCTION {}(x) -> INTEGER {{\n VARIABLES {{INTEGER {{x;}}}}\n IMPLEMENTATION {{RETURN x;}}\n}}\n{}",
+        name,
+        define_program_boilerplate!(["INTEGER {a;}"], [format!("a <- {}(1);", name)])
+    );
+    let errors: Vec<MascalError> = interpreter_with_double().compile(&source).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error_type, MascalErrorType::ParserError);
+    assert_eq!(errors[0].span.unwrap().line, 0);
+
+    let program: Program = Interpreter::new().compile(&source).unwrap();
+    let err: MascalError = interpreter_with_double().run(&program).unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::ParserError);
+}
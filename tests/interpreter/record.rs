@@ -0,0 +1,114 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::{Backend, Interpreter};
+use crate::define_program_boilerplate;
+use crate::run_captured;
+
+const RECORDS: &str = "
+DEFINE_RECORD Point {
+    VARIABLES {
+        INTEGER {x <- 1; y;}
+        STRING {label <- \"origin\";}
+    }
+}
+
+DEFINE_RECORD Line {
+    VARIABLES {
+        Point {start; end;}
+    }
+}
+
+DEFINE_FUNCTION make(n) -> Point {
+    VARIABLES {
+        INTEGER {n;}
+        Point {p;}
+    }
+
+    IMPLEMENTATION {
+        p.x <- n;
+        p.y <- n * 2;
+        RETURN p;
+    }
+}
+";
+
+#[rstest(
+    code, expected_output,
+    case(vec!["WRITE(p.x, p.label);"], "1 origin\n"),
+    case(vec!["p.y <- 5;", "WRITE(p);"], "Point(x: 1, y: 5, label: \"origin\")\n"),
+    case(vec!["l.start.x <- 9;", "WRITE(l.start.x, l.end.x);"], "9 1\n"),
+    case(vec!["ps[1] <- make(4);", "ps[1].y <- ps[1].y + 1;", "WRITE(ps[1].x, ps[1].y);"], "4 9\n"),
+    case(vec!["p <- make(2);", "WRITE(p.x * p.y);"], "8\n"),
+    case(vec!["WRITE(TYPEOF(p), TYPEOF(l));"], "Point Line\n"),
+    case(vec!["q <- Point();", "WRITE(p == q, p != q);", "q.y <- 3;", "WRITE(p == q);"], "TRUE FALSE\nFALSE\n"),
+    case(vec!["q <- p;", "q.x <- 7;", "WRITE(p.x);"], "7\n"),
+)]
+fn test_records(code: Vec<&str>, expected_output: &str) {
+    let (outcome, output) = run_captured!(Backend::TreeWalker, "", RECORDS, ["Point {p; q; ps[2];}", "Line {l;}"], code);
+    assert!(outcome.is_ok());
+    assert_eq!(output, expected_output);
+}
+
+#[rstest(
+    code, expected_type, expected_source,
+    case(vec!["WRITE(p.y);"], MascalErrorType::RuntimeError, "Field \"y\" of the record Point not initialized"),
+    case(vec!["WRITE(p.z);"], MascalErrorType::RuntimeError, "The record Point has no field named \"z\""),
+    case(vec!["p.z <- 1;"], MascalErrorType::RuntimeError, "The record Point has no field named \"z\""),
+    case(vec!["p <- l;"], MascalErrorType::TypeError, "Expected a value of the record Point for \"p\" but got Line"),
+    case(vec!["a <- 1;", "WRITE(a.x);"], MascalErrorType::TypeError, "Expected a record to access the field \"x\" from but got INTEGER"),
+    case(vec!["p <- Point(1);"], MascalErrorType::ArgumentError, "Expected no arguments when creating the record \"Point\" but got 1 argument(s)"),
+)]
+fn test_record_errors(code: Vec<&str>, expected_type: MascalErrorType, expected_source: &str) {
+    let (outcome, _) = run_captured!(Backend::TreeWalker, "", RECORDS, ["Point {p;}", "Line {l;}", "INTEGER {a;}"], code);
+    let err: MascalError = outcome.unwrap_err();
+    assert_eq!(err.error_type, expected_type);
+    assert_eq!(err.source, expected_source);
+}
+
+#[rstest(
+    input, expected_source,
+    case(
+        "DEFINE_RECORD Node {VARIABLES {Node {next;}}}",
+        "The record \"Node\" contains itself, perhaps use an array of it instead?"
+    ),
+    case(
+        "DEFINE_RECORD A {VARIABLES {B {b;}}} DEFINE_RECORD B {VARIABLES {A {a;}}}",
+        "The record \"A\" contains itself, perhaps use an array of it instead?"
+    ),
+    case(
+        "DEFINE_RECORD Point {VARIABLES {INTEGER {x;}}} DEFINE_RECORD Point {VARIABLES {INTEGER {y;}}}",
+        "The record \"Point\" has already been defined"
+    ),
+    case(
+        "DEFINE_RECORD Point {VARIABLES {Shape {s;}}}",
+        "Use of an undefined record type \"Shape\" (perhaps a typo?)"
+    ),
+    case(
+        "DEFINE_FUNCTION f() -> Shape {VARIABLES {} IMPLEMENTATION {}}",
+        "Use of an undefined record type \"Shape\" (perhaps a typo?)"
+    ),
+    case(
+        "DEFINE_RECORD f {VARIABLES {INTEGER {x;}}} DEFINE_FUNCTION f() {VARIABLES {} IMPLEMENTATION {}}",
+        "The function \"f\" shares its name with a record"
+    ),
+)]
+fn test_record_semantic_errors(input: &str, expected_source: &str) {
+    let source: String = String::from(input)
+        + &define_program_boilerplate!(Vec::<String>::new(), Vec::<String>::new());
+    let errors: Vec<MascalError> = Interpreter::new().compile(source.as_str()).unwrap_err();
+    assert_eq!(errors[0].error_type, MascalErrorType::ParserError);
+    assert_eq!(errors[0].source, expected_source);
+}
+
+#[test]
+fn test_nested_records_do_not_share_fields() {
+    let (outcome, output) = run_captured!(
+        Backend::TreeWalker,
+        "",
+        RECORDS,
+        ["Line {l; m;}"],
+        ["l.start.x <- 5;", "WRITE(m.start.x, l.end.x);"]
+    );
+    assert!(outcome.is_ok());
+    assert_eq!(output, "1 1\n");
+}
@@ -0,0 +1,56 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::Backend;
+use crate::run_captured;
+
+const VARIABLES: [&str; 2] = ["INTEGER {i <- 0; j; guess;}", "BOOLEAN {done <- FALSE;}"];
+
+#[rstest(
+    code, expected_output,
+    case(vec!["REPEAT {i <- i + 1; WRITE(i);} UNTIL i >= 3;"], "1\n2\n3\n"),
+    case(vec!["REPEAT {WRITE(\"once\");} UNTIL TRUE;"], "once\n"),
+    case(vec!["DO {WRITE(\"once\");} WHILE FALSE;"], "once\n"),
+    case(vec!["DO {i <- i + 2;} WHILE i < 7;", "WRITE(i);"], "8\n"),
+    case(vec!["REPEAT {i <- i + 1; IF i % 2 == 0 {CONTINUE;} WRITE(i);} UNTIL i >= 5;"], "1\n3\n5\n"),
+    case(vec!["REPEAT {i <- i + 1; IF i == 3 {BREAK;}} UNTIL FALSE;", "WRITE(i);"], "3\n"),
+    case(vec!["DO {i <- i + 1; IF i < 4 {CONTINUE;} done <- TRUE;} WHILE NOT done;", "WRITE(i);"], "4\n"),
+    case(vec!["outer: REPEAT {i <- i + 1; FOR j FROM 1 TO 3 {IF i * j == 6 {BREAK outer;}}} UNTIL FALSE;", "WRITE(i, j);"], "2 3\n"),
+    case(vec!["outer: DO {i <- i + 1; REPEAT {CONTINUE outer;} UNTIL FALSE;} WHILE i < 3;", "WRITE(i);"], "3\n"),
+)]
+fn test_repeat(code: Vec<&str>, expected_output: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", "", VARIABLES, code.clone());
+        assert!(outcome.is_ok());
+        assert_eq!(output, expected_output);
+    }
+}
+
+#[test]
+fn test_input_validation() {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(
+            backend,
+            "12\n-3\n7\n",
+            "",
+            VARIABLES,
+            ["REPEAT {READ(guess);} UNTIL guess >= 1 AND guess <= 10;", "WRITE(guess);"]
+        );
+        assert!(outcome.is_ok());
+        assert_eq!(output, "7\n");
+    }
+}
+
+#[rstest(
+    code,
+    case(vec!["REPEAT {i <- i + 1;} UNTIL i;"]),
+    case(vec!["DO {i <- i + 1;} WHILE \"yes\";"]),
+)]
+fn test_non_boolean_condition(code: Vec<&str>) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, _) = run_captured!(backend, "", "", VARIABLES, code.clone());
+        let err: MascalError = outcome.unwrap_err();
+        assert_eq!(err.error_type, MascalErrorType::RuntimeError);
+        assert!(err.source.starts_with("Expected a boolean variable on the condition but got"));
+        assert_eq!(err.span.unwrap().line, 7);
+    }
+}
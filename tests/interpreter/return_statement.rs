@@ -0,0 +1,161 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::{Backend, Interpreter, Program, RunOutcome};
+use mascal::runtime::io_streams::BufferedIO;
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::define_program_boilerplate;
+
+macro_rules! run_with_functions {
+    ($variables: expr, $code: expr, $functions: expr) => {{
+        let source: String = define_program_boilerplate!($variables, $code) + $functions;
+        let interpreter: Interpreter = Interpreter::new();
+        interpreter
+            .compile(source.as_str())
+            .map(|program: Program| interpreter.run(&program))
+    }};
+}
+
+const FIRST_ABOVE: &str = "
+DEFINE_FUNCTION first_above(limit) -> INTEGER {
+    VARIABLES {
+        INTEGER {limit; i;}
+    }
+
+    IMPLEMENTATION {
+        FOR i FROM 1 TO 100 {
+            IF i * i > limit {
+                RETURN i;
+            }
+        }
+        RETURN -1;
+    }
+}";
+
+const COUNTDOWN: &str = "
+DEFINE_FUNCTION countdown(value, limit) {
+    VARIABLES {
+        INTEGER {value; limit;}
+    }
+
+    IMPLEMENTATION {
+        WHILE TRUE {
+            IF value <= limit {
+                RETURN;
+            }
+            WRITE(value);
+            value <- value - 1;
+        }
+        WRITE(\"unreachable\");
+    }
+}";
+
+#[rstest(
+    limit, expected,
+    case(0, "1"),
+    case(10, "4"),
+    case(99, "10"),
+    case(10000, "-1"),
+)]
+fn test_return_from_inside_a_loop(limit: i32, expected: &str) {
+    let outcome: RunOutcome = run_with_functions!(
+        ["INTEGER {a;}"],
+        [format!("a <- first_above({limit});")],
+        FIRST_ABOVE
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(outcome.get_variable("a").unwrap().as_string().unwrap(), expected);
+}
+
+#[test]
+fn test_bare_return_stops_the_function() {
+    let source: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        ["countdown(12, 9);", "WRITE(\"done\");"]
+    ) + COUNTDOWN;
+    let io: Rc<RefCell<BufferedIO>> = Rc::new(RefCell::new(BufferedIO::default()));
+    let interpreter: Interpreter = Interpreter::new().with_io(io.clone());
+    let program: Program = interpreter.compile(source.as_str()).unwrap();
+    interpreter.run(&program).unwrap();
+    assert_eq!(io.borrow().output(), "12\n11\n10\ndone\n");
+}
+
+#[rstest(backend, case(Backend::TreeWalker), case(Backend::Bytecode))]
+fn test_returning_null_is_still_returning_a_value(backend: Backend) {
+    let source: String = define_program_boilerplate!(["DYNAMIC {a <- 1;}"], ["a <- maybe();"]) + "
+DEFINE_FUNCTION maybe() -> INTEGER {
+    VARIABLES {
+        INTEGER {d?;}
+    }
+
+    IMPLEMENTATION {
+        d <- NULL;
+        RETURN d;
+    }
+}";
+    let interpreter: Interpreter = Interpreter::new().with_backend(backend);
+    let program: Program = interpreter.compile(source.as_str()).unwrap();
+    let outcome: RunOutcome = interpreter.run(&program).unwrap();
+    assert_eq!(outcome.get_variable("a").unwrap().as_string().unwrap(), "NULL");
+}
+
+#[test]
+fn test_returned_value_is_type_checked() {
+    let functions: &str = "
+DEFINE_FUNCTION wrong() -> INTEGER {
+    VARIABLES {
+        DYNAMIC {d <- \"text\";}
+    }
+
+    IMPLEMENTATION {
+        RETURN d;
+    }
+}";
+    let err: MascalError = run_with_functions!(["INTEGER {a;}"], ["a <- wrong();"], functions)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::RuntimeError);
+}
+
+#[rstest(
+    return_type, code, expected_message,
+    case("INTEGER", "RETURN \"text\";", "Expected an integer to be returned but got a string"),
+    case("FLOAT", "RETURN 1;", "Expected a float to be returned but got an integer"),
+    case("BOOLEAN", "IF TRUE {RETURN b + 1;}", "Expected a boolean to be returned but got an integer"),
+    case("STRING", "RETURN b > 1;", "Expected a string to be returned but got a boolean"),
+)]
+fn test_known_return_types_are_checked_ahead_of_time(return_type: &str, code: &str, expected_message: &str) {
+    let functions: String = format!(
+        "\nDEFINE_FUNCTION f(b) -> {return_type} {{VARIABLES {{INTEGER {{b;}}}} IMPLEMENTATION {{{code}}}}}"
+    );
+    let errors: Vec<MascalError> = run_with_functions!(["INTEGER {a;}"], ["a <- 1;"], functions.as_str())
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error_type, MascalErrorType::TypeError);
+    assert_eq!(errors[0].source, expected_message);
+}
+
+#[rstest(
+    code, functions, expected_type,
+    case("RETURN;", "", MascalErrorType::ContextError),
+    case("IF TRUE {RETURN 1;}", "", MascalErrorType::ContextError),
+    case(
+        "a <- 1;",
+        "\nDEFINE_FUNCTION f() {IMPLEMENTATION {RETURN 2;}}",
+        MascalErrorType::TypeError
+    ),
+    case(
+        "a <- 1;",
+        "\nDEFINE_FUNCTION f() -> INTEGER {IMPLEMENTATION {WHILE TRUE {RETURN;}}}",
+        MascalErrorType::TypeError
+    ),
+)]
+fn test_misplaced_returns_are_rejected(code: &str, functions: &str, expected_type: MascalErrorType) {
+    let errors: Vec<MascalError> = run_with_functions!(["INTEGER {a;}"], [code], functions)
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error_type, expected_type);
+}
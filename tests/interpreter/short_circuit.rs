@@ -0,0 +1,59 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::{Backend, Interpreter};
+use crate::interpreter::{TOUCH, VARIABLES};
+use crate::{program_source, run_captured};
+
+#[rstest(
+    code, expected_output,
+    case(vec!["found <- i < LEN(xs) AND xs<<i>> == 2;", "WRITE(found);"], "FALSE\n"),
+    case(vec!["found <- i >= LEN(xs) OR xs<<i>> == 2;", "WRITE(found);"], "TRUE\n"),
+    case(vec!["WRITE(FALSE AND touch(TRUE));"], "FALSE\n"),
+    case(vec!["WRITE(TRUE OR touch(FALSE));"], "TRUE\n"),
+    case(vec!["WRITE(TRUE AND touch(FALSE));"], "touched FALSE\nFALSE\n"),
+    case(vec!["WRITE(FALSE OR touch(TRUE));"], "touched TRUE\nTRUE\n"),
+    case(vec!["WRITE(touch(FALSE) AND touch(TRUE) OR touch(TRUE));"], "touched FALSE\ntouched TRUE\nTRUE\n"),
+    case(vec!["WHILE n < LEN(xs) AND xs<<n>> != 3 {n <- n + 1;}", "WRITE(n);"], "2\n"),
+    case(vec!["WHILE n < LEN(xs) AND xs<<n>> != 7 {n <- n + 1;}", "WRITE(n);"], "3\n"),
+)]
+fn test_short_circuit(code: Vec<&str>, expected_output: &str) {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", TOUCH, VARIABLES, code.clone());
+        assert!(outcome.is_ok(), "{:?}", outcome);
+        assert_eq!(output, expected_output);
+    }
+}
+
+#[test]
+fn test_non_boolean_left_side_at_runtime() {
+    for backend in [Backend::TreeWalker, Backend::Bytecode] {
+        let (outcome, output) = run_captured!(backend, "", TOUCH, VARIABLES, ["d <- 1;", "WRITE(d AND touch(TRUE));"]);
+        assert_eq!(outcome.unwrap_err().error_type, MascalErrorType::UndefinedOperation);
+        assert_eq!(output, "touched TRUE\n");
+    }
+}
+
+#[rstest(
+    code, expected_source,
+    case(vec!["found <- i AND found;"], "Expected a boolean on the left side of AND but got an integer"),
+    case(vec!["found <- found OR s;"], "Expected a boolean on the right side of OR but got a string"),
+    case(vec!["IF found AND i {}"], "Expected a boolean on the right side of AND but got an integer"),
+    case(vec!["WHILE s OR found {}"], "Expected a boolean on the left side of OR but got a string"),
+    case(vec!["REPEAT {} UNTIL found AND NOT found OR f;"], "Expected a boolean on the right side of OR but got a float"),
+    case(vec!["WRITE(Filter(xs, FUNCTION(x) -> i AND x));"], "Expected a boolean on the left side of AND but got an integer"),
+)]
+fn test_non_boolean_operands(code: Vec<&str>, expected_source: &str) {
+    let errors: Vec<MascalError> = Interpreter::new().compile(program_source!(TOUCH, VARIABLES, code).as_str()).unwrap_err();
+    assert_eq!(errors[0].error_type, MascalErrorType::TypeError);
+    assert_eq!(errors[0].source, expected_source);
+}
+
+#[rstest(
+    code,
+    case(vec!["found <- found AND d;"]),
+    case(vec!["found <- i > 0 AND NOT found OR s == \"\";"]),
+    case(vec!["WRITE(Filter(xs, FUNCTION(i) -> i AND found));"]),
+)]
+fn test_undecided_operands_are_left_to_runtime(code: Vec<&str>) {
+    assert!(Interpreter::new().compile(program_source!(TOUCH, VARIABLES, code).as_str()).is_ok());
+}
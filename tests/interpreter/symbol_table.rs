@@ -0,0 +1,99 @@
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::symbol_table::FunctionSymbol;
+use mascal::interpreter::{Backend, Interpreter, Program, RunOutcome};
+use rstest::rstest;
+use crate::{define_program_boilerplate, run_captured};
+
+const DOUBLER: &str = "
+DEFINE_FUNCTION doubled(value) -> INTEGER {
+    VARIABLES {
+        INTEGER {value;}
+    }
+
+    IMPLEMENTATION {
+        doubled <- value * 2;
+    }
+}";
+
+#[test]
+fn test_symbol_table_lists_functions() {
+    let source: String = define_program_boilerplate!(["INTEGER {a;}"], ["a <- doubled(4);"]) + DOUBLER;
+    let program: Program = Interpreter::new().compile(source.as_str()).unwrap();
+    let symbol: &FunctionSymbol = program
+        .abstract_syntax_tree()
+        .symbol_table
+        .get_function("doubled")
+        .unwrap();
+    assert_eq!(symbol.parameter_count, 1);
+    assert_eq!(symbol.span.line, 9);
+    assert!(!program.abstract_syntax_tree().symbol_table.is_function("a"));
+}
+
+#[test]
+fn test_function_names_do_not_leak_between_programs() {
+    let interpreter: Interpreter = Interpreter::new();
+    let first_source: String =
+        define_program_boilerplate!(["INTEGER {a;}"], ["a <- doubled(4);"]) + DOUBLER;
+    let first_program: Program = interpreter.compile(first_source.as_str()).unwrap();
+    let second_source: String =
+        define_program_boilerplate!(["INTEGER {doubled;}"], ["doubled <- 3;", "doubled <- doubled + 1;"]);
+    let second_program: Program = interpreter.compile(second_source.as_str()).unwrap();
+
+    let first_outcome: RunOutcome = interpreter.run(&first_program).unwrap();
+    assert_eq!(first_outcome.get_variable("a").unwrap().as_string().unwrap(), "8");
+    let second_outcome: RunOutcome = interpreter.run(&second_program).unwrap();
+    assert_eq!(second_outcome.get_variable("doubled").unwrap().as_string().unwrap(), "4");
+}
+
+#[test]
+fn test_duplicate_functions_are_rejected() {
+    let source: String = define_program_boilerplate!(["INTEGER {a;}"], ["a <- doubled(4);"])
+        + DOUBLER
+        + DOUBLER;
+    let errors: Vec<MascalError> = Interpreter::new().compile(source.as_str()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error_type, MascalErrorType::ParserError);
+    assert_eq!(errors[0].span.unwrap().line, 18);
+}
+
+const TOTAL_AND_OTHER: &str = "
+DEFINE_FUNCTION total(n) -> INTEGER {
+    VARIABLES {
+        INTEGER {n;}
+    }
+
+    IMPLEMENTATION {
+        total <- n + 100;
+    }
+}
+
+DEFINE_FUNCTION other(n) -> INTEGER {
+    VARIABLES {
+        INTEGER {n; total;}
+    }
+
+    IMPLEMENTATION {
+        total <- n * 2;
+        other <- total + 1;
+        other <- 0;
+    }
+}
+";
+
+/*
+ Only assigning to the name of the running function returns from it, a variable named after some
+ other function is just a variable (and so is one in the program block)
+*/
+#[rstest(backend, case(Backend::TreeWalker), case(Backend::Bytecode))]
+fn test_only_the_running_function_returns_by_assignment(backend: Backend) {
+    let (outcome, _) = run_captured!(
+        backend,
+        "",
+        TOTAL_AND_OTHER,
+        ["INTEGER {a; total;}"],
+        ["a <- other(5);", "total <- 3;"]
+    );
+    let outcome: RunOutcome = outcome.unwrap();
+    assert_eq!(outcome.get_variable("a").unwrap().as_string().unwrap(), "11");
+    assert_eq!(outcome.get_variable("total").unwrap().as_string().unwrap(), "3");
+}
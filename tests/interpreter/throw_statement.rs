@@ -0,0 +1,39 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::Backend;
+use crate::run_captured;
+
+const ERRORS_BLOCK: &str = "ERRORS {\n    NotFound;\n    OutOfStock;\n}\n";
+
+#[rstest(
+    code, expected_type, expected_message,
+    case(vec!["THROW ValueError: \"item \" + STRING(a) + \" is missing\";"], MascalErrorType::ValueError, "item 3 is missing"),
+    case(vec!["THROW NotFound: \"no item \" + STRING(a * 2);"], MascalErrorType::Custom("NotFound".into()), "no item 6"),
+    case(vec!["s <- \"sold out\";", "THROW OutOfStock: s;"], MascalErrorType::Custom("OutOfStock".into()), "sold out"),
+    case(vec!["THROW ValueError: a;"], MascalErrorType::TypeError, "Expected a string message for the throw statement but got \"3\""),
+)]
+fn test_thrown_errors(code: Vec<&str>, expected_type: MascalErrorType, expected_message: &str) {
+    let (outcome, _) = run_captured!(Backend::TreeWalker, "", ERRORS_BLOCK, ["INTEGER {a <- 3;}", "STRING {s;}"], code);
+    let err: MascalError = outcome.unwrap_err();
+    assert_eq!(err.error_type, expected_type);
+    assert_eq!(err.source, expected_message);
+}
+
+#[rstest(
+    code, expected_output,
+    case(vec!["TRY {THROW NotFound: \"gone\";} CATCH OutOfStock {WRITE(1);} CATCH NotFound AS s {WRITE(s);}"], "gone\n"),
+    case(vec!["TRY {THROW NotFound: \"gone\";} CATCH ValueError {WRITE(1);} CATCH AS s {WRITE(\"any\", s);}"], "any gone\n"),
+    case(vec!["TRY {THROW ValueError: \"bad\";} CATCH NotFound {WRITE(1);} CATCH ValueError {WRITE(2);}"], "2\n"),
+)]
+fn test_catching_declared_errors(code: Vec<&str>, expected_output: &str) {
+    let (outcome, output) = run_captured!(Backend::TreeWalker, "", ERRORS_BLOCK, ["STRING {s;}"], code);
+    assert!(outcome.is_ok());
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn test_declared_error_display() {
+    let (outcome, _) = run_captured!(Backend::TreeWalker, "", ERRORS_BLOCK, ["INTEGER {a;}"], ["THROW OutOfStock: \"none left\";"]);
+    let rendered: String = outcome.unwrap_err().to_string();
+    assert!(rendered.contains("OutOfStock: none left"));
+}
@@ -0,0 +1,108 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::interpreter::{Backend, Interpreter, RunOutcome};
+use crate::define_program_boilerplate;
+use crate::run_captured;
+
+const RETURN_FROM_FINALLY: &str = "
+DEFINE_FUNCTION guarded(n) -> INTEGER {
+    VARIABLES {
+        INTEGER {n;}
+    }
+
+    IMPLEMENTATION {
+        TRY {
+            IF n > 0 {
+                RETURN n;
+            }
+            THROW ValueError: \"not positive\";
+        } CATCH ValueError {
+            RETURN 0;
+        } FINALLY {
+            WRITE(\"cleanup\");
+        }
+    }
+}";
+
+#[rstest(
+    code, expected_output,
+    case(vec!["TRY {THROW ValueError: \"bad\";} CATCH ValueError AS e {WRITE(\"caught\", e);}"], "caught bad\n"),
+    case(vec!["TRY {THROW IndexError: \"far\";} CATCH ValueError {WRITE(1);} CATCH {WRITE(2);}"], "2\n"),
+    case(vec!["TRY {a <- 1 / 0;} CATCH UndefinedOperationError AS e {WRITE(\"zero\");}"], "zero\n"),
+    case(vec!["TRY {WRITE(\"fine\");} CATCH {WRITE(\"never\");} FINALLY {WRITE(\"done\");}"], "fine\ndone\n"),
+    case(vec!["TRY {THROW TypeError: \"x\";} CATCH AS e {WRITE(e);} FINALLY {WRITE(\"done\");}"], "x\ndone\n"),
+    case(
+        vec!["FOR a FROM 1 TO 5 {TRY {IF a = 3 {BREAK;}} FINALLY {WRITE(a);}}"],
+        "1\n2\n3\n"
+    ),
+)]
+fn test_try_catches(code: Vec<&str>, expected_output: &str) {
+    let (outcome, output) = run_captured!(Backend::TreeWalker, "", "", ["INTEGER {a;}", "STRING {e;}"], code);
+    assert!(outcome.is_ok(), "{:?}", outcome.err());
+    assert_eq!(output, expected_output);
+}
+
+#[rstest(
+    code, expected_type, expected_output,
+    case(vec!["TRY {THROW IndexError: \"far\";} CATCH ValueError {WRITE(1);} FINALLY {WRITE(2);}"], MascalErrorType::IndexError, "2\n"),
+    case(vec!["TRY {THROW ValueError: \"x\";} CATCH {THROW TypeError: \"again\";}"], MascalErrorType::TypeError, ""),
+    case(vec!["TRY {WRITE(1);} FINALLY {THROW InputError: \"cleanup\";}"], MascalErrorType::InputError, "1\n"),
+    case(vec!["TRY {THROW ValueError: \"x\";} CATCH AS missing {}"], MascalErrorType::RuntimeError, ""),
+    case(vec!["TRY {THROW ValueError: \"x\";} CATCH AS missing {} FINALLY {WRITE(\"done\");}"], MascalErrorType::RuntimeError, "done\n"),
+)]
+fn test_try_errors(code: Vec<&str>, expected_type: MascalErrorType, expected_output: &str) {
+    let (outcome, output) = run_captured!(Backend::TreeWalker, "", "", ["INTEGER {a;}", "STRING {e;}"], code);
+    assert_eq!(outcome.unwrap_err().error_type, expected_type);
+    assert_eq!(output, expected_output);
+}
+
+#[rstest(
+    catch, expected_type, expected_message,
+    case("CATCH AS a {}", MascalErrorType::TypeError, "Expected \"a\" to be a STRING or DYNAMIC variable to hold the caught error"),
+    case("CATCH ValueError AS words {}", MascalErrorType::TypeError, "Expected \"words\" to be a STRING or DYNAMIC variable to hold the caught error"),
+    case("CATCH AS k {}", MascalErrorType::RuntimeError, "Cannot store the caught error in \"k\" as it is a constant"),
+)]
+fn test_catch_variables_are_checked_ahead_of_time(catch: &str, expected_type: MascalErrorType, expected_message: &str) {
+    let source: String = define_program_boilerplate!(
+        ["INTEGER {a;}", "STRING {words<<2>>; CONST k <- \"x\";}"],
+        [format!("TRY {{WRITE(1);}} {} FINALLY {{WRITE(2);}}", catch)]
+    );
+    let errors: Vec<MascalError> = Interpreter::new().compile(source.as_str()).map(|_| ()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error_type, expected_type);
+    assert_eq!(errors[0].source, expected_message);
+}
+
+#[test]
+fn test_read_is_retried_until_valid() {
+    let (outcome, output) = run_captured!(
+        Backend::TreeWalker,
+        "nope\nstill nope\n7",
+        "",
+        ["INTEGER {a;}", "BOOLEAN {done <- FALSE;}", "STRING {e;}"],
+        [
+            "WHILE NOT done {TRY {READ(a); done <- TRUE;} CATCH InputError AS e {WRITE(\"retry:\", e);}}",
+            "WRITE(a);"
+        ]
+    );
+    assert!(outcome.is_ok());
+    assert_eq!(
+        output,
+        "retry: The user input cannot be parsed as an integer\n\
+         retry: The user input cannot be parsed as an integer\n7\n"
+    );
+}
+
+#[rstest(n, expected, case(4, "4"), case(-2, "0"))]
+fn test_return_inside_try(n: i32, expected: &str) {
+    let (outcome, output) = run_captured!(
+        Backend::TreeWalker,
+        "",
+        RETURN_FROM_FINALLY,
+        ["INTEGER {a;}"],
+        [format!("a <- guarded({});", n)]
+    );
+    let outcome: RunOutcome = outcome.unwrap();
+    assert_eq!(outcome.get_variable("a").unwrap().as_string().unwrap(), expected);
+    assert_eq!(output, "cleanup\n");
+}
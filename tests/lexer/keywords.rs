@@ -4,6 +4,9 @@ use crate::{test_individual_token};
 
 test_individual_token!(test_define_func,  "Define_Function", TokenType::DefineFunction);
 test_individual_token!(test_define_program,  "Define_Program", TokenType::DefineProgram);
+test_individual_token!(test_define_record,  "Define_Record", TokenType::DefineRecord);
+test_individual_token!(test_define_enum,  "Define_Enum", TokenType::DefineEnum);
+test_individual_token!(test_import,  "Import", TokenType::Import);
 test_individual_token!(test_false,  "False", TokenType::False);
 test_individual_token!(test_true,  "True", TokenType::True);
 test_individual_token!(test_const,  "Const", TokenType::Const);
@@ -12,6 +15,21 @@ test_individual_token!(test_typeof, "Typeof", TokenType::Typeof);
 test_individual_token!(test_mut, "Mut", TokenType::Mutable);
 test_individual_token!(test_from, "From", TokenType::From);
 test_individual_token!(test_to, "To", TokenType::To);
+test_individual_token!(test_downto, "DOWNTO", TokenType::DownTo);
 test_individual_token!(test_with_step, "With_Step", TokenType::WithStep);
+test_individual_token!(test_each, "Each", TokenType::Each);
+test_individual_token!(test_in, "In", TokenType::In);
 test_individual_token!(test_implementation, "Implementation", TokenType::Implementation);
-test_individual_token!(test_variables, "Variables", TokenType::Variables);
\ No newline at end of file
+test_individual_token!(test_variables, "Variables", TokenType::Variables);
+test_individual_token!(test_try, "Try", TokenType::Try);
+test_individual_token!(test_catch, "Catch", TokenType::Catch);
+test_individual_token!(test_finally, "Finally", TokenType::Finally);
+test_individual_token!(test_as, "As", TokenType::As);
+test_individual_token!(test_match, "Match", TokenType::Match);
+test_individual_token!(test_case, "Case", TokenType::Case);
+test_individual_token!(test_default, "Default", TokenType::Default);
+test_individual_token!(test_repeat, "Repeat", TokenType::Repeat);
+test_individual_token!(test_until, "Until", TokenType::Until);
+test_individual_token!(test_do, "Do", TokenType::Do);
+test_individual_token!(test_then, "Then", TokenType::Then);
+test_individual_token!(test_errors, "Errors", TokenType::Errors);
\ No newline at end of file
@@ -20,4 +20,5 @@ test_stray_token_repetitions!(test_question_mark,  "?", TokenType::QuestionMark)
 test_stray_token_repetitions!(test_semicolon,  ";", TokenType::Semicolon);
 test_stray_token_repetitions!(test_return_indicator,  "->", TokenType::ReturnIndicator);
 test_stray_token_repetitions!(test_variable_init,  "<-", TokenType::VariableInitializer);
-test_stray_token_repetitions!(test_comma,  ",", TokenType::Comma);
\ No newline at end of file
+test_stray_token_repetitions!(test_comma,  ",", TokenType::Comma);
+test_stray_token_repetitions!(test_dot,  ".", TokenType::Dot);
\ No newline at end of file
@@ -224,6 +224,16 @@ fn test_break_statement() {
     assert_eq!(tokens[1].token_type, TokenType::Semicolon);
 }
 
+#[test]
+fn test_return_statement() {
+    let input: &str = "RETURN a + 1;";
+    let tokens: Vec<Token> = tokenize(input).unwrap();
+    assert_eq!(tokens.len(), 5);
+    assert_eq!(tokens[0].token_type, TokenType::Return);
+    assert_eq!(tokens[1].token_type, TokenType::Identifier);
+    assert_eq!(tokens[4].token_type, TokenType::Semicolon);
+}
+
 #[test]
 fn test_logic_statement_expressions() {
     let input: &str = "9 = 3 AND NOT a % 3 = 0 OR b ^ 2 > 4;";
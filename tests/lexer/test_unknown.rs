@@ -14,7 +14,7 @@ use mascal::lexer::tokenize;
 )]
 fn test_unknown(input: &str, start: usize, expected: &str) {
     let tokens: Result<Vec<Token>, (Span, usize, &str)> = tokenize(input);
-    assert_eq!(tokens.is_err(), true);
+    assert!(tokens.is_err());
     let Err((span, line, val)) = tokens else {unreachable!()};
     assert_eq!(line, 0);
     assert_eq!(span.start, start);
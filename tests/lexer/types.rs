@@ -7,4 +7,7 @@ test_individual_token!(test_integer,  "Integer", TokenType::Integer);
 test_individual_token!(test_string,  "String", TokenType::String);
 test_individual_token!(test_float,  "Float", TokenType::Float);
 test_individual_token!(test_type,  "Type", TokenType::Type);
-test_individual_token!(test_boolean, "Boolean", TokenType::Boolean);
\ No newline at end of file
+test_individual_token!(test_boolean, "Boolean", TokenType::Boolean);
+test_individual_token!(test_map, "Map<", TokenType::Map);
+test_individual_token!(test_function, "Function", TokenType::Function);
+
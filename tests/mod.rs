@@ -1,2 +1,5 @@
+mod diagnostics;
+mod interpreter;
 mod lexer;
-mod parser;
\ No newline at end of file
+mod parser;
+mod repl;
\ No newline at end of file
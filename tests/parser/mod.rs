@@ -1,9 +1,25 @@
 mod parse_throw_statement;
+mod parse_try_statement;
+mod parse_record;
+mod parse_enum;
+mod parse_map;
+mod parse_lambda;
 mod parse_declare_statement;
+mod parse_conditional_expression;
 mod parse_conditional_statement;
+mod parse_logical_operators;
 mod parse_while_statements;
+mod parse_repeat_statements;
 mod parse_for_statements;
+mod parse_loop_labels;
+mod parse_match;
 mod parse_function;
+mod parse_spans;
+mod parse_multiple_errors;
+mod parse_return_statement;
+
+use mascal::defs::expressions::{MascalExpression, MascalExpressionKind};
+use mascal::defs::span::Span;
 
 #[macro_export]
 macro_rules! run_parsing {
@@ -15,7 +31,7 @@ macro_rules! run_parsing {
         
         let tokens: Vec<Token> = lexer::tokenize($input).unwrap();
         let token_sequence: TokenSequence = TokenSequence::new(tokens);
-        parser::parse(token_sequence)
+        parser::parse(token_sequence).map_err(|errors| errors.into_iter().next().unwrap())
     }};
 }
 
@@ -29,6 +45,67 @@ macro_rules! expect_error {
     }};
 }
 
+/*
+ Parsing the same expression on its own puts it somewhere else than it was in the statement, so
+ both sides get their spans wiped before being compared
+*/
+pub fn without_spans(expression: &MascalExpression) -> MascalExpression {
+    let strip = |expression: &MascalExpression| Box::new(without_spans(expression));
+    let kind: MascalExpressionKind = match &expression.kind {
+        MascalExpressionKind::DynamicArray(elements) => {
+            MascalExpressionKind::DynamicArray(elements.iter().map(without_spans).collect())
+        }
+        MascalExpressionKind::StaticArray(elements) => {
+            MascalExpressionKind::StaticArray(elements.iter().map(without_spans).collect())
+        }
+        MascalExpressionKind::Map(entries) => MascalExpressionKind::Map(
+            entries.iter().map(|(key, value)| (without_spans(key), without_spans(value))).collect()
+        ),
+        MascalExpressionKind::Unary { operator, value } => MascalExpressionKind::Unary {
+            operator: operator.clone(),
+            value: strip(value),
+        },
+        MascalExpressionKind::Binary { left, operator, right } => MascalExpressionKind::Binary {
+            left: strip(left),
+            operator: operator.clone(),
+            right: strip(right),
+        },
+        MascalExpressionKind::Call { function, arguments } => MascalExpressionKind::Call {
+            function: strip(function),
+            arguments: arguments.iter().map(without_spans).collect(),
+        },
+        MascalExpressionKind::Indexing { array, index, is_dynamic } => MascalExpressionKind::Indexing {
+            array: strip(array),
+            index: strip(index),
+            is_dynamic: *is_dynamic,
+        },
+        MascalExpressionKind::FieldAccess { record, field } => MascalExpressionKind::FieldAccess {
+            record: strip(record),
+            field: field.clone(),
+        },
+        MascalExpressionKind::Lambda { parameters, body } => MascalExpressionKind::Lambda {
+            parameters: parameters.clone(),
+            body: strip(body),
+        },
+        MascalExpressionKind::Conditional { condition, then_branch, else_branch } => {
+            MascalExpressionKind::Conditional {
+                condition: strip(condition),
+                then_branch: strip(then_branch),
+                else_branch: strip(else_branch),
+            }
+        }
+        kind => kind.clone(),
+    };
+    MascalExpression::new(kind, Span::default())
+}
+
+#[macro_export]
+macro_rules! assert_same_expression {
+    ($left: expr, $right: expr) => {{
+        assert_eq!($crate::parser::without_spans($left), $crate::parser::without_spans($right));
+    }};
+}
+
 #[macro_export]
 macro_rules! unwrap_to_expression {
     ($expr_str: expr) => {{
@@ -38,7 +115,7 @@ macro_rules! unwrap_to_expression {
         );
         let Ok(ast) = run_parsing!(input_expect.as_str()) else {unreachable!()};
         let ScopedBlocks::Program(exec) = ast.blocks[0].clone() else {unreachable!()};
-        let MascalStatement::ExpressionStatement(expr) = exec.body[0].clone() else {unreachable!()};
+        let MascalStatementKind::ExpressionStatement(expr) = exec.body[0].clone().kind else {unreachable!()};
         expr
     }};
 }
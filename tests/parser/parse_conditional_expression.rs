@@ -0,0 +1,85 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::blocks::ScopedBlocks;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::expressions::{MascalExpression, MascalExpressionKind};
+use mascal::defs::operators::MascalBinaryOperators;
+use mascal::defs::statements::MascalStatementKind;
+use crate::{assert_same_expression, define_program_boilerplate, run_parsing};
+
+/* A statement starting with IF is an IF statement, so the expression gets assigned to something */
+fn assigned_expression(expression: &str) -> MascalExpression {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [format!("c <- {};", expression)]
+    );
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else { unreachable!() };
+    let MascalStatementKind::Declaration { value, .. } = exec.body[0].kind.clone() else { unreachable!() };
+    value
+}
+
+#[rstest(
+    input, expected_parts,
+    case("IF a THEN 1 ELSE 2", ("a", "1", "2")),
+    case("if a > b then a else b", ("a > b", "a", "b")),
+    case("IF a AND NOT b THEN \"yes\" ELSE \"no\"", ("a AND NOT b", "\"yes\"", "\"no\"")),
+    case("IF a THEN 1 ELSE 2 + 3", ("a", "1", "2 + 3")),
+    case("IF a THEN f(1, 2) ELSE xs[0]", ("a", "f(1, 2)", "xs[0]")),
+    case("IF a THEN IF b THEN 1 ELSE 2 ELSE 3", ("a", "IF b THEN 1 ELSE 2", "3")),
+    case("IF a THEN 1 ELSE IF b THEN 2 ELSE 3", ("a", "1", "IF b THEN 2 ELSE 3")),
+    case("IF a THEN 1 ELIF b THEN 2 ELSE 3", ("a", "1", "IF b THEN 2 ELSE 3")),
+)]
+fn test_correct_parsing(input: &str, expected_parts: (&str, &str, &str)) {
+    let expression: MascalExpression = assigned_expression(input);
+    let MascalExpressionKind::Conditional { condition, then_branch, else_branch } = expression.kind else {
+        panic!("The expression is not a conditional one")
+    };
+    assert_same_expression!(&condition, &assigned_expression(expected_parts.0));
+    assert_same_expression!(&then_branch, &assigned_expression(expected_parts.1));
+    assert_same_expression!(&else_branch, &assigned_expression(expected_parts.2));
+}
+
+#[test]
+fn test_conditional_inside_other_expressions() {
+    let expression: MascalExpression = assigned_expression("1 + (IF a THEN 2 ELSE 3) * 4");
+    let MascalExpressionKind::Binary { operator, right, .. } = expression.kind else { unreachable!() };
+    assert_eq!(operator, MascalBinaryOperators::Plus);
+    let MascalExpressionKind::Binary { left, .. } = right.kind else { unreachable!() };
+    assert!(matches!(left.kind, MascalExpressionKind::Conditional { .. }));
+
+    let expression: MascalExpression = assigned_expression("WRITE(IF a THEN 1 ELSE 2, 3)");
+    let MascalExpressionKind::Call { arguments, .. } = expression.kind else { unreachable!() };
+    assert_eq!(arguments.len(), 2);
+    assert!(matches!(arguments[0].kind, MascalExpressionKind::Conditional { .. }));
+}
+
+#[rstest(
+    input, message,
+    case("c <- IF a 1 ELSE 2;", "Expected THEN after the condition of the IF expression but got \"1\""),
+    case("c <- IF a THEN 1;", "Expected ELSE after the THEN branch of the IF expression but got nothing"),
+    case("c <- IF a THEN 1 2;", "Expected ELSE after the THEN branch of the IF expression but got \"2\""),
+    case("c <- IF a THEN 1 ELSE;", "Expected the ELSE branch of the IF expression after ELSE but got nothing"),
+    case("c <- IF a THEN;", "Expected the THEN branch of the IF expression after THEN but got nothing"),
+    case("c <- IF;", "Expected the condition of the IF expression after IF but got nothing"),
+    case("c <- IF a THEN 1 ELSE IF b THEN 2;", "Expected ELSE after the THEN branch of the IF expression but got nothing"),
+)]
+fn test_incorrect_parsing(input: &str, message: &str) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [input]
+    );
+    let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
+    assert!(
+        matches!(ast.as_ref().unwrap_err(),
+                MascalError {
+                    error_type,
+                    source,
+                    ..
+                } if *error_type == MascalErrorType::ParserError
+                && source == message
+            ),
+        "got {:?}, expected MascalError {{ error_type: {:?}, message: {:?}, ... }}",
+        &ast, MascalErrorType::ParserError, message
+    );
+}
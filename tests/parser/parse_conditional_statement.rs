@@ -3,8 +3,8 @@ use mascal::ast::AbstractSyntaxTree;
 use mascal::defs::blocks::ScopedBlocks;
 use mascal::defs::errors::{MascalError, MascalErrorType};
 use mascal::defs::expressions::MascalExpression;
-use mascal::defs::statements::MascalStatement;
-use crate::{define_program_boilerplate, run_parsing, unwrap_to_expression};
+use mascal::defs::statements::MascalStatementKind;
+use crate::{assert_same_expression, define_program_boilerplate, run_parsing, unwrap_to_expression};
 
 #[rstest(
     input, condition_part,
@@ -20,27 +20,30 @@ use crate::{define_program_boilerplate, run_parsing, unwrap_to_expression};
         "IF FALSE OR 1 = 1 {c <- 1;} ELIF b = a AND a = b {c <- 2;} ELIF 2 < 3 {c <- 3;}", 
         vec![Some("FALSE OR 1 = 1"), Some("b = a AND a = b"), Some("2 < 3")]
     ),
+    case("IF a {IF b {c <- 1;} ELSE {c <- 2;}} ELSE {c <- 3;}", vec![Some("a"), None]),
+    case("IF a {c <- IF b THEN 1 ELSE 2;} ELIF b {c <- 3;}", vec![Some("a"), Some("b")]),
+    case("IF IF a THEN b ELSE c {c <- 1;} ELSE {c <- 2;}", vec![Some("(IF a THEN b ELSE c)"), None]),
 )]
 fn test_correct_parsing(input: &str, condition_part: Vec<Option<&str>>) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
     assert_eq!(ast.blocks.len(), 1);
     let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
     assert_eq!(exec.body.len(), 1);
-    match &exec.body[0] {
-        MascalStatement::ConditionalStatement(branches) => {
+    match &exec.body[0].kind {
+        MascalStatementKind::ConditionalStatement(branches) => {
             assert_eq!(branches.len(), condition_part.len());
             for (branch, expected) in branches.iter().zip(condition_part.iter()) {
                 assert_eq!(branch.statements.len(), 1);
                 if let Some(unwrapped_expected) = *expected {
                     let expr: &MascalExpression = &unwrap_to_expression!(unwrapped_expected);
-                    assert_eq!(branch.condition.as_ref().unwrap(), expr);
+                    assert_same_expression!(branch.condition.as_ref().unwrap(), expr);
                     continue;
                 }
-                assert_eq!(branch.condition.is_none(), true);
+                assert!(branch.condition.is_none());
             }
         }
         _ => panic!("The statement is not a conditional one")
@@ -81,7 +84,7 @@ fn test_correct_parsing(input: &str, condition_part: Vec<Option<&str>>) {
 fn test_incorrect_parsing(input: &str, message: &str) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
     assert!(
@@ -107,7 +110,7 @@ fn test_incorrect_parsing(input: &str, message: &str) {
 fn test_incorrect_parsing2(input: &str, message: &str) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
     assert!(
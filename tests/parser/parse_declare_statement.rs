@@ -2,8 +2,8 @@ use rstest::rstest;
 use mascal::ast::AbstractSyntaxTree;
 use mascal::defs::blocks::ScopedBlocks;
 use mascal::defs::errors::{MascalError, MascalErrorType};
-use mascal::defs::expressions::MascalExpression;
-use mascal::defs::statements::MascalStatement;
+use mascal::defs::expressions::{MascalExpression, MascalExpressionKind};
+use mascal::defs::statements::{MascalStatement, MascalStatementKind};
 use crate::{define_program_boilerplate, run_parsing};
 
 #[rstest(
@@ -16,14 +16,14 @@ use crate::{define_program_boilerplate, run_parsing};
 fn test_correct_parsing1(input: &str) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
     assert!(matches!(&ast.blocks[0],
         ScopedBlocks::Program(exec) if exec.body.len() == 1
-        && matches!(&exec.body[0],
-            MascalStatement::Declaration { variable, .. }
-                if matches!(variable, MascalExpression::Symbolic(_))
+        && matches!(&exec.body[0].kind,
+            MascalStatementKind::Declaration { variable, .. }
+                if matches!(variable.kind, MascalExpressionKind::Symbolic(_))
         )
     ));
 }
@@ -41,22 +41,22 @@ fn test_correct_parsing1(input: &str) {
 fn test_correct_parsing2(input: &str, expected_dynamics: Vec<bool>) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
     assert_eq!(ast.blocks.len(), 1);
     let ScopedBlocks::Program(exec_block) = &ast.blocks[0] else {unreachable!()};
     assert_eq!(exec_block.body.len(), 1);
     let target: &MascalStatement = &exec_block.body[0];
-    assert_eq!(matches!(target, MascalStatement::Declaration {..}), true);
-    let MascalStatement::Declaration {variable: target_var, ..} = target else {
+    assert!(matches!(target.kind, MascalStatementKind::Declaration {..}));
+    let MascalStatementKind::Declaration {variable: target_var, ..} = &target.kind else {
         unreachable!()
     };
     let mut are_dynamics: Vec<bool> = vec![];
     let mut curr: &MascalExpression = target_var;
-    while let MascalExpression::Indexing {is_dynamic, array, ..} = curr {
+    while let MascalExpressionKind::Indexing {is_dynamic, array, ..} = &curr.kind {
         are_dynamics.push(*is_dynamic);
-        curr = &*array;
+        curr = array;
     }
     are_dynamics.reverse();
     assert_eq!(expected_dynamics, are_dynamics);
@@ -78,7 +78,7 @@ fn test_correct_parsing2(input: &str, expected_dynamics: Vec<bool>) {
 fn test_incorrect_parsing1(input: &str, closing_symbol: &str) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
     assert!(
@@ -106,7 +106,7 @@ fn test_incorrect_parsing1(input: &str, closing_symbol: &str) {
 fn test_incorrect_parsing2(input: &str, array_type: &str) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
     assert!(
@@ -146,7 +146,7 @@ fn test_incorrect_parsing2(input: &str, array_type: &str) {
 fn test_incorrect_parsing3(input: &str) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
     assert!(
@@ -178,7 +178,7 @@ fn test_incorrect_parsing3(input: &str) {
 fn test_incorrect_parsing4(input: &str, message: &str) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
     assert!(
@@ -0,0 +1,37 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::blocks::ScopedBlocks;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use crate::{define_program_boilerplate, run_parsing};
+
+#[rstest(
+    input, expected_variants,
+    case("DEFINE_ENUM Color { RED, GREEN, BLUE }", vec!["RED", "GREEN", "BLUE"]),
+    case("define_enum Color { RED, GREEN, BLUE, }", vec!["RED", "GREEN", "BLUE"]),
+    case("Define_Enum Color {\n    ONLY\n}", vec!["ONLY"]),
+)]
+fn test_enum_definition(input: &str, expected_variants: Vec<&str>) {
+    let source: String = String::from(input)
+        + &define_program_boilerplate!(["Color {c;}"], ["c <- Color.RED;"]);
+    let ast: AbstractSyntaxTree = run_parsing!(source.as_str()).unwrap();
+    let ScopedBlocks::Enum { name, variants, .. } = &ast.blocks[0] else { unreachable!() };
+    assert_eq!(name, "Color");
+    assert_eq!(variants.to_vec(), expected_variants);
+}
+
+#[rstest(
+    input, expected_source,
+    case("DEFINE_ENUM { RED }", "Expected a identifier for the enum name"),
+    case("DEFINE_ENUM Color {}", "Expected at least one variant for the enum \"Color\""),
+    case("DEFINE_ENUM Color { RED GREEN }", "Expected a comma between the variants of the enum but got \"GREEN\""),
+    case("DEFINE_ENUM Color { RED, , GREEN }", "Expected a variant name in the enum but got \",\""),
+    case("DEFINE_ENUM Color { RED, 2 }", "Expected a variant name in the enum but got \"2\""),
+    case("DEFINE_ENUM Color { RED, GREEN, RED }", "The variant \"RED\" appears more than once in the enum \"Color\""),
+)]
+fn test_enum_parsing_errors(input: &str, expected_source: &str) {
+    let source: String = String::from(input)
+        + &define_program_boilerplate!(Vec::<String>::new(), Vec::<String>::new());
+    let err: MascalError = run_parsing!(source.as_str()).unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::ParserError);
+    assert_eq!(err.source, expected_source);
+}
@@ -3,8 +3,8 @@ use mascal::ast::AbstractSyntaxTree;
 use mascal::defs::blocks::ScopedBlocks;
 use mascal::defs::errors::{MascalError, MascalErrorType};
 use mascal::defs::expressions::MascalExpression;
-use mascal::defs::statements::MascalStatement;
-use crate::{define_program_boilerplate, run_parsing, unwrap_to_expression};
+use mascal::defs::statements::MascalStatementKind;
+use crate::{assert_same_expression, define_program_boilerplate, run_parsing, unwrap_to_expression};
 
 #[rstest(
     input, expected_exprs,
@@ -12,18 +12,21 @@ use crate::{define_program_boilerplate, run_parsing, unwrap_to_expression};
     case("FOR i FROM 1 / 2 TO 10 / 5 {c <- 3;}", ("i", "1 / 2", "10 / 5", "1")),
     case("FOR j FROM a + b TO 5 - 2 WITH_STEP 3 + 2 {c <- 3;}", ("j", "a + b", "5 - 2", "3 + 2")),
     case("FOR k FROM -2 + -2 TO -5 WITH_STEP -1 {c <- 3;}", ("k", "-2 + -2", "-5", "-1")),
+    case("FOR i FROM 10 DOWNTO 1 {c <- 3;}", ("i", "10", "1", "-1")),
+    case("FOR i FROM n downto 0 WITH_STEP 2 {c <- 3;}", ("i", "n", "0", "-2")),
+    case("FOR i FROM n DOWNTO 0 WITH_STEP a + b {c <- 3;}", ("i", "n", "0", "-(a + b)")),
 )]
 fn test_correct_parsing(input: &str, expected_exprs: (&str, &str, &str, &str)) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
     assert_eq!(ast.blocks.len(), 1);
     let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
     assert_eq!(exec.body.len(), 1);
-    match &exec.body[0] {
-        MascalStatement::For {
+    match &exec.body[0].kind {
+        MascalStatementKind::For {
             variable,
             from,
             to,
@@ -32,11 +35,11 @@ fn test_correct_parsing(input: &str, expected_exprs: (&str, &str, &str, &str)) {
         } => {
             assert_eq!(variable, expected_exprs.0);
             let expr: &MascalExpression = &unwrap_to_expression!(expected_exprs.1);
-            assert_eq!(from, expr);
+            assert_same_expression!(from, expr);
             let expr: &MascalExpression = &unwrap_to_expression!(expected_exprs.2);
-            assert_eq!(to, expr);
+            assert_same_expression!(to, expr);
             let expr: &MascalExpression = &unwrap_to_expression!(expected_exprs.3);
-            assert_eq!(step, expr);
+            assert_same_expression!(step, expr);
         }
         _ => panic!("The statement is not a for loop one")
     }
@@ -57,6 +60,8 @@ fn test_correct_parsing(input: &str, expected_exprs: (&str, &str, &str, &str)) {
     case("FOR i FROM 2 TO ;", "Unexpected characters in primary expression: \";\""),
     case("FOR i FROM 2 TO 5", "Unexpected characters found inside implementation block, perhaps forgot a semicolon?"),
     case("FOR i FROM 2 TO 5;", "Unexpected character sequences found in a supposed expression"),
+    case("FOR i FROM 2 DOWNTO ;", "Unexpected characters in primary expression: \";\""),
+    case("FOR i FROM 2 DOWNTO 5;", "Unexpected character sequences found in a supposed expression"),
     case("FOR i FROM 2 TO 5 {", "DEFINE_PROGRAM block not properly closed"),
     case("FOR i FROM 2 TO 5 {{}", "DEFINE_PROGRAM block not properly closed"),
     case("FOR i FROM 2 TO 5 }", "Unexpected characters found inside implementation block, perhaps forgot a semicolon?"),
@@ -70,7 +75,7 @@ fn test_correct_parsing(input: &str, expected_exprs: (&str, &str, &str, &str)) {
 fn test_incorrect_parsing(input: &str, message: &str) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
     assert!(
@@ -85,4 +90,65 @@ fn test_incorrect_parsing(input: &str, message: &str) {
         "got {:?}, expected MascalError {{ error_type: {:?}, message: {:?}, ... }}",
         &ast, MascalErrorType::ParserError, message
     );
-}
\ No newline at end of file
+}
+#[rstest(
+    input, expected,
+    case("FOR EACH x IN xs {c <- x;}", ("x", None, "xs")),
+    case("FOR EACH i, x IN xs {c <- x;}", ("x", Some("i"), "xs")),
+    case("for each ch in \"abc\" + s {}", ("ch", None, "\"abc\" + s")),
+    case("FOR EACH i, row IN <<<<1>>, <<2, 3>>>> {}", ("row", Some("i"), "<<<<1>>, <<2, 3>>>>")),
+)]
+fn test_correct_for_each_parsing(input: &str, expected: (&str, Option<&str>, &str)) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [input]
+    );
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
+    assert_eq!(exec.body.len(), 1);
+    match &exec.body[0].kind {
+        MascalStatementKind::ForEach {
+            variable,
+            index_variable,
+            iterable,
+            ..
+        } => {
+            assert_eq!(variable, expected.0);
+            assert_eq!(index_variable.as_deref(), expected.1);
+            let expr: &MascalExpression = &unwrap_to_expression!(expected.2);
+            assert_same_expression!(iterable, expr);
+        }
+        _ => panic!("The statement is not a for each loop one")
+    }
+}
+
+#[rstest(
+    input, message,
+    case("FOR EACH {}", "Expected a variable identifier to use but got \"{\""),
+    case("FOR EACH x {}", "Expected IN but got \"{\""),
+    case("FOR EACH x FROM 1 TO 2 {}", "Expected IN but got \"FROM\""),
+    case("FOR EACH i, {}", "Expected a variable identifier for the element but got \"{\""),
+    case("FOR EACH i, i IN xs {}", "The variable \"i\" cannot be both the index and the element"),
+    case("FOR EACH x IN {}", "Expected something to loop over after IN but got nothing"),
+    case("FOR EACH x IN xs {c <- 3}", "Unexpected characters found inside For each loop block, perhaps forgot a semicolon?"),
+    case("FOR EACH x IN xs", "Unexpected characters found inside implementation block, perhaps forgot a semicolon?"),
+)]
+fn test_incorrect_for_each_parsing(input: &str, message: &str) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [input]
+    );
+    let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
+    assert!(
+        matches!(ast.as_ref().unwrap_err(),
+                MascalError {
+                    error_type,
+                    source,
+                    ..
+                } if *error_type == MascalErrorType::ParserError
+                && source == message
+            ),
+        "got {:?}, expected MascalError {{ error_type: {:?}, message: {:?}, ... }}",
+        &ast, MascalErrorType::ParserError, message
+    );
+}
@@ -36,7 +36,7 @@ fn test_correct_parsing(
 ) {
     let mut input_program_boilerplate: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ "" ]
+        [""]
     ) + "\n";
     input_program_boilerplate.push_str(input);
     let ast: AbstractSyntaxTree = run_parsing!(input_program_boilerplate.as_str()).unwrap();
@@ -46,6 +46,7 @@ fn test_correct_parsing(
         name,
         return_type,
         execution_block,
+        ..
     } = &ast.blocks[1] else {unreachable!()};
     assert_eq!(execution_block.body.len(), 0);
     assert_eq!(parameters.len(), expected_params.len());
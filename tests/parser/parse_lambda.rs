@@ -0,0 +1,80 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::blocks::{ScopedBlocks, VariableBlock};
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::expressions::{MascalExpression, MascalExpressionKind};
+use mascal::defs::operators::MascalBinaryOperators;
+use mascal::defs::statements::MascalStatementKind;
+use mascal::defs::types::MascalUnprocessedType;
+use crate::{define_program_boilerplate, run_parsing, unwrap_to_expression};
+
+#[rstest(
+    input, expected_parameters,
+    case("FUNCTION() -> 1", vec![]),
+    case("function(x) -> x * 2", vec!["x"]),
+    case("Function(a, b) -> a < b", vec!["a", "b"]),
+    case("FUNCTION(a, b, c) -> f(a, b)[c]", vec!["a", "b", "c"]),
+)]
+fn test_lambda_parameters(input: &str, expected_parameters: Vec<&str>) {
+    let expression: MascalExpression = unwrap_to_expression!(input);
+    let MascalExpressionKind::Lambda { parameters, .. } = expression.kind else { unreachable!() };
+    assert_eq!(parameters.iter().map(|parameter| &**parameter).collect::<Vec<&str>>(), expected_parameters);
+}
+
+#[test]
+fn test_lambda_body_takes_the_whole_expression() {
+    let expression: MascalExpression = unwrap_to_expression!("FUNCTION(x) -> x + 1 == 2");
+    let MascalExpressionKind::Lambda { body, .. } = expression.kind else { unreachable!() };
+    let MascalExpressionKind::Binary { operator, .. } = body.kind else { unreachable!() };
+    assert_eq!(operator, MascalBinaryOperators::Equals);
+}
+
+#[test]
+fn test_lambda_as_argument() {
+    let expression: MascalExpression = unwrap_to_expression!("apply(FUNCTION(x) -> x * x, 3)");
+    let MascalExpressionKind::Call { arguments, .. } = expression.kind else { unreachable!() };
+    assert_eq!(arguments.len(), 2);
+    assert!(matches!(arguments[0].kind, MascalExpressionKind::Lambda { .. }));
+}
+
+#[test]
+fn test_function_type_expression() {
+    let expression: MascalExpression = unwrap_to_expression!("FUNCTION");
+    let MascalExpressionKind::Type(function_type) = expression.kind else { unreachable!() };
+    assert_eq!(*function_type, MascalUnprocessedType::Function);
+}
+
+#[test]
+fn test_function_variable_group() {
+    let source: String = define_program_boilerplate!(["FUNCTION {f; g <- Len;}"], Vec::<String>::new());
+    let ast: AbstractSyntaxTree = run_parsing!(source.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else { unreachable!() };
+    let variables: &VariableBlock = &exec.variables;
+    assert_eq!(variables.custom_types[0].variable_type, MascalUnprocessedType::Function);
+    assert_eq!(variables.custom_types[0].variables.len(), 2);
+}
+
+#[test]
+fn test_function_return_type() {
+    let source: String = String::from(
+        "DEFINE_FUNCTION f() -> FUNCTION { VARIABLES {} IMPLEMENTATION {} }"
+    ) + &define_program_boilerplate!(Vec::<String>::new(), Vec::<String>::new());
+    let ast: AbstractSyntaxTree = run_parsing!(source.as_str()).unwrap();
+    let ScopedBlocks::Function { return_type, .. } = &ast.blocks[0] else { unreachable!() };
+    assert_eq!(return_type, &Some(MascalUnprocessedType::Function));
+}
+
+#[rstest(
+    code, expected_source,
+    case("f <- FUNCTION(1) -> 1;", "Expected a parameter name for the lambda but got \"1\""),
+    case("f <- FUNCTION(a b) -> a;", "Expected a comma or a closing parenthesis after the parameter of the lambda"),
+    case("f <- FUNCTION(a, a) -> a;", "The parameter \"a\" appears more than once in the lambda"),
+    case("f <- FUNCTION(a) a;", "Expected an arrow '->' followed by the body of the lambda"),
+    case("f <- FUNCTION(a) ->;", "Expected an expression for the body of the lambda but got nothing"),
+)]
+fn test_lambda_parsing_errors(code: &str, expected_source: &str) {
+    let source: String = define_program_boilerplate!(["FUNCTION {f;}"], [code]);
+    let err: MascalError = run_parsing!(source.as_str()).unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::ParserError);
+    assert_eq!(err.source, expected_source);
+}
@@ -0,0 +1,52 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use crate::{define_program_boilerplate, run_parsing};
+
+#[rstest(
+    input, message,
+    case("a <- 1 AND b;", "Expected a boolean on the left side of AND but got an integer"),
+    case("a <- b AND 2.5;", "Expected a boolean on the right side of AND but got a float"),
+    case("a <- \"yes\" OR b;", "Expected a boolean on the left side of OR but got a string"),
+    case("a <- b or <<1, 2>>;", "Expected a boolean on the right side of OR but got an array"),
+    case("a <- b OR c + 1;", "Expected a boolean on the right side of OR but got an arithmetic expression"),
+    case("a <- c * 2 AND b;", "Expected a boolean on the left side of AND but got an arithmetic expression"),
+    case("a <- (-c) AND b;", "Expected a boolean on the left side of AND but got an arithmetic expression"),
+    case("a <- b OR INTEGER;", "Expected a boolean on the right side of OR but got a type"),
+    case("a <- (TYPEOF c) OR b;", "Expected a boolean on the left side of OR but got a type"),
+    case("IF b AND c > 1 OR 0 {}", "Expected a boolean on the right side of OR but got an integer"),
+)]
+fn test_non_boolean_operands(input: &str, message: &str) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [input]
+    );
+    let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
+    assert!(
+        matches!(ast.as_ref().unwrap_err(),
+                MascalError {
+                    error_type,
+                    source,
+                    ..
+                } if *error_type == MascalErrorType::ParserError
+                && source == message
+            ),
+        "got {:?}, expected MascalError {{ error_type: {:?}, message: {:?}, ... }}",
+        &ast, MascalErrorType::ParserError, message
+    );
+}
+
+#[rstest(
+    input,
+    case("a <- b AND c;"),
+    case("a <- TRUE OR NOT b;"),
+    case("a <- c > 1 AND d(2) OR e[1] OR f.g;"),
+    case("a <- (b AND c) OR NULL;"),
+)]
+fn test_possibly_boolean_operands(input: &str) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [input]
+    );
+    assert!(run_parsing!(input.as_str()).is_ok());
+}
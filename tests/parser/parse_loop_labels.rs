@@ -0,0 +1,85 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::blocks::ScopedBlocks;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::statements::MascalStatementKind;
+use crate::{define_program_boilerplate, run_parsing};
+
+#[rstest(
+    input, expected_label,
+    case("outer: FOR i FROM 1 TO 10 {c <- 3;}", "outer"),
+    case("rows: FOR EACH row IN grid {c <- 3;}", "rows"),
+    case("search: WHILE TRUE {c <- 3;}", "search"),
+    case("again: while a < b {}", "again"),
+)]
+fn test_labeled_loops(input: &str, expected_label: &str) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [input]
+    );
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
+    assert_eq!(exec.body.len(), 1);
+    let label: &Option<String> = match &exec.body[0].kind {
+        MascalStatementKind::For { label, .. }
+        | MascalStatementKind::ForEach { label, .. }
+        | MascalStatementKind::While { label, .. } => label,
+        _ => panic!("The statement is not a loop one")
+    };
+    assert_eq!(label.as_deref(), Some(expected_label));
+}
+
+#[rstest(
+    input, expected_break, expected_label,
+    case("BREAK;", true, None),
+    case("BREAK outer;", true, Some("outer")),
+    case("CONTINUE;", false, None),
+    case("continue outer;", false, Some("outer")),
+)]
+fn test_break_and_continue(input: &str, expected_break: bool, expected_label: Option<&str>) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [format!("WHILE TRUE {{{}}}", input)]
+    );
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
+    let MascalStatementKind::While { branch, .. } = &exec.body[0].kind else {unreachable!()};
+    match &branch.statements[0].kind {
+        MascalStatementKind::Break(label) if expected_break => {
+            assert_eq!(label.as_deref(), expected_label);
+        }
+        MascalStatementKind::Continue(label) if !expected_break => {
+            assert_eq!(label.as_deref(), expected_label);
+        }
+        other => panic!("Unexpected statement {:?}", other)
+    }
+}
+
+#[rstest(
+    input, message,
+    case("outer: a <- 1;", "Expected a loop to follow the label \"outer\""),
+    case("outer: IF TRUE {}", "Expected a loop to follow the label \"outer\""),
+    case("outer: FOR {}", "Expected a variable identifier to use but got \"{\""),
+    case("WHILE TRUE {BREAK outer inner;}", "Expected nothing else to be supplied but contents inside the break statement"),
+    case("WHILE TRUE {BREAK 1;}", "Expected nothing else to be supplied but contents inside the break statement"),
+    case("WHILE TRUE {CONTINUE outer.x;}", "Expected nothing else to be supplied but contents inside the continue statement"),
+)]
+fn test_incorrect_parsing(input: &str, message: &str) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [input]
+    );
+    let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
+    assert!(
+        matches!(ast.as_ref().unwrap_err(),
+                MascalError {
+                    error_type,
+                    source,
+                    ..
+                } if *error_type == MascalErrorType::ParserError
+                && source == message
+            ),
+        "got {:?}, expected MascalError {{ error_type: {:?}, message: {:?}, ... }}",
+        &ast, MascalErrorType::ParserError, message
+    );
+}
@@ -0,0 +1,99 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::blocks::{ScopedBlocks, VariableBlock};
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::expressions::{MascalExpression, MascalExpressionKind};
+use mascal::defs::statements::MascalStatementKind;
+use mascal::defs::types::MascalUnprocessedType;
+use crate::{define_program_boilerplate, run_parsing, unwrap_to_expression};
+
+fn map_of(key_type: MascalUnprocessedType, value_type: MascalUnprocessedType) -> MascalUnprocessedType {
+    MascalUnprocessedType::Map(Box::new(key_type), Box::new(value_type))
+}
+
+#[rstest(
+    map_case, declaration, expected_type,
+    case("MAP", "MAP<STRING, INTEGER> {m;}", map_of(MascalUnprocessedType::String, MascalUnprocessedType::Integer)),
+    case("map", "map<INTEGER, Point> {m;}", map_of(MascalUnprocessedType::Integer, MascalUnprocessedType::Record("Point".into()))),
+    case(
+        "Map",
+        "Map<STRING, MAP<BOOLEAN, FLOAT>> {m;}",
+        map_of(MascalUnprocessedType::String, map_of(MascalUnprocessedType::Boolean, MascalUnprocessedType::Float))
+    ),
+    case(
+        "MAP",
+        "MAP<STRING, MAP<BOOLEAN, FLOAT> > {m;}",
+        map_of(MascalUnprocessedType::String, map_of(MascalUnprocessedType::Boolean, MascalUnprocessedType::Float))
+    ),
+)]
+fn test_map_variable_groups(map_case: &str, declaration: &str, expected_type: MascalUnprocessedType) {
+    let source: String = define_program_boilerplate!([declaration, "INTEGER {i;}"], ["m[\"a\"] <- 1;"]);
+    let ast: AbstractSyntaxTree = run_parsing!(source.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else { unreachable!() };
+    let variables: &VariableBlock = &exec.variables;
+    assert!(declaration.starts_with(map_case));
+    assert_eq!(variables.custom_types.len(), 1);
+    assert_eq!(variables.custom_types[0].variable_type, expected_type);
+    assert_eq!(variables.custom_types[0].variables.len(), 1);
+    assert_eq!(variables.integers.len(), 1);
+}
+
+#[rstest(
+    input, expected_length,
+    case("[:]", 0),
+    case("[\"a\": 1]", 1),
+    case("[\"a\": 1, \"b\": [1, 2], \"c\": [1: 2]]", 3),
+    case("[a[0]: (1 + 2), Len(b): <<3>>]", 2),
+)]
+fn test_map_literals(input: &str, expected_length: usize) {
+    let expression: MascalExpression = unwrap_to_expression!(input);
+    let MascalExpressionKind::Map(entries) = expression.kind else { unreachable!() };
+    assert_eq!(entries.len(), expected_length);
+}
+
+#[test]
+fn test_brackets_stay_static_arrays() {
+    let expression: MascalExpression = unwrap_to_expression!("[[1: 2], 3]");
+    let MascalExpressionKind::StaticArray(elements) = expression.kind else { unreachable!() };
+    assert!(matches!(elements[0].kind, MascalExpressionKind::Map(..)));
+}
+
+#[test]
+fn test_map_type_expression() {
+    let expression: MascalExpression = unwrap_to_expression!("MAP<STRING, DYNAMIC>");
+    let MascalExpressionKind::Type(map_type) = expression.kind else { unreachable!() };
+    assert_eq!(*map_type, map_of(MascalUnprocessedType::String, MascalUnprocessedType::Dynamic));
+}
+
+#[test]
+fn test_map_return_type() {
+    let source: String = String::from(
+        "DEFINE_FUNCTION f() -> MAP<STRING, INTEGER>[] { VARIABLES {} IMPLEMENTATION {} }"
+    ) + &define_program_boilerplate!(Vec::<String>::new(), Vec::<String>::new());
+    let ast: AbstractSyntaxTree = run_parsing!(source.as_str()).unwrap();
+    let ScopedBlocks::Function { return_type, .. } = &ast.blocks[0] else { unreachable!() };
+    assert_eq!(
+        return_type,
+        &Some(MascalUnprocessedType::StaticArray(Box::new(map_of(
+            MascalUnprocessedType::String,
+            MascalUnprocessedType::Integer
+        ))))
+    );
+}
+
+#[rstest(
+    declaration, code, expected_source,
+    case("MAP<STRING> {m;}", "", "Expected a comma between the key and the value type of the map"),
+    case("MAP<STRING[], INTEGER> {m;}", "", "Expected a comma between the key and the value type of the map"),
+    case("MAP<Point, INTEGER> {m;}", "", "Expected a type for the keys of the map but got \"Point\""),
+    case("MAP<STRING, INTEGER {m;}", "", "Expected a closing '>' for the map type"),
+    case("MAP<STRING, INTEGER> {m;} MAP<STRING, INTEGER> {n;}", "", "Found redefinition of the same variable type block MAP<STRING, INTEGER> declared before"),
+    case("INTEGER {i;}", "i <- [\"a\": 1, \"b\"];", "Expected a \":\" in the map but got \"]\""),
+    case("INTEGER {i;}", "i <- [: 1];", "Expected a \"]\" in the map but got \"1\""),
+)]
+fn test_map_parsing_errors(declaration: &str, code: &str, expected_source: &str) {
+    let source: String = define_program_boilerplate!([declaration], [code]);
+    let err: MascalError = run_parsing!(source.as_str()).unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::ParserError);
+    assert_eq!(err.source, expected_source);
+}
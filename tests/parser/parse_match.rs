@@ -0,0 +1,91 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::blocks::ScopedBlocks;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::expressions::MascalExpression;
+use mascal::defs::statements::{MascalCasePattern, MascalStatementKind};
+use crate::{assert_same_expression, define_program_boilerplate, run_parsing, unwrap_to_expression};
+
+#[rstest(
+    input, expected_subject, expected_patterns, has_default,
+    case("MATCH a {CASE 1 {b <- 1;}}", "a", vec![vec!["1"]], false),
+    case("MATCH a + 1 {CASE 1, 2 {} CASE 3 {} DEFAULT {b <- 2;}}", "a + 1", vec![vec!["1", "2"], vec!["3"]], true),
+    case("MATCH a {CASE 3 TO 9, 12 {}}", "a", vec![vec!["3..9", "12"]], false),
+    case("MATCH a {CASE -5 TO -1 {} CASE \"a\" TO \"z\" {}}", "a", vec![vec!["-5..-1"], vec!["\"a\"..\"z\""]], false),
+    case("MATCH a {CASE TYPE INTEGER {}}", "a", vec![vec!["type INTEGER"]], false),
+    case("MATCH a {CASE TYPE INTEGER, FLOAT, STRING {}}", "a", vec![vec!["type INTEGER", "type FLOAT", "type STRING"]], false),
+    case("match a {case 1 {} default {}}", "a", vec![vec!["1"]], true),
+    case("MATCH a {DEFAULT {b <- 2;}}", "a", vec![], true),
+)]
+fn test_correct_parsing(
+    input: &str,
+    expected_subject: &str,
+    expected_patterns: Vec<Vec<&str>>,
+    has_default: bool
+) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [input]
+    );
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
+    assert_eq!(exec.body.len(), 1);
+    let MascalStatementKind::Match { subject, cases, default } = &exec.body[0].kind else {
+        panic!("The statement is not a match one")
+    };
+    let expr: &MascalExpression = &unwrap_to_expression!(expected_subject);
+    assert_same_expression!(subject, expr);
+    assert_eq!(default.is_some(), has_default);
+    assert_eq!(cases.len(), expected_patterns.len());
+    for (case, expected_patterns) in cases.iter().zip(expected_patterns) {
+        assert_eq!(case.patterns.len(), expected_patterns.len());
+        for (pattern, expected) in case.patterns.iter().zip(expected_patterns) {
+            match (pattern, expected.split_once("..")) {
+                (MascalCasePattern::Range { from, to }, Some((expected_from, expected_to))) => {
+                    assert_same_expression!(from, &unwrap_to_expression!(expected_from));
+                    assert_same_expression!(to, &unwrap_to_expression!(expected_to));
+                }
+                (MascalCasePattern::Type(value), None) if expected.starts_with("type ") => {
+                    assert_same_expression!(value, &unwrap_to_expression!(&expected[5..]));
+                }
+                (MascalCasePattern::Value(value), None) => {
+                    assert_same_expression!(value, &unwrap_to_expression!(expected));
+                }
+                _ => panic!("The pattern {:?} does not match up with {:?}", pattern, expected),
+            }
+        }
+    }
+}
+
+#[rstest(
+    input, message,
+    case("MATCH {CASE 1 {}}", "Expected a value to match against but got nothing"),
+    case("MATCH a;", "Unexpected character sequences found in a supposed expression"),
+    case("MATCH a {}", "Expected at least one CASE or DEFAULT for the match statement"),
+    case("MATCH a {CASE {}}", "Expected a pattern for the case but got nothing"),
+    case("MATCH a {CASE 1, {}}", "Expected a pattern for the case but got nothing"),
+    case("MATCH a {CASE 1 TO {}}", "Expected a value on both sides of TO for the range of the case"),
+    case("MATCH a {b <- 1;}", "Expected CASE or DEFAULT inside the match statement but got \"b\""),
+    case("MATCH a {DEFAULT {} CASE 1 {}}", "Expected the DEFAULT block to be the last one of the match statement but found CASE after it"),
+    case("CASE 1 {}", "Expected a MATCH statement around this CASE block"),
+    case("DEFAULT {}", "Expected a MATCH statement around this DEFAULT block"),
+)]
+fn test_incorrect_parsing(input: &str, message: &str) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [input]
+    );
+    let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
+    assert!(
+        matches!(ast.as_ref().unwrap_err(),
+                MascalError {
+                    error_type,
+                    source,
+                    ..
+                } if *error_type == MascalErrorType::ParserError
+                && source == message
+            ),
+        "got {:?}, expected MascalError {{ error_type: {:?}, message: {:?}, ... }}",
+        &ast, MascalErrorType::ParserError, message
+    );
+}
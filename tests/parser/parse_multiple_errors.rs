@@ -0,0 +1,92 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::token::Token;
+use mascal::lexer;
+use mascal::parser::{self, TokenSequence};
+use mascal::semantic_analysis::conduct_semantic_analysis;
+use crate::define_program_boilerplate;
+
+macro_rules! collect_errors {
+    ($input: expr) => {{
+        let tokens: Vec<Token> = lexer::tokenize($input).unwrap();
+        parser::parse(TokenSequence::new(tokens)).unwrap_err()
+    }};
+}
+
+#[rstest(
+    input, expected_messages,
+    case(
+        vec!["a <- ;", "b <- 1;", "<- 2;"],
+        vec![
+            "Expected an expression to parse but got nothing",
+            "Expected an expression to parse but got nothing",
+        ]
+    ),
+    case(
+        vec!["THROW Typo: \"x\";", "c <- 2 <- 3;", "d <- 4"],
+        vec![
+            "Use of an undefined usable error type in the throw statement (perhaps a typo?)",
+            "Cannot use more than one variable initializer per statement",
+            "Unexpected characters found inside implementation block, perhaps forgot a semicolon?",
+        ]
+    ),
+    case(
+        vec!["WHILE {a <- 1;}", "FOR;", "THROW ValueError \"x\";"],
+        vec![
+            "Expected an expression to parse but got nothing",
+            "Expected a variable identifier to use but got \";\"",
+            "Expected a colon for the throw statement but got \"x\"",
+        ]
+    ),
+)]
+fn test_statement_recovery(input: Vec<&str>, expected_messages: Vec<&str>) {
+    let input: String = define_program_boilerplate!(Vec::<String>::new(), input);
+    let errors: Vec<MascalError> = collect_errors!(input.as_str());
+    let messages: Vec<&str> = errors.iter().map(|e| e.source.as_str()).collect();
+    assert_eq!(messages, expected_messages);
+    assert!(errors.iter().all(|e| e.error_type == MascalErrorType::ParserError
+        || e.error_type == MascalErrorType::UndefinedErrorType));
+    assert!(errors.windows(2).all(|pair| pair[0].span.unwrap().line < pair[1].span.unwrap().line));
+}
+
+#[test]
+fn test_variable_and_implementation_errors() {
+    let input: String = define_program_boilerplate!(
+        ["INTEGER {a <- ; b;}"],
+        ["b <- ;"]
+    );
+    let errors: Vec<MascalError> = collect_errors!(input.as_str());
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].span.unwrap().line < errors[1].span.unwrap().line);
+}
+
+#[test]
+fn test_errors_across_blocks() {
+    let program: String = define_program_boilerplate!(Vec::<String>::new(), ["a <- ;"]);
+    let input: String = format!(
+        "{program}\nDEFINE_FUNCTION f {{ IMPLEMENTATION {{ b <- ; }} }}"
+    );
+    let errors: Vec<MascalError> = collect_errors!(input.as_str());
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn test_semantic_errors() {
+    let program: String = define_program_boilerplate!(
+        ["INTEGER {a; a;}", "FLOAT {a;}"],
+        Vec::<String>::new()
+    );
+    let input: String = format!(
+        "{program}\nDEFINE_FUNCTION f(x, y) {{ VARIABLES {{ INTEGER {{x <- 1;}} }} IMPLEMENTATION {{}} }}"
+    );
+    let tokens: Vec<Token> = lexer::tokenize(input.as_str()).unwrap();
+    let ast = parser::parse(TokenSequence::new(tokens)).unwrap();
+    let errors: Vec<MascalError> = conduct_semantic_analysis(ast).unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|e| e.source.as_str()).collect();
+    assert_eq!(messages, vec![
+        "Cannot redeclare the same variable in a variable block",
+        "Cannot redeclare the same variable in a variable block",
+        "Cannot define an initial value for a parameter inside a function",
+        "Parameter named \"y\" has not been declared in the variables block",
+    ]);
+}
@@ -0,0 +1,83 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::blocks::{ScopedBlocks, VariableBlock};
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::expressions::{MascalExpression, MascalExpressionKind};
+use mascal::defs::statements::MascalStatementKind;
+use mascal::defs::types::MascalUnprocessedType;
+use crate::{define_program_boilerplate, run_parsing, unwrap_to_expression};
+
+const POINT: &str = "
+DEFINE_RECORD Point {
+    VARIABLES {
+        INTEGER {x <- 0; y;}
+        STRING {label;}
+    }
+}
+";
+
+#[rstest(record_case, case("DEFINE_RECORD"), case("define_record"), case("Define_Record"))]
+fn test_record_definition(record_case: &str) {
+    let source: String = POINT.replace("DEFINE_RECORD", record_case)
+        + &define_program_boilerplate!(["Point {p; ps[2];}"], ["p.x <- 1;"]);
+    let ast: AbstractSyntaxTree = run_parsing!(source.as_str()).unwrap();
+    let ScopedBlocks::Record { name, variables, .. } = &ast.blocks[0] else { unreachable!() };
+    assert_eq!(name, "Point");
+    assert_eq!(variables.integers.len(), 2);
+    assert_eq!(variables.strings.len(), 1);
+
+    let ScopedBlocks::Program(exec) = &ast.blocks[1] else { unreachable!() };
+    let program_variables: &VariableBlock = &exec.variables;
+    assert_eq!(program_variables.custom_types.len(), 1);
+    assert_eq!(
+        program_variables.custom_types[0].variable_type,
+        MascalUnprocessedType::Record("Point".into())
+    );
+    assert_eq!(program_variables.custom_types[0].variables.len(), 2);
+    let MascalStatementKind::Declaration { variable, .. } = &exec.body[0].kind else {
+        unreachable!()
+    };
+    assert!(matches!(&variable.kind, MascalExpressionKind::FieldAccess { field, .. } if field == "x"));
+}
+
+#[test]
+fn test_field_access_chains() {
+    let expression: MascalExpression = unwrap_to_expression!("lines[0].start.x + 1");
+    let MascalExpressionKind::Binary { left, .. } = expression.kind else { unreachable!() };
+    let MascalExpressionKind::FieldAccess { record, field } = left.kind else { unreachable!() };
+    assert_eq!(field, "x");
+    let MascalExpressionKind::FieldAccess { record, field } = record.kind else { unreachable!() };
+    assert_eq!(field, "start");
+    assert!(matches!(record.kind, MascalExpressionKind::Indexing { .. }));
+}
+
+#[test]
+fn test_record_return_type() {
+    let source: String = String::from(POINT)
+        + "DEFINE_FUNCTION origin() -> Point[] { VARIABLES { Point {p[1];} } IMPLEMENTATION { RETURN p; } }"
+        + &define_program_boilerplate!(Vec::<String>::new(), Vec::<String>::new());
+    let ast: AbstractSyntaxTree = run_parsing!(source.as_str()).unwrap();
+    let ScopedBlocks::Function { return_type, .. } = &ast.blocks[1] else { unreachable!() };
+    assert_eq!(
+        return_type,
+        &Some(MascalUnprocessedType::StaticArray(Box::new(MascalUnprocessedType::Record("Point".into()))))
+    );
+}
+
+#[rstest(
+    input, expected_source,
+    case("DEFINE_RECORD {VARIABLES {INTEGER {x;}}}", "Expected a identifier for the record name"),
+    case("DEFINE_RECORD Point {}", "Missing required block(s): Variables"),
+    case("DEFINE_RECORD Point {VARIABLES {Other {a;} Other {b;}}}", "Found redefinition of the same variable type block Other declared before"),
+    case("DEFINE_RECORD Point {VARIABLES {INTEGER {x;}}} DEFINE_PROGRAM {IMPLEMENTATION {p. <- 1;}}", "Expected a field name after the \".\" of a record"),
+)]
+fn test_record_parsing_errors(input: &str, expected_source: &str) {
+    let source: String = if input.contains("DEFINE_PROGRAM") {
+        String::from(input)
+    } else {
+        String::from(input) + &define_program_boilerplate!(Vec::<String>::new(), Vec::<String>::new())
+    };
+    let err: MascalError = run_parsing!(source.as_str()).unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::ParserError);
+    assert_eq!(err.source, expected_source);
+}
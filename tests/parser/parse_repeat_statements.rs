@@ -0,0 +1,78 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::blocks::ScopedBlocks;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::expressions::MascalExpression;
+use mascal::defs::statements::MascalStatementKind;
+use crate::{assert_same_expression, define_program_boilerplate, run_parsing, unwrap_to_expression};
+
+#[rstest(
+    input, expected_condition, expected_until, expected_count, expected_label,
+    case("REPEAT {c <- 3;} UNTIL a = b;", "a = b", true, 1, None),
+    case("repeat {c <- 3; d <- 4;} until a > 2 AND b;", "a > 2 AND b", true, 2, None),
+    case("REPEAT {} UNTIL TRUE;", "TRUE", true, 0, None),
+    case("DO {c <- 3;} WHILE a < 10;", "a < 10", false, 1, None),
+    case("again: REPEAT {c <- 3;} UNTIL done;", "done", true, 1, Some("again")),
+    case("again: DO {} WHILE c;", "c", false, 0, Some("again")),
+)]
+fn test_correct_parsing(
+    input: &str,
+    expected_condition: &str,
+    expected_until: bool,
+    expected_count: usize,
+    expected_label: Option<&str>
+) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [input, "c <- 0;"]
+    );
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
+    assert_eq!(exec.body.len(), 2);
+    match &exec.body[0].kind {
+        MascalStatementKind::Repeat {
+            statements,
+            condition,
+            is_until,
+            label,
+        } => {
+            let expr: &MascalExpression = &unwrap_to_expression!(expected_condition);
+            assert_same_expression!(condition, expr);
+            assert_eq!(*is_until, expected_until);
+            assert_eq!(statements.len(), expected_count);
+            assert_eq!(label.as_deref(), expected_label);
+        }
+        _ => panic!("The statement is not a repeat one")
+    }
+}
+
+#[rstest(
+    input, message,
+    case("REPEAT c <- 3; UNTIL TRUE;", "Expected a opening brace for a Repeat loop block"),
+    case("REPEAT {c <- 3;}", "Expected UNTIL after the block of the loop but got nothing"),
+    case("REPEAT {c <- 3;} WHILE TRUE;", "Expected UNTIL after the block of the loop but got nothing"),
+    case("DO {c <- 3;} UNTIL TRUE;", "Expected WHILE after the block of the loop but got nothing"),
+    case("REPEAT {c <- 3;} UNTIL;", "Expected a condition after UNTIL but got nothing"),
+    case("REPEAT {c <- 3;} UNTIL TRUE", "Unexpected characters found inside implementation block, perhaps forgot a semicolon?"),
+    case("REPEAT {c <- 3} UNTIL TRUE;", "Unexpected characters found inside Repeat loop block, perhaps forgot a semicolon?"),
+    case("UNTIL TRUE;", "Expected a REPEAT block before this UNTIL condition"),
+)]
+fn test_incorrect_parsing(input: &str, message: &str) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [input]
+    );
+    let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
+    assert!(
+        matches!(ast.as_ref().unwrap_err(),
+                MascalError {
+                    error_type,
+                    source,
+                    ..
+                } if *error_type == MascalErrorType::ParserError
+                && source == message
+            ),
+        "got {:?}, expected MascalError {{ error_type: {:?}, message: {:?}, ... }}",
+        &ast, MascalErrorType::ParserError, message
+    );
+}
@@ -0,0 +1,37 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::blocks::ScopedBlocks;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::expressions::MascalExpressionKind;
+use mascal::defs::statements::MascalStatementKind;
+use crate::{define_program_boilerplate, expect_error, run_parsing};
+
+#[rstest(return_case, case("RETURN"), case("return"), case("Return"))]
+fn test_bare_return(return_case: &str) {
+    let input: String = define_program_boilerplate!(Vec::<String>::new(), [format!("{return_case};")]);
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    assert!(matches!(&ast.blocks[0],
+        ScopedBlocks::Program(exec) if exec.body.len() == 1
+            && matches!(&exec.body[0].kind, MascalStatementKind::Return(None))
+    ));
+}
+
+#[rstest(
+    input, is_expected_kind,
+    case("RETURN 3;", |kind: &MascalExpressionKind| matches!(kind, MascalExpressionKind::Literal(..))),
+    case("RETURN a + b * 2;", |kind: &MascalExpressionKind| matches!(kind, MascalExpressionKind::Binary { .. })),
+    case("RETURN f(a, [1, 2]);", |kind: &MascalExpressionKind| matches!(kind, MascalExpressionKind::Call { .. })),
+)]
+fn test_return_with_value(input: &str, is_expected_kind: fn(&MascalExpressionKind) -> bool) {
+    let input: String = define_program_boilerplate!(Vec::<String>::new(), [input]);
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else { unreachable!() };
+    let MascalStatementKind::Return(Some(expression)) = &exec.body[0].kind else { unreachable!() };
+    assert!(is_expected_kind(&expression.kind));
+}
+
+#[rstest(input, case("RETURN 3"), case("RETURN +;"))]
+fn test_incorrect_return(input: &str) {
+    let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!([input]);
+    assert_eq!(ast.unwrap_err().error_type, MascalErrorType::ParserError);
+}
@@ -0,0 +1,66 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::blocks::ScopedBlocks;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::statements::MascalStatementKind;
+use mascal::runtime::interpert;
+use mascal::runtime::io_streams::BufferedIO;
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::{define_program_boilerplate, run_parsing};
+
+#[rstest(
+    input,
+    case(vec!["a <- 3;", "b <- a + 1;", "c <- b * 2;"]),
+    case(vec!["WHILE TRUE {a <- 1;}", "FOR i FROM 1 TO 3 {b <- i;}"]),
+    case(vec!["IF a = b {c <- 0;}", "THROW ValueError: \"test\";", "BREAK;"]),
+)]
+fn test_statement_spans(input: Vec<&str>) {
+    let code: String = define_program_boilerplate!(Vec::<String>::new(), input);
+    let ast: AbstractSyntaxTree = run_parsing!(code.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
+    assert_eq!(exec.body.len(), input.len());
+    let first_line: usize = exec.body[0].span.line;
+    for (index, (statement, source)) in exec.body.iter().zip(input.iter()).enumerate() {
+        assert_eq!(statement.span.line, first_line + index);
+        let line: &str = code.lines().nth(statement.span.line).unwrap();
+        let line_start: usize = code.lines()
+            .take(statement.span.line)
+            .map(|l| l.len() + 1)
+            .sum();
+        assert_eq!(&line[statement.span.character - line_start..], *source);
+    }
+}
+
+#[test]
+fn test_expression_spans() {
+    let code: String = define_program_boilerplate!(Vec::<String>::new(), ["a <- b + c;"]);
+    let ast: AbstractSyntaxTree = run_parsing!(code.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
+    let MascalStatementKind::Declaration { variable, value } = &exec.body[0].kind else {
+        unreachable!()
+    };
+    assert_eq!(&code[variable.span.character..variable.span.character + 1], "a");
+    assert_eq!(&code[value.span.character..value.span.character + 1], "+");
+}
+
+#[test]
+fn test_runtime_error_span() {
+    let code: String = define_program_boilerplate!(
+        ["INTEGER {a;}"],
+        ["a <- 3;", "THROW ValueError: \"test\";"]
+    );
+    let ast: AbstractSyntaxTree = run_parsing!(code.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
+    let throw_line: usize = exec.body[1].span.line;
+    let err: MascalError = interpert(
+        ast,
+        Rc::new(RefCell::new(BufferedIO::default())),
+        Rc::new(FxHashMap::default()),
+    )
+    .unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::ValueError);
+    assert_eq!(err.span.unwrap().line, throw_line);
+    assert_ne!(err.span.unwrap().line, 0);
+}
@@ -2,7 +2,9 @@ use rstest::rstest;
 use mascal::ast::AbstractSyntaxTree;
 use mascal::defs::blocks::ScopedBlocks;
 use mascal::defs::errors::{MascalError, MascalErrorType};
-use mascal::defs::statements::MascalStatement;
+use mascal::defs::expressions::MascalExpressionKind;
+use mascal::defs::literal::MascalLiteral;
+use mascal::defs::statements::MascalStatementKind;
 use crate::{define_program_boilerplate, expect_error, run_parsing};
 
 macro_rules! get_correct_error_inputs {
@@ -50,15 +52,18 @@ fn test_correct_parsing(error_id: &str, expected: MascalErrorType) {
     for throw_case in &["THROW", "throw", "Throw"] {
         let input = define_program_boilerplate!(
             Vec::<String>::new(),
-            vec![ format!("{throw_case} {error_id}: \"test Test TEST!\";") ]
+            [format!("{throw_case} {error_id}: \"test Test TEST!\";")]
         );
         let ast = run_parsing!(input.as_str()).unwrap();
 
         assert!(matches!(&ast.blocks[0],
             ScopedBlocks::Program(exec) if exec.body.len() == 1
-                && matches!(&exec.body[0],
-                    MascalStatement::Throw { error_type, message }
-                        if *error_type == expected && message == "test Test TEST!"
+                && matches!(&exec.body[0].kind,
+                    MascalStatementKind::Throw { error_type, message }
+                        if *error_type == expected && matches!(
+                            &message.kind,
+                            MascalExpressionKind::Literal(MascalLiteral::String(text)) if text == "test Test TEST!"
+                        )
                 )
         ));
     }
@@ -76,10 +81,8 @@ fn test_correct_parsing(error_id: &str, expected: MascalErrorType) {
     case("Value"),
 )]
 fn test_incorrect_parsing1(error_id: &str) {
-    for throw_case in vec!["THROW", "throw", "Throw"] {
-        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!(vec![
-                format!("{throw_case} {error_id}: \"test Test TEST!\";")
-            ]);
+    for throw_case in ["THROW", "throw", "Throw"] {
+        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!([format!("{throw_case} {error_id}: \"test Test TEST!\";")]);
         assert!(
             matches!(ast.as_ref().unwrap_err(),
                     MascalError {
@@ -98,9 +101,7 @@ fn test_incorrect_parsing1(error_id: &str) {
 #[test]
 fn test_incorrect_parsing2() {
     define_general_throw_statement_boilerplate!(|error_id, throw_case, _| {
-        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!(vec![
-                format!("{throw_case} {error_id} \"test Test TEST!\";")
-        ]);
+        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!([format!("{throw_case} {error_id} \"test Test TEST!\";")]);
         assert!(
             matches!(ast.as_ref().unwrap_err(),
                 MascalError {
@@ -119,9 +120,7 @@ fn test_incorrect_parsing2() {
 #[test]
 fn test_incorrect_parsing3() {
     define_general_throw_statement_boilerplate!(|error_id, throw_case, _| {
-        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!(vec![
-                format!("{throw_case} {error_id} ;")
-        ]);
+        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!([format!("{throw_case} {error_id} ;")]);
         assert!(
             matches!(ast.as_ref().unwrap_err(),
                 MascalError {
@@ -140,9 +139,7 @@ fn test_incorrect_parsing3() {
 #[test]
 fn test_incorrect_parsing4() {
     define_general_throw_statement_boilerplate!(|error_id, throw_case, _| {
-        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!(vec![
-                format!("{throw_case}: {error_id};")
-        ]);
+        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!([format!("{throw_case}: {error_id};")]);
         assert!(
             matches!(ast.as_ref().unwrap_err(),
                 MascalError {
@@ -161,9 +158,7 @@ fn test_incorrect_parsing4() {
 #[test]
 fn test_incorrect_parsing5() {
     define_general_throw_statement_boilerplate!(|error_id, throw_case, _| {
-        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!(vec![
-                format!("{throw_case} {error_id};")
-        ]);
+        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!([format!("{throw_case} {error_id};")]);
         assert!(
             matches!(ast.as_ref().unwrap_err(),
                 MascalError {
@@ -182,9 +177,7 @@ fn test_incorrect_parsing5() {
 #[test]
 fn test_incorrect_parsing6() {
     define_general_throw_statement_boilerplate!(|error_id, throw_case, _| {
-        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!(vec![
-                format!("{throw_case} {error_id}:;")
-        ]);
+        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!([format!("{throw_case} {error_id}:;")]);
         assert!(
             matches!(ast.as_ref().unwrap_err(),
                 MascalError {
@@ -204,9 +197,7 @@ fn test_incorrect_parsing6() {
 fn test_incorrect_parsing7() {
     let errors: Vec<(&str, MascalErrorType)> = get_correct_error_inputs!();
     for (error_id, _) in errors {
-        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!(vec![
-                format!("ThrOW {error_id}: \"12345\";")
-        ]);
+        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!([format!("ThrOW {error_id}: \"12345\";")]);
         assert!(
             matches!(ast.as_ref().unwrap_err(),
                 MascalError {
@@ -226,9 +217,7 @@ fn test_incorrect_parsing7() {
 fn test_incorrect_parsing8() {
     let errors: Vec<(&str, MascalErrorType)> = get_correct_error_inputs!();
     for (error_id, _) in errors {
-        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!(vec![
-                format!("THROW {error_id}: \"12345\"")
-        ]);
+        let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!([format!("THROW {error_id}: \"12345\"")]);
         assert!(
             matches!(ast.as_ref().unwrap_err(),
                 MascalError {
@@ -242,4 +231,72 @@ fn test_incorrect_parsing8() {
             &ast, MascalErrorType::ParserError, "Unexpected characters found inside implementation block, perhaps forgot a semicolon?"
         );
     }
-}
\ No newline at end of file
+}
+#[rstest(
+    message, is_expected_kind,
+    case("\"missing \" + name", |kind: &MascalExpressionKind| matches!(kind, MascalExpressionKind::Binary { .. })),
+    case("LEN(name)", |kind: &MascalExpressionKind| matches!(kind, MascalExpressionKind::Call { .. })),
+    case("name", |kind: &MascalExpressionKind| matches!(kind, MascalExpressionKind::Symbolic(..))),
+)]
+fn test_computed_message(message: &str, is_expected_kind: fn(&MascalExpressionKind) -> bool) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [format!("THROW ValueError: {message};")]
+    );
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else { unreachable!() };
+    let MascalStatementKind::Throw { message, .. } = &exec.body[0].kind else { unreachable!() };
+    assert!(is_expected_kind(&message.kind));
+}
+
+#[rstest(errors_case, case("ERRORS"), case("errors"), case("Errors"))]
+fn test_declared_error_types(errors_case: &str) {
+    let input: String = format!("{errors_case} {{NotFound; OutOfStock;}}\n")
+        + &define_program_boilerplate!(
+            Vec::<String>::new(),
+            ["TRY {THROW NotFound: \"x\";} CATCH OutOfStock {}"]
+        );
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else { unreachable!() };
+    let MascalStatementKind::Try { statements, catches, .. } = &exec.body[0].kind else {
+        unreachable!()
+    };
+    assert!(matches!(&statements[0].kind,
+        MascalStatementKind::Throw { error_type: MascalErrorType::Custom(name), .. } if &**name == "NotFound"
+    ));
+    assert_eq!(catches[0].error_type, Some(MascalErrorType::Custom("OutOfStock".into())));
+}
+
+#[rstest(
+    errors_block, code, expected_type, expected_message,
+    case("", "TRY {} CATCH NotFound {}", MascalErrorType::UndefinedErrorType,
+        "Use of an undefined usable error type in the catch statement (perhaps a typo?)"),
+    case("ERRORS {NotFound; NotFound;}", "a <- 1;", MascalErrorType::ParserError,
+        "The error type \"NotFound\" has already been defined"),
+    case("ERRORS {ValueError;}", "a <- 1;", MascalErrorType::ParserError,
+        "The error type \"ValueError\" has already been defined"),
+    case("ERRORS {NotFound <- 1;}", "a <- 1;", MascalErrorType::ParserError,
+        "Expected a single name for the error type but got \"NotFound\""),
+    case("ERRORS {NotFound}", "a <- 1;", MascalErrorType::ParserError,
+        "Unexpected characters found inside errors block, perhaps forgot a semicolon?"),
+)]
+fn test_incorrect_declared_error_types(
+    errors_block: &str,
+    code: &str,
+    expected_type: MascalErrorType,
+    expected_message: &str,
+) {
+    let input: String =
+        format!("{errors_block}\n") + &define_program_boilerplate!(Vec::<String>::new(), [code]);
+    let err: MascalError = run_parsing!(input.as_str()).unwrap_err();
+    assert_eq!(err.error_type, expected_type);
+    assert_eq!(err.source, expected_message);
+}
+
+#[test]
+fn test_errors_block_inside_program() {
+    let input: String = define_program_boilerplate!(Vec::<String>::new(), ["a <- 1;"])
+        .replacen("DEFINE_PROGRAM {", "DEFINE_PROGRAM {\nERRORS {NotFound;}", 1);
+    let err: MascalError = run_parsing!(input.as_str()).unwrap_err();
+    assert_eq!(err.error_type, MascalErrorType::ParserError);
+}
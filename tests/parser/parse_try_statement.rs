@@ -0,0 +1,63 @@
+use rstest::rstest;
+use mascal::ast::AbstractSyntaxTree;
+use mascal::defs::blocks::ScopedBlocks;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::defs::statements::MascalStatementKind;
+use crate::{define_program_boilerplate, expect_error, run_parsing};
+
+#[rstest(try_case, case("TRY"), case("try"), case("Try"))]
+fn test_try_with_every_branch(try_case: &str) {
+    let input: String = define_program_boilerplate!(
+        Vec::<String>::new(),
+        [format!(
+            "{try_case} {{READ(a);}} CATCH InputError AS e {{WRITE(e);}} CATCH {{a <- 0;}} FINALLY {{WRITE(a); WRITE(a);}}"
+        )]
+    );
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else { unreachable!() };
+    assert_eq!(exec.body.len(), 1);
+    let MascalStatementKind::Try { statements, catches, finally } = &exec.body[0].kind else {
+        unreachable!()
+    };
+    assert_eq!(statements.len(), 1);
+    assert_eq!(catches.len(), 2);
+    assert_eq!(catches[0].error_type, Some(MascalErrorType::InputError));
+    assert_eq!(catches[0].variable.as_deref(), Some("e"));
+    assert_eq!(catches[1].error_type, None);
+    assert_eq!(catches[1].variable, None);
+    assert_eq!(finally.as_ref().unwrap().len(), 2);
+}
+
+#[rstest(
+    input, expected_catches, has_finally,
+    case("TRY {a <- 1;} FINALLY {a <- 2;}", 0, true),
+    case("TRY {a <- 1;} CATCH AS e {a <- 2;}", 1, false),
+    case("TRY {} CATCH ValueError {} CATCH TypeError {}", 2, false),
+)]
+fn test_try_branch_combinations(input: &str, expected_catches: usize, has_finally: bool) {
+    let input: String = define_program_boilerplate!(Vec::<String>::new(), [input, "a <- 3;"]);
+    let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
+    let ScopedBlocks::Program(exec) = &ast.blocks[0] else { unreachable!() };
+    assert_eq!(exec.body.len(), 2);
+    let MascalStatementKind::Try { catches, finally, .. } = &exec.body[0].kind else {
+        unreachable!()
+    };
+    assert_eq!(catches.len(), expected_catches);
+    assert_eq!(finally.is_some(), has_finally);
+}
+
+#[rstest(
+    input, expected_type,
+    case("TRY {a <- 1;}", MascalErrorType::ParserError),
+    case("TRY a <- 1; CATCH {}", MascalErrorType::ParserError),
+    case("TRY {} FINALLY {} CATCH {}", MascalErrorType::ParserError),
+    case("TRY {} FINALLY {} FINALLY {}", MascalErrorType::ParserError),
+    case("TRY {} CATCH AS {}", MascalErrorType::ParserError),
+    case("TRY {} CATCH Valueerror {}", MascalErrorType::UndefinedErrorType),
+    case("CATCH {}", MascalErrorType::ParserError),
+    case("FINALLY {a <- 1;}", MascalErrorType::ParserError),
+)]
+fn test_incorrect_try(input: &str, expected_type: MascalErrorType) {
+    let ast: Result<AbstractSyntaxTree, MascalError> = expect_error!([input]);
+    assert_eq!(ast.unwrap_err().error_type, expected_type);
+}
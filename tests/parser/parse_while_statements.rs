@@ -2,8 +2,8 @@ use rstest::rstest;
 use mascal::ast::AbstractSyntaxTree;
 use mascal::defs::blocks::ScopedBlocks;
 use mascal::defs::errors::{MascalError, MascalErrorType};
-use mascal::defs::statements::MascalStatement;
-use crate::{define_program_boilerplate, run_parsing};
+use mascal::defs::statements::MascalStatementKind;
+use crate::{assert_same_expression, define_program_boilerplate, run_parsing};
 
 #[rstest(
     input, condition_part,
@@ -15,23 +15,23 @@ use crate::{define_program_boilerplate, run_parsing};
 fn test_correct_parsing(input: &str, condition_part: &str) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: AbstractSyntaxTree = run_parsing!(input.as_str()).unwrap();
     assert_eq!(ast.blocks.len(), 1);
     let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
     assert_eq!(exec.body.len(), 1);
-    match &exec.body[0] {
-        MascalStatement::While(branch) => {
+    match &exec.body[0].kind {
+        MascalStatementKind::While { branch, label: None } => {
             let input_expect: String = define_program_boilerplate!(
                 Vec::<String>::new(),
-                vec![ format!("{condition_part};") ]
+                [format!("{condition_part};")]
             );
             
             let ast: AbstractSyntaxTree = run_parsing!(input_expect.as_str()).unwrap();
             let ScopedBlocks::Program(exec) = &ast.blocks[0] else {unreachable!()};
-            let MascalStatement::ExpressionStatement(expr) = &exec.body[0] else {unreachable!()};
-            assert_eq!(branch.condition.as_ref().unwrap(), expr);
+            let MascalStatementKind::ExpressionStatement(expr) = &exec.body[0].kind else {unreachable!()};
+            assert_same_expression!(branch.condition.as_ref().unwrap(), expr);
         }
         _ => panic!("The statement is not a while one")
     }
@@ -50,7 +50,7 @@ fn test_correct_parsing(input: &str, condition_part: &str) {
 fn test_incorrect_parsing(input: &str, message: &str) {
     let input: String = define_program_boilerplate!(
         Vec::<String>::new(),
-        vec![ input ]
+        [input]
     );
     let ast: Result<AbstractSyntaxTree, MascalError> = run_parsing!(input.as_str());
     assert!(
@@ -0,0 +1,193 @@
+use rstest::rstest;
+use mascal::defs::errors::{MascalError, MascalErrorType};
+use mascal::repl::ReplSession;
+use mascal::defs::span::Span;
+use mascal::runtime::io_streams::BufferedIO;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn evaluate_all(session: &mut ReplSession, inputs: &[&str]) -> Vec<Option<String>> {
+    inputs
+        .iter()
+        .map(|input| session.evaluate(input).unwrap())
+        .collect()
+}
+
+#[rstest(
+    inputs, expected,
+    case(&["1 + 2"], vec![Some("3")]),
+    case(&["1 + 2;"], vec![Some("3")]),
+    case(&["\"text\""], vec![Some("\"text\"")]),
+    case(&["INTEGER {a <- 4;}", "a * a", "a <- a + 1;", "a"], vec![None, Some("16"), None, Some("5")]),
+    case(&["VARIABLES {STRING {s <- \"x\";}}", "s + s"], vec![None, Some("\"xx\"")]),
+    case(&["DYNAMIC {d;}", "d <- 5;", "d <- \"a\";", "d"], vec![None, None, None, Some("\"a\"")]),
+    case(&["INTEGER {a <- 1;}", "INTEGER {a <- 7;}", "a"], vec![None, None, Some("7")]),
+    case(&["", "   "], vec![None, None]),
+)]
+fn test_values_are_echoed(inputs: &[&str], expected: Vec<Option<&str>>) {
+    let mut session: ReplSession = ReplSession::new();
+    let results: Vec<Option<String>> = evaluate_all(&mut session, inputs);
+    assert_eq!(results, expected.into_iter().map(|v| v.map(String::from)).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_functions_are_defined_incrementally() {
+    let mut session: ReplSession = ReplSession::new();
+    let results: Vec<Option<String>> = evaluate_all(&mut session, &[
+        "DEFINE_FUNCTION twice(x) -> INTEGER {\n VARIABLES {INTEGER {x;}}\n IMPLEMENTATION {RETURN x * 2;}\n}",
+        "twice(4)",
+        "DEFINE_FUNCTION thrice(x) -> INTEGER {\n VARIABLES {INTEGER {x;}}\n IMPLEMENTATION {RETURN twice(x) + x;}\n}",
+        "thrice(5)",
+        "DEFINE_FUNCTION twice(x) -> INTEGER {\n VARIABLES {INTEGER {x;}}\n IMPLEMENTATION {RETURN x + x + 1;}\n}",
+        "twice(4)",
+    ]);
+    assert_eq!(results[1].as_deref(), Some("8"));
+    assert_eq!(results[3].as_deref(), Some("15"));
+    assert_eq!(results[5].as_deref(), Some("9"));
+    assert!(session.symbol_table().is_function("thrice"));
+}
+
+#[test]
+fn test_records_are_defined_incrementally() {
+    let mut session: ReplSession = ReplSession::new();
+    let results: Vec<Option<String>> = evaluate_all(&mut session, &[
+        "DEFINE_RECORD Point {\n VARIABLES {INTEGER {x <- 1; y <- 2;}}\n}",
+        "DYNAMIC {p;}",
+        "p <- Point();",
+        "p.x <- p.y * 5;",
+        "p",
+    ]);
+    assert_eq!(results[4].as_deref(), Some("Point(x: 10, y: 2)"));
+    assert!(session.symbol_table().is_record("Point"));
+}
+
+#[test]
+fn test_enums_are_defined_incrementally() {
+    let mut session: ReplSession = ReplSession::new();
+    let results: Vec<Option<String>> = evaluate_all(&mut session, &[
+        "DEFINE_ENUM Color { RED, GREEN }",
+        "Color.GREEN > Color.RED",
+    ]);
+    assert_eq!(results[1].as_deref(), Some("TRUE"));
+    assert!(session.symbol_table().is_enum("Color"));
+}
+
+#[test]
+fn test_imports_are_defined_in_the_session() {
+    let mut session: ReplSession = ReplSession::new();
+    let results: Vec<Option<String>> = evaluate_all(&mut session, &[
+        "IMPORT \"tests/interpreter/imports/lib/math.mascal\";",
+        "square(6)",
+    ]);
+    assert_eq!(results[1].as_deref(), Some("36"));
+}
+
+#[test]
+fn test_map_variables_persist() {
+    let mut session: ReplSession = ReplSession::new();
+    let results: Vec<Option<String>> = evaluate_all(&mut session, &[
+        "MAP<STRING, INTEGER> {ages;}",
+        "ages[\"ada\"] <- 36;",
+        "ages[\"ada\"] + 1",
+    ]);
+    assert_eq!(results[2].as_deref(), Some("37"));
+}
+
+#[test]
+fn test_writes_go_through_the_io() {
+    let io: Rc<RefCell<BufferedIO>> = Rc::new(RefCell::new(BufferedIO::default()));
+    let mut session: ReplSession = ReplSession::new().with_io(io.clone());
+    let results: Vec<Option<String>> = evaluate_all(&mut session, &[
+        "INTEGER {i;}",
+        "FOR i FROM 1 TO 3 {\n WRITE(i);\n}",
+        "WRITE(\"done\")",
+    ]);
+    assert_eq!(results, vec![None, None, None]);
+    assert_eq!(io.borrow().output(), "1\n2\n3\ndone\n");
+}
+
+#[test]
+fn test_errors_keep_the_session_alive() {
+    let mut session: ReplSession = ReplSession::new();
+    session.evaluate("INTEGER {a <- 2;}").unwrap();
+    let errors: Vec<MascalError> = session.evaluate("a <- missing + 1;").unwrap_err();
+    assert_eq!(errors[0].error_type, MascalErrorType::RuntimeError);
+    let errors: Vec<MascalError> = session.evaluate("a <-").unwrap_err();
+    assert_eq!(errors[0].error_type, MascalErrorType::ParserError);
+    assert_eq!(session.evaluate("a").unwrap().as_deref(), Some("2"));
+}
+
+#[rstest(
+    input, expected_character,
+    case("missing <- 1;", 0),
+    case("1 + $", 4),
+    case("WRITE(1, 2 +)", 12),
+)]
+fn test_error_positions_match_the_input(input: &str, expected_character: usize) {
+    let mut session: ReplSession = ReplSession::new();
+    let errors: Vec<MascalError> = session.evaluate(input).unwrap_err();
+    assert_eq!(errors[0].span.unwrap().line, 0);
+    assert_eq!(errors[0].span.unwrap().character, expected_character);
+}
+
+#[rstest(
+    input, expected,
+    case("a + 1", true),
+    case("IF a > 1 {", false),
+    case("IF a > 1 {\n WRITE(a);\n}", true),
+    case("DEFINE_FUNCTION f() {\n IMPLEMENTATION {", false),
+    case("WRITE(\"{\")", true),
+    case("}", true),
+)]
+fn test_input_completeness(input: &str, expected: bool) {
+    assert_eq!(ReplSession::is_input_complete(input), expected);
+}
+
+#[test]
+fn test_records_and_enums_declare_variables_in_later_inputs() {
+    let mut session: ReplSession = ReplSession::new();
+    let results: Vec<Option<String>> = evaluate_all(&mut session, &[
+        "DEFINE_RECORD P {\n VARIABLES {INTEGER {x <- 1;}}\n}",
+        "DEFINE_ENUM Color { RED, GREEN }",
+        "VARIABLES { P {p;} }",
+        "Color {c <- Color.GREEN;}",
+        "p <- P();",
+        "p.x + 1",
+        "c",
+    ]);
+    assert_eq!(results[5].as_deref(), Some("2"));
+    assert_eq!(results[6].as_deref(), Some("GREEN"));
+}
+
+#[test]
+fn test_records_can_be_redefined() {
+    let mut session: ReplSession = ReplSession::new();
+    let results: Vec<Option<String>> = evaluate_all(&mut session, &[
+        "DEFINE_RECORD P {\n VARIABLES {INTEGER {x <- 1;}}\n}",
+        "DEFINE_RECORD P {\n VARIABLES {INTEGER {x <- 5;}}\n}",
+        "P {p;}",
+        "p <- P();",
+        "p.x",
+    ]);
+    assert_eq!(results[4].as_deref(), Some("5"));
+}
+
+#[test]
+fn test_errors_in_earlier_inputs_are_shown_against_them() {
+    let mut session: ReplSession = ReplSession::new();
+    let definition: &str =
+        "DEFINE_FUNCTION bad(x) -> INTEGER {\n VARIABLES {INTEGER {x;}}\n IMPLEMENTATION {RETURN x / 0;}\n}";
+    session.evaluate(definition).unwrap();
+    let errors: Vec<MascalError> = session.evaluate("bad(1)").unwrap_err();
+    let span: Span = errors[0].span.unwrap();
+    assert_eq!(span.line, 2);
+    assert_eq!(&session.source_file(span).unwrap().text[span.character..], "/ 0;}\n}");
+    let rendered: String = session.render_error("bad(1)", &errors[0], false);
+    assert!(rendered.contains("IMPLEMENTATION {RETURN x / 0;}"));
+
+    let errors: Vec<MascalError> = session.evaluate("1 + bad(1)").unwrap_err();
+    assert!(session.source_file(errors[0].span.unwrap()).is_some());
+    let errors: Vec<MascalError> = session.evaluate("1 / 0").unwrap_err();
+    assert_eq!(errors[0].span.unwrap().file, 0);
+    assert!(session.source_file(errors[0].span.unwrap()).is_none());
+}